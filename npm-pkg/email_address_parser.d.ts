@@ -72,6 +72,107 @@ export class EmailAddress {
    * ```
    */
   public get domain(): string;
+
+  /**
+   * @returns {string} The domain of the email address in its ASCII form; i.e. an internationalized domain is converted to Punycode.
+   * @example
+   * ```ts
+   * const email = EmailAddress.parse(`foo@Bücher.de`);
+   * assert(email.domainAscii === "xn--bcher-kva.de");
+   * ```
+   */
+  public get domainAscii(): string;
+
+  /**
+   * @returns {string} The domain of the email address in its Unicode form; i.e. a Punycode domain is converted back.
+   * @example
+   * ```ts
+   * const email = EmailAddress.parse(`foo@xn--bcher-kva.de`);
+   * assert(email.domainUnicode === "bücher.de");
+   * ```
+   */
+  public get domainUnicode(): string;
+
+  /**
+   * @returns {EmailAddress} The canonical form of the email address; i.e. without comments, folding white space, and unnecessary quotes, and with the domain lower cased.
+   * @example
+   * ```ts
+   * const email = EmailAddress.parse(`(comment)"Foo"@BAR.com`);
+   * assert(`${email.canonical()}` === "Foo@bar.com");
+   * ```
+   */
+  public canonical(): EmailAddress;
+
+  /**
+   * Compares the email address with another one, as per the steps of a normalizer.
+   * @param {EmailAddress} other The email address to compare with.
+   * @param {Normalizer} normalizer The normalizer whose steps are the relaxations of the comparison.
+   * @returns {boolean} `true` if both addresses are the same mailbox, `false` otherwise.
+   * @example
+   * ```ts
+   * const normalizer = new Normalizer();
+   * normalizer.stripTags = true;
+   * const email = EmailAddress.parse(`jane+news@example.com`);
+   * assert(email.isEquivalent(EmailAddress.parse(`jane@example.com`), normalizer));
+   * ```
+   */
+  public isEquivalent(other: EmailAddress, normalizer: Normalizer): boolean;
+
+  /**
+   * @param {Normalizer} normalizer The normalizer to apply.
+   * @returns {EmailAddress} The normalized form of the email address; e.g. to deduplicate addresses.
+   * @example
+   * ```ts
+   * const normalizer = new Normalizer();
+   * normalizer.lowercaseLocalPart = true;
+   * normalizer.providerRules = true;
+   * const email = EmailAddress.parse(`J.Ane@GoogleMail.com`);
+   * assert(`${email.normalize(normalizer)}` === "jane@gmail.com");
+   * ```
+   */
+  public normalize(normalizer: Normalizer): EmailAddress;
+
+  /**
+   * Suggests a correction of a likely typo in the domain of the email address; e.g. for a "Did you mean…?" prompt of a form.
+   * The domain is compared to those of the given dictionary, followed by popular ones; a domain of either is never corrected.
+   * @param {string[]} dictionary The domains to suggest, on top of the popular ones.
+   * @returns {(EmailAddress | undefined)} The corrected email address, or `undefined` if the domain is not a likely typo.
+   * @example
+   * ```ts
+   * const email = EmailAddress.parse(`jane@gmial.com`);
+   * assert(`${email.suggest([])}` === "jane@gmail.com");
+   * assert(EmailAddress.parse(`jane@example.com`).suggest([]) === undefined);
+   * ```
+   */
+  public suggest(dictionary: string[]): EmailAddress | undefined;
+}
+
+/**
+ * Normalizes email addresses beyond their canonical forms; e.g. to deduplicate a mailing list.
+ * Normalization starts with the canonical form, and then applies the enabled steps, none of which is enabled by default.
+ */
+export class Normalizer {
+  public free(): void;
+  /**
+   * Instantiates a normalizer that only canonicalizes.
+   */
+  public constructor();
+  /**
+   * Whether the local part is lower cased.
+   */
+  public lowercaseLocalPart: boolean;
+  /**
+   * Whether the sub-address (tag) is removed from the local part; e.g. `jane+news` becomes `jane`.
+   */
+  public stripTags: boolean;
+  /**
+   * Whether the rules of well-known providers apply; e.g. the dots of a Gmail local part are insignificant, and `googlemail.com` is `gmail.com`.
+   */
+  public providerRules: boolean;
+  /**
+   * Whether an internationalized domain is converted to its ASCII form; e.g. `bücher.de` becomes `xn--bcher-kva.de`.
+   */
+  public punycode: boolean;
 }
 
 /**
@@ -88,4 +189,8 @@ export class ParsingOptions {
    * Returns the is_lax option set during instantiation.
    */
   public readonly is_lax: boolean;
+  /**
+   * Whether the non-ASCII characters of RFC 6532 are allowed in the address. Defaults to `true`.
+   */
+  public allowUtf8: boolean;
 }
@@ -23,4 +23,46 @@ describe('integration', function () {
     assert.strictEqual(`${EmailAddress.parse('foö@bücher.de')}`, 'foö@bücher.de');
     assert.strictEqual(EmailAddress.isValid('foö@bücher.de'), true);
   });
+
+  it('canonicalizes and normalizes', async function () {
+    const { EmailAddress, Normalizer } = (await import('../')).default;
+
+    const email = EmailAddress.parse('(comment)"Foo"@BAR.com');
+    assert.strictEqual(`${email?.canonical()}`, 'Foo@bar.com');
+
+    const normalizer = new Normalizer();
+    assert.strictEqual(normalizer.stripTags, false);
+    normalizer.lowercaseLocalPart = true;
+    normalizer.stripTags = true;
+    normalizer.providerRules = true;
+    const gmail = EmailAddress.parse('J.Ane+News@GoogleMail.com');
+    assert.strictEqual(`${gmail?.normalize(normalizer)}`, 'jane@gmail.com');
+    assert.strictEqual(gmail?.isEquivalent(new EmailAddress('jane', 'gmail.com'), normalizer), true);
+    assert.strictEqual(gmail?.isEquivalent(new EmailAddress('jane', 'gmail.com'), new Normalizer()), false);
+  });
+
+  it('suggests corrections of domain typos', async function () {
+    const { EmailAddress } = (await import('../')).default;
+
+    assert.strictEqual(`${EmailAddress.parse('jane@gmial.com')?.suggest([])}`, 'jane@gmail.com');
+    assert.strictEqual(`${EmailAddress.parse('jane@hooli.xzy')?.suggest(['hooli.xyz'])}`, 'jane@hooli.xyz');
+    assert.strictEqual(EmailAddress.parse('jane@example.com')?.suggest([]), undefined);
+  });
+
+  it('renders both forms of internationalized domains', async function () {
+    const { EmailAddress, Normalizer, ParsingOptions } = (await import('../')).default;
+
+    const email = EmailAddress.parse('foo@Bücher.de');
+    assert.strictEqual(email?.domainAscii, 'xn--bcher-kva.de');
+    assert.strictEqual(EmailAddress.parse('foo@xn--bcher-kva.de')?.domainUnicode, 'bücher.de');
+
+    const normalizer = new Normalizer();
+    normalizer.punycode = true;
+    assert.strictEqual(`${email?.normalize(normalizer)}`, 'foo@xn--bcher-kva.de');
+
+    const options = new ParsingOptions(false);
+    assert.strictEqual(options.allowUtf8, true);
+    options.allowUtf8 = false;
+    assert.strictEqual(EmailAddress.isValid('foö@bücher.de', options), false);
+  });
 });
\ No newline at end of file
@@ -0,0 +1,70 @@
+from enum import Enum
+from typing import List, Optional
+
+class ParsingOptions:
+    """Options for parsing.
+
+    Only `is_lax` is bound so far; the other options of the Rust crate keep their defaults.
+
+    :param is_lax: Can be set to `True` or `False` to enable/disable obsolete parts parsing.
+    """
+
+    is_lax: bool
+
+    def __init__(self, is_lax: bool = False) -> None: ...
+
+class EmailAddress:
+    """An RFC 5322, and RFC 6532 compliant email address."""
+
+    def __init__(
+        self, local_part: str, domain: str, options: Optional[ParsingOptions] = None
+    ) -> None:
+        """Instantiates a new `EmailAddress`.
+
+        Raises `ValueError` if either the local part or domain is invalid.
+        """
+        ...
+    @staticmethod
+    def parse(input: str, options: Optional[ParsingOptions] = None) -> Optional[EmailAddress]:
+        """Parses a given string as an email address.
+
+        Returns `None` if the input is not a valid email address.
+        When `options` is not provided, strict parsing is used; i.e. obsolete parts as defined by RFC 5322 are not allowed.
+        """
+        ...
+    @staticmethod
+    def is_valid(input: str, options: Optional[ParsingOptions] = None) -> bool:
+        """Validates if the given `input` string is an email address or not."""
+        ...
+    @property
+    def local_part(self) -> str: ...
+    @property
+    def domain(self) -> str: ...
+
+class MultipleAt(Enum):
+    """How `scan` handles the candidates with several `@`."""
+
+    SPLIT = 0
+    """Splits the candidates into addresses where it is likely; the default."""
+    SKIP = 1
+    """Skips the candidates whose domain runs into another `@`, or whose local part directly follows one."""
+
+class AddressMatch:
+    """An email address found by `scan`; `text[match.start:match.end] == match.text`."""
+
+    start: int
+    end: int
+    text: str
+    email: EmailAddress
+
+def scan(
+    text: str,
+    options: Optional[ParsingOptions] = None,
+    multiple_at: MultipleAt = MultipleAt.SPLIT,
+    html: bool = False,
+) -> List[AddressMatch]:
+    """Finds the email addresses in a free text; e.g. a document, a log, or a mail body.
+
+    With `html=True`, the text is scanned as an HTML document.
+    """
+    ...
@@ -0,0 +1,230 @@
+//! Python bindings for the [email-address-parser](https://crates.io/crates/email-address-parser) crate.
+//!
+//! The extension module is built with [maturin](https://github.com/PyO3/maturin), and exposes the
+//! `EmailAddress` and `ParsingOptions` classes, and the `scan` function, with the same semantics as
+//! the Rust crate.
+//!
+//! ```python
+//! from email_address_parser import EmailAddress, ParsingOptions, scan
+//!
+//! email = EmailAddress.parse("foo@bar.com")
+//! assert email.local_part == "foo"
+//! assert email.domain == "bar.com"
+//!
+//! assert EmailAddress.parse("foo@-bar.com", ParsingOptions(is_lax=True)) is None
+//!
+//! [found] = scan("Write to <jane@example.com>.")
+//! assert (found.start, found.end, found.text) == (10, 26, "jane@example.com")
+//! ```
+
+use email_address_parser as core;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Options for parsing.
+///
+/// Only `is_lax` is bound so far; it can be set to `True` or `False` to enable/disable obsolete
+/// parts parsing, and defaults to `False`. The other options of the Rust crate, like the
+/// strictness of the domain labels, keep their defaults.
+#[pyclass(name = "ParsingOptions", module = "email_address_parser", frozen)]
+#[derive(Debug)]
+struct ParsingOptions {
+    #[pyo3(get)]
+    is_lax: bool,
+}
+
+#[pymethods]
+impl ParsingOptions {
+    #[new]
+    #[pyo3(signature = (is_lax = false))]
+    fn new(is_lax: bool) -> Self {
+        ParsingOptions { is_lax }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ParsingOptions(is_lax={})",
+            if self.is_lax { "True" } else { "False" }
+        )
+    }
+}
+
+impl ParsingOptions {
+    fn to_core(options: Option<&ParsingOptions>) -> Option<core::ParsingOptions> {
+        options.map(|options| core::ParsingOptions::new(options.is_lax))
+    }
+}
+
+/// Email address class.
+///
+/// Instances are immutable, hashable, and compare equal when both the local part and the
+/// domain are equal.
+#[pyclass(
+    name = "EmailAddress",
+    module = "email_address_parser",
+    frozen,
+    eq,
+    hash
+)]
+#[derive(PartialEq, Eq, Hash)]
+struct EmailAddress(core::EmailAddress);
+
+#[pymethods]
+impl EmailAddress {
+    /// Instantiates a new `EmailAddress` for a valid local part and domain.
+    ///
+    /// Raises `ValueError` if either the local part or the domain is invalid.
+    #[new]
+    #[pyo3(signature = (local_part, domain, options = None))]
+    fn new(local_part: &str, domain: &str, options: Option<&ParsingOptions>) -> PyResult<Self> {
+        core::EmailAddress::new(local_part, domain, ParsingOptions::to_core(options))
+            .map(EmailAddress)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Parses a given string as an email address.
+    ///
+    /// Returns an `EmailAddress` if the parsing is successful, else `None`.
+    #[staticmethod]
+    #[pyo3(signature = (input, options = None))]
+    fn parse(input: &str, options: Option<&ParsingOptions>) -> Option<Self> {
+        core::EmailAddress::parse(input, ParsingOptions::to_core(options)).map(EmailAddress)
+    }
+
+    /// Validates if the given `input` string is an email address or not.
+    ///
+    /// Unlike the `parse` method, it does not instantiate an `EmailAddress`.
+    #[staticmethod]
+    #[pyo3(signature = (input, options = None))]
+    fn is_valid(input: &str, options: Option<&ParsingOptions>) -> bool {
+        core::EmailAddress::is_valid(input, ParsingOptions::to_core(options))
+    }
+
+    /// The local part of the email address.
+    #[getter]
+    fn local_part(&self) -> &str {
+        self.0.get_local_part()
+    }
+
+    /// The domain of the email address.
+    #[getter]
+    fn domain(&self) -> &str {
+        self.0.get_domain()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EmailAddress({:?})", self.0.to_string())
+    }
+}
+
+/// How `scan` handles the candidates with several `@`; e.g. two addresses jammed together, like
+/// `foo@bar.comjane@example.org`.
+#[pyclass(
+    name = "MultipleAt",
+    module = "email_address_parser",
+    frozen,
+    eq,
+    eq_int,
+    from_py_object
+)]
+#[derive(Clone, Copy, PartialEq)]
+enum MultipleAt {
+    /// Splits the candidates into addresses where it is likely; the default.
+    #[pyo3(name = "SPLIT")]
+    Split,
+    /// Skips the candidates whose domain runs into another `@`, or whose local part directly
+    /// follows one.
+    #[pyo3(name = "SKIP")]
+    Skip,
+}
+
+impl From<MultipleAt> for core::MultipleAt {
+    fn from(multiple_at: MultipleAt) -> Self {
+        match multiple_at {
+            MultipleAt::Split => core::MultipleAt::Split,
+            MultipleAt::Skip => core::MultipleAt::Skip,
+        }
+    }
+}
+
+/// An email address found by `scan`.
+///
+/// `start` and `end` are the indices of the match in the scanned string, so that
+/// `text[match.start:match.end] == match.text`.
+#[pyclass(name = "AddressMatch", module = "email_address_parser", frozen)]
+struct AddressMatch {
+    #[pyo3(get)]
+    start: usize,
+    #[pyo3(get)]
+    end: usize,
+    #[pyo3(get)]
+    text: String,
+    #[pyo3(get)]
+    email: Py<EmailAddress>,
+}
+
+#[pymethods]
+impl AddressMatch {
+    fn __repr__(&self) -> String {
+        format!(
+            "AddressMatch(start={}, end={}, text={:?})",
+            self.start, self.end, self.text
+        )
+    }
+}
+
+/// Finds the email addresses in a free text; e.g. a document, a log, or a mail body.
+///
+/// With `html=True`, the text is scanned as an HTML document; i.e. the character references are
+/// decoded, and the content of the `script` and `style` elements is skipped.
+#[pyfunction]
+#[pyo3(signature = (text, options = None, multiple_at = MultipleAt::Split, html = false))]
+fn scan(
+    py: Python<'_>,
+    text: &str,
+    options: Option<&ParsingOptions>,
+    multiple_at: MultipleAt,
+    html: bool,
+) -> PyResult<Vec<AddressMatch>> {
+    let scanner = if html {
+        core::Scanner::html(text)
+    } else {
+        core::Scanner::new(text)
+    };
+    let scanner = scanner
+        .options(ParsingOptions::to_core(options).unwrap_or_default())
+        .multiple_at(multiple_at.into());
+    // Python indexes strings by code point, whereas the matches are delimited by byte offsets.
+    let (mut offset, mut index) = (0, 0);
+    let mut index_of = |byte_offset: usize| {
+        index += text[offset..byte_offset].chars().count();
+        offset = byte_offset;
+        index
+    };
+    scanner
+        .map(|found| {
+            let (start, end) = (index_of(found.start()), index_of(found.end()));
+            Ok(AddressMatch {
+                start,
+                end,
+                text: found.as_str().to_owned(),
+                email: Py::new(py, EmailAddress(found.into_email()))?,
+            })
+        })
+        .collect()
+}
+
+#[pymodule]
+#[pyo3(name = "email_address_parser")]
+fn email_address_parser_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<EmailAddress>()?;
+    module.add_class::<ParsingOptions>()?;
+    module.add_class::<MultipleAt>()?;
+    module.add_class::<AddressMatch>()?;
+    module.add_function(wrap_pyfunction!(scan, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+import unittest
+
+from email_address_parser import EmailAddress, MultipleAt, ParsingOptions, scan
+
+
+class IntegrationTest(unittest.TestCase):
+    def test_parses_valid_address(self):
+        email = EmailAddress.parse("foo@bar.com")
+        self.assertEqual(email.local_part, "foo")
+        self.assertEqual(email.domain, "bar.com")
+        self.assertEqual(str(email), "foo@bar.com")
+
+    def test_does_not_parse_invalid_address(self):
+        self.assertIsNone(EmailAddress.parse("foo@-bar.com", ParsingOptions(True)))
+        self.assertIsNone(EmailAddress.parse("test"))
+
+    def test_parses_obsolete_address_only_in_lax_mode(self):
+        email = "\r\n \r\n test@iana.org"
+        self.assertIsNone(EmailAddress.parse(email))
+        self.assertIsNotNone(EmailAddress.parse(email, ParsingOptions(is_lax=True)))
+
+    def test_instantiates_address(self):
+        self.assertEqual(str(EmailAddress("foo", "bar.com")), "foo@bar.com")
+        with self.assertRaises(ValueError):
+            EmailAddress("foo", "-bar.com")
+        with self.assertRaises(ValueError):
+            EmailAddress("-foo", "-bar.com")
+
+    def test_supports_unicode(self):
+        self.assertEqual(str(EmailAddress("foö", "bücher.de")), "foö@bücher.de")
+        self.assertEqual(str(EmailAddress.parse("foö@bücher.de")), "foö@bücher.de")
+        self.assertTrue(EmailAddress.is_valid("foö@bücher.de"))
+
+    def test_supports_equality_and_hashing(self):
+        first = EmailAddress.parse("foo@bar.com")
+        second = EmailAddress("foo", "bar.com")
+        self.assertEqual(first, second)
+        self.assertNotEqual(first, EmailAddress("foob", "ar.com"))
+        self.assertEqual(len({first, second}), 1)
+
+    def test_scans_text_for_addresses(self):
+        text = "Write to <jane.doe@example.com>, or \"john doe\"@example.org."
+        found = scan(text)
+        self.assertEqual(
+            [m.text for m in found], ["jane.doe@example.com", '"john doe"@example.org']
+        )
+        self.assertEqual((found[0].start, found[0].end), (10, 30))
+        self.assertEqual(found[0].email, EmailAddress("jane.doe", "example.com"))
+
+    def test_scans_with_code_point_indices(self):
+        text = "Grüße an jürgen@bücher.example!"
+        [found] = scan(text)
+        self.assertEqual(text[found.start : found.end], "jürgen@bücher.example")
+        self.assertEqual(found.email.domain, "bücher.example")
+
+    def test_scans_as_per_options(self):
+        text = "foo@bar.comjane@example.org"
+        self.assertEqual(
+            [m.text for m in scan(text)], ["foo@bar.com", "jane@example.org"]
+        )
+        self.assertEqual(scan(text, multiple_at=MultipleAt.SKIP), [])
+        html = "<p>joe&#64;example&period;com</p>"
+        self.assertEqual(scan(html), [])
+        [found] = scan(html, html=True)
+        self.assertEqual(str(found.email), "joe@example.com")
+        self.assertEqual(found.text, "joe&#64;example&period;com")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,129 @@
+use email_address_parser::Scanner;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub positions: bool,
+    pub all: bool,
+}
+
+/// Prints the email addresses found in texts, deduplicated by their canonical forms across all
+/// the texts, unless all the occurrences are asked for.
+#[derive(Debug, Default)]
+pub struct Extractor {
+    options: Options,
+    seen: HashSet<String>,
+    found: usize,
+}
+
+impl Extractor {
+    pub fn new(options: Options) -> Self {
+        Extractor {
+            options,
+            ..Extractor::default()
+        }
+    }
+
+    /// Returns the number of printed addresses.
+    pub fn found(&self) -> usize {
+        self.found
+    }
+
+    /// Scans a text, named after its source.
+    pub fn extract<W: Write>(&mut self, name: &str, text: &str, output: &mut W) -> io::Result<()> {
+        let mut cursor = Cursor::default();
+        for found in Scanner::new(text) {
+            if !self.options.all && !self.seen.insert(found.email().canonical().to_string()) {
+                continue;
+            }
+            self.found += 1;
+            if self.options.positions {
+                let (line, column) = cursor.advance(text, found.start());
+                writeln!(output, "{}:{}:{}\t{}", name, line, column, found.as_str())?;
+            } else {
+                writeln!(output, "{}", found.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the line and column of increasing byte offsets in a text; both are 1-based, and the
+/// column counts characters.
+#[derive(Debug)]
+struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl Cursor {
+    fn advance(&mut self, text: &str, offset: usize) -> (usize, usize) {
+        for c in text[self.offset..offset].chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = offset;
+        (self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(options: Options, texts: &[&str]) -> String {
+        let mut extractor = Extractor::new(options);
+        let mut output = Vec::new();
+        for (index, text) in texts.iter().enumerate() {
+            let name = format!("file{}", index + 1);
+            extractor.extract(&name, text, &mut output).unwrap();
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn prints_distinct_addresses() {
+        let texts = [
+            "From: foo@bar.com\nTo: <Baz@Example.org>, foo@BAR.com.",
+            "cc baz@example.org",
+        ];
+        assert_eq!(
+            extract(Options::default(), &texts),
+            "foo@bar.com\nBaz@Example.org\nbaz@example.org\n"
+        );
+
+        let all = Options {
+            all: true,
+            ..Options::default()
+        };
+        assert_eq!(extract(all, &texts).lines().count(), 4);
+    }
+
+    #[test]
+    fn prints_positions() {
+        let positions = Options {
+            positions: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            extract(positions, &["Frö: foo@bar.com\nTo: x@y.org", "z@y.org"]),
+            "file1:1:6\tfoo@bar.com\nfile1:2:5\tx@y.org\nfile2:1:1\tz@y.org\n"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use email_address_parser::{suggest, Diagnostic, EmailAddress, ParsingOptions};
+use std::io::{self, Write};
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub is_lax: bool,
+    /// Domains suggested on top of the popular ones.
+    pub dictionary: Vec<String>,
+}
+
+/// Counts of the fixed inputs.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub unchanged: usize,
+    pub fixed: usize,
+    pub invalid: usize,
+}
+
+/// How likely a suggestion is what the input meant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Confidence {
+    /// A domain typo was corrected; e.g. `gmial.com`. The input may be another, real domain.
+    Low,
+    /// A missing part was guessed; e.g. the dot of `gmailcom`.
+    Medium,
+    /// Only the noise around or in the address was removed; e.g. white space or `mailto:`.
+    High,
+}
+
+impl Confidence {
+    fn code(self) -> &'static str {
+        match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        }
+    }
+
+    /// Returns the confidence of a repair.
+    fn of(diagnostic: Diagnostic) -> Confidence {
+        match diagnostic {
+            Diagnostic::InsertedTopLevelDomainDot | Diagnostic::ReplacedSpacesWithDots => {
+                Confidence::Medium
+            }
+            _ => Confidence::High,
+        }
+    }
+}
+
+/// Repairs every input, and suggests a correction of the typos in its domain; and prints a tab
+/// separated line for every input that is not a valid address as it is: the input, its
+/// suggestion, the confidence in the suggestion (`high`, `medium`, or `low`), and the comma
+/// separated codes of the corrections; e.g. `foo@gmial.com<TAB>foo@gmail.com<TAB>low<TAB>domain_typo`.
+///
+/// An input that cannot be repaired has no suggestion, and the confidence `none`, followed by the
+/// code of its error.
+///
+/// Blank lines are skipped.
+pub fn run<I, W>(inputs: I, options: &Options, output: &mut W) -> io::Result<Summary>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+    W: Write,
+{
+    let parsing_options = ParsingOptions::new(options.is_lax)
+        .trim(true)
+        .strip_wrappers(true)
+        .map_to_ascii(true);
+    let dictionary: Vec<&str> = options.dictionary.iter().map(String::as_str).collect();
+    let mut summary = Summary::default();
+    for input in inputs {
+        let input = input?;
+        if input.trim().is_empty() {
+            continue;
+        }
+        let (email, repairs) =
+            match EmailAddress::parse_with_repair(&input, Some(parsing_options.clone())) {
+                Ok(repaired) => repaired,
+                Err(error) => {
+                    summary.invalid += 1;
+                    writeln!(output, "{}\t\tnone\t{}", input, error.kind().code())?;
+                    continue;
+                }
+            };
+        let mut codes: Vec<&str> = repairs.iter().map(Diagnostic::code).collect();
+        let mut confidence = repairs
+            .into_iter()
+            .map(Confidence::of)
+            .min()
+            .unwrap_or(Confidence::High);
+        let suggestion = match suggest(&email, &dictionary) {
+            Some(corrected) => {
+                codes.push("domain_typo");
+                confidence = Confidence::Low;
+                corrected.to_string()
+            }
+            None => email.to_string(),
+        };
+        if suggestion == input {
+            summary.unchanged += 1;
+            continue;
+        }
+        summary.fixed += 1;
+        writeln!(
+            output,
+            "{}\t{}\t{}\t{}",
+            input,
+            suggestion,
+            confidence.code(),
+            codes.join(",")
+        )?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(inputs: &[&str], options: &Options) -> (Summary, String) {
+        let mut output = Vec::new();
+        let inputs = inputs.iter().map(|input| Ok(String::from(*input)));
+        let summary = run(inputs, options, &mut output).unwrap();
+        (summary, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn prints_the_suggestions_with_their_confidence() {
+        let inputs = [
+            "foo@example.com",
+            " <foo@example.com>, ",
+            "",
+            "foo@gmailcom",
+            "foo@gmial.com",
+            "foo@@hotmial.com",
+            "foo@-example.com",
+        ];
+        let (summary, output) = fix(&inputs, &Options::default());
+        assert_eq!(
+            summary,
+            Summary {
+                unchanged: 1,
+                fixed: 4,
+                invalid: 1
+            }
+        );
+        assert_eq!(
+            output,
+            " <foo@example.com>, \tfoo@example.com\thigh\tstripped_trailing_punctuation,trimmed,stripped_angle_brackets\n\
+             foo@gmailcom\tfoo@gmail.com\tmedium\tinserted_top_level_domain_dot\n\
+             foo@gmial.com\tfoo@gmail.com\tlow\tdomain_typo\n\
+             foo@@hotmial.com\tfoo@hotmail.com\tlow\tcollapsed_separators,domain_typo\n\
+             foo@-example.com\t\tnone\tinvalid_domain\n"
+        );
+    }
+
+    #[test]
+    fn suggests_the_domains_of_the_dictionary() {
+        let options = Options {
+            dictionary: vec![String::from("hooli.xyz")],
+            ..Options::default()
+        };
+        let (_, output) = fix(&["jane@hooli.xzy"], &options);
+        assert_eq!(output, "jane@hooli.xzy\tjane@hooli.xyz\tlow\tdomain_typo\n");
+    }
+}
@@ -0,0 +1,345 @@
+//! `email-parser`: validates, extracts, and normalizes email addresses from the command line.
+
+mod extract;
+mod fix;
+mod mx;
+mod normalize;
+mod validate;
+
+use clap::{Args, Parser, Subcommand};
+use email_address_parser::Normalizer;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+use validate::{Format, Verdict};
+
+/// Validates RFC 5322 email addresses.
+///
+/// The addresses are taken from the arguments, or else from the standard input, one per line.
+/// For every address, a tab separated verdict is printed; e.g. `valid<TAB>foo@bar.com`, or
+/// `invalid<TAB>foo@-bar.com<TAB>reason`.
+///
+/// Exit status: 0 if all the addresses are valid, 1 if any of them is invalid, and 2 on errors.
+#[derive(Debug, Parser)]
+#[command(
+    name = "email-parser",
+    version,
+    about,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    validate: ValidateArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Finds the email addresses in free text, and prints every distinct one on its own line.
+    ///
+    /// Exit status: 0 if any address is found, 1 if none is, and 2 on errors.
+    Extract(ExtractArgs),
+
+    /// Repairs a list of addresses, one per line, and suggests corrections of the typos in their
+    /// domains; e.g. to review the corrections of a mailing list before applying them.
+    ///
+    /// A tab separated line is printed for every address that is not valid as it is: the address,
+    /// its suggestion, the confidence in the suggestion, and the codes of the corrections; e.g.
+    /// `foo@gmial.com<TAB>foo@gmail.com<TAB>low<TAB>domain_typo`. The confidence is `high` if
+    /// only the noise around the address (like white space, `mailto:`, or trailing punctuation)
+    /// was removed, `medium` if a missing dot was guessed, `low` if a domain typo was corrected,
+    /// and `none`, without suggestion, if the address cannot be repaired.
+    ///
+    /// Exit status: 0 if all the addresses are valid as they are, 1 otherwise, and 2 on errors.
+    Fix(FixArgs),
+
+    /// Normalizes a list of addresses, one per line, and prints the first one of every distinct
+    /// normalized form; e.g. to clean up a mailing list.
+    ///
+    /// By default, the local parts are lower cased, the tags (like `+news`) are removed, the rules
+    /// of well-known providers (like the insignificant dots of Gmail) are applied, and the domains
+    /// are converted to their ASCII form.
+    ///
+    /// Exit status: 0 if all the addresses are valid, 1 if any of them is invalid, and 2 on errors.
+    Normalize(NormalizeArgs),
+}
+
+#[derive(Debug, Args)]
+struct ValidateArgs {
+    /// Addresses to validate; if none is given, they are read from the standard input.
+    addresses: Vec<String>,
+
+    /// Accepts the obsolete syntax of RFC 5322 as well.
+    #[arg(long)]
+    lax: bool,
+
+    /// Prints nothing; only the exit status tells whether all the addresses are valid.
+    #[arg(short, long, conflicts_with = "only")]
+    quiet: bool,
+
+    /// Prints only the valid, or only the invalid addresses; as they are in the text format.
+    #[arg(long, value_enum)]
+    only: Option<Verdict>,
+
+    /// Output format; `jsonl` (or `json`) prints a JSON object per address, with its parts,
+    /// canonical form, and diagnostics, or the error codes and message. `csv` and `tsv` print a
+    /// header, and a record per address with the fields `input`, `valid`, `local_part`, `domain`,
+    /// `canonical`, `diagnostics` (separated by semicolons), and `error` (a code).
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Checks that the domains of the valid addresses accept email, as per their MX records (or
+    /// else their address records); an address is invalid if its domain does not exist, has a null
+    /// MX, or cannot be looked up.
+    #[arg(long)]
+    check_mx: bool,
+
+    /// Number of concurrent DNS lookups.
+    #[arg(long, value_name = "N", default_value_t = 16, requires = "check_mx")]
+    concurrency: usize,
+
+    /// Timeout of a DNS lookup, in seconds.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 5,
+        requires = "check_mx"
+    )]
+    timeout: u64,
+
+    /// Number of threads parsing the addresses; `0` for one per CPU. The verdicts are printed in
+    /// the order of the addresses either way.
+    #[arg(short, long, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
+    /// Prints the counts, the elapsed time, and the throughput to the standard error at the end.
+    #[arg(long)]
+    stats: bool,
+}
+
+#[derive(Debug, Args)]
+struct ExtractArgs {
+    /// Files to scan; `-`, or none, for the standard input.
+    files: Vec<PathBuf>,
+
+    /// Prefixes every address with the file, line, and column of its first occurrence;
+    /// e.g. `notes.txt:12:5<TAB>foo@bar.com`.
+    #[arg(short = 'n', long)]
+    positions: bool,
+
+    /// Prints every occurrence, instead of only the first one of each distinct address.
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Debug, Args)]
+struct FixArgs {
+    /// Files to read; `-`, or none, for the standard input.
+    files: Vec<PathBuf>,
+
+    /// Accepts the obsolete syntax of RFC 5322 as well.
+    #[arg(long)]
+    lax: bool,
+
+    /// Domain to suggest, on top of the popular ones; e.g. the domain of a company. Can be repeated.
+    #[arg(long = "domain", value_name = "DOMAIN")]
+    domains: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct NormalizeArgs {
+    /// Files to read; `-`, or none, for the standard input.
+    files: Vec<PathBuf>,
+
+    /// Accepts the obsolete syntax of RFC 5322 as well.
+    #[arg(long)]
+    lax: bool,
+
+    /// Keeps the case of the local parts.
+    #[arg(long)]
+    keep_case: bool,
+
+    /// Keeps the tags of the local parts.
+    #[arg(long)]
+    keep_tags: bool,
+
+    /// Does not apply the rules of well-known providers.
+    #[arg(long)]
+    no_provider_rules: bool,
+
+    /// Keeps the internationalized domains as they are.
+    #[arg(long)]
+    no_punycode: bool,
+
+    /// Prints the first spelling of every distinct address, instead of its normalized form.
+    #[arg(long)]
+    original: bool,
+
+    /// Writes a tab separated line to the file for every address, with the address, its normalized
+    /// form, and whether it is `kept`, a `duplicate`, or `invalid`.
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let stdout = io::stdout();
+    let mut output = io::BufWriter::new(stdout.lock());
+    let result = match cli.command {
+        Some(Command::Extract(args)) => extract(args, &mut output),
+        Some(Command::Fix(args)) => fix(args, &mut output),
+        Some(Command::Normalize(args)) => normalize(args, &mut output),
+        None => validate(cli.validate, &mut output),
+    };
+    match result.and_then(|is_success| output.flush().map(|_| is_success)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        // The reader of the output (e.g. `head`) is gone; the verdict so far still holds.
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("email-parser: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether all the addresses are valid.
+fn validate<W: Write>(args: ValidateArgs, output: &mut W) -> io::Result<bool> {
+    let options = validate::Options {
+        is_lax: args.lax,
+        quiet: args.quiet,
+        only: args.only,
+        format: args.format,
+        jobs: match args.jobs {
+            0 => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+            jobs => jobs,
+        },
+    };
+    let lookup = if args.check_mx {
+        Some(mx::DnsLookup::new(Duration::from_secs(args.timeout))?)
+    } else {
+        None
+    };
+    let mut checker = lookup
+        .as_ref()
+        .map(|lookup| mx::MxChecker::new(lookup, args.concurrency));
+    let start = Instant::now();
+    let summary = if args.addresses.is_empty() {
+        let stdin = io::stdin();
+        let lines = stdin.lock().lines();
+        validate::run(lines, &options, checker.as_mut(), output)?
+    } else {
+        let lines = args.addresses.into_iter().map(Ok);
+        validate::run(lines, &options, checker.as_mut(), output)?
+    };
+    if args.stats {
+        let elapsed = start.elapsed().as_secs_f64();
+        let total = summary.valid + summary.invalid;
+        eprintln!(
+            "email-parser: {} addresses ({} valid, {} invalid) in {:.2} s; {:.0} addresses/s",
+            total,
+            summary.valid,
+            summary.invalid,
+            elapsed,
+            total as f64 / elapsed.max(f64::EPSILON)
+        );
+    }
+    Ok(summary.invalid == 0)
+}
+
+/// Returns whether all the addresses are valid as they are.
+fn fix<W: Write>(args: FixArgs, output: &mut W) -> io::Result<bool> {
+    let options = fix::Options {
+        is_lax: args.lax,
+        dictionary: args.domains,
+    };
+    let readers = open(args.files)?;
+    let lines = readers.into_iter().flat_map(|reader| reader.lines());
+    let summary = fix::run(lines, &options, output)?;
+    Ok(summary.fixed == 0 && summary.invalid == 0)
+}
+
+/// Returns whether all the addresses are valid.
+fn normalize<W: Write>(args: NormalizeArgs, output: &mut W) -> io::Result<bool> {
+    let options = normalize::Options {
+        is_lax: args.lax,
+        normalizer: Normalizer::new()
+            .lowercase_local_part(!args.keep_case)
+            .strip_tags(!args.keep_tags)
+            .provider_rules(!args.no_provider_rules)
+            .punycode(!args.no_punycode),
+        original: args.original,
+    };
+    let readers = open(args.files)?;
+    let mut report = match args.report {
+        Some(file) => {
+            let writer = fs::File::create(&file).map_err(|error| with_path(&file, error))?;
+            Some(io::BufWriter::new(writer))
+        }
+        None => None,
+    };
+    let lines = readers.into_iter().flat_map(|reader| reader.lines());
+    let summary = normalize::run(
+        lines,
+        &options,
+        output,
+        report.as_mut().map(|report| report as &mut dyn Write),
+    )?;
+    if let Some(mut report) = report {
+        report.flush()?;
+    }
+    Ok(summary.invalid == 0)
+}
+
+/// Returns whether any address is found.
+fn extract<W: Write>(args: ExtractArgs, output: &mut W) -> io::Result<bool> {
+    let mut extractor = extract::Extractor::new(extract::Options {
+        positions: args.positions,
+        all: args.all,
+    });
+    for file in with_stdin(args.files) {
+        let (name, bytes) = if file.as_os_str() == "-" {
+            let mut bytes = Vec::new();
+            io::stdin().lock().read_to_end(&mut bytes)?;
+            (String::from("<stdin>"), bytes)
+        } else {
+            let bytes = fs::read(&file).map_err(|error| with_path(&file, error))?;
+            (file.display().to_string(), bytes)
+        };
+        extractor.extract(&name, &String::from_utf8_lossy(&bytes), output)?;
+    }
+    Ok(extractor.found() > 0)
+}
+
+/// Opens the files, or the standard input (`-`), for reading.
+fn open(files: Vec<PathBuf>) -> io::Result<Vec<Box<dyn BufRead>>> {
+    let mut readers: Vec<Box<dyn BufRead>> = Vec::new();
+    for file in with_stdin(files) {
+        if file.as_os_str() == "-" {
+            readers.push(Box::new(io::stdin().lock()));
+        } else {
+            let reader = fs::File::open(&file).map_err(|error| with_path(&file, error))?;
+            readers.push(Box::new(io::BufReader::new(reader)));
+        }
+    }
+    Ok(readers)
+}
+
+/// Returns the files, or the standard input (`-`) if there is none.
+fn with_stdin(files: Vec<PathBuf>) -> Vec<PathBuf> {
+    if files.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        files
+    }
+}
+
+/// Prefixes the error message with the path of the file.
+fn with_path(file: &Path, error: io::Error) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {}", file.display(), error))
+}
@@ -0,0 +1,202 @@
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+use hickory_resolver::proto::op::ResponseCode;
+use hickory_resolver::TokioAsyncResolver;
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Whether a domain accepts email, as per its DNS records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MxStatus {
+    /// The domain has MX records, or else address records (an implicit MX; RFC 5321, section 5.1).
+    Accepts,
+    /// The domain does not exist, has neither MX nor address records, or has a null MX (RFC 7505).
+    NoMail(String),
+    /// The lookup failed; e.g. it timed out.
+    Unknown(String),
+}
+
+impl MxStatus {
+    pub fn code(&self) -> &'static str {
+        match self {
+            MxStatus::Accepts => "accepts",
+            MxStatus::NoMail(_) => "no_mail",
+            MxStatus::Unknown(_) => "unknown",
+        }
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            MxStatus::Accepts => None,
+            MxStatus::NoMail(message) | MxStatus::Unknown(message) => Some(message),
+        }
+    }
+}
+
+/// Looks up whether a domain accepts email.
+pub trait Lookup: Sync {
+    fn lookup(&self, domain: &str) -> MxStatus;
+}
+
+/// Looks up the DNS records with the resolvers of the system configuration.
+pub struct DnsLookup {
+    runtime: Runtime,
+    resolver: TokioAsyncResolver,
+}
+
+impl DnsLookup {
+    pub fn new(timeout: Duration) -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        let (config, mut options) = hickory_resolver::system_conf::read_system_conf()
+            .unwrap_or_else(|_| (ResolverConfig::default(), ResolverOpts::default()));
+        options.timeout = timeout;
+        options.attempts = 1;
+        let resolver = {
+            let _guard = runtime.enter();
+            TokioAsyncResolver::tokio(config, options)
+        };
+        Ok(DnsLookup { runtime, resolver })
+    }
+
+    fn status(domain: &str, error: &ResolveError) -> MxStatus {
+        match error.kind() {
+            ResolveErrorKind::NoRecordsFound { response_code, .. }
+                if *response_code == ResponseCode::NXDomain =>
+            {
+                MxStatus::NoMail(format!("Domain '{}' does not exist.", domain))
+            }
+            ResolveErrorKind::NoRecordsFound { .. } => MxStatus::NoMail(format!(
+                "Domain '{}' has neither MX nor address records.",
+                domain
+            )),
+            _ => MxStatus::Unknown(format!("Could not look up domain '{}': {}", domain, error)),
+        }
+    }
+}
+
+impl Lookup for DnsLookup {
+    fn lookup(&self, domain: &str) -> MxStatus {
+        // The trailing dot keeps the resolver from appending the search domains.
+        let name = format!("{}.", domain);
+        self.runtime.block_on(async {
+            match self.resolver.mx_lookup(name.as_str()).await {
+                Ok(records) => {
+                    if records.iter().all(|mx| mx.exchange().is_root()) {
+                        MxStatus::NoMail(format!(
+                            "Domain '{}' does not accept email (null MX).",
+                            domain
+                        ))
+                    } else {
+                        MxStatus::Accepts
+                    }
+                }
+                Err(error) if matches!(error.kind(), ResolveErrorKind::NoRecordsFound { .. }) => {
+                    match self.resolver.lookup_ip(name.as_str()).await {
+                        Ok(_) => MxStatus::Accepts,
+                        Err(_) => DnsLookup::status(domain, &error),
+                    }
+                }
+                Err(error) => DnsLookup::status(domain, &error),
+            }
+        })
+    }
+}
+
+/// Checks the domains concurrently, and caches the results.
+pub struct MxChecker<'l> {
+    lookup: &'l dyn Lookup,
+    concurrency: usize,
+    cache: HashMap<String, MxStatus>,
+}
+
+impl<'l> MxChecker<'l> {
+    pub fn new(lookup: &'l dyn Lookup, concurrency: usize) -> Self {
+        MxChecker {
+            lookup,
+            concurrency: concurrency.max(1),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Looks up the domains that are not cached yet, with up to `concurrency` lookups at a time.
+    ///
+    /// Domain literals are not looked up; they are taken to accept email.
+    pub fn check_all<'d, I: IntoIterator<Item = &'d str>>(&mut self, domains: I) {
+        let mut pending: Vec<&str> = Vec::new();
+        for domain in domains {
+            if domain.starts_with('[') {
+                self.cache.insert(String::from(domain), MxStatus::Accepts);
+            } else if !self.cache.contains_key(domain) && !pending.contains(&domain) {
+                pending.push(domain);
+            }
+        }
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(pending.len()));
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(pending.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let domain = match pending.get(index) {
+                        Some(domain) => *domain,
+                        None => break,
+                    };
+                    let status = self.lookup.lookup(domain);
+                    results.lock().unwrap().push((String::from(domain), status));
+                });
+            }
+        });
+        self.cache.extend(results.into_inner().unwrap());
+    }
+
+    /// Returns the status of a domain that has been checked.
+    pub fn status(&self, domain: &str) -> Option<&MxStatus> {
+        self.cache.get(domain)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Looks up the domains in a fixed table, counting the lookups.
+    #[derive(Default)]
+    pub struct FakeLookup {
+        pub lookups: AtomicUsize,
+    }
+
+    impl Lookup for FakeLookup {
+        fn lookup(&self, domain: &str) -> MxStatus {
+            self.lookups.fetch_add(1, Ordering::Relaxed);
+            match domain {
+                "nomail.example" => MxStatus::NoMail(String::from("no mail")),
+                "timeout.example" => MxStatus::Unknown(String::from("timed out")),
+                _ => MxStatus::Accepts,
+            }
+        }
+    }
+
+    #[test]
+    fn checks_each_domain_once() {
+        let lookup = FakeLookup::default();
+        let mut checker = MxChecker::new(&lookup, 4);
+        checker.check_all(vec!["bar.com", "nomail.example", "bar.com", "[127.0.0.1]"]);
+        checker.check_all(vec!["bar.com", "timeout.example"]);
+        assert_eq!(lookup.lookups.load(Ordering::Relaxed), 3);
+        assert_eq!(checker.status("bar.com"), Some(&MxStatus::Accepts));
+        assert_eq!(checker.status("[127.0.0.1]"), Some(&MxStatus::Accepts));
+        assert_eq!(checker.status("nomail.example").unwrap().code(), "no_mail");
+        assert_eq!(
+            checker.status("timeout.example").unwrap().message(),
+            Some("timed out")
+        );
+        assert_eq!(checker.status("qux.com"), None);
+    }
+}
@@ -0,0 +1,141 @@
+use email_address_parser::{EmailAddress, Normalizer, ParsingOptions};
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub is_lax: bool,
+    pub normalizer: Normalizer,
+    /// Prints the first spelling of every survivor, instead of its normalized form.
+    pub original: bool,
+}
+
+/// Counts of the normalized inputs.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub kept: usize,
+    pub duplicate: usize,
+    pub invalid: usize,
+}
+
+/// Normalizes every input, and prints the first one of every distinct normalized form.
+///
+/// If a report is given, a tab separated line is written to it for every input, with the input,
+/// its normalized form (empty if it is invalid), and its status: `kept`, `duplicate`, or `invalid`.
+///
+/// The inputs are trimmed, and blank lines are skipped.
+pub fn run<I, W>(
+    inputs: I,
+    options: &Options,
+    output: &mut W,
+    mut report: Option<&mut dyn Write>,
+) -> io::Result<Summary>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+    W: Write,
+{
+    let mut summary = Summary::default();
+    let mut seen = HashSet::new();
+    for input in inputs {
+        let input = input?;
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let parsing_options = Some(ParsingOptions::new(options.is_lax));
+        let (normalized, status) = match EmailAddress::parse(input, parsing_options) {
+            Some(email) => {
+                let normalized = options.normalizer.normalize(&email).to_string();
+                if seen.insert(normalized.clone()) {
+                    summary.kept += 1;
+                    if options.original {
+                        writeln!(output, "{}", input)?;
+                    } else {
+                        writeln!(output, "{}", normalized)?;
+                    }
+                    (normalized, "kept")
+                } else {
+                    summary.duplicate += 1;
+                    (normalized, "duplicate")
+                }
+            }
+            None => {
+                summary.invalid += 1;
+                (String::new(), "invalid")
+            }
+        };
+        if let Some(report) = report.as_mut() {
+            writeln!(report, "{}\t{}\t{}", input, normalized, status)?;
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(inputs: &[&str], options: &Options) -> (Summary, String, String) {
+        let mut output = Vec::new();
+        let mut report = Vec::new();
+        let inputs = inputs.iter().map(|input| Ok(String::from(*input)));
+        let summary = run(inputs, options, &mut output, Some(&mut report)).unwrap();
+        (
+            summary,
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(report).unwrap(),
+        )
+    }
+
+    #[test]
+    fn prints_survivors_and_report() {
+        let options = Options {
+            normalizer: Normalizer::new()
+                .lowercase_local_part(true)
+                .strip_tags(true)
+                .provider_rules(true),
+            ..Options::default()
+        };
+        let inputs = [
+            "Jane.Doe@gmail.com",
+            " janedoe+news@googlemail.com ",
+            "",
+            "foo@-bar.com",
+            "bob@Example.org",
+        ];
+        let (summary, output, report) = normalize(&inputs, &options);
+        assert_eq!(
+            summary,
+            Summary {
+                kept: 2,
+                duplicate: 1,
+                invalid: 1
+            }
+        );
+        assert_eq!(output, "janedoe@gmail.com\nbob@example.org\n");
+        assert_eq!(
+            report,
+            "Jane.Doe@gmail.com\tjanedoe@gmail.com\tkept\n\
+             janedoe+news@googlemail.com\tjanedoe@gmail.com\tduplicate\n\
+             foo@-bar.com\t\tinvalid\n\
+             bob@Example.org\tbob@example.org\tkept\n"
+        );
+
+        let original = Options {
+            original: true,
+            ..options
+        };
+        let (_, output, _) = normalize(&inputs, &original);
+        assert_eq!(output, "Jane.Doe@gmail.com\nbob@Example.org\n");
+    }
+
+    #[test]
+    fn only_canonicalizes_by_default() {
+        let (summary, output, _) = normalize(
+            &["Foo@BAR.com", "foo@bar.com", "foo+x@bar.com"],
+            &Options::default(),
+        );
+        assert_eq!(summary.kept, 3);
+        assert_eq!(output, "Foo@bar.com\nfoo@bar.com\nfoo+x@bar.com\n");
+    }
+}
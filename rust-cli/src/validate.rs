@@ -0,0 +1,515 @@
+use crate::mx::{MxChecker, MxStatus};
+use clap::ValueEnum;
+use email_address_parser::{Diagnostic, EmailAddress, ParseError, ParsingOptions};
+use serde_json::json;
+use std::io::{self, Write};
+use std::thread;
+
+/// Verdict on an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Verdict {
+    Valid,
+    Invalid,
+}
+
+/// Output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Tab separated verdicts.
+    #[default]
+    Text,
+    /// One JSON object per input.
+    #[value(alias = "json")]
+    Jsonl,
+    /// Comma separated values, with a header; see `RECORD_FIELDS`.
+    Csv,
+    /// Tab separated values, with a header; see `RECORD_FIELDS`.
+    Tsv,
+}
+
+/// The fields of the records of the CSV and TSV formats.
+const RECORD_FIELDS: [&str; 7] = [
+    "input",
+    "valid",
+    "local_part",
+    "domain",
+    "canonical",
+    "diagnostics",
+    "error",
+];
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub is_lax: bool,
+    pub quiet: bool,
+    pub only: Option<Verdict>,
+    pub format: Format,
+    /// Number of threads parsing the inputs; `0` is taken as `1`.
+    pub jobs: usize,
+}
+
+/// Counts of the validated inputs.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub valid: usize,
+    pub invalid: usize,
+}
+
+/// Number of inputs parsed ahead per thread, or when the domains are checked, so that the work runs
+/// concurrently; the memory use is bounded by the batch, whatever the number of inputs is.
+const BATCH_SIZE: usize = 1024;
+
+/// The result of parsing an input; a valid address, with its diagnostics.
+type ParseResult = Result<(EmailAddress, Vec<Diagnostic>), ParseError>;
+
+/// A parsed input, with the canonical domain of a valid address.
+type Parsed = (ParseResult, Option<String>);
+
+fn parse(input: &str, is_lax: bool) -> Parsed {
+    let result = EmailAddress::parse_with_diagnostics(input, Some(ParsingOptions::new(is_lax)));
+    let domain = result
+        .as_ref()
+        .ok()
+        .map(|(email, _)| String::from(email.canonical().get_domain()));
+    (result, domain)
+}
+
+/// Parses the inputs, split evenly across the given number of threads.
+fn parse_all(inputs: &[String], is_lax: bool, jobs: usize) -> Vec<Parsed> {
+    if jobs <= 1 {
+        return inputs.iter().map(|input| parse(input, is_lax)).collect();
+    }
+    let chunk_size = inputs.len().div_ceil(jobs);
+    thread::scope(|scope| {
+        let threads: Vec<_> = inputs
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|input| parse(input, is_lax))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .flat_map(|thread| thread.join().expect("parsing never panics"))
+            .collect()
+    })
+}
+
+/// Validates every input, and prints the verdicts as per the options.
+///
+/// If a checker is given, a valid address is only taken to be valid if its domain accepts email.
+/// The verdicts are printed in the order of the inputs, whatever the number of jobs is.
+/// Blank lines are skipped.
+pub fn run<I, W>(
+    inputs: I,
+    options: &Options,
+    mut checker: Option<&mut MxChecker>,
+    output: &mut W,
+) -> io::Result<Summary>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+    W: Write,
+{
+    let jobs = options.jobs.max(1);
+    let batch_size = if checker.is_some() || jobs > 1 {
+        BATCH_SIZE * jobs
+    } else {
+        1
+    };
+    let mut inputs = inputs.into_iter();
+    let mut summary = Summary::default();
+    if !options.quiet {
+        write_record(output, options.format, &RECORD_FIELDS)?;
+    }
+    loop {
+        let mut batch = Vec::new();
+        for input in inputs.by_ref() {
+            let input = input?;
+            if input.trim().is_empty() {
+                continue;
+            }
+            batch.push(input);
+            if batch.len() == batch_size {
+                break;
+            }
+        }
+        if batch.is_empty() {
+            return Ok(summary);
+        }
+        let parsed = parse_all(&batch, options.is_lax, jobs);
+        let batch: Vec<_> = batch
+            .into_iter()
+            .zip(parsed)
+            .map(|(input, (result, domain))| (input, result, domain))
+            .collect();
+        if let Some(checker) = checker.as_mut() {
+            checker.check_all(batch.iter().filter_map(|(_, _, domain)| domain.as_deref()));
+        }
+        for (input, result, domain) in batch {
+            let mx = match (&checker, domain) {
+                (Some(checker), Some(domain)) => checker.status(&domain),
+                _ => None,
+            };
+            let verdict = match (&result, mx) {
+                (Ok(_), None) | (Ok(_), Some(MxStatus::Accepts)) => {
+                    summary.valid += 1;
+                    Verdict::Valid
+                }
+                _ => {
+                    summary.invalid += 1;
+                    Verdict::Invalid
+                }
+            };
+            if options.quiet {
+                continue;
+            }
+            match (options.format, options.only, result) {
+                (_, Some(only), _) if only != verdict => (),
+                (Format::Jsonl, _, result) => writeln!(output, "{}", to_json(&input, &result, mx))?,
+                (Format::Csv | Format::Tsv, _, result) => {
+                    write_record(output, options.format, &to_record(&input, &result, mx))?
+                }
+                (Format::Text, Some(_), _) => writeln!(output, "{}", input)?,
+                (Format::Text, None, Ok(_)) => match mx.and_then(MxStatus::message) {
+                    Some(message) => writeln!(output, "invalid\t{}\t{}", input, message)?,
+                    None => writeln!(output, "valid\t{}", input)?,
+                },
+                (Format::Text, None, Err(error)) => {
+                    writeln!(output, "invalid\t{}\t{}", input, error)?
+                }
+            }
+        }
+    }
+}
+
+/// Describes the result of parsing as a JSON object; the parts, the canonical form, and the codes
+/// of the diagnostics of a valid address, along with the status of its domain if it is checked, or
+/// the error codes, the message, and the byte span of the offending part otherwise.
+fn to_json(input: &str, result: &ParseResult, mx: Option<&MxStatus>) -> serde_json::Value {
+    match result {
+        Ok((email, diagnostics)) => {
+            let codes: Vec<&str> = diagnostics.iter().map(Diagnostic::code).collect();
+            let mut json = json!({
+                "input": input,
+                "valid": true,
+                "local_part": email.get_local_part(),
+                "domain": email.get_domain(),
+                "canonical": email.canonical().to_string(),
+                "diagnostics": codes,
+            });
+            if let Some(mx) = mx {
+                json["mx"] = json!({ "status": mx.code(), "message": mx.message() });
+            }
+            json
+        }
+        Err(error) => json!({
+            "input": input,
+            "valid": false,
+            "error": {
+                "code": error.kind().code(),
+                "hint": error.hint().map(|hint| hint.code()),
+                "message": error.to_string(),
+                "span": [error.span().start, error.span().end],
+            },
+        }),
+    }
+}
+
+/// Describes the result of parsing as a record of `RECORD_FIELDS`; the diagnostics are separated
+/// by semicolons, and the error is the code of the parse error, or of the status of the domain if
+/// it does not accept email.
+fn to_record(input: &str, result: &ParseResult, mx: Option<&MxStatus>) -> [String; 7] {
+    match result {
+        Ok((email, diagnostics)) => {
+            let codes: Vec<&str> = diagnostics.iter().map(Diagnostic::code).collect();
+            let error = mx.filter(|mx| mx.message().is_some()).map(MxStatus::code);
+            [
+                String::from(input),
+                error.is_none().to_string(),
+                String::from(email.get_local_part()),
+                String::from(email.get_domain()),
+                email.canonical().to_string(),
+                codes.join(";"),
+                String::from(error.unwrap_or_default()),
+            ]
+        }
+        Err(error) => [
+            String::from(input),
+            String::from("false"),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::from(error.kind().code()),
+        ],
+    }
+}
+
+/// Writes a record in the CSV (as per RFC 4180) or TSV format; nothing in the other formats.
+///
+/// A CSV field is quoted if it has a comma, a double quote, or a line break. In a TSV field, the
+/// tabs, the line breaks, and the backslashes are escaped with a backslash; e.g. `\t`.
+fn write_record<W: Write, F: AsRef<str>>(
+    output: &mut W,
+    format: Format,
+    fields: &[F],
+) -> io::Result<()> {
+    let (separator, escape): (&str, fn(&str) -> String) = match format {
+        Format::Csv => (",", |field| {
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                String::from(field)
+            }
+        }),
+        Format::Tsv => ("\t", |field| {
+            field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        }),
+        Format::Text | Format::Jsonl => return Ok(()),
+    };
+    let fields: Vec<String> = fields.iter().map(|field| escape(field.as_ref())).collect();
+    writeln!(output, "{}", fields.join(separator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mx::tests::FakeLookup;
+    use std::sync::atomic::Ordering;
+
+    fn validate(inputs: &[&str], options: &Options) -> (Summary, String) {
+        let mut output = Vec::new();
+        let inputs = inputs.iter().map(|input| Ok(String::from(*input)));
+        let summary = run(inputs, options, None, &mut output).unwrap();
+        (summary, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn prints_verdicts() {
+        let (summary, output) = validate(
+            &["foo@bar.com", "", "foo@-bar.com", "test . test@iana.org"],
+            &Options::default(),
+        );
+        assert_eq!(
+            summary,
+            Summary {
+                valid: 1,
+                invalid: 2
+            }
+        );
+        assert_eq!(
+            output,
+            "valid\tfoo@bar.com\n\
+             invalid\tfoo@-bar.com\tInvalid domain '-bar.com'. A domain label can neither start nor end with a hyphen.\n\
+             invalid\ttest . test@iana.org\tInvalid local part 'test . test'. The address contains obsolete syntax, and can only be parsed with lax parsing options.\n"
+        );
+    }
+
+    #[test]
+    fn lax_mode_accepts_obsolete_syntax() {
+        let options = Options {
+            is_lax: true,
+            ..Options::default()
+        };
+        let (summary, _) = validate(&["test . test@iana.org"], &options);
+        assert_eq!(
+            summary,
+            Summary {
+                valid: 1,
+                invalid: 0
+            }
+        );
+    }
+
+    #[test]
+    fn filters_and_quiet_mode() {
+        let inputs = ["foo@bar.com", "foo@-bar.com"];
+        let only = |only| Options {
+            only: Some(only),
+            ..Options::default()
+        };
+        assert_eq!(validate(&inputs, &only(Verdict::Valid)).1, "foo@bar.com\n");
+        assert_eq!(
+            validate(&inputs, &only(Verdict::Invalid)).1,
+            "foo@-bar.com\n"
+        );
+
+        let quiet = Options {
+            quiet: true,
+            ..Options::default()
+        };
+        assert_eq!(validate(&inputs, &quiet).1, "");
+    }
+
+    #[test]
+    fn prints_json_lines() {
+        let options = Options {
+            format: Format::Jsonl,
+            ..Options::default()
+        };
+        let (_, output) = validate(&["\"Foo\"@BAR.com", "foo@-bar.com"], &options);
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            json!({
+                "input": "\"Foo\"@BAR.com",
+                "valid": true,
+                "local_part": "\"Foo\"",
+                "domain": "BAR.com",
+                "canonical": "Foo@bar.com",
+                "diagnostics": [],
+            })
+        );
+        assert_eq!(
+            lines[1],
+            json!({
+                "input": "foo@-bar.com",
+                "valid": false,
+                "error": {
+                    "code": "invalid_domain",
+                    "hint": "misplaced_hyphen",
+                    "message": "Invalid domain '-bar.com'. A domain label can neither start nor end with a hyphen.",
+                    "span": [4, 12],
+                },
+            })
+        );
+
+        let options = Options {
+            format: Format::Jsonl,
+            only: Some(Verdict::Invalid),
+            ..Options::default()
+        };
+        assert_eq!(
+            validate(&["foo@bar.com", "foo@-bar.com"], &options)
+                .1
+                .lines()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn prints_csv_and_tsv_records() {
+        let inputs = [
+            "\"Foo,Bar\"@BAR.com",
+            "@relay.example:foo@bar.com",
+            "foo@-bar.com",
+        ];
+        let with_format = |format| Options {
+            is_lax: true,
+            format,
+            ..Options::default()
+        };
+        assert_eq!(
+            validate(&inputs, &with_format(Format::Csv)).1,
+            "input,valid,local_part,domain,canonical,diagnostics,error\n\
+             \"\"\"Foo,Bar\"\"@BAR.com\",true,\"\"\"Foo,Bar\"\"\",BAR.com,\"\"\"Foo,Bar\"\"@bar.com\",,\n\
+             @relay.example:foo@bar.com,true,foo,bar.com,foo@bar.com,obsolete_route,\n\
+             foo@-bar.com,false,,,,,invalid_domain\n"
+        );
+        assert_eq!(
+            validate(&["a\tb@bar.com", "foo@bar.com"], &with_format(Format::Tsv)).1,
+            "input\tvalid\tlocal_part\tdomain\tcanonical\tdiagnostics\terror\n\
+             a\\tb@bar.com\tfalse\t\t\t\t\tinvalid_local_part\n\
+             foo@bar.com\ttrue\tfoo\tbar.com\tfoo@bar.com\t\t\n"
+        );
+        let quiet = Options {
+            quiet: true,
+            ..with_format(Format::Csv)
+        };
+        assert_eq!(validate(&inputs, &quiet).1, "");
+    }
+
+    #[test]
+    fn input_error_is_propagated() {
+        let inputs = vec![Err(io::Error::new(io::ErrorKind::InvalidData, "not UTF-8"))];
+        assert!(run(inputs, &Options::default(), None, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn checks_domains() {
+        let lookup = FakeLookup::default();
+        let mut checker = MxChecker::new(&lookup, 2);
+        let inputs = [
+            "foo@bar.com",
+            "foo@NoMail.example",
+            "bar@bar.com",
+            "foo@timeout.example",
+        ];
+        let mut output = Vec::new();
+        let summary = run(
+            inputs.iter().map(|input| Ok(String::from(*input))),
+            &Options::default(),
+            Some(&mut checker),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                valid: 2,
+                invalid: 2
+            }
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "valid\tfoo@bar.com\n\
+             invalid\tfoo@NoMail.example\tno mail\n\
+             valid\tbar@bar.com\n\
+             invalid\tfoo@timeout.example\ttimed out\n"
+        );
+        assert_eq!(lookup.lookups.load(Ordering::Relaxed), 3);
+
+        let jsonl = Options {
+            format: Format::Jsonl,
+            ..Options::default()
+        };
+        let mut output = Vec::new();
+        let input = Ok(String::from("foo@nomail.example"));
+        run(vec![input], &jsonl, Some(&mut checker), &mut output).unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            line["mx"],
+            json!({ "status": "no_mail", "message": "no mail" })
+        );
+    }
+
+    #[test]
+    fn parallel_jobs_keep_the_order() {
+        let inputs: Vec<String> = (0..5000)
+            .map(|index| match index % 3 {
+                0 => format!("user{}@bar.com", index),
+                1 => format!("user{}@-bar.com", index),
+                _ => String::from(""),
+            })
+            .collect();
+        let validate_with = |jobs| {
+            let options = Options {
+                jobs,
+                ..Options::default()
+            };
+            let strings: Vec<&str> = inputs.iter().map(String::as_str).collect();
+            validate(&strings, &options)
+        };
+        let (summary, output) = validate_with(4);
+        assert_eq!(
+            summary,
+            Summary {
+                valid: 1667,
+                invalid: 1667
+            }
+        );
+        assert_eq!(output, validate_with(1).1);
+    }
+}
@@ -1,3 +1,10 @@
+#![allow(
+    clippy::expect_fun_call,
+    clippy::format_in_format_args,
+    clippy::match_like_matches_macro,
+    clippy::redundant_closure
+)]
+
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::env;
@@ -37,6 +44,15 @@ fn main() {
     );
 
     create_is_email_tests(&mut content, &test_data_root);
+    let is_email_corpus = create_is_email_corpus(&test_data_root, path::Path::new(&out_dir));
+    create_fixture_corpus(
+        path::Path::new(&out_dir),
+        &valid_local_parts,
+        &valid_domains,
+        &invalid_local_parts,
+        &invalid_domains,
+        &is_email_corpus,
+    );
 
     create_valid_instantiation_tests(&mut content, &valid_local_parts, &valid_domains);
     create_invalid_instantiation_tests(
@@ -61,6 +77,7 @@ fn main() {
     println!("cargo:rerun-if-changed=resources/.test_data/valid_domains.txt");
     println!("cargo:rerun-if-changed=resources/.test_data/invalid_local_parts.txt");
     println!("cargo:rerun-if-changed=resources/.test_data/invalid_domains.txt");
+    println!("cargo:rerun-if-changed=.test_data/isemail_tests.xml");
 }
 
 fn create_case(
@@ -167,7 +184,7 @@ fn create_is_email_tests(content: &mut String, test_data_root: &path::Path) {
     "
 macro_rules! generate_is_email_test {
   ($($case:ident: ($email:literal, $is_email:literal),)+) => {
-    #[cfg(test)]
+    #[cfg(all(test, not(feature = \"strict-only\")))]
     mod is_email_parsing_tests {
       use email_address_parser::*;
       use wasm_bindgen_test::*;
@@ -177,6 +194,15 @@ macro_rules! generate_is_email_test {
         #[wasm_bindgen_test]
         fn $case() {
           let email = EmailAddress::parse(&$email, Some(ParsingOptions::new(true)));
+          // With the `heapless` feature, the parts longer than their capacities do not parse.
+          if cfg!(feature = \"heapless\") && $is_email && email.is_none() {
+            let error = EmailAddress::try_parse(&$email, Some(ParsingOptions::new(true))).unwrap_err();
+            assert!(
+              matches!(error.kind(), ErrorKind::LocalPartTooLong | ErrorKind::DomainTooLong),
+              \"expected {} to be too long: {:?}\", $email, error
+            );
+            return;
+          }
           assert_eq!(email.is_some(), $is_email, \"expected {} to be valid: {}\", $email, $is_email);
           if $is_email {
             assert_eq!(
@@ -261,7 +287,113 @@ generate_is_email_test!{
         buf.clear();
     }
 
-    content.push_str("}");
+    content.push('}');
+}
+
+/// Writes the whole is_email corpus, as `(id, address, category, diagnosis)` tuples, for the conformance report.
+fn create_is_email_corpus(
+    test_data_root: &path::Path,
+    out_dir: &path::Path,
+) -> Vec<(u32, String, String, String)> {
+    let is_email_xml = fs::read_to_string(test_data_root.join("isemail_tests.xml")).unwrap();
+    let mut content = String::from("pub const IS_EMAIL_CORPUS: &[(u32, &str, &str, &str)] = &[\n");
+
+    let mut reader = Reader::from_str(&is_email_xml);
+    let mut buf = Vec::new();
+    let mut capture = String::new();
+    let (mut address, mut category, mut diagnosis) = (String::new(), String::new(), String::new());
+    let mut id = 0;
+    let mut corpus = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(_)) | Ok(Event::Empty(_)) => capture.clear(),
+            Ok(Event::Text(e)) => capture = e.unescape_and_decode(&reader).unwrap(),
+            Ok(Event::End(ref e)) => match e.name() {
+                b"address" => {
+                    // The control characters are stored as their Unicode symbols; e.g. U+240D for CR.
+                    address = capture
+                        .chars()
+                        .map(|c| match c as u32 {
+                            0x2400..=0x241F => std::char::from_u32(c as u32 - 0x2400).unwrap(),
+                            _ => c,
+                        })
+                        .collect()
+                }
+                b"category" => category = capture.clone(),
+                b"diagnosis" => diagnosis = capture.clone(),
+                b"test" => {
+                    id += 1;
+                    content.push_str(&format!(
+                        "  ({}, {:?}, {:?}, {:?}),\n",
+                        id, address, category, diagnosis
+                    ));
+                    corpus.push((id, address.clone(), category.clone(), diagnosis.clone()));
+                    address.clear();
+                }
+                _ => (),
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    content.push_str("];\n");
+    fs::write(out_dir.join("is_email_corpus.rs"), content).unwrap();
+    corpus
+}
+
+/// Writes the corpus of the `fixtures` feature, as `(address, category, source, diagnosis)`
+/// tuples: the same addresses as the generated tests, with the same expectations.
+fn create_fixture_corpus(
+    out_dir: &path::Path,
+    valid_local_parts: &Vec<String>,
+    valid_domains: &Vec<String>,
+    invalid_local_parts: &Vec<String>,
+    invalid_domains: &Vec<String>,
+    is_email_corpus: &[(u32, String, String, String)],
+) {
+    // The same as those that the generated is_email tests ignore.
+    let ignored_emails = [
+        "test@[RFC-5322-\\\t-domain-literal]",
+        "test@[RFC-5322-\\\u{07}-domain-literal]",
+        "test@[RFC-5322-\\]-domain-literal]",
+    ];
+    let mut content = String::from("&[\n");
+    let mut push_cases = |local_parts: &Vec<String>, domains: &Vec<String>, category: &str| {
+        for local_part in local_parts {
+            for domain in domains {
+                content.push_str(&format!(
+                    "  (\"{}@{}\", Category::{}, \"test_data\", None),\n",
+                    local_part, domain, category
+                ));
+            }
+        }
+    };
+    push_cases(valid_local_parts, valid_domains, "Valid");
+    push_cases(invalid_local_parts, valid_domains, "Invalid");
+    push_cases(valid_local_parts, invalid_domains, "Invalid");
+    push_cases(invalid_local_parts, invalid_domains, "Invalid");
+
+    for (_, address, category, diagnosis) in is_email_corpus {
+        if ignored_emails.contains(&address.as_str()) {
+            continue;
+        }
+        let category = match category.as_str() {
+            "ISEMAIL_ERR" => "Invalid",
+            "ISEMAIL_DEPREC" => "Obsolete",
+            _ => "Valid",
+        };
+        content.push_str(&format!(
+            "  ({:?}, Category::{}, \"isemail\", Some({:?})),\n",
+            address, category, diagnosis
+        ));
+    }
+
+    content.push_str("]\n");
+    fs::write(out_dir.join("fixture_corpus.rs"), content).unwrap();
 }
 
 fn create_valid_instantiation_tests(
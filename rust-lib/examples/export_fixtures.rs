@@ -0,0 +1,25 @@
+//! Writes the fixture corpus as JSON to the file of the first argument, or else to the standard
+//! output; see the `fixtures` module.
+//!
+//! `cargo run --example export_fixtures --features fixtures -- fixtures.json`
+
+use email_address_parser::fixtures::write_json;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match std::env::args_os().nth(1) {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            write_json(&mut writer)?;
+            writer.flush()?;
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut writer = stdout.lock();
+            write_json(&mut writer)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
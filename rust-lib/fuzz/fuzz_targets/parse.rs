@@ -0,0 +1,35 @@
+#![no_main]
+
+use email_address_parser::{EmailAddress, ParsingOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        check(input);
+    }
+});
+
+fn check(input: &str) {
+    for &is_lax in &[false, true] {
+        let parsed = EmailAddress::parse(input, Some(ParsingOptions::new(is_lax)));
+        assert_eq!(
+            EmailAddress::is_valid(input, Some(ParsingOptions::new(is_lax))),
+            parsed.is_some()
+        );
+        if let Some(email) = parsed {
+            let _ = EmailAddress::new(
+                email.get_local_part(),
+                email.get_domain(),
+                Some(ParsingOptions::new(is_lax)),
+            );
+            let _ = email.to_string();
+        }
+    }
+    match input.parse::<EmailAddress>() {
+        Ok(email) => assert!(EmailAddress::is_valid(&email.to_string(), None)),
+        Err(error) => {
+            assert!(input.get(error.span()).is_some());
+            let _ = error.to_string();
+        }
+    }
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use email_address_parser::{AddressCandidate, EmailAddress, ParsingOptions};
+use libfuzzer_sys::fuzz_target;
+
+// Structure-aware variant of the `parse` target; the candidates are built of the fragments that
+// are significant to the grammar, which reaches the corners of the grammar much sooner.
+fuzz_target!(|candidate: AddressCandidate| {
+    let input = candidate.as_str();
+    for &is_lax in &[false, true] {
+        let _ = EmailAddress::parse(input, Some(ParsingOptions::new(is_lax)));
+    }
+    if let Err(error) = input.parse::<EmailAddress>() {
+        assert!(input.get(error.span()).is_some());
+        let _ = error.to_string();
+    }
+});
@@ -0,0 +1,29 @@
+#![no_main]
+
+use email_address_parser::{Address, HeaderFolder, ParsingOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(value) = std::str::from_utf8(data) {
+        check(value);
+    }
+});
+
+fn check(value: &str) {
+    for &is_lax in &[false, true] {
+        match Address::parse_list(value, Some(ParsingOptions::new(is_lax))) {
+            Ok(addresses) => {
+                for &encode_words in &[false, true] {
+                    let folder = HeaderFolder::new().encode_words(encode_words);
+                    if let Ok(field) = folder.fold_address_list("To", &addresses) {
+                        assert!(field.starts_with("To:"));
+                    }
+                }
+            }
+            Err(error) => {
+                assert!(error.input().get(error.span()).is_some());
+                let _ = error.to_string();
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use email_address_parser::{EmailAddress, Scanner};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let mut end = 0;
+        for found in Scanner::new(text) {
+            assert!(found.start() >= end);
+            end = found.end();
+            assert!(EmailAddress::is_valid(found.as_str(), None));
+        }
+    }
+});
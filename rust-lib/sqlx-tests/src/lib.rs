@@ -0,0 +1 @@
+//! Tests the `sqlx` integration of `email-address-parser` against SQLite; see `tests/`.
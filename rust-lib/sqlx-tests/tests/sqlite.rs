@@ -0,0 +1,55 @@
+use email_address_parser::{EmailAddress, LaxEmailAddress};
+use sqlx::sqlite::SqlitePool;
+
+async fn pool() -> SqlitePool {
+    SqlitePool::connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn email_address_round_trips_through_text() {
+    let pool = pool().await;
+    let email = EmailAddress::new("foö", "bücher.de", None).unwrap();
+    let actual: EmailAddress = sqlx::query_scalar("SELECT ?")
+        .bind(&email)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(actual, email);
+    let actual: String = sqlx::query_scalar("SELECT ?")
+        .bind(&email)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(actual, "foö@bücher.de");
+}
+
+#[tokio::test]
+async fn invalid_text_fails_to_decode() {
+    let pool = pool().await;
+    let actual = sqlx::query_scalar::<_, EmailAddress>("SELECT 'foo@-bar.com'")
+        .fetch_one(&pool)
+        .await;
+    assert!(actual.is_err());
+    let actual = sqlx::query_scalar::<_, LaxEmailAddress>("SELECT 'foo@-bar.com'")
+        .fetch_one(&pool)
+        .await;
+    assert!(actual.is_err());
+}
+
+#[tokio::test]
+async fn obsolete_text_decodes_as_lax_email_address_only() {
+    let pool = pool().await;
+    let query = "SELECT char(13, 10, 32) || 'test@iana.org'";
+    let actual = sqlx::query_scalar::<_, EmailAddress>(query)
+        .fetch_one(&pool)
+        .await;
+    assert!(actual.is_err());
+    let actual: LaxEmailAddress = sqlx::query_scalar(query).fetch_one(&pool).await.unwrap();
+    assert_eq!(actual.to_string(), "\r\n test@iana.org");
+    let actual: LaxEmailAddress = sqlx::query_scalar("SELECT ?")
+        .bind(&actual)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(actual.to_string(), "\r\n test@iana.org");
+}
@@ -0,0 +1,177 @@
+use crate::canonical::canonical_domain;
+use crate::email_address::EmailAddress;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// The address literal of a domain literal, as per RFC 5321 (section 4.1.3); i.e. an IPv4
+/// address, like `[127.0.0.1]`, an IPv6 address, like `[IPv6:::1]`, or a general address
+/// literal, like `[FooProtocol:xyz]`, made of a standardized tag and of its content.
+///
+/// A domain literal of RFC 5322 that is none of them, like `[foo bar]`, is not an address literal.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{AddressLiteral, EmailAddress};
+/// use std::net::Ipv4Addr;
+///
+/// let email = EmailAddress::parse("foo@[127.0.0.1]", None).unwrap();
+/// assert_eq!(email.address_literal(), Some(AddressLiteral::Ipv4(Ipv4Addr::LOCALHOST)));
+///
+/// let literal = AddressLiteral::parse("[FooProtocol:xyz]").unwrap();
+/// assert_eq!((literal.tag(), literal.content()), (Some("FooProtocol"), "xyz".into()));
+/// assert!(literal.ip_addr().is_none());
+///
+/// assert_eq!(AddressLiteral::parse("[foo bar]"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddressLiteral {
+    /// An IPv4 address literal, like `[127.0.0.1]`.
+    Ipv4(Ipv4Addr),
+    /// An IPv6 address literal, like `[IPv6:::1]`.
+    Ipv6(Ipv6Addr),
+    /// A general address literal, like `[FooProtocol:xyz]`.
+    General {
+        /// The standardized tag; a letter-digit-hyphen string, like `FooProtocol`.
+        tag: String,
+        /// The content after the tag, without the colon; printable ASCII characters other than
+        /// the brackets and the backslash, like `xyz`.
+        content: String,
+    },
+}
+
+impl AddressLiteral {
+    /// Parses a domain literal, with its brackets, as an address literal; `None` if it is not one.
+    ///
+    /// The white space and the comments around the brackets, and the white space in them, are
+    /// ignored, as in the canonical form of the domain. The `IPv6` tag is matched
+    /// case-insensitively; a literal with this tag and an invalid IPv6 address is not an address
+    /// literal, as it cannot be a general one.
+    pub fn parse(literal: &str) -> Option<AddressLiteral> {
+        let canonical = canonical_domain(literal);
+        let content = canonical.strip_prefix('[')?.strip_suffix(']')?;
+        let (tag, content) = match content.split_once(':') {
+            Some(tagged) => tagged,
+            None => return parse_ipv4(content).map(AddressLiteral::Ipv4),
+        };
+        if tag.eq_ignore_ascii_case("IPv6") {
+            return content.parse().ok().map(AddressLiteral::Ipv6);
+        }
+        let is_dcontent = |c: char| matches!(c, '!'..='Z' | '^'..='~');
+        if !is_ldh_str(tag) || content.is_empty() || !content.chars().all(is_dcontent) {
+            return None;
+        }
+        Some(AddressLiteral::General {
+            tag: tag.to_string(),
+            content: content.to_string(),
+        })
+    }
+
+    /// Returns the tag of the literal; `IPv6` for an IPv6 address, and none for an IPv4 one.
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            AddressLiteral::Ipv4(_) => None,
+            AddressLiteral::Ipv6(_) => Some("IPv6"),
+            AddressLiteral::General { tag, .. } => Some(tag),
+        }
+    }
+
+    /// Returns the content of the literal, after the tag, if any.
+    pub fn content(&self) -> String {
+        match self {
+            AddressLiteral::Ipv4(ip) => ip.to_string(),
+            AddressLiteral::Ipv6(ip) => ip.to_string(),
+            AddressLiteral::General { content, .. } => content.clone(),
+        }
+    }
+
+    /// Returns the IP address of the literal; none for a general address literal.
+    pub fn ip_addr(&self) -> Option<IpAddr> {
+        match self {
+            AddressLiteral::Ipv4(ip) => Some(IpAddr::V4(*ip)),
+            AddressLiteral::Ipv6(ip) => Some(IpAddr::V6(*ip)),
+            AddressLiteral::General { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for AddressLiteral {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressLiteral::Ipv4(ip) => write!(formatter, "[{}]", ip),
+            AddressLiteral::Ipv6(ip) => write!(formatter, "[IPv6:{}]", ip),
+            AddressLiteral::General { tag, content } => write!(formatter, "[{}:{}]", tag, content),
+        }
+    }
+}
+
+/// Parses an IPv4 address literal of RFC 5321; i.e. four decimal numbers of up to three digits,
+/// that may have leading zeros, unlike the addresses of `Ipv4Addr::from_str`.
+fn parse_ipv4(content: &str) -> Option<Ipv4Addr> {
+    let mut octets = [0; 4];
+    let mut numbers = content.split('.');
+    for octet in octets.iter_mut() {
+        let number = numbers.next()?;
+        if number.is_empty() || number.len() > 3 || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        *octet = number.parse().ok()?;
+    }
+    match numbers.next() {
+        Some(_) => None,
+        None => Some(Ipv4Addr::from(octets)),
+    }
+}
+
+/// Returns whether a string is an `Ldh-str` of RFC 5321; i.e. letters, digits, and hyphens, that
+/// neither starts nor ends with a hyphen.
+fn is_ldh_str(tag: &str) -> bool {
+    !tag.is_empty()
+        && !tag.starts_with('-')
+        && !tag.ends_with('-')
+        && tag.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+impl EmailAddress {
+    /// Returns the address literal of the domain, if it is a domain literal that is one; see
+    /// `AddressLiteral`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@[IPv6:::1]", None).unwrap();
+    /// assert_eq!(email.address_literal().unwrap().tag(), Some("IPv6"));
+    /// assert!(EmailAddress::parse("foo@bar.com", None).unwrap().address_literal().is_none());
+    /// ```
+    pub fn address_literal(&self) -> Option<AddressLiteral> {
+        AddressLiteral::parse(self.get_domain())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_address_literals_of_rfc_5321() {
+        let literal = AddressLiteral::parse(" [ 127.000.0.1 ](comment)").unwrap();
+        assert_eq!(literal, AddressLiteral::Ipv4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(literal.to_string(), "[127.0.0.1]");
+        assert_eq!(AddressLiteral::parse("[256.0.0.1]"), None);
+        assert_eq!(AddressLiteral::parse("[1.2.3]"), None);
+        let literal = AddressLiteral::parse("[ipv6:2001:db8::1]").unwrap();
+        assert_eq!(literal.content(), "2001:db8::1");
+        assert_eq!(literal.to_string(), "[IPv6:2001:db8::1]");
+        assert_eq!(AddressLiteral::parse("[IPv6:127.0.0.1]"), None);
+        let literal = AddressLiteral::parse("[x-tag:a:b=c]").unwrap();
+        assert_eq!(
+            (literal.tag(), literal.content().as_str()),
+            (Some("x-tag"), "a:b=c")
+        );
+        assert_eq!(literal.to_string(), "[x-tag:a:b=c]");
+        for input in &["[-tag:a]", "[tag:]", "[t_g:a]", "[foo]", "foo.com"] {
+            assert_eq!(AddressLiteral::parse(input), None, "{}", input);
+        }
+    }
+}
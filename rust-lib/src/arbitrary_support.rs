@@ -0,0 +1,220 @@
+use crate::email_address::EmailAddress;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::fmt;
+
+const ATEXT: &[char] = &[
+    'a', 'b', 'x', 'y', 'z', 'A', 'Z', '0', '9', '!', '#', '$', '%', '&', '\'', '*', '+', '/', '=',
+    '?', '^', '_', '`', '{', '|', '}', '~', 'ö', '中',
+];
+const QTEXT: &[char] = &[
+    'a', 'z', '0', ' ', '!', '#', '(', ')', ',', ':', ';', '<', '>', '@', '[', ']', '.', 'ö',
+];
+const LDH: &[char] = &['a', 'b', 'm', 'x', 'z', '0', '7', '9', 'ä', '中'];
+const DTEXT: &[char] = &['a', 'z', '0', '9', ':', '.', '-', '!', '~'];
+/// Fragments a candidate is built from; i.e. the tokens that matter to the grammar.
+const FRAGMENTS: &[&str] = &[
+    "a",
+    "foo",
+    "bar",
+    "0",
+    "-",
+    "_",
+    "+",
+    "ö",
+    "中",
+    ".",
+    "..",
+    "@",
+    "@@",
+    "\"",
+    "\\",
+    "(",
+    ")",
+    "[",
+    "]",
+    "<",
+    ">",
+    ",",
+    ";",
+    ":",
+    " ",
+    "\t",
+    "\r\n",
+    "\r\n ",
+    "\u{0}",
+    "\u{7f}",
+    "com",
+    "IPv6:",
+    "127.0.0.1",
+    "xn--",
+];
+
+/// Generates well-formed email addresses, for structure-aware fuzzing of code consuming them.
+///
+/// The generated addresses have dot-atom or quoted local parts, and host names or domain literals.
+/// Any of them can be parsed strictly.
+///
+/// # Examples
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use email_address_parser::EmailAddress;
+///
+/// let mut unstructured = Unstructured::new(b"some fuzzer provided bytes");
+/// let email = EmailAddress::arbitrary(&mut unstructured).unwrap();
+/// assert!(EmailAddress::is_valid(&email.to_string(), None));
+/// ```
+impl<'a> Arbitrary<'a> for EmailAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let local_part = if u.ratio(1, 5)? {
+            quoted_string(u)?
+        } else {
+            dot_atom(u, ATEXT)?
+        };
+        let domain = if u.ratio(1, 5)? {
+            domain_literal(u)?
+        } else {
+            host_name(u)?
+        };
+        EmailAddress::new(&local_part, &domain, None).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+fn run(u: &mut Unstructured, alphabet: &[char], max_len: u32) -> Result<String> {
+    let len = u.int_in_range(1..=max_len)?;
+    (0..len).map(|_| u.choose(alphabet).copied()).collect()
+}
+
+/// A dot-atom; the hyphens are placed so that no segment starts or ends with one.
+fn dot_atom(u: &mut Unstructured, alphabet: &[char]) -> Result<String> {
+    let segments = u.int_in_range(1..=3)?;
+    let mut dot_atom = String::new();
+    for index in 0..segments {
+        if index > 0 {
+            dot_atom.push('.');
+        }
+        dot_atom.push_str(&run(u, alphabet, 6)?);
+        if u.ratio(1, 4)? {
+            dot_atom.push('-');
+            dot_atom.push_str(&run(u, alphabet, 6)?);
+        }
+    }
+    Ok(dot_atom)
+}
+
+fn quoted_string(u: &mut Unstructured) -> Result<String> {
+    let len = u.int_in_range(0..=8)?;
+    let mut quoted = String::from("\"");
+    for _ in 0..len {
+        if u.ratio(1, 6)? {
+            quoted.push('\\');
+            quoted.push(*u.choose(&['"', '\\', ' ', 'a'])?);
+        } else {
+            quoted.push(*u.choose(QTEXT)?);
+        }
+    }
+    quoted.push('"');
+    Ok(quoted)
+}
+
+fn host_name(u: &mut Unstructured) -> Result<String> {
+    let mut host_name = dot_atom(u, LDH)?;
+    host_name.push('.');
+    host_name.push_str(&run(u, &['c', 'o', 'm', 'd', 'e'], 4)?);
+    Ok(host_name)
+}
+
+fn domain_literal(u: &mut Unstructured) -> Result<String> {
+    if u.arbitrary()? {
+        let [a, b, c, d] = u.arbitrary::<[u8; 4]>()?;
+        Ok(format!("[{}.{}.{}.{}]", a, b, c, d))
+    } else {
+        Ok(format!("[{}]", run(u, DTEXT, 12)?))
+    }
+}
+
+/// A raw string, that may or may not be an email address, for fuzzing parsers of email addresses.
+///
+/// Unlike an arbitrary `String`, a candidate consists of the fragments that are significant to
+/// the RFC 5322 grammar, like `@`, dots, quotes, brackets, comments, and folding white space.
+/// Thus, a fuzzer reaches the corners of the grammar much sooner.
+///
+/// # Examples
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use email_address_parser::{AddressCandidate, EmailAddress};
+///
+/// let mut unstructured = Unstructured::new(b"some fuzzer provided bytes");
+/// let candidate = AddressCandidate::arbitrary(&mut unstructured).unwrap();
+/// let _ = EmailAddress::parse(candidate.as_str(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddressCandidate(pub String);
+
+impl AddressCandidate {
+    /// Returns the candidate as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl fmt::Display for AddressCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for AddressCandidate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return Ok(AddressCandidate(EmailAddress::arbitrary(u)?.to_string()));
+        }
+        let mut candidate = String::new();
+        u.arbitrary_loop(Some(1), Some(32), |u| {
+            candidate.push_str(u.choose(FRAGMENTS)?);
+            Ok(std::ops::ControlFlow::Continue(()))
+        })?;
+        Ok(AddressCandidate(candidate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeds() -> impl Iterator<Item = Vec<u8>> {
+        (0u32..500).map(|seed| {
+            (0..256u32)
+                .map(|index| (seed.wrapping_mul(2_654_435_761) ^ index.wrapping_mul(40_503)) as u8)
+                .map(|byte| byte.rotate_left(3))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn arbitrary_email_address_is_valid() {
+        let emails: Vec<_> = seeds()
+            .filter_map(|seed| EmailAddress::arbitrary(&mut Unstructured::new(&seed)).ok())
+            .collect();
+        assert!(emails.len() > 400);
+        for email in emails {
+            assert!(
+                EmailAddress::is_valid(&email.to_string(), None),
+                "{}",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn arbitrary_candidates_vary() {
+        let candidates: Vec<_> = seeds()
+            .filter_map(|seed| AddressCandidate::arbitrary(&mut Unstructured::new(&seed)).ok())
+            .collect();
+        assert!(candidates
+            .iter()
+            .any(|c| EmailAddress::is_valid(c.as_str(), None)));
+        assert!(candidates
+            .iter()
+            .any(|c| !EmailAddress::is_valid(c.as_str(), None)));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+use alloc::string::String;
+use core::fmt;
+use core::future::{ready, Future, Ready};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_util::stream::FuturesOrdered;
+use futures_util::StreamExt;
+
+/// The result of parsing a string of a stream.
+type ParseResult = Result<EmailAddress, ParseError>;
+
+/// The stream of `ParseEmailStream::parse_emails`, that parses on the task that polls it.
+type ParsedInPlace<S> =
+    ParsedEmailStream<S, fn(ParseJob) -> Ready<ParseResult>, Ready<ParseResult>>;
+
+/// The parsing of a string as an email address, that can run anywhere; e.g. on the blocking
+/// threads of a runtime. See `ParseEmailStream::parse_emails_with`.
+#[derive(Debug, Clone)]
+pub struct ParseJob {
+    input: String,
+    options: ParsingOptions,
+}
+
+impl ParseJob {
+    /// Returns the string to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Parses the string, like `EmailAddress::try_parse`.
+    pub fn run(self) -> ParseResult {
+        EmailAddress::parse_with(&self.input, &self.options).map(|(email, _)| email)
+    }
+}
+
+/// Runs a job on the task that polls its future; see `ParseEmailStream::parse_emails`.
+fn run_in_place(job: ParseJob) -> Ready<ParseResult> {
+    ready(job.run())
+}
+
+/// Extension of the streams of strings, to parse each of them as an email address; e.g. the
+/// records of an ETL pipeline. It is the asynchronous counterpart of `ParseEmails`.
+///
+/// The results are in the order of the strings. Up to `concurrency` strings are parsed at once;
+/// the stream is not polled for more until the earliest of them is done, which bounds the memory
+/// use. The stream must be `Unpin`; pin it with `Box::pin` if it is not.
+///
+/// Requires the `async` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{ParseEmailStream, ParsingOptions};
+/// use futures_util::{stream, StreamExt};
+///
+/// let lines = stream::iter(["foo@bar.com", "foo@-bar.com"]);
+/// let results: Vec<_> =
+///     futures_executor::block_on(lines.parse_emails(&ParsingOptions::default(), 16).collect());
+/// assert!(results[0].is_ok());
+/// assert_eq!(results[1].as_ref().unwrap_err().span(), 4..12);
+/// ```
+///
+/// To parse off the tasks of the runtime, e.g. on the blocking threads of tokio, spawn the jobs:
+/// ```ignore
+/// let emails = lines.parse_emails_with(&options, 8, |job| async move {
+///     tokio::task::spawn_blocking(move || job.run()).await.expect("parsing never panics")
+/// });
+/// ```
+pub trait ParseEmailStream: Stream + Unpin + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Parses each string as an email address as per the options, like `EmailAddress::try_parse`,
+    /// on the task that polls the stream.
+    fn parse_emails(self, options: &ParsingOptions, concurrency: usize) -> ParsedInPlace<Self> {
+        self.parse_emails_with(options, concurrency, run_in_place as fn(_) -> _)
+    }
+
+    /// Parses each string as an email address as per the options, like `EmailAddress::try_parse`,
+    /// with the futures that the given function returns for the jobs; e.g. to run them on other
+    /// threads.
+    fn parse_emails_with<F, Fut>(
+        self,
+        options: &ParsingOptions,
+        concurrency: usize,
+        spawn: F,
+    ) -> ParsedEmailStream<Self, F, Fut>
+    where
+        F: FnMut(ParseJob) -> Fut,
+        Fut: Future<Output = ParseResult>,
+    {
+        ParsedEmailStream {
+            stream: Some(self),
+            options: options.clone(),
+            concurrency: concurrency.max(1),
+            spawn,
+            pending: FuturesOrdered::new(),
+        }
+    }
+}
+
+impl<S: Stream + Unpin> ParseEmailStream for S where S::Item: AsRef<str> {}
+
+/// Stream of the results of parsing the strings of a stream; see `ParseEmailStream`.
+pub struct ParsedEmailStream<S, F, Fut: Future> {
+    /// The stream of the strings, until it ends.
+    stream: Option<S>,
+    options: ParsingOptions,
+    concurrency: usize,
+    spawn: F,
+    pending: FuturesOrdered<Fut>,
+}
+
+impl<S, F, Fut: Future> fmt::Debug for ParsedEmailStream<S, F, Fut> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ParsedEmailStream")
+            .field("options", &self.options)
+            .field("concurrency", &self.concurrency)
+            .field("pending", &self.pending.len())
+            .field("is_done", &self.stream.is_none())
+            .finish()
+    }
+}
+
+// Neither the stream nor the futures are pinned in place; the former is `Unpin`, and the latter
+// are pinned by `FuturesOrdered`.
+impl<S, F, Fut: Future> Unpin for ParsedEmailStream<S, F, Fut> {}
+
+impl<S, F, Fut> Stream for ParsedEmailStream<S, F, Fut>
+where
+    S: Stream + Unpin,
+    S::Item: AsRef<str>,
+    F: FnMut(ParseJob) -> Fut,
+    Fut: Future<Output = ParseResult>,
+{
+    type Item = ParseResult;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while this.pending.len() < this.concurrency {
+            let stream = match this.stream.as_mut() {
+                Some(stream) => stream,
+                None => break,
+            };
+            match stream.poll_next_unpin(context) {
+                Poll::Ready(Some(input)) => {
+                    let job = ParseJob {
+                        input: String::from(input.as_ref()),
+                        options: this.options.clone(),
+                    };
+                    this.pending.push_back((this.spawn)(job));
+                }
+                Poll::Ready(None) => this.stream = None,
+                Poll::Pending => break,
+            }
+        }
+        match this.pending.poll_next_unpin(context) {
+            Poll::Ready(None) if this.stream.is_some() => Poll::Pending,
+            poll => poll,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.as_ref().map_or((0, Some(0)), Stream::size_hint);
+        let pending = self.pending.len();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::Cell;
+    use futures_executor::block_on;
+    use futures_util::stream;
+
+    /// A future that is pending the first time it is polled.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            context.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn parses_in_order_with_bounded_concurrency() {
+        let inputs = stream::iter(["foo@bar.com", "foo@-bar.com", "baz@qux.org", "a@b.c"]);
+        let (in_flight, most_in_flight) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let spawn = |job: ParseJob| {
+            in_flight.set(in_flight.get() + 1);
+            most_in_flight.set(most_in_flight.get().max(in_flight.get()));
+            let in_flight = Rc::clone(&in_flight);
+            async move {
+                YieldOnce(false).await;
+                in_flight.set(in_flight.get() - 1);
+                job.run()
+            }
+        };
+        let results: Vec<_> = block_on(
+            inputs
+                .parse_emails_with(&ParsingOptions::default(), 2, spawn)
+                .collect(),
+        );
+        let results: Vec<_> = results.iter().map(Result::is_ok).collect();
+        assert_eq!(results, [true, false, true, true]);
+        assert_eq!(most_in_flight.get(), 2);
+
+        let emails = stream::iter(["foo@bar.com"]).parse_emails(&ParsingOptions::default(), 0);
+        assert_eq!(emails.size_hint(), (1, Some(1)));
+        assert!(block_on(emails.collect::<Vec<_>>())[0].is_ok());
+    }
+}
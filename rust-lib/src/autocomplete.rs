@@ -0,0 +1,79 @@
+use crate::dictionary::POPULAR_DOMAINS;
+use crate::email_address::EmailAddress;
+use crate::error::find_separator;
+use alloc::string::String;
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::*;
+
+/// Returns the completions of the domain of a partial input, like the value of a form field as it
+/// is being typed; e.g. `foo@gmail.com` for `foo@gma`.
+///
+/// The domains of the given dictionary that start with the typed domain, ignoring case, come
+/// first, in order, followed by those of `POPULAR_DOMAINS`; a domain that is typed in full is not
+/// completed. The completions are the whole input, with the domain completed, so that they can
+/// replace it; there is none before the `@` is typed.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::complete_domain;
+///
+/// assert_eq!(complete_domain("foo@gma", &[]), ["foo@gmail.com"]);
+/// assert_eq!(
+///     complete_domain("foo@Ho", &["hooli.xyz"]),
+///     ["foo@hooli.xyz", "foo@hotmail.com", "foo@hotmail.co.uk", "foo@hotmail.fr"]
+/// );
+/// assert!(complete_domain("foo@gmail.com", &[]).is_empty());
+/// assert!(complete_domain("foo", &[]).is_empty());
+/// ```
+pub fn complete_domain(input: &str, dictionary: &[&str]) -> Vec<String> {
+    let at = match find_separator(input) {
+        Some(at) => at,
+        None => return Vec::new(),
+    };
+    let (local_part, typed) = input.split_at(at + 1);
+    let mut completions: Vec<String> = Vec::new();
+    for domain in dictionary.iter().chain(POPULAR_DOMAINS) {
+        let is_completion = domain.len() > typed.len()
+            && domain.is_char_boundary(typed.len())
+            && domain[..typed.len()].eq_ignore_ascii_case(typed);
+        let completion = [local_part, domain].concat();
+        if is_completion && !completions.contains(&completion) {
+            completions.push(completion);
+        }
+    }
+    completions
+}
+
+#[wasm_bindgen]
+impl EmailAddress {
+    /// This is a WASM wrapper over `complete_domain`.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "completeDomain")]
+    pub fn _complete_domain(input: &str, dictionary: Vec<String>) -> Vec<String> {
+        let dictionary: Vec<&str> = dictionary.iter().map(String::as_str).collect();
+        complete_domain(input, &dictionary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_from_the_dictionaries_in_order() {
+        assert_eq!(
+            complete_domain("\"a@b\"@YA", &["yahoo.com", "yapily.io"]),
+            [
+                "\"a@b\"@yahoo.com",
+                "\"a@b\"@yapily.io",
+                "\"a@b\"@yahoo.co.uk",
+                "\"a@b\"@yandex.ru",
+                "\"a@b\"@yahoo.fr",
+                "\"a@b\"@yahoo.co.jp"
+            ]
+        );
+        assert_eq!(complete_domain("foo@", &[]).len(), POPULAR_DOMAINS.len());
+        assert_eq!(complete_domain("foo@bü", &["bücher.de"]), ["foo@bücher.de"]);
+        assert!(complete_domain("foo@gmail.comm", &[]).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+
+/// Extension of the iterators over strings, to parse each of them as an email address; e.g. the
+/// lines of a file, or a column of a CSV file.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{ParseEmails, ParsingOptions};
+///
+/// let lines = "foo@bar.com\nfoo@-bar.com\n\"jane doe\"@example.org";
+/// let options = ParsingOptions::default();
+///
+/// let results: Vec<_> = lines.lines().parse_emails(&options).collect();
+/// assert!(results[0].is_ok());
+/// assert_eq!(results[1].as_ref().unwrap_err().span(), 4..12);
+///
+/// let valid: Vec<_> = lines.lines().valid_emails(&options).map(|email| email.to_string()).collect();
+/// assert_eq!(valid, ["foo@bar.com", "\"jane doe\"@example.org"]);
+/// ```
+pub trait ParseEmails: Iterator + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Parses each string as an email address as per the options, like `EmailAddress::try_parse`.
+    fn parse_emails(self, options: &ParsingOptions) -> ParsedEmails<Self> {
+        ParsedEmails {
+            iter: self,
+            options: options.clone(),
+        }
+    }
+
+    /// Parses each string as an email address as per the options, skipping the invalid ones.
+    fn valid_emails(self, options: &ParsingOptions) -> ValidEmails<Self> {
+        ValidEmails {
+            iter: self,
+            options: options.clone(),
+        }
+    }
+}
+
+impl<I: Iterator> ParseEmails for I where I::Item: AsRef<str> {}
+
+/// Iterator over the results of parsing the strings of an iterator; see `ParseEmails::parse_emails`.
+#[derive(Debug, Clone)]
+pub struct ParsedEmails<I> {
+    iter: I,
+    options: ParsingOptions,
+}
+
+impl<I: Iterator> Iterator for ParsedEmails<I>
+where
+    I::Item: AsRef<str>,
+{
+    type Item = Result<EmailAddress, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.iter.next()?;
+        Some(EmailAddress::parse_with(input.as_ref(), &self.options).map(|(email, _)| email))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator over the valid email addresses among the strings of an iterator; see
+/// `ParseEmails::valid_emails`.
+#[derive(Debug, Clone)]
+pub struct ValidEmails<I> {
+    iter: I,
+    options: ParsingOptions,
+}
+
+impl<I: Iterator> Iterator for ValidEmails<I>
+where
+    I::Item: AsRef<str>,
+{
+    type Item = EmailAddress;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let options = &self.options;
+        self.iter
+            .by_ref()
+            .find_map(|input| EmailAddress::parse_with(input.as_ref(), options).ok())
+            .map(|(email, _)| email)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn parses_owned_and_borrowed_strings() {
+        let options = ParsingOptions::new(true);
+        let inputs = vec![String::from("foo . bar@baz.com"), String::from("foo")];
+        let results: Vec<_> = inputs.iter().parse_emails(&options).collect();
+        assert_eq!(results[0].as_ref().unwrap().get_local_part(), "foo . bar");
+        assert_eq!(results[1].as_ref().unwrap_err().input(), "foo");
+        assert_eq!(
+            inputs.iter().parse_emails(&options).size_hint(),
+            (2, Some(2))
+        );
+
+        let valid: Vec<_> = inputs.into_iter().valid_emails(&options).collect();
+        assert_eq!(valid.len(), 1);
+        let strict = ParsingOptions::default();
+        assert_eq!(
+            ["foo . bar@baz.com"].iter().valid_emails(&strict).count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::{ErrorKind, Hint, ParseError};
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// How `EmailAddress::parse_bytes` decodes an input that is not valid UTF-8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvalidUtf8 {
+    /// Rejects the input, with an error on its first invalid sequence; the default.
+    #[default]
+    Reject,
+    /// Replaces each invalid sequence with the replacement character `U+FFFD`, like
+    /// `String::from_utf8_lossy`; the address is then parsed with the replacement characters.
+    Lossy,
+    /// Decodes the whole input as Latin-1 (ISO 8859-1), where each octet is a character; e.g. for
+    /// the headers of legacy systems. A valid UTF-8 input is decoded as UTF-8.
+    Latin1,
+}
+
+impl EmailAddress {
+    /// Parses a byte slice as an email address like `try_parse`, decoding it as UTF-8; e.g. in
+    /// an MTA, or in the processing of logs, where the inputs may not be valid UTF-8.
+    ///
+    /// An input that is not valid UTF-8 is handled as per `invalid_utf8`. The error of an
+    /// invalid input refers to the decoded input; for a rejected one, it spans the replacement
+    /// character of its first invalid sequence, with the `Hint::InvalidUtf8` hint.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// let input = b"j\xf6rg@example.com";
+    /// let error = EmailAddress::parse_bytes(input, InvalidUtf8::Reject, None).unwrap_err();
+    /// assert_eq!(error.hint(), Some(Hint::InvalidUtf8));
+    /// assert_eq!(error.span(), 1..4);
+    ///
+    /// let email = EmailAddress::parse_bytes(input, InvalidUtf8::Latin1, None).unwrap();
+    /// assert_eq!(email.get_local_part(), "jörg");
+    ///
+    /// let email = EmailAddress::parse_bytes(input, InvalidUtf8::Lossy, None).unwrap();
+    /// assert_eq!(email.get_local_part(), "j\u{fffd}rg");
+    /// ```
+    pub fn parse_bytes(
+        input: &[u8],
+        invalid_utf8: InvalidUtf8,
+        options: Option<ParsingOptions>,
+    ) -> Result<EmailAddress, ParseError> {
+        let decoded = match core::str::from_utf8(input) {
+            Ok(input) => Cow::Borrowed(input),
+            Err(error) => match invalid_utf8 {
+                InvalidUtf8::Reject => {
+                    let start = error.valid_up_to();
+                    let span = start..start + char::REPLACEMENT_CHARACTER.len_utf8();
+                    let decoded = String::from_utf8_lossy(input);
+                    return Err(ParseError::new(
+                        ErrorKind::Invalid,
+                        &decoded,
+                        span,
+                        Some(Hint::InvalidUtf8),
+                    ));
+                }
+                InvalidUtf8::Lossy => String::from_utf8_lossy(input),
+                InvalidUtf8::Latin1 => Cow::Owned(input.iter().map(|&b| char::from(b)).collect()),
+            },
+        };
+        EmailAddress::try_parse(&decoded, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_bytes_as_per_the_option() {
+        let email =
+            EmailAddress::parse_bytes("jörg@example.com".as_bytes(), Default::default(), None);
+        assert_eq!(email.unwrap().get_local_part(), "jörg");
+        let input = b"foo@ex\xe4mple.com";
+        let error = EmailAddress::parse_bytes(input, InvalidUtf8::Reject, None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Invalid);
+        assert_eq!(&error.input()[error.span()], "\u{fffd}");
+        let email = EmailAddress::parse_bytes(input, InvalidUtf8::Latin1, None).unwrap();
+        assert_eq!(email.get_domain(), "exämple.com");
+        let error = EmailAddress::parse_bytes(b"\xff@-bar.com", InvalidUtf8::Latin1, None);
+        assert_eq!(error.unwrap_err().input(), "ÿ@-bar.com");
+        assert!(EmailAddress::parse_bytes(input, InvalidUtf8::Lossy, None).is_ok());
+    }
+}
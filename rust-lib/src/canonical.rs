@@ -0,0 +1,273 @@
+use crate::email_address::{Rule, RFC5322};
+use alloc::string::String;
+use alloc::vec::Vec;
+use pest::Parser;
+
+/// A semantic token of a local part or a domain; comments and folding white space are dropped.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Atom(&'a str),
+    Dot,
+    /// The content of a quoted string, with the quoted pairs resolved and the folding unfolded.
+    Quoted(String),
+    /// The content of a domain literal, without white space.
+    Literal(&'a str),
+}
+
+pub(crate) fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c) || !c.is_ascii()
+}
+
+/// Splits a (syntactically valid) local part or domain into tokens.
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            ' ' | '\t' | '\r' | '\n' => (),
+            '.' => tokens.push(Token::Dot),
+            '(' => {
+                let mut depth = 1;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '(' => depth += 1,
+                        ')' if depth == 1 => break,
+                        ')' => depth -= 1,
+                        _ => (),
+                    }
+                }
+            }
+            '"' => {
+                let mut content = String::new();
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => content.extend(chars.next().map(|(_, c)| c)),
+                        '"' => break,
+                        '\r' | '\n' => (),
+                        c => content.push(c),
+                    }
+                }
+                tokens.push(Token::Quoted(content));
+            }
+            '[' => {
+                let mut end = input.len();
+                while let Some((index, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        ']' => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => (),
+                    }
+                }
+                tokens.push(Token::Literal(&input[start..end]));
+            }
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(index, c)) = chars.peek() {
+                    if " \t\r\n.()\"[".contains(c) {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Atom(&input[start..end]));
+            }
+        }
+    }
+    tokens
+}
+
+/// Returns the semantic content of a local part; i.e. without comments and folding white space,
+/// and with the quoted strings unquoted and their quoted pairs resolved.
+pub(crate) fn unquoted_local_part(local_part: &str) -> String {
+    let mut content = String::new();
+    for token in tokenize(local_part) {
+        match token {
+            Token::Atom(atom) | Token::Literal(atom) => content.push_str(atom),
+            Token::Dot => content.push('.'),
+            Token::Quoted(quoted) => content.push_str(&quoted),
+        }
+    }
+    content
+}
+
+/// Returns the canonical form of a local part; i.e. its semantic content as a dot-atom, or as a
+/// single quoted string if it cannot be a dot-atom.
+///
+/// The control characters that only the obsolete syntax allows are dropped, so that the canonical
+/// form is valid as per strict parsing.
+pub(crate) fn canonical_local_part(local_part: &str) -> String {
+    let mut content = unquoted_local_part(local_part);
+    content.retain(|c| !is_obsolete_control(c));
+    quote_local_part(&content)
+}
+
+/// Returns whether canonicalizing the parts of an address drops control characters from its local
+/// part or its domain literal; see `canonical_local_part` and `canonical_domain`.
+pub(crate) fn drops_controls(local_part: &str, domain: &str) -> bool {
+    unquoted_local_part(local_part)
+        .chars()
+        .any(is_obsolete_control)
+        || tokenize(domain).iter().any(|token| match token {
+            Token::Literal(literal) => literal
+                .chars()
+                .any(|c| is_obsolete_control(c) && c != '\r' && c != '\n'),
+            _ => false,
+        })
+}
+
+/// Returns whether a character is a control character that strict parsing does not allow in a
+/// quoted string or in a domain literal; i.e. a control character other than a tab.
+fn is_obsolete_control(c: char) -> bool {
+    c.is_ascii_control() && c != '\t'
+}
+
+/// Returns whether a local part value, like a user name, has to be quoted to be a local part;
+/// i.e. whether it is not a dot-atom.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::needs_quoting;
+///
+/// assert!(!needs_quoting("john.doe"));
+/// assert!(!needs_quoting("jöhn+tag"));
+/// assert!(needs_quoting("john doe"));
+/// assert!(needs_quoting("john..doe"));
+/// assert!(needs_quoting(""));
+/// ```
+pub fn needs_quoting(local_part: &str) -> bool {
+    let is_dot_atom = local_part.chars().all(|c| c == '.' || is_atext(c))
+        && RFC5322::parse(Rule::local_part_complete, local_part).is_ok();
+    !is_dot_atom
+}
+
+/// Returns a local part value, like a user name, as a local part; i.e. as it is if it is a
+/// dot-atom, and as a quoted string otherwise, with the double quotes and backslashes escaped.
+///
+/// This is the inverse of `EmailAddress::local_part_unquoted`. The control characters allowed by
+/// the obsolete syntax only (NUL, CR, and LF) are escaped too, and require lax parsing.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{quote_local_part, EmailAddress};
+///
+/// assert_eq!(quote_local_part("john.doe"), "john.doe");
+/// assert_eq!(quote_local_part("john \"jd\" doe"), "\"john \\\"jd\\\" doe\"");
+///
+/// let email = EmailAddress::new(&quote_local_part("john doe"), "example.com", None).unwrap();
+/// assert_eq!(email.to_string(), "\"john doe\"@example.com");
+/// assert_eq!(email.local_part_unquoted(), "john doe");
+/// ```
+pub fn quote_local_part(local_part: &str) -> String {
+    if !needs_quoting(local_part) {
+        return String::from(local_part);
+    }
+    let mut quoted = String::from("\"");
+    for c in local_part.chars() {
+        if matches!(c, '"' | '\\' | '\u{0}' | '\r' | '\n') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns the canonical form of a domain; i.e. a lower case host name without comments and white
+/// space, or a domain literal without white space.
+///
+/// The control characters that only the obsolete syntax allows in a domain literal are dropped,
+/// so that the canonical form is valid as per strict parsing.
+pub(crate) fn canonical_domain(domain: &str) -> String {
+    let mut canonical = String::new();
+    for token in tokenize(domain) {
+        match token {
+            Token::Atom(atom) => canonical.push_str(&atom.to_lowercase()),
+            Token::Dot => canonical.push('.'),
+            Token::Quoted(quoted) => canonical.push_str(&quoted),
+            Token::Literal(literal) => canonical.extend(
+                literal
+                    .chars()
+                    .filter(|&c| !(is_obsolete_control(c) || c == ' ' || c == '\t')),
+            ),
+        }
+    }
+    canonical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizer_drops_comments_and_folding_white_space() {
+        assert_eq!(
+            tokenize(" (a (nested) \\) comment)foo .\r\n bar"),
+            vec![Token::Atom("foo"), Token::Dot, Token::Atom("bar")]
+        );
+        assert_eq!(
+            tokenize("\"a\\\"b\r\n c\"(x)"),
+            vec![Token::Quoted(String::from("a\"b c"))]
+        );
+    }
+
+    #[test]
+    fn local_part_is_unquoted_when_possible() {
+        assert_eq!(canonical_local_part(" test "), "test");
+        assert_eq!(canonical_local_part("test . test"), "test.test");
+        assert_eq!(canonical_local_part("(comment)\"test\""), "test");
+        assert_eq!(canonical_local_part("\"test\".\"test\""), "test.test");
+        assert_eq!(canonical_local_part("\"\\a\""), "a");
+        assert_eq!(canonical_local_part("\"a b\".c"), "\"a b.c\"");
+        assert_eq!(canonical_local_part("\"a..b\""), "\"a..b\"");
+        assert_eq!(canonical_local_part("\"\""), "\"\"");
+        assert_eq!(canonical_local_part("\"a\\\\b\""), "\"a\\\\b\"");
+        assert_eq!(canonical_local_part("\"a\u{0}\\\rb\tc\""), "\"ab\tc\"");
+    }
+
+    #[test]
+    fn local_part_is_unquoted_with_quoted_pairs_resolved() {
+        assert_eq!(unquoted_local_part("\"john doe\""), "john doe");
+        assert_eq!(unquoted_local_part("\"a\\\\b\\\"c\""), "a\\b\"c");
+        assert_eq!(
+            unquoted_local_part("(x) \"john\" . \"doe\" (y)"),
+            "john.doe"
+        );
+        assert_eq!(unquoted_local_part("\"\""), "");
+    }
+
+    #[test]
+    fn local_part_values_are_quoted_when_needed() {
+        for value in &["foo", "a.b", "foö", "!#$%&'*+-/=?^_`{|}~"] {
+            assert_eq!(quote_local_part(value), *value);
+        }
+        for (value, quoted) in &[
+            ("", "\"\""),
+            (".foo", "\".foo\""),
+            ("foo@bar", "\"foo@bar\""),
+            ("a\\b", "\"a\\\\b\""),
+            ("a\rb", "\"a\\\rb\""),
+        ] {
+            assert!(needs_quoting(value));
+            assert_eq!(quote_local_part(value), *quoted);
+            assert_eq!(unquoted_local_part(quoted), *value);
+        }
+    }
+
+    #[test]
+    fn domain_is_lower_case_without_comments() {
+        assert_eq!(canonical_domain("IANA.org"), "iana.org");
+        assert_eq!(canonical_domain("(comment)iana.(x)ORG "), "iana.org");
+        assert_eq!(canonical_domain("BÜCHER.de"), "bücher.de");
+        assert_eq!(canonical_domain("(c) [ 1.2.3.4 ] "), "[1.2.3.4]");
+        assert_eq!(canonical_domain("[IPv6:::1]"), "[IPv6:::1]");
+        assert_eq!(canonical_domain("[a\u{7}\r\n b]"), "[ab]");
+    }
+}
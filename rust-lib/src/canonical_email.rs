@@ -0,0 +1,158 @@
+use crate::email_address::EmailAddress;
+use crate::eq_policy::EqPolicy;
+use alloc::string::{String, ToString};
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// The canonical form of an email address, stored as a single string; e.g. as the key of a
+/// suppression list, that can be looked up by a `&str` without parsing it.
+///
+/// It borrows as `str`, so that a `HashSet<CanonicalEmail>`, or a `BTreeSet<CanonicalEmail>`, can
+/// be looked up by a string as it is; which therefore only finds the address if the string is in
+/// its canonical form too (see `EmailAddress::canonical`), like the ones stored by the application.
+/// Parse the strings that may not be, like the ones typed by users.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{CanonicalEmail, EmailAddress};
+/// use std::collections::HashSet;
+///
+/// let suppressed: HashSet<CanonicalEmail> = ["Jane@Example.com", "\"joe\"@example.com"]
+///     .iter()
+///     .map(|input| EmailAddress::parse(input, None).unwrap().into())
+///     .collect();
+/// assert!(suppressed.contains("Jane@example.com"));
+/// assert!(suppressed.contains("joe@example.com"));
+/// assert!(!suppressed.contains("jane@example.com"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CanonicalEmail {
+    address: String,
+    /// The index of the `@` separating the local part and the domain.
+    at: usize,
+}
+
+impl CanonicalEmail {
+    /// Instantiates the canonical form of an address.
+    pub fn new(email: &EmailAddress) -> Self {
+        let canonical = email.canonical();
+        CanonicalEmail {
+            address: canonical.to_string(),
+            at: canonical.get_local_part().len(),
+        }
+    }
+
+    /// Instantiates the form of an address that it is compared by as per a policy; e.g. to build
+    /// a suppression list where tagged addresses are the same as untagged ones. See `EqPolicy::key`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{CanonicalEmail, EmailAddress, EqPolicy};
+    ///
+    /// let email = EmailAddress::parse("Jane+news@example.com", None).unwrap();
+    /// let policy = EqPolicy::new().ci_local_part(true).strip_tags(true);
+    /// assert_eq!(CanonicalEmail::with_policy(&email, &policy).as_str(), "jane@example.com");
+    /// ```
+    pub fn with_policy(email: &EmailAddress, policy: &EqPolicy) -> Self {
+        policy.key(email)
+    }
+
+    /// Returns the canonical form as a string.
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the local part of the canonical form.
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// Returns the domain of the canonical form.
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+
+    /// Returns the canonical form as an `EmailAddress`.
+    pub fn to_email_address(&self) -> EmailAddress {
+        // The parts are those of the canonical form of an address, which is stored in these buffers
+        // already; with the `heapless` feature, its domain can be longer than `DOMAIN_CAPACITY`.
+        EmailAddress::store_in_buffers(self.local_part(), self.domain())
+            .expect("the canonical parts fit in the buffers")
+    }
+}
+
+impl From<EmailAddress> for CanonicalEmail {
+    fn from(email: EmailAddress) -> Self {
+        CanonicalEmail::new(&email)
+    }
+}
+
+impl From<&EmailAddress> for CanonicalEmail {
+    fn from(email: &EmailAddress) -> Self {
+        CanonicalEmail::new(email)
+    }
+}
+
+impl From<CanonicalEmail> for String {
+    fn from(email: CanonicalEmail) -> Self {
+        email.address
+    }
+}
+
+/// Hashes the canonical form as a `str`, as required by the `Borrow<str>` implementation.
+impl Hash for CanonicalEmail {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address.hash(state)
+    }
+}
+
+impl Borrow<str> for CanonicalEmail {
+    fn borrow(&self) -> &str {
+        &self.address
+    }
+}
+
+impl AsRef<str> for CanonicalEmail {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
+}
+
+impl fmt::Display for CanonicalEmail {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.address)
+    }
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use crate::email_address::ParsingOptions;
+    use alloc::collections::BTreeMap;
+
+    #[test]
+    fn looks_up_by_canonical_strings() {
+        let options = Some(ParsingOptions::new(true));
+        let email = EmailAddress::parse("\"a@b\" . c@(comment)Example.COM", options).unwrap();
+        let canonical = CanonicalEmail::new(&email);
+        assert_eq!(canonical.as_str(), "\"a@b.c\"@example.com");
+        assert_eq!(canonical.local_part(), "\"a@b.c\"");
+        assert_eq!(canonical.domain(), "example.com");
+        assert_eq!(canonical.to_email_address(), email.canonical());
+        let mut map = BTreeMap::new();
+        map.insert(canonical, 1);
+        assert_eq!(map.get("\"a@b.c\"@example.com"), Some(&1));
+        assert_eq!(map.get("\"a@b\".c@example.com"), None);
+    }
+
+    #[test]
+    fn converts_a_canonical_domain_longer_than_the_domain() {
+        // 245 octets, whose lower case is 365 octets.
+        let label = "Ⱥ".repeat(60);
+        let email = EmailAddress::new("foo", &format!("{}.{}.com", label, label), None).unwrap();
+        let canonical = CanonicalEmail::new(&email);
+        assert_eq!(canonical.domain().len(), 365);
+        assert_eq!(canonical.to_email_address(), email.canonical());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use clap::builder::{StringValueParser, TypedValueParser};
+use clap::{Arg, Command};
+use std::ffi::OsStr;
+
+/// A clap value parser for `EmailAddress` arguments.
+///
+/// `EmailAddress` implements `FromStr`, and therefore it can be used as an argument type without
+/// this parser; e.g. with `clap::value_parser!(EmailAddress)`, which parses strictly.
+/// Use this parser to accept the obsolete syntax as well.
+/// In both cases, an invalid value is reported along with a hint on how to fix it.
+///
+/// # Examples
+/// ```
+/// use clap::{Arg, Command};
+/// use email_address_parser::{EmailAddress, EmailAddressValueParser};
+///
+/// let command = Command::new("mailer").arg(
+///     Arg::new("to")
+///         .long("to")
+///         .value_parser(EmailAddressValueParser::new().lax(true)),
+/// );
+///
+/// # #[cfg(not(feature = "strict-only"))] {
+/// let matches = command
+///     .clone()
+///     .try_get_matches_from(["mailer", "--to", "test . test@iana.org"])
+///     .unwrap();
+/// let to = matches.get_one::<EmailAddress>("to").unwrap();
+/// assert_eq!(to.get_domain(), "iana.org");
+/// # }
+///
+/// let error = command
+///     .try_get_matches_from(["mailer", "--to", "foo@-bar.com"])
+///     .unwrap_err();
+/// assert!(error
+///     .to_string()
+///     .contains("A domain label can neither start nor end with a hyphen."));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmailAddressValueParser {
+    is_lax: bool,
+}
+
+impl EmailAddressValueParser {
+    /// Instantiates a value parser that parses strictly.
+    pub fn new() -> Self {
+        EmailAddressValueParser::default()
+    }
+
+    /// Enables/disables obsolete parts parsing.
+    pub fn lax(self, is_lax: bool) -> Self {
+        EmailAddressValueParser { is_lax }
+    }
+}
+
+impl TypedValueParser for EmailAddressValueParser {
+    type Value = EmailAddress;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let is_lax = self.is_lax;
+        StringValueParser::new()
+            .try_map(move |input| {
+                EmailAddress::try_parse(&input, Some(ParsingOptions::new(is_lax)))
+            })
+            .parse_ref(cmd, arg, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "strict-only"))]
+    fn command(parser: EmailAddressValueParser) -> Command {
+        Command::new("mailer").arg(Arg::new("to").long("to").value_parser(parser))
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn strict_parser_rejects_obsolete_syntax_with_hint() {
+        let error = command(EmailAddressValueParser::new())
+            .try_get_matches_from(["mailer", "--to", "test . test@iana.org"])
+            .unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(error.to_string().contains(
+            "Invalid local part 'test . test'. The address contains obsolete syntax, and can only be parsed with lax parsing options."
+        ));
+    }
+
+    #[test]
+    fn from_str_is_usable_as_value_parser() {
+        let matches = Command::new("mailer")
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .value_parser(clap::value_parser!(EmailAddress)),
+            )
+            .try_get_matches_from(["mailer", "--to", "foo@bar.com"])
+            .unwrap();
+        assert_eq!(
+            matches
+                .get_one::<EmailAddress>("to")
+                .unwrap()
+                .get_local_part(),
+            "foo"
+        );
+    }
+}
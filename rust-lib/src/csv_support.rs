@@ -0,0 +1,389 @@
+use crate::diagnostic::Diagnostic;
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+use std::fmt;
+use std::io::Read;
+
+/// The number of records that are sampled to detect the column of the addresses by its values.
+const SAMPLE_SIZE: usize = 100;
+
+/// The lower case header names, without separators, of a column of addresses.
+const HEADER_NAMES: &[&str] = &["email", "emails", "emailaddress", "mail", "mailaddress"];
+
+/// Imports the addresses of a column of a CSV file, like a mailing list export, into a report of
+/// the accepted, repaired, and rejected rows.
+///
+/// The column is either given, or detected: by its header, like `Email` or `E-mail address`, if
+/// there is one, and by its values otherwise, as the column with the most addresses among the
+/// first 100 records.
+///
+/// Only available with the `csv` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{CsvImporter, Hint};
+///
+/// let csv = "Name,E-mail\nJane,jane@example.com\nJohn,john@gmailcom\nJoe,joe\n";
+/// let report = CsvImporter::new().repair(true).import(csv.as_bytes()).unwrap();
+/// assert_eq!(report.column(), 1);
+/// assert_eq!(report.accepted()[0].email().to_string(), "jane@example.com");
+/// assert_eq!(report.repaired()[0].email().to_string(), "john@gmail.com");
+/// assert_eq!(report.rejected()[0].line(), 4);
+/// assert_eq!(report.rejected()[0].error().hint(), Some(Hint::MissingAt));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsvImporter {
+    options: ParsingOptions,
+    repair: bool,
+    column: Option<Column>,
+    has_headers: bool,
+    delimiter: u8,
+}
+
+#[derive(Debug, Clone)]
+enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl Default for CsvImporter {
+    fn default() -> Self {
+        CsvImporter {
+            options: ParsingOptions::default().trim(true),
+            repair: false,
+            column: None,
+            has_headers: true,
+            delimiter: b',',
+        }
+    }
+}
+
+impl CsvImporter {
+    /// Instantiates an importer of comma separated values with a header row, that parses the
+    /// trimmed values with the default options, without repairing them, and detects the column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the options to parse the values with.
+    pub fn options(self, options: ParsingOptions) -> Self {
+        CsvImporter { options, ..self }
+    }
+
+    /// Sets whether to repair the common mistakes of typed addresses, as per
+    /// `EmailAddress::parse_with_repair`.
+    pub fn repair(self, repair: bool) -> Self {
+        CsvImporter { repair, ..self }
+    }
+
+    /// Sets the name of the header of the column of the addresses, instead of detecting it; the
+    /// name is compared ignoring case.
+    pub fn column(self, name: &str) -> Self {
+        CsvImporter {
+            column: Some(Column::Name(String::from(name))),
+            ..self
+        }
+    }
+
+    /// Sets the zero-based index of the column of the addresses, instead of detecting it.
+    pub fn column_index(self, index: usize) -> Self {
+        CsvImporter {
+            column: Some(Column::Index(index)),
+            ..self
+        }
+    }
+
+    /// Sets whether the first record is a header row.
+    pub fn has_headers(self, has_headers: bool) -> Self {
+        CsvImporter {
+            has_headers,
+            ..self
+        }
+    }
+
+    /// Sets the field delimiter; e.g. `b';'` or `b'\t'`.
+    pub fn delimiter(self, delimiter: u8) -> Self {
+        CsvImporter { delimiter, ..self }
+    }
+
+    /// Reads a CSV, and imports its addresses.
+    pub fn import<R: Read>(&self, reader: R) -> Result<ImportReport, CsvImportError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(self.has_headers)
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_reader(reader);
+        let headers = match self.has_headers {
+            true => Some(reader.headers()?.clone()),
+            false => None,
+        };
+        let records = reader.records().collect::<Result<Vec<_>, _>>()?;
+        let column = match &self.column {
+            Some(Column::Index(index)) => *index,
+            Some(Column::Name(name)) => headers
+                .iter()
+                .flatten()
+                .position(|header| header.trim().eq_ignore_ascii_case(name))
+                .ok_or_else(|| CsvImportError::MissingColumn(name.clone()))?,
+            None => headers
+                .as_ref()
+                .and_then(detect_header)
+                .or_else(|| self.detect_values(&records))
+                .ok_or(CsvImportError::UndetectedColumn)?,
+        };
+        let mut report = ImportReport {
+            column,
+            accepted: Vec::new(),
+            repaired: Vec::new(),
+            rejected: Vec::new(),
+        };
+        for record in &records {
+            let line = record.position().map_or(0, |position| position.line());
+            let value = String::from(record.get(column).unwrap_or_default());
+            match self.parse(&value) {
+                Ok((email, diagnostics)) => {
+                    let row = ImportedRow {
+                        line,
+                        email,
+                        diagnostics,
+                        value,
+                    };
+                    match row.email.to_string() == row.value {
+                        true => report.accepted.push(row),
+                        false => report.repaired.push(row),
+                    }
+                }
+                Err(error) => report.rejected.push(RejectedRow { line, value, error }),
+            }
+        }
+        Ok(report)
+    }
+
+    fn parse(&self, value: &str) -> Result<(EmailAddress, Vec<Diagnostic>), ParseError> {
+        let options = Some(self.options.clone());
+        match self.repair {
+            true => EmailAddress::parse_with_repair(value, options),
+            false => EmailAddress::parse_with_diagnostics(value, options),
+        }
+    }
+
+    /// Returns the index of the column with the most addresses among the first records, if any.
+    fn detect_values(&self, records: &[csv::StringRecord]) -> Option<usize> {
+        let sample = &records[..records.len().min(SAMPLE_SIZE)];
+        let columns = sample.iter().map(csv::StringRecord::len).max()?;
+        (0..columns)
+            .map(|column| {
+                let count = sample
+                    .iter()
+                    .filter_map(|record| record.get(column))
+                    .filter(|value| EmailAddress::is_valid(value, Some(self.options.clone())))
+                    .count();
+                (count, column)
+            })
+            .filter(|(count, _)| *count > 0)
+            // The first of the columns with the most addresses.
+            .max_by_key(|(count, column)| (*count, core::cmp::Reverse(*column)))
+            .map(|(_, column)| column)
+    }
+}
+
+/// Returns the index of the first column with the header of a column of addresses, if any.
+fn detect_header(headers: &csv::StringRecord) -> Option<usize> {
+    let names: Vec<String> = headers
+        .iter()
+        .map(|header| {
+            header
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase()
+        })
+        .collect();
+    names
+        .iter()
+        .position(|name| HEADER_NAMES.contains(&name.as_str()))
+        .or_else(|| names.iter().position(|name| name.contains("email")))
+}
+
+/// The report of a `CsvImporter`.
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    column: usize,
+    accepted: Vec<ImportedRow>,
+    repaired: Vec<ImportedRow>,
+    rejected: Vec<RejectedRow>,
+}
+
+impl ImportReport {
+    /// Returns the zero-based index of the column of the addresses.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the rows with an address that is the value of the cell as it is.
+    pub fn accepted(&self) -> &[ImportedRow] {
+        &self.accepted
+    }
+
+    /// Returns the rows with an address that differs from the value of the cell; e.g. as it was
+    /// trimmed or repaired.
+    pub fn repaired(&self) -> &[ImportedRow] {
+        &self.repaired
+    }
+
+    /// Returns the rows without an address.
+    pub fn rejected(&self) -> &[RejectedRow] {
+        &self.rejected
+    }
+}
+
+/// A row with an address, of an `ImportReport`.
+#[derive(Debug, Clone)]
+pub struct ImportedRow {
+    line: u64,
+    value: String,
+    email: EmailAddress,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ImportedRow {
+    /// Returns the line number of the row in the CSV, starting at 1.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Returns the value of the cell.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the address.
+    pub fn email(&self) -> &EmailAddress {
+        &self.email
+    }
+
+    /// Returns how the value was interpreted, and repaired, to parse it.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// A row without an address, of an `ImportReport`.
+#[derive(Debug, Clone)]
+pub struct RejectedRow {
+    line: u64,
+    value: String,
+    error: ParseError,
+}
+
+impl RejectedRow {
+    /// Returns the line number of the row in the CSV, starting at 1.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Returns the value of the cell; empty if the row has no such cell.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns why the value is not an address.
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+}
+
+/// Error returned when a CSV cannot be imported.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvImportError {
+    /// The CSV could not be read.
+    Csv(csv::Error),
+    /// There is no column with the given header name.
+    MissingColumn(String),
+    /// No column was detected as the column of the addresses.
+    UndetectedColumn,
+}
+
+impl From<csv::Error> for CsvImportError {
+    fn from(error: csv::Error) -> Self {
+        CsvImportError::Csv(error)
+    }
+}
+
+impl fmt::Display for CsvImportError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvImportError::Csv(error) => write!(formatter, "Invalid CSV: {}", error),
+            CsvImportError::MissingColumn(name) => write!(formatter, "No column {:?}.", name),
+            CsvImportError::UndetectedColumn => formatter.write_str("No column of addresses."),
+        }
+    }
+}
+
+impl std::error::Error for CsvImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvImportError::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_the_column_by_header_then_by_values() {
+        let csv = "id,name,Work E-Mail\n1,Jane,jane@example.com\n";
+        let report = CsvImporter::new().import(csv.as_bytes()).unwrap();
+        assert_eq!(report.column(), 2);
+        let csv = "1;Jane;jane@example.com;joe@example.com\n2;John;john@example.com;x\n";
+        let importer = CsvImporter::new().has_headers(false).delimiter(b';');
+        let report = importer.import(csv.as_bytes()).unwrap();
+        assert_eq!(report.column(), 2);
+        assert_eq!(report.accepted().len(), 2);
+        let csv = "id,name\n1,Jane\n";
+        assert!(matches!(
+            CsvImporter::new().import(csv.as_bytes()),
+            Err(CsvImportError::UndetectedColumn)
+        ));
+    }
+
+    #[test]
+    fn reports_the_rows() {
+        let csv = "Email,Name\n jane@example.com ,Jane\n\"<john@example.com>\",John\n,Joe\nx\n";
+        let importer = CsvImporter::new()
+            .column("EMAIL")
+            .options(ParsingOptions::default().trim(true).strip_wrappers(true));
+        let report = importer.import(csv.as_bytes()).unwrap();
+        assert!(report.accepted().is_empty());
+        let repaired: Vec<_> = report
+            .repaired()
+            .iter()
+            .map(|row| (row.line(), row.value(), row.diagnostics()))
+            .collect();
+        assert_eq!(
+            repaired,
+            [
+                (2, " jane@example.com ", &[Diagnostic::Trimmed][..]),
+                (
+                    3,
+                    "<john@example.com>",
+                    &[Diagnostic::StrippedAngleBrackets][..]
+                )
+            ]
+        );
+        let rejected: Vec<_> = report
+            .rejected()
+            .iter()
+            .map(|row| (row.line(), row.value()))
+            .collect();
+        assert_eq!(rejected, [(4, ""), (5, "x")]);
+        assert!(matches!(
+            CsvImporter::new().column("mail").import(csv.as_bytes()),
+            Err(CsvImportError::MissingColumn(_))
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use core::fmt;
+
+/// A note on how an input was interpreted to parse it as an email address; e.g. an obsolete part
+/// that was discarded. Unlike a `Hint`, it does not prevent the parsing.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// # #[cfg(not(feature = "strict-only"))] {
+/// let options = ParsingOptions::new(true);
+/// let (email, diagnostics) =
+///     EmailAddress::parse_with_diagnostics("@relay.example.org:foo@bar.com", Some(options)).unwrap();
+/// assert_eq!(email.to_string(), "foo@bar.com");
+/// assert_eq!(diagnostics, vec![Diagnostic::ObsoleteRoute]);
+/// assert_eq!(diagnostics[0].code(), "obsolete_route");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// Full-width forms, like `＠`, were mapped to their ASCII equivalents, as per the parsing
+    /// options.
+    FullWidthForms,
+    /// Smart punctuation marks, like curly quotes, were mapped to their ASCII equivalents, as per
+    /// the parsing options.
+    SmartPunctuation,
+    /// A repeated `@`, like in `foo@@bar.com`, was collapsed; by `EmailAddress::parse_with_repair`.
+    CollapsedSeparators,
+    /// The punctuation after the address, like in `foo@bar.com.`, was stripped; by
+    /// `EmailAddress::parse_with_repair`.
+    StrippedTrailingPunctuation,
+    /// The missing dot before the top-level domain, like in `foo@gmailcom`, was inserted; by
+    /// `EmailAddress::parse_with_repair`.
+    InsertedTopLevelDomainDot,
+    /// The spaces between the labels of the domain, like in `foo@gmail com`, were replaced with
+    /// dots; by `EmailAddress::parse_with_repair`.
+    ReplacedSpacesWithDots,
+    /// The white space around the address was trimmed, as per the parsing options.
+    Trimmed,
+    /// A leading `mailto:` was stripped, as per the parsing options.
+    StrippedMailto,
+    /// The angle brackets around the address were stripped, as per the parsing options, or as
+    /// lax parsing accepts a bare angle-addr.
+    StrippedAngleBrackets,
+    /// The double quotes around the address were stripped, as per the parsing options.
+    StrippedQuotes,
+    /// An obsolete source route before the address, like `@relay.example.org:`, was discarded.
+    ObsoleteRoute,
+    /// The domain is a host name with an underscore, like `my_host.internal.corp`; RFC 5322 allows
+    /// it, but RFC 1035 does not, and it may not resolve outside of an intranet.
+    UnderscoreInDomain,
+    /// The local part routes the address through its domain, as a relay, to the host after its
+    /// first `%`; i.e. the legacy percent hack, as in `user%final.example@relay.example`. See
+    /// `EmailAddress::final_destination`.
+    PercentHack,
+    /// The local part is a UUCP bang path, that routes the address to the host before its last
+    /// `!`, as in `final.example!user@relay.example`. See `EmailAddress::final_destination`.
+    BangPath,
+    /// A semicolon separated the addresses of a list, as in the lists of Outlook; RFC 5322 only
+    /// allows commas, and lax parsing accepts semicolons as well. See
+    /// `Address::parse_list_with_diagnostics`.
+    SemicolonSeparator,
+}
+
+impl Diagnostic {
+    /// Returns a stable, snake case code of the diagnostic; e.g. `obsolete_route`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Diagnostic::FullWidthForms => "full_width_forms",
+            Diagnostic::SmartPunctuation => "smart_punctuation",
+            Diagnostic::CollapsedSeparators => "collapsed_separators",
+            Diagnostic::StrippedTrailingPunctuation => "stripped_trailing_punctuation",
+            Diagnostic::InsertedTopLevelDomainDot => "inserted_top_level_domain_dot",
+            Diagnostic::ReplacedSpacesWithDots => "replaced_spaces_with_dots",
+            Diagnostic::Trimmed => "trimmed",
+            Diagnostic::StrippedMailto => "stripped_mailto",
+            Diagnostic::StrippedAngleBrackets => "stripped_angle_brackets",
+            Diagnostic::StrippedQuotes => "stripped_quotes",
+            Diagnostic::ObsoleteRoute => "obsolete_route",
+            Diagnostic::UnderscoreInDomain => "underscore_in_domain",
+            Diagnostic::PercentHack => "percent_hack",
+            Diagnostic::BangPath => "bang_path",
+            Diagnostic::SemicolonSeparator => "semicolon_separator",
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Diagnostic::FullWidthForms => {
+                "The full-width forms were mapped to their ASCII equivalents."
+            }
+            Diagnostic::SmartPunctuation => {
+                "The smart punctuation marks were mapped to their ASCII equivalents."
+            }
+            Diagnostic::CollapsedSeparators => "The repeated '@' was collapsed.",
+            Diagnostic::StrippedTrailingPunctuation => {
+                "The punctuation after the address was stripped."
+            }
+            Diagnostic::InsertedTopLevelDomainDot => {
+                "The missing dot before the top-level domain was inserted."
+            }
+            Diagnostic::ReplacedSpacesWithDots => {
+                "The spaces between the labels of the domain were replaced with dots."
+            }
+            Diagnostic::Trimmed => "The white space around the address was trimmed.",
+            Diagnostic::StrippedMailto => "The leading 'mailto:' was stripped.",
+            Diagnostic::StrippedAngleBrackets => {
+                "The angle brackets around the address were stripped."
+            }
+            Diagnostic::StrippedQuotes => "The double quotes around the address were stripped.",
+            Diagnostic::ObsoleteRoute => {
+                "The obsolete source route before the address was discarded."
+            }
+            Diagnostic::UnderscoreInDomain => {
+                "The domain contains an underscore, that is not allowed in host names as per RFC 1035."
+            }
+            Diagnostic::PercentHack => {
+                "The local part routes the address to another host with the percent hack."
+            }
+            Diagnostic::BangPath => "The local part is a bang path to another host.",
+            Diagnostic::SemicolonSeparator => {
+                "A semicolon, instead of a comma, separated the addresses of the list."
+            }
+        })
+    }
+}
@@ -0,0 +1,95 @@
+/// The domains of popular mailbox providers, from the most to the least popular; e.g. to complete
+/// or to correct the domains of typed addresses.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::POPULAR_DOMAINS;
+///
+/// assert_eq!(POPULAR_DOMAINS[0], "gmail.com");
+/// ```
+pub const POPULAR_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "yahoo.com",
+    "hotmail.com",
+    "outlook.com",
+    "aol.com",
+    "icloud.com",
+    "hotmail.co.uk",
+    "live.com",
+    "msn.com",
+    "yahoo.co.uk",
+    "comcast.net",
+    "googlemail.com",
+    "me.com",
+    "mail.ru",
+    "yandex.ru",
+    "gmx.de",
+    "web.de",
+    "qq.com",
+    "163.com",
+    "orange.fr",
+    "free.fr",
+    "yahoo.fr",
+    "hotmail.fr",
+    "libero.it",
+    "t-online.de",
+    "protonmail.com",
+    "proton.me",
+    "gmx.com",
+    "mail.com",
+    "email.com",
+    "zoho.com",
+    "att.net",
+    "verizon.net",
+    "ymail.com",
+    "live.co.uk",
+    "btinternet.com",
+    "naver.com",
+    "yahoo.co.jp",
+    "rediffmail.com",
+    "fastmail.com",
+    "mac.com",
+];
+
+/// The domains of well-known disposable (temporary) mailbox providers.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::DISPOSABLE_DOMAINS;
+///
+/// assert!(DISPOSABLE_DOMAINS.contains(&"mailinator.com"));
+/// ```
+pub const DISPOSABLE_DOMAINS: &[&str] = &[
+    "10minutemail.com",
+    "dispostable.com",
+    "fakeinbox.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "guerrillamail.net",
+    "mailinator.com",
+    "maildrop.cc",
+    "mintemail.com",
+    "mytemp.email",
+    "sharklasers.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dictionaries_have_distinct_lower_case_domains() {
+        for dictionary in &[POPULAR_DOMAINS, DISPOSABLE_DOMAINS] {
+            for (index, domain) in dictionary.iter().enumerate() {
+                assert!(crate::validate_domain(domain, &Default::default()).is_ok());
+                assert_eq!(domain.to_ascii_lowercase(), *domain);
+                assert!(!dictionary[..index].contains(domain), "{}", domain);
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! `EmailAddress` is stored in `Text` columns; i.e. as its string representation.
+//!
+//! On read, the stored value is parsed strictly. Use `LaxEmailAddress` for columns containing
+//! legacy addresses with obsolete parts.
+//! The conversions to SQL are implemented for the PostgreSQL and MySQL backends with the `diesel`
+//! feature, and for the SQLite backend with the `diesel-sqlite` feature.
+//!
+//! ```ignore
+//! use email_address_parser::EmailAddress;
+//!
+//! #[derive(Queryable, Insertable)]
+//! #[diesel(table_name = users)]
+//! struct User {
+//!     id: i32,
+//!     email: EmailAddress,
+//! }
+//! ```
+
+use crate::email_address::EmailAddress;
+use crate::lax::LaxEmailAddress;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use std::io::Write;
+
+macro_rules! impl_diesel_text {
+    ($type:ty) => {
+        impl<DB> FromSql<Text, DB> for $type
+        where
+            DB: Backend,
+            String: FromSql<Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                let value = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+                Ok(value.parse::<$type>()?)
+            }
+        }
+
+        impl ToSql<Text, diesel::pg::Pg> for $type {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+                write!(out, "{}", self)?;
+                Ok(IsNull::No)
+            }
+        }
+
+        impl ToSql<Text, diesel::mysql::Mysql> for $type {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut Output<'b, '_, diesel::mysql::Mysql>,
+            ) -> serialize::Result {
+                write!(out, "{}", self)?;
+                Ok(IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-sqlite")]
+        impl ToSql<Text, diesel::sqlite::Sqlite> for $type {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut Output<'b, '_, diesel::sqlite::Sqlite>,
+            ) -> serialize::Result {
+                out.set_value(self.to_string());
+                Ok(IsNull::No)
+            }
+        }
+    };
+}
+
+impl_diesel_text!(EmailAddress);
+impl_diesel_text!(LaxEmailAddress);
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod tests {
+    use super::*;
+    use diesel::dsl::sql;
+    use diesel::{Connection, IntoSql, RunQueryDsl, SqliteConnection};
+
+    fn connection() -> SqliteConnection {
+        SqliteConnection::establish(":memory:").unwrap()
+    }
+
+    #[test]
+    fn email_address_round_trips_through_text() {
+        let email = EmailAddress::new("foö", "bücher.de", None).unwrap();
+        let actual: EmailAddress = diesel::select(email.clone().into_sql::<Text>())
+            .get_result(&mut connection())
+            .unwrap();
+        assert_eq!(actual, email);
+        let actual: String = diesel::select(email.into_sql::<Text>())
+            .get_result(&mut connection())
+            .unwrap();
+        assert_eq!(actual, "foö@bücher.de");
+    }
+
+    #[test]
+    fn invalid_text_fails_to_load() {
+        let actual = diesel::select(sql::<Text>("'foo@-bar.com'"))
+            .get_result::<EmailAddress>(&mut connection());
+        assert!(actual.is_err());
+        let actual = diesel::select(sql::<Text>("'foo@-bar.com'"))
+            .get_result::<LaxEmailAddress>(&mut connection());
+        assert!(actual.is_err());
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn obsolete_text_loads_as_lax_email_address_only() {
+        let value = sql::<Text>("char(13, 10, 32) || 'test@iana.org'");
+        let actual = diesel::select(value.clone()).get_result::<EmailAddress>(&mut connection());
+        assert!(actual.is_err());
+        let actual: LaxEmailAddress = diesel::select(value).get_result(&mut connection()).unwrap();
+        assert_eq!(actual.to_string(), "\r\n test@iana.org");
+    }
+}
@@ -0,0 +1,214 @@
+use crate::canonical::canonical_domain;
+use crate::email_address::EmailAddress;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+/// A set of domains that answers whether a domain, or one of its parents, is in it; e.g. for
+/// allowlists and blocklists of hundreds of thousands of domains.
+///
+/// The domains are stored in a trie of their labels, from the top-level domain down, so that a
+/// lookup takes a number of steps that is the number of labels of the looked up domain, whatever
+/// the size of the set. The domains are compared ignoring case, and without their trailing dots.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{DomainSet, EmailAddress};
+///
+/// let set: DomainSet = ["spam.example", "Mailinator.com"].iter().collect();
+/// assert!(set.contains("mailinator.com"));
+/// assert!(!set.contains("mail.spam.example"));
+///
+/// let email = EmailAddress::parse("jane@mail.SPAM.example", None).unwrap();
+/// assert!(set.contains_domain_of(&email));
+///
+/// let email = EmailAddress::parse("jane@notspam.example", None).unwrap();
+/// assert!(!set.contains_domain_of(&email));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainSet {
+    root: Node,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    is_member: bool,
+    children: BTreeMap<String, Node>,
+}
+
+/// Returns the labels of a lower case domain, from the top-level domain down; a domain literal is
+/// a single label.
+fn reversed_labels(domain: &str) -> Vec<&str> {
+    match domain.starts_with('[') {
+        true => alloc::vec![domain],
+        false => domain.rsplit('.').collect(),
+    }
+}
+
+/// Returns the lower case form of a domain, without its surrounding white space and trailing dots.
+fn lower_case_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_lowercase()
+}
+
+impl DomainSet {
+    /// Instantiates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a set from a list of domains, one per line, like a blocklist file; blank lines, and
+    /// lines starting with `#`, are skipped.
+    ///
+    /// Only available with the `std` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::DomainSet;
+    ///
+    /// let list = "# Blocked domains\nspam.example\n\nmailinator.com\n";
+    /// let set = DomainSet::from_reader(list.as_bytes()).unwrap();
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::BufRead>(reader: R) -> std::io::Result<Self> {
+        let mut set = DomainSet::new();
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim_start().starts_with('#') {
+                set.insert(&line);
+            }
+        }
+        Ok(set)
+    }
+
+    /// Inserts a domain into the set; returns whether it was not in it yet. An empty domain is
+    /// never inserted.
+    pub fn insert(&mut self, domain: &str) -> bool {
+        let domain = lower_case_domain(domain);
+        if domain.is_empty() {
+            return false;
+        }
+        let mut node = &mut self.root;
+        for label in reversed_labels(&domain) {
+            node = node.children.entry(String::from(label)).or_default();
+        }
+        let is_new = !node.is_member;
+        node.is_member = true;
+        self.len += usize::from(is_new);
+        is_new
+    }
+
+    /// Returns whether a domain is in the set, as it is; see `contains_domain_of` to also look up
+    /// its parents.
+    pub fn contains(&self, domain: &str) -> bool {
+        let domain = lower_case_domain(domain);
+        let mut node = &self.root;
+        for label in reversed_labels(&domain) {
+            match node.children.get(label) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        node.is_member
+    }
+
+    /// Returns the domain of the set that is a domain, or one of its parents, if any; the
+    /// shortest one if there are several.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::DomainSet;
+    ///
+    /// let set: DomainSet = ["example.com", "mail.example.com"].iter().collect();
+    /// assert_eq!(set.find_suffix("EU.Mail.example.com").as_deref(), Some("example.com"));
+    /// assert_eq!(set.find_suffix("example.org"), None);
+    /// ```
+    pub fn find_suffix(&self, domain: &str) -> Option<String> {
+        let domain = lower_case_domain(domain);
+        let labels = reversed_labels(&domain);
+        let mut node = &self.root;
+        for (depth, label) in labels.iter().enumerate() {
+            node = node.children.get(*label)?;
+            if node.is_member {
+                let mut suffix: Vec<&str> = labels[..=depth].to_vec();
+                suffix.reverse();
+                return Some(suffix.join("."));
+            }
+        }
+        None
+    }
+
+    /// Returns whether the domain of an address, or one of its parents, is in the set.
+    pub fn contains_domain_of(&self, email: &EmailAddress) -> bool {
+        self.find_suffix(&canonical_domain(email.get_domain()))
+            .is_some()
+    }
+
+    /// Returns the number of domains in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for DomainSet {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, domains: I) {
+        for domain in domains {
+            self.insert(domain.as_ref());
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for DomainSet {
+    fn from_iter<I: IntoIterator<Item = S>>(domains: I) -> Self {
+        let mut set = DomainSet::new();
+        set.extend(domains);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_labels_only() {
+        let set: DomainSet = ["example.com", "co.uk", "[127.0.0.1]"].iter().collect();
+        assert!(set.contains("Example.COM."));
+        assert!(!set.contains("com"));
+        assert_eq!(
+            set.find_suffix("a.b.example.com").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(set.find_suffix("bar.co.uk").as_deref(), Some("co.uk"));
+        assert_eq!(set.find_suffix("notexample.com"), None);
+        assert_eq!(set.find_suffix("example.com.evil"), None);
+        assert_eq!(set.find_suffix("com"), None);
+        let email = EmailAddress::parse("foo@[ 127.0.0.1 ]", None).unwrap();
+        assert!(set.contains_domain_of(&email));
+        #[cfg(not(feature = "strict-only"))]
+        {
+            let options = crate::email_address::ParsingOptions::new(true);
+            let email = EmailAddress::parse("foo@(comment)example . com", Some(options)).unwrap();
+            assert!(set.contains_domain_of(&email));
+        }
+    }
+
+    #[test]
+    fn counts_distinct_domains() {
+        let mut set = DomainSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert("example.com"));
+        assert!(set.insert("mail.example.com"));
+        assert!(!set.insert("EXAMPLE.com."));
+        assert!(!set.insert(" "));
+        assert_eq!(set.len(), 2);
+        assert!(!set.contains(""));
+    }
+}
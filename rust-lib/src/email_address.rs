@@ -1,27 +1,370 @@
-extern crate console_error_panic_hook;
 extern crate pest;
 extern crate pest_derive;
+use crate::canonical::{canonical_domain, canonical_local_part, unquoted_local_part};
+use crate::canonical_email::CanonicalEmail;
+use crate::diagnostic::Diagnostic;
+use crate::error::{diagnose, find_separator, ErrorKind, Hint, ParseError};
+#[cfg(any(feature = "tracing", feature = "log"))]
+use crate::events;
+use crate::labels::{has_underscore, mask_hyphens};
+use crate::literal::is_dot_atom;
+use crate::mask::MaskPolicy;
+use crate::normalize::to_ascii;
+use crate::prefilter::{
+    is_too_large, prefilter, Rejection, MAX_COMMENT_DEPTH, MAX_INPUT_LENGTH, MAX_WHITESPACE_RUNS,
+};
+#[cfg(feature = "tracing")]
+use crate::redact::Redacted;
+use crate::repair::{repair, unwrap, Repaired};
+use crate::restrictions::{check_domain, check_local_part};
+use crate::routing::legacy_route;
+use crate::sanitize::check_lengths;
+use crate::validate::{validate_domain, validate_local_part};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::Hash;
+use core::ops::Range;
+use core::str::FromStr;
 use pest::{iterators::Pairs, Parser};
-use std::fmt;
-use std::hash::Hash;
 use wasm_bindgen::prelude::*;
 
 /// Options for parsing.
 ///
-/// The is only one available option so far `is_lax` which can be set to
-/// `true` or `false` to  enable/disable obsolete parts parsing.
-/// The default is `false`.
+/// `is_lax` can be set to `true` or `false` to enable/disable obsolete parts parsing.
+/// The default is `false`. Lax parsing also accepts a bare angle-addr, like `<foo@bar.com>`, as
+/// the address in the angle brackets.
+/// With the `strict-only` feature, that does not compile the obsolete syntax, lax parsing only
+/// has the other relaxations.
+///
+/// The strictness of the domain labels can be tuned too; by default, a label can neither start
+/// nor end with a hyphen, as per RFC 1035, while consecutive hyphens and all-numeric labels,
+/// including the top-level domain, are allowed. Underscores are allowed by strict parsing only,
+/// unless set otherwise. The labels of domain literals are not checked.
+///
+/// Some of the forms that RFC 5322 allows, but that are hardly ever wanted in the addresses of an
+/// application, can be rejected too; e.g. comments, domain literals, or quoted local parts.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, ParsingOptions};
+///
+/// let options = ParsingOptions::default().allow_leading_hyphen(true);
+/// assert!(EmailAddress::parse("foo@-bar.com", Some(options)).is_some());
+///
+/// let options = ParsingOptions::default().allow_numeric_tld(false);
+/// assert!(EmailAddress::parse("foo@bar.123", Some(options)).is_none());
+///
+/// let options = ParsingOptions::default().allow_comments(false);
+/// assert!(EmailAddress::parse("\"foo\"(comment)@bar.com", Some(options)).is_none());
+///
+/// let options = ParsingOptions::default().allow_domain_literal(false);
+/// assert!(EmailAddress::parse("foo@[127.0.0.1]", Some(options)).is_none());
+///
+/// let options = ParsingOptions::default().allow_quoted_local_part(false);
+/// assert!(EmailAddress::parse("\"foo bar\"@baz.com", Some(options)).is_none());
+///
+/// let options = ParsingOptions::default().allow_utf8(false);
+/// assert!(EmailAddress::parse("jürgen@bücher.example", Some(options)).is_none());
+///
+/// let options = ParsingOptions::default().trim(true);
+/// assert_eq!(EmailAddress::parse(" foo@bar.com\r\n", Some(options)).unwrap().to_string(), "foo@bar.com");
+///
+/// let options = ParsingOptions::default().strip_wrappers(true);
+/// assert_eq!(EmailAddress::parse("<mailto:foo@bar.com>", Some(options)).unwrap().to_string(), "foo@bar.com");
+///
+/// let options = ParsingOptions::default().map_to_ascii(true);
+/// assert_eq!(EmailAddress::parse("foo＠bar．com", Some(options)).unwrap().to_string(), "foo@bar.com");
+/// ```
 #[wasm_bindgen]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct ParsingOptions {
     pub is_lax: bool,
+    pub(crate) allow_leading_hyphen: bool,
+    pub(crate) allow_trailing_hyphen: bool,
+    pub(crate) allow_double_hyphen: bool,
+    pub(crate) allow_numeric_tld: bool,
+    pub(crate) allow_numeric_labels: bool,
+    pub(crate) allow_underscore: Option<bool>,
+    pub(crate) allow_comments: bool,
+    pub(crate) allow_domain_literal: bool,
+    pub(crate) allow_quoted_local_part: bool,
+    pub(crate) allow_utf8: bool,
+    pub(crate) trim: bool,
+    pub(crate) strip_wrappers: bool,
+    pub(crate) map_to_ascii: bool,
+    pub(crate) rfc3696: bool,
+    pub(crate) max_input_length: usize,
+    pub(crate) max_comment_depth: usize,
+    pub(crate) max_whitespace_runs: usize,
+    /// Set by `EmailAddress::parse_with_repair` only.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) fix_mistakes: bool,
 }
 
 #[wasm_bindgen]
 impl ParsingOptions {
     #[wasm_bindgen(constructor)]
     pub fn new(is_lax: bool) -> ParsingOptions {
-        ParsingOptions { is_lax }
+        ParsingOptions {
+            is_lax,
+            allow_leading_hyphen: false,
+            allow_trailing_hyphen: false,
+            allow_double_hyphen: true,
+            allow_numeric_tld: true,
+            allow_numeric_labels: true,
+            allow_underscore: None,
+            allow_comments: true,
+            allow_domain_literal: true,
+            allow_quoted_local_part: true,
+            allow_utf8: true,
+            trim: false,
+            strip_wrappers: false,
+            map_to_ascii: false,
+            rfc3696: false,
+            max_input_length: MAX_INPUT_LENGTH,
+            max_comment_depth: MAX_COMMENT_DEPTH,
+            max_whitespace_runs: MAX_WHITESPACE_RUNS,
+            fix_mistakes: false,
+        }
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = allowUtf8)]
+    pub fn _allow_utf8(&self) -> bool {
+        self.allow_utf8
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(setter = allowUtf8)]
+    pub fn _set_allow_utf8(&mut self, allow_utf8: bool) {
+        self.allow_utf8 = allow_utf8;
+    }
+}
+
+impl ParsingOptions {
+    /// Sets whether a domain label can start with a hyphen; e.g. `-bar.com`. Defaults to `false`.
+    pub fn allow_leading_hyphen(self, allow_leading_hyphen: bool) -> Self {
+        ParsingOptions {
+            allow_leading_hyphen,
+            ..self
+        }
+    }
+
+    /// Sets whether a domain label can end with a hyphen; e.g. `bar-.com`. Defaults to `false`.
+    pub fn allow_trailing_hyphen(self, allow_trailing_hyphen: bool) -> Self {
+        ParsingOptions {
+            allow_trailing_hyphen,
+            ..self
+        }
+    }
+
+    /// Sets whether a domain label can contain consecutive hyphens; e.g. `b--ar.com`. The A-labels
+    /// of internationalized domains, that start with `xn--`, are always allowed. Defaults to
+    /// `true`.
+    pub fn allow_double_hyphen(self, allow_double_hyphen: bool) -> Self {
+        ParsingOptions {
+            allow_double_hyphen,
+            ..self
+        }
+    }
+
+    /// Sets whether the top-level domain can be all-numeric; e.g. `bar.123`. Defaults to `true`.
+    pub fn allow_numeric_tld(self, allow_numeric_tld: bool) -> Self {
+        ParsingOptions {
+            allow_numeric_tld,
+            ..self
+        }
+    }
+
+    /// Sets whether any domain label can be all-numeric; e.g. `123.com`. Defaults to `true`.
+    pub fn allow_numeric_labels(self, allow_numeric_labels: bool) -> Self {
+        ParsingOptions {
+            allow_numeric_labels,
+            ..self
+        }
+    }
+
+    /// Sets whether a domain label can contain an underscore; e.g. `my_host.internal.corp`, as
+    /// RFC 5322 allows, while RFC 1035 does not. Such domains are common on intranets; an accepted
+    /// one is reported by `EmailAddress::parse_with_diagnostics`. Defaults to `true` for strict
+    /// parsing, that has always accepted them, and to `false` for lax parsing.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ParsingOptions};
+    ///
+    /// assert!(EmailAddress::is_valid("user@my_host.corp", None));
+    /// assert!(!EmailAddress::is_valid("user@my_host.corp", Some(ParsingOptions::new(true))));
+    ///
+    /// let options = ParsingOptions::new(true).allow_underscore(true);
+    /// assert!(EmailAddress::is_valid("user@my_host.corp", Some(options)));
+    /// ```
+    pub fn allow_underscore(self, allow_underscore: bool) -> Self {
+        ParsingOptions {
+            allow_underscore: Some(allow_underscore),
+            ..self
+        }
+    }
+
+    /// Sets whether the parts can have comments; e.g. `foo(comment)@bar.com`, as RFC 5322 allows.
+    /// Defaults to `true`.
+    pub fn allow_comments(self, allow_comments: bool) -> Self {
+        ParsingOptions {
+            allow_comments,
+            ..self
+        }
+    }
+
+    /// Sets whether the domain can be a domain literal; e.g. `foo@[127.0.0.1]`. Defaults to `true`.
+    pub fn allow_domain_literal(self, allow_domain_literal: bool) -> Self {
+        ParsingOptions {
+            allow_domain_literal,
+            ..self
+        }
+    }
+
+    /// Sets whether the local part can be, or have, a quoted string; e.g. `"foo bar"@baz.com`.
+    /// Without them, only dot-atom local parts are allowed, and the others fail with
+    /// `ErrorKind::QuotedLocalPart`. Defaults to `true`.
+    pub fn allow_quoted_local_part(self, allow_quoted_local_part: bool) -> Self {
+        ParsingOptions {
+            allow_quoted_local_part,
+            ..self
+        }
+    }
+
+    /// Sets whether internationalized addresses (EAI, as per RFC 6532) are allowed; i.e. non-ASCII
+    /// characters in the local part and in the domain. If not, e.g. for a system without SMTPUTF8
+    /// support, such an address is rejected with the `Hint::NonAscii` hint. Defaults to `true`.
+    ///
+    /// Note that an A-label, like `xn--bcher-kva.example`, is ASCII.
+    pub fn allow_utf8(self, allow_utf8: bool) -> Self {
+        ParsingOptions { allow_utf8, ..self }
+    }
+
+    /// Sets whether the white space around the address, including line breaks, is trimmed before
+    /// parsing, rather than kept as folding white space; e.g. for `" foo@bar.com\n"` from a CSV
+    /// cell. The trimming is reported by `EmailAddress::parse_with_diagnostics`, and the spans of
+    /// the errors still refer to the untrimmed input. Defaults to `false`.
+    pub fn trim(self, trim: bool) -> Self {
+        ParsingOptions { trim, ..self }
+    }
+
+    /// Sets whether the wrappers that are common in pasted input are stripped before parsing; i.e.
+    /// a leading `mailto:`, angle brackets, and double quotes around the whole address, as in
+    /// `<mailto:foo@bar.com>`. Each stripped wrapper is reported by
+    /// `EmailAddress::parse_with_diagnostics`, and the spans of the errors still refer to the
+    /// unstripped input. Defaults to `false`.
+    pub fn strip_wrappers(self, strip_wrappers: bool) -> Self {
+        ParsingOptions {
+            strip_wrappers,
+            ..self
+        }
+    }
+
+    /// Sets whether the full-width forms and the smart punctuation that mobile input methods
+    /// produce are mapped to their ASCII equivalents before parsing; e.g. `ｆｏｏ＠ｂａｒ。ｃｏｍ`
+    /// to `foo@bar.com`, or curly quotes to straight ones. The parsed address has the mapped
+    /// characters; each kind of mapping is reported by `EmailAddress::parse_with_diagnostics`,
+    /// and the spans of the errors still refer to the unmapped input. Defaults to `false`.
+    ///
+    /// Note that these characters are valid in internationalized addresses as they are; the
+    /// mapping is meant for input that is known to be typed by a user.
+    pub fn map_to_ascii(self, map_to_ascii: bool) -> Self {
+        ParsingOptions {
+            map_to_ascii,
+            ..self
+        }
+    }
+
+    /// Sets whether the addresses follow the pragmatic rules of RFC 3696 (with its errata), that
+    /// many systems take for the addresses of the real world; stricter than the grammar of RFC
+    /// 5322, but less than the parsing options of RFC 5321 would be. Defaults to `false`.
+    ///
+    /// On top of the other options, which it overrides, that is:
+    /// - no comments, and no white space outside a quoted local part,
+    /// - a domain that is a host name; i.e. of labels of letters, digits, and hyphens, or of
+    ///   non-ASCII characters for internationalized domains, with a top-level domain that is not
+    ///   all-numeric, rather than a domain literal,
+    /// - the length limits of RFC 5321, as per `EmailAddress::validate_lengths`, of which the
+    ///   errata of RFC 3696 caps the address at 254 octets.
+    ///
+    /// Quoted local parts are allowed, as RFC 3696 does, unless `allow_quoted_local_part` is
+    /// `false`. The obsolete syntax needs `is_lax`, that RFC 3696 does not allow; hence it is best
+    /// left `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, Hint, ParsingOptions};
+    ///
+    /// let options = ParsingOptions::default().rfc3696(true);
+    /// assert!(EmailAddress::parse("\"john doe\"@example.com", Some(options.clone())).is_some());
+    ///
+    /// let error = EmailAddress::try_parse("john@ex!ample.com", Some(options.clone())).unwrap_err();
+    /// assert_eq!(error.hint(), Some(Hint::InvalidLabelCharacter));
+    ///
+    /// let error = EmailAddress::try_parse("john @example.com", Some(options)).unwrap_err();
+    /// assert_eq!(error.hint(), Some(Hint::Whitespace));
+    /// ```
+    pub fn rfc3696(self, rfc3696: bool) -> Self {
+        ParsingOptions { rfc3696, ..self }
+    }
+
+    /// Sets the longest input that is parsed, in octets, before any trimming or repair; a longer
+    /// one fails with `ErrorKind::InputTooLarge` without being parsed. Defaults to 998, the limit of
+    /// a line of a message header.
+    ///
+    /// Along with `max_comment_depth` and `max_whitespace_runs`, this bounds the worst-case cost of
+    /// parsing untrusted input; e.g. that of a public endpoint.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ErrorKind, ParsingOptions};
+    ///
+    /// let options = ParsingOptions::default().max_input_length(16);
+    /// let error = EmailAddress::try_parse("jane.doe@example.com", Some(options)).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InputTooLarge);
+    /// ```
+    pub fn max_input_length(self, max_input_length: usize) -> Self {
+        ParsingOptions {
+            max_input_length,
+            ..self
+        }
+    }
+
+    /// Sets the deepest nesting of comments that is parsed; e.g. 2 for `foo((comment))@bar.com`.
+    /// An input with deeper comments fails with `ErrorKind::TooComplex` without being parsed.
+    /// Defaults to 16.
+    pub fn max_comment_depth(self, max_comment_depth: usize) -> Self {
+        ParsingOptions {
+            max_comment_depth,
+            ..self
+        }
+    }
+
+    /// Sets the most runs of white space, i.e. of folding white space, that are parsed, after
+    /// trimming if `trim` is set; e.g. 2 for `foo @ bar.com`. An input with more runs fails with
+    /// `ErrorKind::TooComplex` without being parsed, as each one makes lax parsing backtrack.
+    /// Defaults to 64.
+    pub fn max_whitespace_runs(self, max_whitespace_runs: usize) -> Self {
+        ParsingOptions {
+            max_whitespace_runs,
+            ..self
+        }
+    }
+
+    /// Returns whether a domain label can contain an underscore, as set, or as defaults for the
+    /// parsing mode.
+    pub(crate) fn allows_underscore(&self) -> bool {
+        self.allow_underscore.unwrap_or(!self.is_lax)
     }
 }
 
@@ -33,7 +376,57 @@ impl Default for ParsingOptions {
 
 #[derive(Parser)]
 #[grammar = "rfc5322.pest"]
-struct RFC5322;
+#[cfg_attr(not(feature = "strict-only"), grammar = "rfc5322_obs.pest")]
+pub(crate) struct RFC5322;
+
+/// The complete inputs of the obsolete syntax of lax parsing; e.g. to hint that an input is only
+/// valid in lax mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Obsolete {
+    Address,
+    LocalPart,
+    Domain,
+}
+
+impl Obsolete {
+    /// Returns whether an input is of the obsolete syntax; never with the `strict-only` feature,
+    /// that does not compile its rules.
+    pub(crate) fn matches(self, input: &str) -> bool {
+        #[cfg(not(feature = "strict-only"))]
+        {
+            let rule = match self {
+                Obsolete::Address => Rule::address_single_obs,
+                Obsolete::LocalPart => Rule::obs_local_part_complete,
+                Obsolete::Domain => Rule::obs_domain_complete,
+            };
+            RFC5322::parse(rule, input).is_ok()
+        }
+        #[cfg(feature = "strict-only")]
+        {
+            let _ = (self, input);
+            false
+        }
+    }
+}
+
+/// Capacity of the local part, in octets, with the `heapless` feature; the limit of RFC 5321.
+pub const LOCAL_PART_CAPACITY: usize = 64;
+/// Capacity of the domain, in octets, with the `heapless` feature; the limit of RFC 5321.
+pub const DOMAIN_CAPACITY: usize = 255;
+
+#[cfg(not(feature = "heapless"))]
+type LocalPart = String;
+#[cfg(not(feature = "heapless"))]
+type Domain = String;
+#[cfg(feature = "heapless")]
+type LocalPart = heapless::String<LOCAL_PART_CAPACITY>;
+/// Capacity of the buffer of the domain, with the `heapless` feature. The canonical form of a
+/// domain of `DOMAIN_CAPACITY` octets may be longer, since lower casing lengthens a character by
+/// half at most; e.g. `Ⱥ`, of 2 octets, to `ⱥ`, of 3.
+#[cfg(feature = "heapless")]
+const DOMAIN_BUFFER_CAPACITY: usize = DOMAIN_CAPACITY * 3 / 2;
+#[cfg(feature = "heapless")]
+type Domain = heapless::String<DOMAIN_BUFFER_CAPACITY>;
 
 /// Email address struct.
 ///
@@ -50,10 +443,15 @@ struct RFC5322;
 /// assert_eq!(format!("{}", email), "foo@bar.com");
 /// ```
 #[wasm_bindgen]
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
 pub struct EmailAddress {
-    local_part: String,
-    domain: String,
+    local_part: LocalPart,
+    domain: Domain,
 }
 
 #[wasm_bindgen]
@@ -83,10 +481,11 @@ impl EmailAddress {
     #[doc(hidden)]
     #[wasm_bindgen(constructor)]
     pub fn _new(local_part: &str, domain: &str, options: Option<ParsingOptions>) -> EmailAddress {
+        #[cfg(feature = "std")]
         console_error_panic_hook::set_once();
         match EmailAddress::new(local_part, domain, options) {
             Ok(instance) => instance,
-            Err(message) => panic!(message),
+            Err(message) => panic!("{}", message),
         }
     }
 
@@ -106,9 +505,11 @@ impl EmailAddress {
     /// assert_eq!(email.get_local_part(), "foo");
     /// assert_eq!(email.get_domain(), "bar.com");
     ///
+    /// # #[cfg(not(feature = "strict-only"))] {
     /// // non-strict parsing
     /// let email = EmailAddress::parse("\u{0d}\u{0a} \u{0d}\u{0a} test@iana.org", Some(ParsingOptions::new(true)));
     /// assert!(email.is_some());
+    /// # }
     ///
     /// // parsing invalid address
     /// let email = EmailAddress::parse("test@-iana.org", Some(ParsingOptions::new(true)));
@@ -121,23 +522,10 @@ impl EmailAddress {
     /// assert!(email.is_none());
     /// ```
     pub fn parse(input: &str, options: Option<ParsingOptions>) -> Option<EmailAddress> {
-        let instantiate = |mut parsed: pest::iterators::Pairs<Rule>| {
-            let mut parsed = parsed
-                .next()
-                .unwrap()
-                .into_inner()
-                .next()
-                .unwrap()
-                .into_inner();
-            Some(EmailAddress {
-                local_part: String::from(parsed.next().unwrap().as_str()),
-                domain: String::from(parsed.next().unwrap().as_str()),
-            })
-        };
-        match EmailAddress::parse_core(input, options) {
-            Some(parsed) => instantiate(parsed),
-            None => None,
-        }
+        let options = options.unwrap_or_default();
+        let located = EmailAddress::parse_core(input, &options)?;
+        located.check(input, &options).ok()?;
+        EmailAddress::store(located.local_part(input), located.domain(input)).ok()
     }
     /// Validates if the given `input` string is an email address or not.
     ///
@@ -150,8 +538,10 @@ impl EmailAddress {
     /// // strict validation
     /// assert!(EmailAddress::is_valid("foo@bar.com", None));
     ///
+    /// # #[cfg(not(feature = "strict-only"))] {
     /// // non-strict validation
     /// assert!(EmailAddress::is_valid("\u{0d}\u{0a} \u{0d}\u{0a} test@iana.org", Some(ParsingOptions::new(true))));
+    /// # }
     ///
     /// // invalid address
     /// assert!(!EmailAddress::is_valid("test@-iana.org", Some(ParsingOptions::new(true))));
@@ -161,7 +551,9 @@ impl EmailAddress {
     /// ```
     #[wasm_bindgen(js_name = "isValid")]
     pub fn is_valid(input: &str, options: Option<ParsingOptions>) -> bool {
-        EmailAddress::parse_core(input, options).is_some()
+        let options = options.unwrap_or_default();
+        EmailAddress::parse_core(input, &options)
+            .is_some_and(|located| located.check(input, &options).is_ok())
     }
 
     /// Returns the local part of the email address.
@@ -183,7 +575,7 @@ impl EmailAddress {
     #[allow(non_snake_case)]
     #[wasm_bindgen(getter)]
     pub fn localPart(&self) -> String {
-        self.local_part.clone()
+        String::from(self.local_part.as_str())
     }
 
     /// Returns the domain of the email address.
@@ -204,7 +596,7 @@ impl EmailAddress {
     #[doc(hidden)]
     #[wasm_bindgen(getter)]
     pub fn domain(&self) -> String {
-        self.domain.clone()
+        String::from(self.domain.as_str())
     }
 
     /// Returns the formatted EmailAddress.
@@ -216,22 +608,216 @@ impl EmailAddress {
         format!("{}@{}", self.local_part, self.domain)
     }
 
-    fn parse_core<'i>(input: &'i str, options: Option<ParsingOptions>) -> Option<Pairs<'i, Rule>> {
-        let options = options.unwrap_or_default();
-        let is_strict = !options.is_lax;
-        match RFC5322::parse(Rule::address_single, input) {
-            Ok(parsed) => Some(parsed),
-            Err(_) => {
-                if is_strict {
-                    None
-                } else {
-                    match RFC5322::parse(Rule::address_single_obs, input) {
-                        Ok(parsed) => Some(parsed),
-                        Err(_) => None,
-                    }
+    /// This is a WASM wrapper over `canonical`.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "canonical")]
+    pub fn _canonical(&self) -> EmailAddress {
+        self.canonical()
+    }
+
+    /// Returns the ranges of the parts in the pairs of a successful parse.
+    fn locate(parsed: Pairs<Rule>) -> Option<Located> {
+        let mut local_part = None;
+        let mut domain = None;
+        #[cfg_attr(feature = "strict-only", allow(unused_mut))]
+        let mut route = None;
+        for pair in parsed.flatten() {
+            let span = pair.as_span();
+            match pair.as_rule() {
+                Rule::local_part if local_part.is_none() => {
+                    local_part = Some(span.start()..span.end())
+                }
+                Rule::domain if domain.is_none() => domain = Some(span.start()..span.end()),
+                #[cfg(not(feature = "strict-only"))]
+                Rule::local_part_obs if local_part.is_none() => {
+                    local_part = Some(span.start()..span.end())
                 }
+                #[cfg(not(feature = "strict-only"))]
+                Rule::domain_obs if domain.is_none() => domain = Some(span.start()..span.end()),
+                #[cfg(not(feature = "strict-only"))]
+                Rule::obs_route => route = Some(span.start()..span.end()),
+                _ => (),
             }
         }
+        Some(Located {
+            repaired: None,
+            local_part: local_part?,
+            domain: domain?,
+            route,
+            diagnostics: Vec::new(),
+        })
+    }
+
+    /// Stores the (valid) parts; with the `heapless` feature, this fails if a part exceeds its
+    /// capacity, with the kind of the error.
+    pub(crate) fn store(local_part: &str, domain: &str) -> Result<EmailAddress, ErrorKind> {
+        if cfg!(feature = "heapless") && domain.len() > DOMAIN_CAPACITY {
+            return Err(ErrorKind::DomainTooLong);
+        }
+        EmailAddress::store_in_buffers(local_part, domain)
+    }
+
+    /// Stores the parts, failing if they do not fit in their buffers; the domain buffer is larger
+    /// than `DOMAIN_CAPACITY` with the `heapless` feature, for the canonical forms.
+    // The conversions are fallible with the `heapless` feature only.
+    #[allow(clippy::unnecessary_fallible_conversions)]
+    pub(crate) fn store_in_buffers(
+        local_part: &str,
+        domain: &str,
+    ) -> Result<EmailAddress, ErrorKind> {
+        Ok(EmailAddress {
+            local_part: LocalPart::try_from(local_part).map_err(|_| ErrorKind::LocalPartTooLong)?,
+            domain: Domain::try_from(domain).map_err(|_| ErrorKind::DomainTooLong)?,
+        })
+    }
+
+    /// Returns the ranges of the parts of the input, if its syntax is valid as per the options;
+    /// the other restrictions of the options are left to `Located::check`.
+    fn parse_core(input: &str, options: &ParsingOptions) -> Option<Located> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::trace_span!("parse", input = %Redacted(input), lax = options.is_lax).entered();
+        if is_too_large(input, options) {
+            #[cfg(any(feature = "tracing", feature = "log"))]
+            events::invalid_syntax(input);
+            return None;
+        }
+        let mut diagnostics = Vec::new();
+        let repaired = repair(input, options, &mut diagnostics);
+        let text = repaired.as_ref().map_or(input, |repaired| &repaired.text);
+        let (unwrapped, offset) = unwrap(text, options, &mut diagnostics);
+        #[cfg(any(feature = "tracing", feature = "log"))]
+        for diagnostic in &diagnostics {
+            events::repaired(input, *diagnostic);
+        }
+        // The obviously invalid inputs are rejected before the comparatively expensive grammar.
+        if prefilter(unwrapped, options).is_err() {
+            #[cfg(any(feature = "tracing", feature = "log"))]
+            events::invalid_syntax(input);
+            return None;
+        }
+        let masked =
+            find_separator(unwrapped).and_then(|at| mask_hyphens(unwrapped, at + 1, options));
+        let unwrapped = masked.as_deref().unwrap_or(unwrapped);
+        let parsed = RFC5322::parse(Rule::address_single, unwrapped);
+        #[cfg(not(feature = "strict-only"))]
+        let parsed = match parsed {
+            Err(_) if options.is_lax => RFC5322::parse(Rule::address_single_obs, unwrapped),
+            parsed => parsed,
+        };
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                #[cfg(any(feature = "tracing", feature = "log"))]
+                events::invalid_syntax(input);
+                return None;
+            }
+        };
+        let located = EmailAddress::locate(parsed)?;
+        let shift = |range: Range<usize>| range.start + offset..range.end + offset;
+        Some(Located {
+            repaired,
+            local_part: shift(located.local_part),
+            domain: shift(located.domain),
+            route: located.route.map(shift),
+            diagnostics,
+        })
+    }
+
+    /// Explains why the given input, which failed to parse as per the options, is not an email
+    /// address; the span of the error refers to the input before any repair.
+    fn diagnose_core(input: &str, options: &ParsingOptions) -> ParseError {
+        if is_too_large(input, options) {
+            #[cfg(any(feature = "tracing", feature = "log"))]
+            events::invalid(input, ErrorKind::InputTooLarge, None, &(0..input.len()));
+            return ParseError::new(ErrorKind::InputTooLarge, input, 0..input.len(), None);
+        }
+        let repaired = repair(input, options, &mut Vec::new());
+        let text = repaired.as_ref().map_or(input, |repaired| &repaired.text);
+        let (unwrapped, offset) = unwrap(text, options, &mut Vec::new());
+        let error = match prefilter(unwrapped, options) {
+            Err(Rejection::TooComplex) => {
+                ParseError::new(ErrorKind::TooComplex, unwrapped, 0..unwrapped.len(), None)
+            }
+            Err(Rejection::NonAscii(kind, span)) => {
+                ParseError::new(kind, unwrapped, span, Some(Hint::NonAscii))
+            }
+            _ => diagnose(unwrapped, options.is_lax),
+        };
+        let span = error.span().start + offset..error.span().end + offset;
+        let span = repaired.map_or(span.clone(), |repaired| repaired.source(span));
+        #[cfg(any(feature = "tracing", feature = "log"))]
+        events::invalid(input, error.kind(), error.hint(), &span);
+        ParseError::new(error.kind(), input, span, error.hint())
+    }
+}
+
+/// The ranges of the parts of a successfully parsed input, in the parsed text; i.e. in the input,
+/// or in its repaired copy.
+#[derive(Debug, PartialEq)]
+struct Located {
+    /// The repaired copy of the input, if the options changed more than its bounds.
+    repaired: Option<Repaired>,
+    local_part: Range<usize>,
+    domain: Range<usize>,
+    /// The obsolete source route before the local part, that is discarded.
+    route: Option<Range<usize>>,
+    /// The repairs of the input, as per the options.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Located {
+    fn local_part<'a>(&'a self, input: &'a str) -> &'a str {
+        &self.text(input)[self.local_part.clone()]
+    }
+
+    fn domain<'a>(&'a self, input: &'a str) -> &'a str {
+        &self.text(input)[self.domain.clone()]
+    }
+
+    fn text<'a>(&'a self, input: &'a str) -> &'a str {
+        self.repaired
+            .as_ref()
+            .map_or(input, |repaired| &repaired.text)
+    }
+
+    /// Returns the range of the input that a range of the parsed text comes from.
+    fn source(&self, range: Range<usize>) -> Range<usize> {
+        match &self.repaired {
+            Some(repaired) => repaired.source(range),
+            None => range,
+        }
+    }
+
+    /// Checks the parts against the restrictions of the options, beyond the syntax; failing with
+    /// the kind of the error, its span in the input, and a hint, if any.
+    fn check(
+        &self,
+        input: &str,
+        options: &ParsingOptions,
+    ) -> Result<(), (ErrorKind, Range<usize>, Option<Hint>)> {
+        check_local_part(self.local_part(input), options)
+            .map_err(|(kind, hint)| (kind, self.source(self.local_part.clone()), hint))?;
+        check_domain(self.domain(input), options).map_err(|hint| {
+            (
+                ErrorKind::InvalidDomain,
+                self.source(self.domain.clone()),
+                Some(hint),
+            )
+        })?;
+        if options.rfc3696 {
+            let local_part = canonical_local_part(self.local_part(input));
+            let domain = to_ascii(&canonical_domain(self.domain(input)));
+            check_lengths(&local_part, &domain).map_err(|(kind, _)| {
+                let span = match kind {
+                    ErrorKind::LocalPartTooLong => self.source(self.local_part.clone()),
+                    ErrorKind::DomainTooLong => self.source(self.domain.clone()),
+                    _ => 0..input.len(),
+                };
+                (kind, span, None)
+            })?;
+        }
+        Ok(())
     }
 }
 
@@ -256,23 +842,148 @@ impl EmailAddress {
         options: Option<ParsingOptions>,
     ) -> Result<EmailAddress, String> {
         let options = options.unwrap_or_default();
-        let is_strict = !options.is_lax;
+        validate_local_part(local_part, &options)
+            .map_err(|_| format!("Invalid local part '{}'.", local_part))?;
+        validate_domain(domain, &options).map_err(|_| format!("Invalid domain '{}'.", domain))?;
 
-        if (is_strict && !RFC5322::parse(Rule::local_part_complete, local_part).is_ok())
-            || (!is_strict && !RFC5322::parse(Rule::local_part_complete, local_part).is_ok())
-        {
-            return Err(format!("Invalid local part '{}'.", local_part));
+        EmailAddress::store(local_part, domain).map_err(|kind| match kind {
+            ErrorKind::LocalPartTooLong => format!("Too long local part '{}'.", local_part),
+            _ => format!("Too long domain '{}'.", domain),
+        })
+    }
+
+    /// Parses a given string as an email address, explaining the failure with a `ParseError`.
+    ///
+    /// This is `str::parse` with a choice of parsing options.
+    /// Not accessible from WASM.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let email = EmailAddress::try_parse("test . test@iana.org", Some(ParsingOptions::new(true))).unwrap();
+    /// assert_eq!(email.get_domain(), "iana.org");
+    ///
+    /// let error = EmailAddress::try_parse("test . test@iana.org", None).unwrap_err();
+    /// assert_eq!(error.hint(), Some(Hint::ObsoleteSyntax));
+    /// # }
+    /// ```
+    pub fn try_parse(
+        input: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<EmailAddress, ParseError> {
+        EmailAddress::parse_with_diagnostics(input, options).map(|(email, _)| email)
+    }
+
+    /// Parses a given string as an email address like `try_parse`, also returning the diagnostics
+    /// on how the input was interpreted; e.g. that an obsolete source route, which lax parsing
+    /// accepts as in `@relay.example.org:foo@bar.com`, was discarded.
+    ///
+    /// Not accessible from WASM.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// let (email, diagnostics) = EmailAddress::parse_with_diagnostics("foo@bar.com", None).unwrap();
+    /// assert_eq!(email.to_string(), "foo@bar.com");
+    /// assert!(diagnostics.is_empty());
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let options = ParsingOptions::new(true);
+    /// let (email, diagnostics) =
+    ///     EmailAddress::parse_with_diagnostics("@a.org,@b.org:foo@bar.com", Some(options)).unwrap();
+    /// assert_eq!(email.to_string(), "foo@bar.com");
+    /// assert_eq!(diagnostics, vec![Diagnostic::ObsoleteRoute]);
+    /// # }
+    /// ```
+    pub fn parse_with_diagnostics(
+        input: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<(EmailAddress, Vec<Diagnostic>), ParseError> {
+        EmailAddress::parse_with(input, &options.unwrap_or_default())
+    }
+
+    /// Parses a given string as an email address like `parse_with_diagnostics`, with borrowed
+    /// options; e.g. those of an `EmailParser`, that are not cloned for each input.
+    pub(crate) fn parse_with(
+        input: &str,
+        options: &ParsingOptions,
+    ) -> Result<(EmailAddress, Vec<Diagnostic>), ParseError> {
+        let mut located = EmailAddress::parse_core(input, options)
+            .ok_or_else(|| EmailAddress::diagnose_core(input, options))?;
+        if let Err((kind, span, hint)) = located.check(input, options) {
+            #[cfg(any(feature = "tracing", feature = "log"))]
+            events::invalid(input, kind, hint, &span);
+            return Err(ParseError::new(kind, input, span, hint));
         }
-        if (is_strict && !RFC5322::parse(Rule::domain_complete, domain).is_ok())
-            || (!is_strict && !RFC5322::parse(Rule::domain_complete, domain).is_ok())
-        {
-            return Err(format!("Invalid domain '{}'.", domain));
+        let mut diagnostics = core::mem::take(&mut located.diagnostics);
+        if located.route.is_some() {
+            diagnostics.push(Diagnostic::ObsoleteRoute);
+        }
+        if has_underscore(located.domain(input)) {
+            diagnostics.push(Diagnostic::UnderscoreInDomain);
         }
+        let local_part = unquoted_local_part(located.local_part(input));
+        if let Some(route) = legacy_route(&local_part) {
+            diagnostics.push(route.diagnostic);
+        }
+        let email = EmailAddress::store(located.local_part(input), located.domain(input)).map_err(
+            |kind| {
+                let part = match kind {
+                    ErrorKind::LocalPartTooLong => located.local_part.clone(),
+                    _ => located.domain.clone(),
+                };
+                ParseError::new(kind, input, located.source(part), None)
+            },
+        )?;
+        Ok((email, diagnostics))
+    }
 
-        Ok(EmailAddress {
-            local_part: String::from(local_part),
-            domain: String::from(domain),
-        })
+    /// Parses a given string as an email address like `parse_with_diagnostics`, after repairing
+    /// the common mistakes of typed addresses, in this order:
+    /// - a repeated `@`, like in `foo@@bar.com`, is collapsed;
+    /// - the punctuation after the address, like in `foo@bar.com.` or `foo@bar.com,`, is stripped;
+    /// - the missing dot before a common top-level domain (`com`, `net`, `org`, `edu` or `gov`) of
+    ///   a host name without dots, like in `foo@gmailcom`, is inserted;
+    /// - the spaces between the labels of a host name, like in `foo@gmail com`, are replaced with
+    ///   dots.
+    ///
+    /// The applied repairs are among the diagnostics, in order. As some repairs, like the missing
+    /// dot, turn a valid address into another one, this is meant for input that is known to be
+    /// typed by a user. The spans of the errors refer to the input before any repair.
+    ///
+    /// Not accessible from WASM.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// let (email, repairs) = EmailAddress::parse_with_repair("foo@@gmailcom,", None).unwrap();
+    /// assert_eq!(email.to_string(), "foo@gmail.com");
+    /// assert_eq!(
+    ///     repairs,
+    ///     vec![
+    ///         Diagnostic::CollapsedSeparators,
+    ///         Diagnostic::StrippedTrailingPunctuation,
+    ///         Diagnostic::InsertedTopLevelDomainDot,
+    ///     ]
+    /// );
+    ///
+    /// let (email, repairs) = EmailAddress::parse_with_repair("foo@bar.com", None).unwrap();
+    /// assert_eq!(email.to_string(), "foo@bar.com");
+    /// assert!(repairs.is_empty());
+    /// ```
+    pub fn parse_with_repair(
+        input: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<(EmailAddress, Vec<Diagnostic>), ParseError> {
+        let options = ParsingOptions {
+            fix_mistakes: true,
+            ..options.unwrap_or_default()
+        };
+        EmailAddress::parse_with_diagnostics(input, Some(options))
     }
 
     /// Returns the local part of the email address.
@@ -309,15 +1020,367 @@ impl EmailAddress {
     pub fn get_domain(&self) -> &str {
         self.domain.as_str()
     }
+
+    /// Returns the semantic value of the local part; i.e. without comments and folding white
+    /// space, and with the quoted strings unquoted and their quoted pairs resolved.
+    ///
+    /// Unlike `get_local_part`, that returns the local part as it was written, this is what the
+    /// local part means; e.g. to compare local parts, or to store them. Note that it is not always
+    /// a valid local part on its own; see `canonical` for that.
+    ///
+    /// Not accessible from WASM.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ParsingOptions};
+    ///
+    /// let email = EmailAddress::parse("\"john doe\"@example.com", None).unwrap();
+    /// assert_eq!(email.get_local_part(), "\"john doe\"");
+    /// assert_eq!(email.local_part_unquoted(), "john doe");
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let email = EmailAddress::parse("\"john\\\"s\".doe@example.com", Some(ParsingOptions::new(true))).unwrap();
+    /// assert_eq!(email.local_part_unquoted(), "john\"s.doe");
+    /// # }
+    /// ```
+    pub fn local_part_unquoted(&self) -> String {
+        unquoted_local_part(&self.local_part)
+    }
+
+    /// Returns the canonical form of the email address.
+    ///
+    /// Unlike the parsed address, that keeps the input as it is, the canonical form has:
+    /// - no comments and no folding white space,
+    /// - a local part quoted only if it cannot be a dot-atom, with the obsolete syntax resolved,
+    /// - a lower case domain.
+    ///
+    /// The local part keeps its case, as it is case-sensitive as per RFC 5321.
+    /// Canonicalizing a canonical form does not change it.
+    ///
+    /// The canonical form always re-parses with strict parsing options, to itself; the control
+    /// characters that only the obsolete syntax allows in quoted strings and domain literals are
+    /// dropped to that end. The exceptions are the domain labels that the parsing options may
+    /// allow beyond the strict defaults: a label with a hyphen at its edge, e.g. `-bar.com`, only
+    /// re-parses with `allow_leading_hyphen` or `allow_trailing_hyphen`, and a label with an
+    /// underscore, e.g. `my_host.corp`, only re-parses with the options that allow it; i.e. not
+    /// with lax ones, or with `allow_underscore(false)`. Unlike the canonical form, the address
+    /// as it is written by `Display` keeps the white space and the obsolete syntax of its input;
+    /// e.g. `\r\n test@iana.org` parsed with lax options does not re-parse with strict ones.
+    ///
+    /// With the `heapless` feature, the canonical domain may be longer than `DOMAIN_CAPACITY`,
+    /// since lower casing lengthens some characters; e.g. `İ`, of 2 octets, to `i̇`, of 3. Such an
+    /// address is canonicalized all the same, but does not re-parse.
+    ///
+    /// Accessible from WASM.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// let email = EmailAddress::parse("(comment)\"Foo\"@BAR.com ", None).unwrap();
+    /// assert_eq!(email.canonical().to_string(), "Foo@bar.com");
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let email = EmailAddress::parse("foo . bar@iana.org", Some(ParsingOptions::new(true))).unwrap();
+    /// assert_eq!(email.canonical().to_string(), "foo.bar@iana.org");
+    /// # }
+    ///
+    /// let email = EmailAddress::parse("\"foo bar\"@iana.org", None).unwrap();
+    /// assert_eq!(email.canonical().to_string(), "\"foo bar\"@iana.org");
+    /// ```
+    pub fn canonical(&self) -> EmailAddress {
+        // The canonical local part is never longer than the local part, and the canonical domain
+        // is at most half as long again as the domain; hence, they always fit in the buffers.
+        EmailAddress::store_in_buffers(
+            &canonical_local_part(&self.local_part),
+            &canonical_domain(&self.domain),
+        )
+        .expect("the canonical parts fit in the buffers")
+    }
+
+    /// Returns the canonical form of the address masked as per the policy; e.g. `f***@example.com`,
+    /// for display in user interfaces, logs, or support tools. See `MaskPolicy` for the options.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{DomainMask, EmailAddress, MaskPolicy};
+    ///
+    /// let email = EmailAddress::parse("jane.doe@example.com", None).unwrap();
+    /// assert_eq!(email.mask(&MaskPolicy::new()), "j*******@example.com");
+    ///
+    /// let policy = MaskPolicy::new().domain(DomainMask::Labels);
+    /// assert_eq!(email.mask(&policy), "j*******@e******.com");
+    /// ```
+    pub fn mask(&self, policy: &MaskPolicy) -> String {
+        let canonical = self.canonical();
+        policy.apply(&canonical.local_part, &canonical.domain)
+    }
+
+    /// Returns whether the canonical form of the address is the stored one, in time that depends on
+    /// the length of `self` only; e.g. to check a user supplied address against a stored one in
+    /// token or unsubscribe verification, without leaking where they differ, or how long the stored
+    /// one is.
+    ///
+    /// Call it on the user supplied address. The stored address is compared in its canonical form,
+    /// computed once when it is stored, so that it is not parsed again; both are padded with zeros,
+    /// which never occur in an address, to a length of at least 320 octets (that of the longest
+    /// address as per RFC 5321), and compared whole, without branching on their content.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{CanonicalEmail, EmailAddress};
+    ///
+    /// let stored = CanonicalEmail::new(&EmailAddress::parse("foo@bar.com", None).unwrap());
+    /// let supplied = EmailAddress::parse("\"foo\"@BAR.com", None).unwrap();
+    /// assert!(supplied.eq_constant_time(&stored));
+    ///
+    /// let supplied = EmailAddress::parse("fob@bar.com", None).unwrap();
+    /// assert!(!supplied.eq_constant_time(&stored));
+    /// ```
+    pub fn eq_constant_time(&self, stored: &CanonicalEmail) -> bool {
+        let supplied = self.canonical().to_string();
+        let (supplied, stored) = padded_pair(supplied.as_bytes(), stored.as_str().as_bytes());
+        let difference = supplied
+            .iter()
+            .zip(&stored)
+            .fold(0, |difference, (byte, expected)| {
+                difference | core::hint::black_box(byte ^ expected)
+            });
+        core::hint::black_box(difference) == 0
+    }
+
+    /// Returns whether the domain of the address is a domain, ignoring case, as its canonical form
+    /// does; e.g. to match the addresses against the domains of routing rules.
+    ///
+    /// Unlike comparing `canonical().get_domain()`, this does not allocate for an ASCII domain
+    /// without comments and white space, which is the usual one. The other domain is compared as
+    /// it is written; e.g. `bar.com.` is not `bar.com`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@Bar.COM", None).unwrap();
+    /// assert!(email.domain_eq("bar.com"));
+    /// assert!(email.domain_eq("BAR.com"));
+    /// assert!(!email.domain_eq("bar.co"));
+    /// ```
+    pub fn domain_eq(&self, other_domain: &str) -> bool {
+        let is_plain = |domain: &str| {
+            domain.is_ascii()
+                && !domain
+                    .bytes()
+                    .any(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n' | b'('))
+        };
+        if is_plain(&self.domain) && other_domain.is_ascii() {
+            return self.domain.eq_ignore_ascii_case(other_domain);
+        }
+        canonical_domain(&self.domain) == other_domain.to_lowercase()
+    }
+
+    /// Returns whether the domain of the address is a domain, as per `domain_eq`, or else as per
+    /// their ASCII forms (IDNA); e.g. `bücher.example` is `xn--bcher-kva.example`.
+    ///
+    /// Requires the `idna` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@Bücher.example", None).unwrap();
+    /// assert!(email.domain_eq_idna("xn--bcher-kva.example"));
+    /// assert!(email.domain_eq_idna("BÜCHER.example"));
+    /// assert!(!email.domain_eq("xn--bcher-kva.example"));
+    /// ```
+    #[cfg(feature = "idna")]
+    pub fn domain_eq_idna(&self, other_domain: &str) -> bool {
+        self.domain_eq(other_domain)
+            || to_ascii(&canonical_domain(&self.domain))
+                .eq_ignore_ascii_case(&to_ascii(other_domain))
+    }
+
+    /// Writes the address, as `to_string` returns it, without allocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut header = String::from("From: ");
+    /// EmailAddress::parse("foo@bar.com", None).unwrap().write_to(&mut header).unwrap();
+    /// assert_eq!(header, "From: foo@bar.com");
+    /// ```
+    pub fn write_to<W: fmt::Write>(&self, output: &mut W) -> fmt::Result {
+        output.write_str(&self.local_part)?;
+        output.write_char('@')?;
+        output.write_str(&self.domain)
+    }
+
+    /// Writes the canonical form of the address, as `canonical().to_string()` returns it; without
+    /// allocating if the address is in its canonical form already, as most are.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut output = String::new();
+    /// let email = EmailAddress::parse("(comment)\"foo\"@BAR.com", None).unwrap();
+    /// email.write_canonical_to(&mut output).unwrap();
+    /// assert_eq!(output, "foo@bar.com");
+    /// ```
+    pub fn write_canonical_to<W: fmt::Write>(&self, output: &mut W) -> fmt::Result {
+        if self.is_canonical() {
+            self.write_to(output)
+        } else {
+            self.canonical().write_to(output)
+        }
+    }
+
+    /// Writes the address, as `to_string` returns it, without allocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut output = Vec::new();
+    /// EmailAddress::parse("foo@bar.com", None).unwrap().write_to_io(&mut output).unwrap();
+    /// assert_eq!(output, b"foo@bar.com");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to_io<W: std::io::Write>(&self, output: &mut W) -> std::io::Result<()> {
+        output.write_all(self.local_part.as_bytes())?;
+        output.write_all(b"@")?;
+        output.write_all(self.domain.as_bytes())
+    }
+
+    /// Writes the canonical form of the address, as `canonical().to_string()` returns it; without
+    /// allocating if the address is in its canonical form already, as most are.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut output = Vec::new();
+    /// let email = EmailAddress::parse("foo@BAR.com", None).unwrap();
+    /// email.write_canonical_to_io(&mut output).unwrap();
+    /// assert_eq!(output, b"foo@bar.com");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_canonical_to_io<W: std::io::Write>(&self, output: &mut W) -> std::io::Result<()> {
+        if self.is_canonical() {
+            self.write_to_io(output)
+        } else {
+            self.canonical().write_to_io(output)
+        }
+    }
+
+    /// Returns whether the address is in its canonical form for sure; i.e. its local part is a
+    /// dot-atom, and its domain is a lower case ASCII host name.
+    fn is_canonical(&self) -> bool {
+        let local_part = self.local_part.as_bytes();
+        let domain = self.domain.as_bytes();
+        is_dot_atom(local_part, 0, local_part.len())
+            && domain.is_ascii()
+            && !domain.iter().any(u8::is_ascii_uppercase)
+            && is_dot_atom(domain, 0, domain.len())
+    }
+
+    /// Instantiates an `EmailAddress` from parts that are validated beforehand; used by the
+    /// `email!` macro, that validates them at compile time.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if a part exceeds its capacity with the `heapless` feature.
+    #[doc(hidden)]
+    pub fn __new_unchecked(local_part: &str, domain: &str) -> EmailAddress {
+        EmailAddress::store(local_part, domain).expect("the parts are validated by `email!`")
+    }
 }
 
 impl fmt::Display for EmailAddress {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        formatter.write_fmt(format_args!("{}@{}", self.local_part, self.domain))
+        self.write_to(formatter)
+    }
+}
+
+/// Parses an email address strictly, explaining the failure with a `ParseError`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let email: EmailAddress = "foo@bar.com".parse().unwrap();
+/// assert_eq!(email.get_domain(), "bar.com");
+///
+/// let error = "foo.bar.com".parse::<EmailAddress>().unwrap_err();
+/// assert_eq!(error.hint(), Some(Hint::MissingAt));
+/// ```
+impl FromStr for EmailAddress {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        EmailAddress::try_parse(input, None)
+    }
+}
+
+/// Converts the address into its canonical form; see `EmailAddress::canonical`. Unlike
+/// `to_string`, that keeps the address as it was parsed, comments and all.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+///
+/// let email = EmailAddress::parse("(comment)\"foo\"@BAR.com", None).unwrap();
+/// assert_eq!(String::from(email), "foo@bar.com");
+/// ```
+impl From<EmailAddress> for String {
+    fn from(email: EmailAddress) -> Self {
+        let mut canonical = String::new();
+        // Writing into a `String` never fails.
+        let _ = email.write_canonical_to(&mut canonical);
+        canonical
+    }
+}
+
+/// Converts the address into the local part and the domain of its canonical form; see
+/// `EmailAddress::canonical`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+///
+/// let email = EmailAddress::parse("\"foo\"@BAR.com", None).unwrap();
+/// let (local_part, domain): (String, String) = email.into();
+/// assert_eq!((local_part.as_str(), domain.as_str()), ("foo", "bar.com"));
+/// ```
+impl From<EmailAddress> for (String, String) {
+    fn from(email: EmailAddress) -> Self {
+        let canonical = email.canonical();
+        (
+            String::from(canonical.get_local_part()),
+            String::from(canonical.get_domain()),
+        )
     }
 }
 
+/// The length that the addresses are padded to by `EmailAddress::eq_constant_time`: that of the
+/// longest address as per RFC 5321.
+const CONSTANT_TIME_LENGTH: usize = LOCAL_PART_CAPACITY + 1 + DOMAIN_CAPACITY;
+
+/// Pads the supplied and the stored addresses with zeros to the same length, that depends on the
+/// supplied one only; the stored one is truncated if it is longer, which its padding tells apart.
+fn padded_pair(supplied: &[u8], stored: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let length = supplied.len().max(CONSTANT_TIME_LENGTH);
+    let pad = |bytes: &[u8]| {
+        let mut padded = alloc::vec![0; length + 1];
+        let copied = bytes.len().min(length + 1);
+        padded[..copied].copy_from_slice(&bytes[..copied]);
+        padded
+    };
+    (pad(supplied), pad(stored))
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
 mod tests {
     use super::*;
 
@@ -341,6 +1404,460 @@ mod tests {
         assert_ne!(foo_at_bar_dot_com_2, foob_at_ar_dot_com);
     }
 
+    #[test]
+    fn follows_rfc3696() {
+        let options = Some(ParsingOptions::default().rfc3696(true));
+        let hint = |input: &str| {
+            EmailAddress::try_parse(input, options.clone())
+                .map(|_| ())
+                .map_err(|error| (error.kind(), error.hint()))
+        };
+        assert_eq!(hint("jöhn.doe@bücher.example"), Ok(()));
+        assert_eq!(hint("\"john..doe\"@example.com"), Ok(()));
+        assert_eq!(
+            hint("\"john\"(x)@example.com"),
+            Err((ErrorKind::InvalidLocalPart, Some(Hint::Comment)))
+        );
+        assert_eq!(
+            hint("john@ example.com"),
+            Err((ErrorKind::InvalidDomain, Some(Hint::Whitespace)))
+        );
+        assert_eq!(
+            hint("john@[127.0.0.1]"),
+            Err((ErrorKind::InvalidDomain, Some(Hint::DomainLiteral)))
+        );
+        assert_eq!(
+            hint("john@my_host.example"),
+            Err((ErrorKind::InvalidDomain, Some(Hint::Underscore)))
+        );
+        assert_eq!(
+            hint("john@example.123"),
+            Err((ErrorKind::InvalidDomain, Some(Hint::NumericTopLevelDomain)))
+        );
+        assert_eq!(
+            hint(&format!("{}@example.com", "a".repeat(65))),
+            Err((ErrorKind::LocalPartTooLong, None))
+        );
+        let address = format!("{}@{1}.{1}.{1}.com", "a".repeat(64), "a".repeat(63));
+        let error = EmailAddress::try_parse(&address, options.clone()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AddressTooLong);
+        assert!(EmailAddress::parse(&address, None).is_some());
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn converts_into_canonical_strings() {
+        let email = EmailAddress::parse(
+            "foo . bar@(comment)Bar.com",
+            Some(ParsingOptions::new(true)),
+        )
+        .unwrap();
+        assert_eq!(String::from(email.clone()), "foo.bar@bar.com");
+        let (local_part, domain) = <(String, String)>::from(email);
+        assert_eq!(local_part, "foo.bar");
+        assert_eq!(domain, "bar.com");
+        let email = EmailAddress::parse("\"foo bar\"@bar.com", None).unwrap();
+        assert_eq!(String::from(email), "\"foo bar\"@bar.com");
+    }
+
+    #[test]
+    fn eq_constant_time_compares_canonical_forms() {
+        let stored = CanonicalEmail::new(&EmailAddress::new("foo", "bar.com", None).unwrap());
+        for (supplied, expected) in &[
+            ("foo@bar.com", true),
+            ("\"foo\"@BAR.com", true),
+            ("Foo@bar.com", false),
+            ("foo@bar.co", false),
+            ("foo@bar.com.au", false),
+            ("fo@obar.com", false),
+        ] {
+            let supplied = EmailAddress::parse(supplied, None).unwrap();
+            assert_eq!(
+                supplied.eq_constant_time(&stored),
+                *expected,
+                "{}",
+                supplied
+            );
+        }
+        // With the `heapless` feature, such a local part does not even parse.
+        #[cfg(not(feature = "heapless"))]
+        {
+            let long = "a".repeat(400);
+            let stored = CanonicalEmail::new(&EmailAddress::new(&long, "bar.com", None).unwrap());
+            let supplied =
+                EmailAddress::new(&long[..CONSTANT_TIME_LENGTH], "bar.com", None).unwrap();
+            assert!(!supplied.eq_constant_time(&stored));
+        }
+    }
+
+    #[test]
+    fn eq_constant_time_compares_buffers_independent_of_the_stored_address() {
+        let supplied = b"foo@bar.com";
+        let stored: &[&[u8]] = &[b"", b"foo@bar.com", b"bar@foo.com", &[b'a'; 1000]];
+        for stored in stored {
+            let (padded_supplied, padded_stored) = padded_pair(supplied, stored);
+            assert_eq!(padded_supplied.len(), CONSTANT_TIME_LENGTH + 1);
+            assert_eq!(padded_stored.len(), CONSTANT_TIME_LENGTH + 1);
+            assert_eq!(&padded_supplied[..supplied.len()], supplied);
+        }
+        let (padded_supplied, _) = padded_pair(&[b'a'; 500], b"");
+        assert_eq!(padded_supplied.len(), 501);
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn domain_eq_ignores_case_and_comments() {
+        let email = EmailAddress::parse("foo@Bar.com", None).unwrap();
+        assert!(email.domain_eq("bar.COM"));
+        assert!(!email.domain_eq("bar.com."));
+        assert!(!email.domain_eq("bar"));
+        let email =
+            EmailAddress::parse("foo@ (comment) Bar . com", Some(ParsingOptions::new(true)));
+        assert!(email.unwrap().domain_eq("bar.com"));
+        let email = EmailAddress::parse("foo@[127.0.0.1]", None).unwrap();
+        assert!(email.domain_eq("[127.0.0.1]"));
+        let email = EmailAddress::parse("foo@Bücher.example", None).unwrap();
+        assert!(email.domain_eq("bücher.example"));
+        assert!(email.domain_eq("BÜCHER.EXAMPLE"));
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn writes_canonical_form() {
+        let lax = Some(ParsingOptions::new(true));
+        for input in &[
+            "foo@bar.com",
+            "Foo.Bar@BAR.com",
+            "foö@Bücher.de",
+            "\"foo\"@bar.com",
+            "\"foo bar\"@[127.0.0.1]",
+            "foo . bar@ iana .com",
+            "\"-foo\"@bar.com",
+        ] {
+            let email = EmailAddress::parse(input, lax.clone()).unwrap();
+            let mut output = String::new();
+            email.write_canonical_to(&mut output).unwrap();
+            assert_eq!(output, email.canonical().to_string(), "{}", input);
+        }
+        let email = EmailAddress::parse("foo . bar@iana.org", lax).unwrap();
+        let mut output = String::new();
+        email.write_to(&mut output).unwrap();
+        assert_eq!(output, "foo . bar@iana.org");
+    }
+
+    #[test]
+    fn email_address_supports_from_str() {
+        let address: EmailAddress = "foo@bar.com".parse().unwrap();
+        assert_eq!(address, EmailAddress::new("foo", "bar.com", None).unwrap());
+
+        let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::InvalidDomain);
+        assert_eq!(error.input(), "foo@-bar.com");
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn location_in_unexpected_pairs_yields_none() {
+        let parsed = RFC5322::parse(Rule::local_part_complete, "foo").unwrap();
+        assert_eq!(EmailAddress::locate(parsed), None);
+
+        let parsed = RFC5322::parse(Rule::address_single_obs, "foo . bar@baz.com").unwrap();
+        let located = EmailAddress::locate(parsed).unwrap();
+        assert_eq!(located.local_part, 0..9);
+        assert_eq!(located.domain, 10..17);
+        assert_eq!(located.route, None);
+        assert!(located.diagnostics.is_empty());
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn obsolete_routes_are_discarded_in_lax_mode() {
+        let lax = || Some(ParsingOptions::new(true));
+        for input in &[
+            "@relay.org:foo@bar.com",
+            "@a.org,@b.org:foo@bar.com",
+            " , @a.org, ,@[1.2.3.4] :foo@bar.com",
+        ] {
+            let email = EmailAddress::parse(input, lax()).unwrap();
+            assert_eq!(email.get_local_part(), "foo", "{}", input);
+            assert_eq!(email.get_domain(), "bar.com", "{}", input);
+        }
+        let (_, diagnostics) =
+            EmailAddress::parse_with_diagnostics("@relay.org:foo@bar.com", lax()).unwrap();
+        assert_eq!(diagnostics, vec![Diagnostic::ObsoleteRoute]);
+
+        let error = EmailAddress::try_parse("@relay.org:foo@bar.com", None).unwrap_err();
+        assert_eq!(error.hint(), Some(crate::Hint::ObsoleteSyntax));
+        for input in &["relay.org:foo@bar.com", "@:foo@bar.com", "@relay.org:"] {
+            assert!(!EmailAddress::is_valid(input, lax()), "{}", input);
+        }
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn comments_are_rejected_as_per_the_options() {
+        let options = || Some(ParsingOptions::new(true).allow_comments(false));
+        assert!(EmailAddress::is_valid("\"(x)\"@[(y)]", options()));
+        let error = EmailAddress::try_parse("foo(x)@bar.com", options()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+        assert_eq!(error.hint(), Some(Hint::Comment));
+        assert_eq!(error.span(), 0..6);
+        let error = EmailAddress::try_parse("foo@[1.2.3.4] (x)", options()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+        assert_eq!(
+            error.to_string(),
+            "Invalid domain '[1.2.3.4] (x)'. Comments are not allowed."
+        );
+        assert!(EmailAddress::is_valid(
+            "foo(x)@bar.com",
+            Some(ParsingOptions::new(true))
+        ));
+    }
+
+    #[test]
+    fn domain_literals_are_rejected_as_per_the_options() {
+        let options = || Some(ParsingOptions::new(true).allow_domain_literal(false));
+        assert!(EmailAddress::is_valid("\"[x]\"@bar.com", options()));
+        for input in &["foo@[127.0.0.1]", "foo@[IPv6:::1]", "foo@ [127.0.0.1] "] {
+            let error = EmailAddress::try_parse(input, options()).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidDomain, "{}", input);
+            assert_eq!(error.hint(), Some(Hint::DomainLiteral), "{}", input);
+        }
+        assert!(EmailAddress::is_valid("foo@[127.0.0.1]", None));
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn quoted_local_parts_are_rejected_as_per_the_options() {
+        let options = || Some(ParsingOptions::new(true).allow_quoted_local_part(false));
+        assert!(EmailAddress::is_valid("foo.bar(\"x\")@baz.com", options()));
+        for input in &["\"foo bar\"@baz.com", "\"foo\".bar@baz.com", "\"\"@baz.com"] {
+            let error = EmailAddress::try_parse(input, options()).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::QuotedLocalPart, "{}", input);
+            assert_eq!(error.hint(), None);
+        }
+        let error = EmailAddress::try_parse("\"foo bar\"@baz.com", options()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Quoted local part '\"foo bar\"' is not allowed."
+        );
+        assert_eq!(error.kind().code(), "quoted_local_part");
+    }
+
+    #[test]
+    fn white_space_is_trimmed_as_per_the_options() {
+        let options = || Some(ParsingOptions::default().trim(true));
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics(" \tfoo@bar.com\r\n", options()).unwrap();
+        assert_eq!(email.to_string(), "foo@bar.com");
+        assert_eq!(diagnostics, vec![Diagnostic::Trimmed]);
+        let (_, diagnostics) =
+            EmailAddress::parse_with_diagnostics("foo@bar.com", options()).unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(!EmailAddress::is_valid(" foo@bar.com\n", None));
+
+        let error = EmailAddress::try_parse("  foo@-bar.com ", options()).unwrap_err();
+        assert_eq!(error.input(), "  foo@-bar.com ");
+        assert_eq!(error.span(), 6..14);
+        let options = || Some(ParsingOptions::default().trim(true).allow_comments(false));
+        let error = EmailAddress::try_parse("  foo@[1.2.3.4](x) ", options()).unwrap_err();
+        assert_eq!(&error.input()[error.span()], "[1.2.3.4](x)");
+    }
+
+    #[test]
+    fn wrappers_are_stripped_as_per_the_options() {
+        let options = || Some(ParsingOptions::default().strip_wrappers(true));
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics("\"mailto:foo@bar.com\"", options()).unwrap();
+        assert_eq!(email.to_string(), "foo@bar.com");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::StrippedQuotes, Diagnostic::StrippedMailto]
+        );
+        let email = EmailAddress::parse("\"foo bar\"@baz.com", options()).unwrap();
+        assert_eq!(email.get_local_part(), "\"foo bar\"");
+        assert!(!EmailAddress::is_valid("<foo@bar.com>", None));
+
+        let error = EmailAddress::try_parse("<foo@-bar.com>", options()).unwrap_err();
+        assert_eq!(error.input(), "<foo@-bar.com>");
+        assert_eq!(error.span(), 5..13);
+    }
+
+    #[test]
+    fn lax_parsing_accepts_a_bare_angle_addr() {
+        let lax = || Some(ParsingOptions::new(true));
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics("<foo@bar.com>", lax()).unwrap();
+        assert_eq!(email.to_string(), "foo@bar.com");
+        assert_eq!(diagnostics, vec![Diagnostic::StrippedAngleBrackets]);
+        assert!(!EmailAddress::is_valid(
+            "<foo@bar.com>",
+            Some(ParsingOptions::new(false))
+        ));
+        assert!(!EmailAddress::is_valid("<<foo@bar.com>>", lax()));
+        assert!(!EmailAddress::is_valid("<mailto:foo@bar.com>", lax()));
+        let error = EmailAddress::try_parse("<foo@-bar.com>", lax()).unwrap_err();
+        assert_eq!(error.span(), 5..13);
+    }
+
+    #[test]
+    fn full_width_forms_are_mapped_as_per_the_options() {
+        let options = || {
+            Some(
+                ParsingOptions::default()
+                    .map_to_ascii(true)
+                    .strip_wrappers(true),
+            )
+        };
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics("“ｆｏｏ＠ｂａｒ。ｃｏｍ”", options()).unwrap();
+        assert_eq!(email.to_string(), "foo@bar.com");
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::SmartPunctuation,
+                Diagnostic::FullWidthForms,
+                Diagnostic::StrippedQuotes
+            ]
+        );
+        assert!(EmailAddress::is_valid("ｆｏｏ@ｂａｒ.com", None));
+        assert!(!EmailAddress::is_valid("foo＠bar.com", None));
+
+        let input = "ｆｏｏ＠－ｂａｒ.com";
+        let error = EmailAddress::try_parse(input, options()).unwrap_err();
+        assert_eq!(error.input(), input);
+        assert_eq!(&input[error.span()], "－ｂａｒ.com");
+        let options = ParsingOptions::default()
+            .map_to_ascii(true)
+            .allow_numeric_tld(false);
+        let error = EmailAddress::try_parse("ｆｏｏ＠ｂａｒ.１２３", Some(options)).unwrap_err();
+        assert_eq!(&error.input()[error.span()], "ｂａｒ.１２３");
+    }
+
+    #[test]
+    fn common_mistakes_are_repaired() {
+        let (email, repairs) = EmailAddress::parse_with_repair("foo@@gmail com.", None).unwrap();
+        assert_eq!(email.to_string(), "foo@gmail.com");
+        assert_eq!(
+            repairs,
+            vec![
+                Diagnostic::CollapsedSeparators,
+                Diagnostic::StrippedTrailingPunctuation,
+                Diagnostic::ReplacedSpacesWithDots
+            ]
+        );
+        assert!(EmailAddress::parse("foo@@gmail com.", None).is_none());
+
+        let options = ParsingOptions::default().map_to_ascii(true).trim(true);
+        let (email, repairs) =
+            EmailAddress::parse_with_repair("ｆｏｏ＠ｂａｒｃｏｍ；", Some(options)).unwrap();
+        assert_eq!(email.to_string(), "foo@bar.com");
+        assert_eq!(
+            repairs,
+            vec![
+                Diagnostic::FullWidthForms,
+                Diagnostic::StrippedTrailingPunctuation,
+                Diagnostic::InsertedTopLevelDomainDot
+            ]
+        );
+
+        let input = "foo@@-bar.com,";
+        let error = EmailAddress::parse_with_repair(input, None).unwrap_err();
+        assert_eq!(error.input(), input);
+        assert_eq!(&input[error.span()], "-bar.com");
+    }
+
+    #[test]
+    fn underscores_in_host_names_are_reported() {
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics("user@my_host.internal.corp", None).unwrap();
+        assert_eq!(email.get_domain(), "my_host.internal.corp");
+        assert_eq!(diagnostics, vec![Diagnostic::UnderscoreInDomain]);
+
+        let lax = Some(ParsingOptions::new(true));
+        let error = EmailAddress::try_parse("user@my_host.internal.corp", lax).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::Underscore));
+        assert_eq!(error.span(), 5..26);
+
+        let options = ParsingOptions::default().allow_underscore(false);
+        assert!(!EmailAddress::is_valid(
+            "user@my_host.internal.corp",
+            Some(options)
+        ));
+
+        let options = ParsingOptions::new(true).allow_underscore(true);
+        let (email, diagnostics) =
+            EmailAddress::parse_with_diagnostics("user@my_host.internal.corp", Some(options))
+                .unwrap();
+        assert_eq!(email.get_domain(), "my_host.internal.corp");
+        assert_eq!(diagnostics, vec![Diagnostic::UnderscoreInDomain]);
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn domain_labels_are_checked_as_per_the_options() {
+        let options = ParsingOptions::new(true)
+            .allow_leading_hyphen(true)
+            .allow_trailing_hyphen(true);
+        let email = EmailAddress::parse("foo@-bar-.com", Some(options.clone())).unwrap();
+        assert_eq!(email.get_domain(), "-bar-.com");
+        assert!(EmailAddress::is_valid("\"-a\"@ bar .-com-", Some(options)));
+        assert!(!EmailAddress::is_valid(
+            "foo@-bar.com",
+            Some(ParsingOptions::default().allow_trailing_hyphen(true))
+        ));
+
+        let options = ParsingOptions::default().allow_double_hyphen(false);
+        assert!(EmailAddress::is_valid(
+            "foo@xn--bcher-kva.de",
+            Some(options.clone())
+        ));
+        let error = EmailAddress::try_parse("foo@b--ar.com", Some(options)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+        assert_eq!(error.hint(), Some(crate::Hint::DoubleHyphen));
+        assert_eq!(error.span(), 4..13);
+        assert_eq!(
+            error.to_string(),
+            "Invalid domain 'b--ar.com'. A domain label cannot contain consecutive hyphens, unless it starts with 'xn--'."
+        );
+
+        let options = ParsingOptions::default().allow_numeric_labels(false);
+        assert!(EmailAddress::parse("foo@123.com", Some(options)).is_none());
+        let options = ParsingOptions::default().allow_numeric_tld(false);
+        assert!(EmailAddress::parse("foo@[127.0.0.1]", Some(options)).is_some());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn parts_longer_than_capacity_are_rejected() {
+        let longest = format!("{}@bar.com", "a".repeat(LOCAL_PART_CAPACITY));
+        assert!(EmailAddress::parse(&longest, None).is_some());
+
+        let input = format!("{}@bar.com", "a".repeat(LOCAL_PART_CAPACITY + 1));
+        assert!(EmailAddress::parse(&input, None).is_none());
+        let error = EmailAddress::try_parse(&input, None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::LocalPartTooLong);
+        assert_eq!(error.span(), 0..LOCAL_PART_CAPACITY + 1);
+
+        let domain = format!("{}com", "a.".repeat(DOMAIN_CAPACITY / 2));
+        let error = EmailAddress::try_parse(&format!("foo@{}", domain), None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::DomainTooLong);
+        assert_eq!(error.to_string(), format!("Too long domain '{}'.", domain));
+        assert!(EmailAddress::new("foo", &domain, None).is_err());
+    }
+
+    #[test]
+    fn canonical_domain_can_be_longer_than_the_domain() {
+        // 245 octets, whose lower case is 365 octets.
+        let label = "Ⱥ".repeat(60);
+        let domain = format!("{}.{}.com", label, label);
+        let email = EmailAddress::new("foo", &domain, None).unwrap();
+        let canonical = email.canonical();
+        assert_eq!(canonical.get_domain(), domain.to_lowercase());
+        assert_eq!(canonical.get_domain().len(), 365);
+        assert_eq!(canonical.get_local_part(), "foo");
+    }
+
     #[test]
     fn domain_rule_does_not_parse_dash_google_dot_com() {
         let address = RFC5322::parse(Rule::domain_complete, "-google.com");
@@ -348,6 +1865,7 @@ mod tests {
         assert_eq!(address.is_err(), true);
     }
 
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn domain_rule_does_not_parse_dash_google_dot_com_obs() {
         let address = RFC5322::parse(Rule::domain_obs, "-google.com");
@@ -376,6 +1894,7 @@ mod tests {
         assert_eq!(actual.is_err(), false);
     }
 
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn can_parse_deprecated_local_part() {
         let actual = RFC5322::parse(Rule::local_part_obs, "\"test\".\"test\"");
@@ -383,6 +1902,7 @@ mod tests {
         assert_eq!(actual.is_err(), false);
     }
 
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn can_parse_email_with_deprecated_local_part() {
         let actual = RFC5322::parse(Rule::address_single_obs, "\"test\".\"test\"@iana.org");
@@ -390,6 +1910,7 @@ mod tests {
         assert_eq!(actual.is_err(), false);
     }
 
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn can_parse_domain_with_space() {
         println!("{:#?}", RFC5322::parse(Rule::domain_obs, " iana .com"));
@@ -406,6 +1927,68 @@ mod tests {
         assert_eq!(format!("{}", actual.unwrap()), email);
     }
 
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn canonical_forms_reparse_strictly() {
+        let lax = || Some(ParsingOptions::new(true));
+        for input in &[
+            " test @iana.org",
+            "\u{0d}\u{0a} \u{0d}\u{0a} test@iana.org",
+            "test . test@ iana .com",
+            "\"test\".\"test\"@iana.org",
+            "\"a\\\u{0}\\\u{0d}b\u{7f}\"@iana.org",
+            "\"\u{0d}\u{0a} a\"@iana.org",
+            "test@[RFC-5322-\u{07}-domain-literal]",
+            "(x)\"\"@[ 1.2.3.4 ]",
+        ] {
+            let canonical = EmailAddress::parse(input, lax()).unwrap().canonical();
+            let reparsed = EmailAddress::parse(&canonical.to_string(), None);
+            assert_eq!(reparsed.as_ref(), Some(&canonical), "{:?}", input);
+            assert_eq!(reparsed.unwrap().canonical(), canonical);
+        }
+
+        let relaxations = [
+            (
+                ParsingOptions::default().allow_leading_hyphen(true),
+                "foo@-bar.com",
+            ),
+            (
+                ParsingOptions::default().allow_trailing_hyphen(true),
+                "foo@bar-.com",
+            ),
+            (
+                ParsingOptions::new(true).allow_underscore(true),
+                "foo@my_host.corp",
+            ),
+            (ParsingOptions::default().trim(true), " foo@bar.com\r\n"),
+            (
+                ParsingOptions::default().strip_wrappers(true),
+                "<mailto:foo@bar.com>",
+            ),
+            (
+                ParsingOptions::default().map_to_ascii(true),
+                "foo＠bar．com",
+            ),
+        ];
+        for (options, input) in relaxations.iter() {
+            let canonical = EmailAddress::parse(input, Some(options.clone()))
+                .unwrap()
+                .canonical();
+            let reparsed = EmailAddress::parse(&canonical.to_string(), Some(options.clone()));
+            assert_eq!(reparsed.as_ref(), Some(&canonical), "{:?}", input);
+            let is_hyphenated = options.allow_leading_hyphen || options.allow_trailing_hyphen;
+            let reparsed = EmailAddress::parse(&canonical.to_string(), None);
+            assert_eq!(reparsed.is_some(), !is_hyphenated, "{:?}", input);
+        }
+        let canonical = EmailAddress::parse("foo@my_host.corp", None)
+            .unwrap()
+            .canonical();
+        assert!(EmailAddress::parse(&canonical.to_string(), lax()).is_none());
+        let options = ParsingOptions::default().allow_underscore(false);
+        assert!(EmailAddress::parse(&canonical.to_string(), Some(options)).is_none());
+    }
+
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn can_parse_email_with_crlf() {
         let email = "\u{0d}\u{0a} test@iana.org";
@@ -414,6 +1997,7 @@ mod tests {
         assert_eq!(format!("{}", actual.unwrap()), email);
     }
 
+    #[cfg(not(feature = "strict-only"))]
     #[test]
     fn can_parse_local_part_with_space() {
         let actual = RFC5322::parse(Rule::address_single_obs, "test . test@iana.org");
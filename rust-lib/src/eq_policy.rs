@@ -0,0 +1,138 @@
+use crate::canonical_email::CanonicalEmail;
+use crate::email_address::EmailAddress;
+use crate::normalize::Normalizer;
+use wasm_bindgen::prelude::*;
+
+/// The semantics of comparing email addresses; i.e. which spellings of an address are the same
+/// mailbox. It is configured once, and shared by `EmailAddress::is_equivalent`,
+/// `CanonicalEmail::with_policy`, and the deduplication of `EmailAddressList` and `Harvester`.
+///
+/// By default, addresses are compared by their canonical forms (see `EmailAddress::canonical`);
+/// the relaxations are those of `Normalizer`, that a policy applies to compare them:
+/// - `ci_local_part`: compares the local parts case-insensitively,
+/// - `strip_tags`: ignores the sub-addresses (tags); e.g. `jane+news` is `jane`,
+/// - `provider_rules`: applies the rules of well-known providers; e.g. the dots of a Gmail local
+///   part are insignificant, and `googlemail.com` is `gmail.com`,
+/// - `punycode`: compares internationalized domains by their ASCII forms (requires the `idna`
+///   feature).
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, EqPolicy};
+///
+/// let policy = EqPolicy::new()
+///     .ci_local_part(true)
+///     .strip_tags(true)
+///     .provider_rules(true);
+/// let email = EmailAddress::parse("J.Ane+News@GoogleMail.com", None).unwrap();
+/// let other = EmailAddress::parse("jane@gmail.com", None).unwrap();
+/// assert!(email.is_equivalent(&other, &policy));
+/// assert!(!email.is_equivalent(&other, &EqPolicy::new()));
+/// assert_eq!(policy.key(&email).as_str(), "jane@gmail.com");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EqPolicy {
+    normalizer: Normalizer,
+}
+
+impl EqPolicy {
+    /// Instantiates a policy that compares addresses by their canonical forms.
+    pub fn new() -> Self {
+        EqPolicy::default()
+    }
+
+    /// Sets whether the local parts are compared case-insensitively. Defaults to `false`.
+    pub fn ci_local_part(self, ci_local_part: bool) -> Self {
+        EqPolicy {
+            normalizer: self.normalizer.lowercase_local_part(ci_local_part),
+        }
+    }
+
+    /// Sets whether the sub-addresses (tags) of the local parts are ignored. Defaults to `false`.
+    pub fn strip_tags(self, strip_tags: bool) -> Self {
+        EqPolicy {
+            normalizer: self.normalizer.strip_tags(strip_tags),
+        }
+    }
+
+    /// Sets whether the rules of well-known providers apply. Defaults to `false`.
+    pub fn provider_rules(self, provider_rules: bool) -> Self {
+        EqPolicy {
+            normalizer: self.normalizer.provider_rules(provider_rules),
+        }
+    }
+
+    /// Sets whether internationalized domains are compared by their ASCII forms. Defaults to
+    /// `false`.
+    #[cfg(feature = "idna")]
+    pub fn punycode(self, punycode: bool) -> Self {
+        EqPolicy {
+            normalizer: self.normalizer.punycode(punycode),
+        }
+    }
+
+    /// Returns the normalizer that maps the addresses to the forms they are compared by.
+    pub fn normalizer(&self) -> Normalizer {
+        self.normalizer
+    }
+
+    /// Returns the form an address is compared by; e.g. to look it up in a set.
+    pub fn key(&self, email: &EmailAddress) -> CanonicalEmail {
+        CanonicalEmail::new(&self.normalizer.normalize(email))
+    }
+
+    /// Returns whether two addresses are the same as per the policy.
+    pub fn equivalent(&self, email: &EmailAddress, other: &EmailAddress) -> bool {
+        self.key(email) == self.key(other)
+    }
+}
+
+impl From<Normalizer> for EqPolicy {
+    fn from(normalizer: Normalizer) -> Self {
+        EqPolicy { normalizer }
+    }
+}
+
+impl EmailAddress {
+    /// Returns whether the address is the same as another one, as per a comparison policy; see
+    /// `EqPolicy`.
+    pub fn is_equivalent(&self, other: &EmailAddress, policy: &EqPolicy) -> bool {
+        policy.equivalent(self, other)
+    }
+}
+
+#[wasm_bindgen]
+impl EmailAddress {
+    /// This is a WASM wrapper over `is_equivalent`, with the policy of a normalizer.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "isEquivalent")]
+    pub fn _is_equivalent(&self, other: &EmailAddress, normalizer: &Normalizer) -> bool {
+        self.is_equivalent(other, &EqPolicy::from(*normalizer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> EmailAddress {
+        EmailAddress::parse(input, None).unwrap()
+    }
+
+    #[test]
+    fn compares_as_per_the_policy() {
+        let (email, other) = (parse("Jane+x@Example.com"), parse("jane@example.com"));
+        assert!(email.is_equivalent(&parse("\"Jane+x\"@EXAMPLE.com"), &EqPolicy::new()));
+        assert!(!email.is_equivalent(&other, &EqPolicy::new().ci_local_part(true)));
+        assert!(!email.is_equivalent(&other, &EqPolicy::new().strip_tags(true)));
+        let policy = EqPolicy::new().ci_local_part(true).strip_tags(true);
+        assert!(email.is_equivalent(&other, &policy));
+        assert_eq!(EqPolicy::from(policy.normalizer()), policy);
+        let yahoo = parse("jane-x@yahoo.com");
+        assert!(!yahoo.is_equivalent(&parse("jane@yahoo.com"), &policy));
+        let policy = policy.provider_rules(true);
+        assert!(yahoo.is_equivalent(&parse("jane@yahoo.com"), &policy));
+        assert_eq!(policy.key(&yahoo).local_part(), "jane");
+        assert!(yahoo._is_equivalent(&parse("jane@yahoo.com"), &policy.normalizer()));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::email_address::{Obsolete, Rule, RFC5322};
+use crate::messages::{EnglishMessages, LocalizedError, MessageProvider};
+use alloc::string::String;
+use core::fmt;
+use core::ops::Range;
+use pest::Parser;
+
+/// The kind of an error encountered while parsing an email address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The input is not an email address as a whole.
+    Invalid,
+    /// The local part of the input is invalid.
+    InvalidLocalPart,
+    /// The domain of the input is invalid.
+    InvalidDomain,
+    /// The local part of the input is valid, but longer than `LOCAL_PART_CAPACITY`; only with the
+    /// `heapless` feature, or from `EmailAddress::validate_lengths`.
+    LocalPartTooLong,
+    /// The domain of the input is valid, but longer than `DOMAIN_CAPACITY`; only with the
+    /// `heapless` feature, or from `EmailAddress::validate_lengths`, that also checks the length of
+    /// the labels.
+    DomainTooLong,
+    /// The local part of the input is a quoted string, or has one, and the parsing options only
+    /// allow dot-atom local parts.
+    QuotedLocalPart,
+    /// The input is valid, but longer than an SMTP path allows; only from
+    /// `EmailAddress::validate_lengths`.
+    AddressTooLong,
+    /// The input is longer than `ParsingOptions::max_input_length` allows (998 octets by default,
+    /// the limit of a line of a message header), and it was rejected before its parsing.
+    InputTooLarge,
+    /// The input nests comments deeper, or has more runs of white space, than
+    /// `ParsingOptions::max_comment_depth` and `ParsingOptions::max_whitespace_runs` allow, and it
+    /// was rejected before its parsing; e.g. a hostile input, crafted to make parsing expensive.
+    TooComplex,
+}
+
+impl ErrorKind {
+    /// Returns a stable, snake case code of the kind; e.g. `invalid_domain`.
+    ///
+    /// Unlike the `Debug` representation, the code is meant for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Invalid => "invalid",
+            ErrorKind::InvalidLocalPart => "invalid_local_part",
+            ErrorKind::InvalidDomain => "invalid_domain",
+            ErrorKind::LocalPartTooLong => "local_part_too_long",
+            ErrorKind::DomainTooLong => "domain_too_long",
+            ErrorKind::AddressTooLong => "address_too_long",
+            ErrorKind::InputTooLarge => "input_too_large",
+            ErrorKind::TooComplex => "too_complex",
+            ErrorKind::QuotedLocalPart => "quoted_local_part",
+        }
+    }
+}
+
+/// A suggestion on how to fix an invalid email address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Hint {
+    /// There is no `@` separating the local part and the domain.
+    MissingAt,
+    /// There is more than one `@` outside a quoted local part.
+    MultipleAt,
+    /// The local part is empty.
+    EmptyLocalPart,
+    /// The local part starts or ends with a dot, or contains consecutive dots.
+    MisplacedDot,
+    /// The local part contains special characters, that are allowed only in a quoted local part.
+    UnquotedSpecial,
+    /// The domain is empty.
+    EmptyDomain,
+    /// The domain starts or ends with a dot, or contains consecutive dots.
+    MisplacedDomainDot,
+    /// A domain label starts or ends with a hyphen.
+    MisplacedHyphen,
+    /// A domain label contains consecutive hyphens, and is not an A-label (`xn--`).
+    DoubleHyphen,
+    /// The top-level domain is all-numeric.
+    NumericTopLevelDomain,
+    /// A domain label is all-numeric.
+    NumericLabel,
+    /// The address contains a comment, and the parsing options do not allow comments.
+    Comment,
+    /// A domain label contains an underscore, and the parsing options only allow host names as per
+    /// RFC 1035.
+    Underscore,
+    /// The domain is a domain literal, like `[127.0.0.1]`, and the parsing options do not allow
+    /// domain literals.
+    DomainLiteral,
+    /// The address contains obsolete syntax, and can only be parsed with lax parsing options.
+    ObsoleteSyntax,
+    /// The address contains white space outside a quoted string, and the parsing options follow
+    /// RFC 3696.
+    Whitespace,
+    /// A domain label contains a character other than a letter, a digit, or a hyphen, and the
+    /// parsing options follow RFC 3696.
+    InvalidLabelCharacter,
+    /// The input is not valid UTF-8; only from `EmailAddress::parse_bytes`.
+    InvalidUtf8,
+    /// The address has a non-ASCII character, and the parsing options do not allow
+    /// internationalized addresses.
+    NonAscii,
+}
+
+impl Hint {
+    /// Returns a stable, snake case code of the hint; e.g. `misplaced_hyphen`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Hint::MissingAt => "missing_at",
+            Hint::MultipleAt => "multiple_at",
+            Hint::EmptyLocalPart => "empty_local_part",
+            Hint::MisplacedDot => "misplaced_dot",
+            Hint::UnquotedSpecial => "unquoted_special",
+            Hint::EmptyDomain => "empty_domain",
+            Hint::MisplacedDomainDot => "misplaced_domain_dot",
+            Hint::MisplacedHyphen => "misplaced_hyphen",
+            Hint::DoubleHyphen => "double_hyphen",
+            Hint::NumericTopLevelDomain => "numeric_top_level_domain",
+            Hint::NumericLabel => "numeric_label",
+            Hint::Comment => "comment",
+            Hint::Underscore => "underscore",
+            Hint::DomainLiteral => "domain_literal",
+            Hint::ObsoleteSyntax => "obsolete_syntax",
+            Hint::Whitespace => "whitespace",
+            Hint::InvalidLabelCharacter => "invalid_label_character",
+            Hint::InvalidUtf8 => "invalid_utf8",
+            Hint::NonAscii => "non_ascii",
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        EnglishMessages.hint(formatter, *self)
+    }
+}
+
+/// Error returned when an input cannot be parsed as an email address.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+/// assert_eq!(error.kind().code(), "invalid_domain");
+/// assert_eq!(error.hint(), Some(Hint::MisplacedHyphen));
+/// assert_eq!(error.hint().unwrap().code(), "misplaced_hyphen");
+/// assert_eq!(&error.input()[error.span()], "-bar.com");
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid domain '-bar.com'. A domain label can neither start nor end with a hyphen."
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ErrorKind,
+    pub(crate) input: String,
+    span: Range<usize>,
+    hint: Option<Hint>,
+}
+
+impl ParseError {
+    pub(crate) fn new(
+        kind: ErrorKind,
+        input: &str,
+        span: Range<usize>,
+        hint: Option<Hint>,
+    ) -> Self {
+        ParseError {
+            kind,
+            input: String::from(input),
+            span,
+            hint,
+        }
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the input that failed to parse.
+    pub fn input(&self) -> &str {
+        self.input.as_str()
+    }
+
+    /// Returns the byte range of the offending part in the input.
+    ///
+    /// That is the local part or the domain for the respective error kinds, and the whole input otherwise.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns a suggestion on how to fix the input, if there is one.
+    pub fn hint(&self) -> Option<Hint> {
+        self.hint
+    }
+
+    /// Returns the error, to display with the messages of a provider instead of the English ones;
+    /// see `MessageProvider`.
+    pub fn localize<'e, P: MessageProvider + ?Sized>(
+        &'e self,
+        provider: &'e P,
+    ) -> LocalizedError<'e, P> {
+        LocalizedError::new(self, provider)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        EnglishMessages.parse_error(formatter, self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Returns the byte index of the first `@` that is neither in a quoted string nor in a comment.
+pub(crate) fn find_separator(input: &str) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut comment_depth = 0;
+    let mut escaped = false;
+    for (index, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes || comment_depth > 0 => escaped = true,
+            '"' if comment_depth == 0 => in_quotes = !in_quotes,
+            '(' if !in_quotes => comment_depth += 1,
+            ')' if !in_quotes && comment_depth > 0 => comment_depth -= 1,
+            '@' if !in_quotes && comment_depth == 0 => return Some(index),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Explains why the given input, which failed to parse, is not an email address.
+pub(crate) fn diagnose(input: &str, is_lax: bool) -> ParseError {
+    let whole = 0..input.len();
+    let at = match find_separator(input) {
+        Some(at) => at,
+        None => return ParseError::new(ErrorKind::Invalid, input, whole, Some(Hint::MissingAt)),
+    };
+    let obsolete_hint = if !is_lax && Obsolete::Address.matches(input) {
+        Some(Hint::ObsoleteSyntax)
+    } else {
+        None
+    };
+    let local_part = &input[..at];
+    let domain = &input[at + 1..];
+
+    let local_part_is_valid = RFC5322::parse(Rule::local_part_complete, local_part).is_ok()
+        || (is_lax && Obsolete::LocalPart.matches(local_part));
+    if !local_part_is_valid {
+        return ParseError::new(
+            ErrorKind::InvalidLocalPart,
+            input,
+            0..at,
+            obsolete_hint.or_else(|| local_part_hint(local_part)),
+        );
+    }
+
+    let domain_is_valid = RFC5322::parse(Rule::domain_complete, domain).is_ok()
+        || (is_lax && Obsolete::Domain.matches(domain));
+    if !domain_is_valid {
+        return ParseError::new(
+            ErrorKind::InvalidDomain,
+            input,
+            at + 1..input.len(),
+            obsolete_hint.or_else(|| domain_hint(domain)),
+        );
+    }
+
+    ParseError::new(ErrorKind::Invalid, input, whole, obsolete_hint)
+}
+
+pub(crate) fn local_part_hint(local_part: &str) -> Option<Hint> {
+    let trimmed = local_part.trim();
+    if trimmed.is_empty() {
+        Some(Hint::EmptyLocalPart)
+    } else if trimmed.starts_with('"') {
+        None
+    } else if trimmed.starts_with('.') || trimmed.ends_with('.') || trimmed.contains("..") {
+        Some(Hint::MisplacedDot)
+    } else if trimmed
+        .chars()
+        .any(|c| c.is_whitespace() || "\"(),:;<>[\\]".contains(c))
+    {
+        Some(Hint::UnquotedSpecial)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn domain_hint(domain: &str) -> Option<Hint> {
+    let trimmed = domain.trim();
+    if trimmed.is_empty() {
+        Some(Hint::EmptyDomain)
+    } else if trimmed.starts_with('[') {
+        None
+    } else if find_separator(domain).is_some() {
+        Some(Hint::MultipleAt)
+    } else if trimmed.starts_with('.') || trimmed.ends_with('.') || trimmed.contains("..") {
+        Some(Hint::MisplacedDomainDot)
+    } else if trimmed
+        .split('.')
+        .any(|label| label.starts_with('-') || label.ends_with('-'))
+    {
+        Some(Hint::MisplacedHyphen)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separator_ignores_quoted_at_and_comment() {
+        assert_eq!(find_separator("foo@bar.com"), Some(3));
+        assert_eq!(find_separator("\"a@b\"@bar.com"), Some(5));
+        assert_eq!(find_separator("\"a\\\"@b\"@bar.com"), Some(7));
+        assert_eq!(find_separator("foo(a@b)@bar.com"), Some(8));
+        assert_eq!(find_separator("foo"), None);
+    }
+
+    #[test]
+    fn diagnoses_missing_at() {
+        let error = diagnose("foo", false);
+        assert_eq!(error.kind(), ErrorKind::Invalid);
+        assert_eq!(error.hint(), Some(Hint::MissingAt));
+        assert_eq!(error.span(), 0..3);
+    }
+
+    #[test]
+    fn diagnoses_invalid_local_part() {
+        let error = diagnose("te..st@bar.com", false);
+        assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+        assert_eq!(error.hint(), Some(Hint::MisplacedDot));
+        assert_eq!(error.span(), 0..6);
+
+        assert_eq!(
+            diagnose("te st@bar.com", false).hint(),
+            Some(Hint::UnquotedSpecial)
+        );
+        assert_eq!(
+            diagnose("@bar.com", false).hint(),
+            Some(Hint::EmptyLocalPart)
+        );
+    }
+
+    #[test]
+    fn diagnoses_invalid_domain() {
+        let error = diagnose("foo@bar..com", false);
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+        assert_eq!(error.hint(), Some(Hint::MisplacedDomainDot));
+        assert_eq!(error.span(), 4..12);
+
+        assert_eq!(
+            diagnose("foo@bar-.com", false).hint(),
+            Some(Hint::MisplacedHyphen)
+        );
+        assert_eq!(
+            diagnose("foo@bar@baz.com", false).hint(),
+            Some(Hint::MultipleAt)
+        );
+        assert_eq!(diagnose("foo@", false).hint(), Some(Hint::EmptyDomain));
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn diagnoses_obsolete_syntax_in_strict_mode() {
+        let error = diagnose("test . test@iana.org", false);
+        assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+        assert_eq!(error.hint(), Some(Hint::ObsoleteSyntax));
+    }
+}
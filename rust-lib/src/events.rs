@@ -0,0 +1,240 @@
+use crate::diagnostic::Diagnostic;
+use crate::error::{ErrorKind, Hint};
+use crate::redact::Redacted;
+use core::ops::Range;
+
+// The events of the `tracing` and `log` features; the inputs are redacted as per `set_redaction`.
+
+/// Records that an input was repaired, or unwrapped, to parse it.
+pub(crate) fn repaired(input: &str, diagnostic: Diagnostic) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(diagnostic = diagnostic.code(), "repaired the input");
+    #[cfg(feature = "log")]
+    log::debug!("Repaired '{}': {}", Redacted(input), diagnostic.code());
+    let _ = (input, diagnostic);
+}
+
+/// Records that the syntax of an input is invalid; that is the only failure of `EmailAddress::parse`
+/// and `EmailAddress::is_valid` that is recorded, as they do not diagnose it.
+pub(crate) fn invalid_syntax(input: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!("invalid syntax");
+    #[cfg(feature = "log")]
+    log::trace!("Invalid syntax '{}'", Redacted(input));
+    let _ = input;
+}
+
+/// Records the error of an input that is not an email address.
+pub(crate) fn invalid(input: &str, kind: ErrorKind, hint: Option<Hint>, span: &Range<usize>) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        input = %Redacted(input),
+        kind = ?kind,
+        hint = ?hint,
+        span = ?span,
+        "invalid email address"
+    );
+    #[cfg(feature = "log")]
+    log::debug!(
+        "Invalid email address '{}': {:?} at {:?}, {:?}",
+        Redacted(input),
+        kind,
+        span,
+        hint
+    );
+    let _ = (input, kind, hint, span);
+}
+
+/// Records that an address was rejected by a rule of a `Policy`.
+pub(crate) fn rejected(input: &str, code: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(code, "rejected by a rule");
+    #[cfg(feature = "log")]
+    log::debug!("Rejected '{}': {}", Redacted(input), code);
+    let _ = (input, code);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::email_address::{EmailAddress, ParsingOptions};
+    use crate::policy::Policy;
+    use crate::rules::DomainDenylist;
+    use std::cell::RefCell;
+    use std::format;
+    use std::string::String;
+    use std::sync::Once;
+    use std::vec::Vec;
+
+    std::thread_local! {
+        /// The records of the current test; the events are emitted on the thread of their caller.
+        static RECORDS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn push(record: String) {
+        RECORDS.with(|records| records.borrow_mut().push(record));
+    }
+
+    #[cfg(feature = "log")]
+    struct Logger;
+
+    #[cfg(feature = "log")]
+    impl log::Log for Logger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            push(format!("log {} {}", record.level(), record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    struct Subscriber;
+
+    #[cfg(feature = "tracing")]
+    struct Fields(String);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for Fields {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn core::fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Subscriber {
+        fn enabled(&self, _: &tracing::Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes) -> tracing::span::Id {
+            let mut fields = Fields(format!("tracing span {}", span.metadata().name()));
+            span.record(&mut fields);
+            push(fields.0);
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event) {
+            let mut fields = Fields(format!("tracing {}", event.metadata().level()));
+            event.record(&mut fields);
+            push(fields.0);
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    /// Returns the records of the events of `f`.
+    fn capture(f: impl FnOnce()) -> Vec<String> {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            #[cfg(feature = "log")]
+            {
+                log::set_logger(&Logger).unwrap();
+                log::set_max_level(log::LevelFilter::Trace);
+            }
+            #[cfg(feature = "tracing")]
+            tracing::subscriber::set_global_default(Subscriber).unwrap();
+        });
+        RECORDS.with(|records| records.borrow_mut().clear());
+        f();
+        RECORDS.with(|records| records.take())
+    }
+
+    /// Asserts that the records hold those of the enabled features.
+    fn assert_recorded(records: &[String], log: &str, tracing: &str) {
+        #[cfg(feature = "log")]
+        assert!(records.iter().any(|record| record == log), "{:#?}", records);
+        #[cfg(feature = "tracing")]
+        assert!(
+            records.iter().any(|record| record == tracing),
+            "{:#?}",
+            records
+        );
+        let _ = (log, tracing);
+    }
+
+    #[test]
+    fn records_repairs() {
+        let options = ParsingOptions::default().trim(true);
+        let records = capture(|| {
+            EmailAddress::parse(" jane@example.com", Some(options)).unwrap();
+        });
+        assert_recorded(
+            &records,
+            "log DEBUG Repaired ' ****@example.com': trimmed",
+            "tracing DEBUG message=repaired the input diagnostic=\"trimmed\"",
+        );
+    }
+
+    #[test]
+    fn records_invalid_syntax() {
+        let records = capture(|| assert!(!EmailAddress::is_valid("jane@", None)));
+        assert_recorded(
+            &records,
+            "log TRACE Invalid syntax 'j***@'",
+            "tracing TRACE message=invalid syntax",
+        );
+    }
+
+    #[test]
+    fn records_invalid_syntax_of_lax_parsing() {
+        let options = ParsingOptions::new(true);
+        let records = capture(|| assert!(!EmailAddress::is_valid("jane@", Some(options))));
+        assert_recorded(
+            &records,
+            "log TRACE Invalid syntax 'j***@'",
+            "tracing TRACE message=invalid syntax",
+        );
+    }
+
+    #[test]
+    fn records_errors() {
+        let records = capture(|| {
+            EmailAddress::try_parse("jane@-example.com", None).unwrap_err();
+        });
+        assert_recorded(
+            &records,
+            "log DEBUG Invalid email address 'j***@-example.com': InvalidDomain at 5..17, \
+             Some(MisplacedHyphen)",
+            "tracing DEBUG message=invalid email address input=j***@-example.com \
+             kind=InvalidDomain hint=Some(MisplacedHyphen) span=5..17",
+        );
+    }
+
+    #[test]
+    fn records_rejections() {
+        let policy = Policy::new().rule(DomainDenylist::new(["example.com"]));
+        let records = capture(|| {
+            policy.validate("jane@example.com").unwrap_err();
+        });
+        assert_recorded(
+            &records,
+            "log DEBUG Rejected 'j***@example.com': denied_domain",
+            "tracing DEBUG message=rejected by a rule code=\"denied_domain\"",
+        );
+        #[cfg(feature = "tracing")]
+        assert!(records.contains(&String::from(
+            "tracing span validate input=j***@example.com"
+        )));
+    }
+
+    #[test]
+    fn redacts_the_local_part_but_not_the_domain() {
+        let records = capture(|| {
+            let _ = EmailAddress::try_parse("jane.doe@-example.com", None);
+        });
+        assert!(!records.is_empty());
+        assert!(records.iter().all(|record| !record.contains("jane.doe")));
+        assert!(records
+            .iter()
+            .any(|record| record.contains("@-example.com")));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::email_address::EmailAddress;
+use alloc::format;
+use alloc::vec::Vec;
+
+/// The kind of an address of `ExampleAddresses`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Plain,
+    Quoted,
+    Idn,
+}
+
+/// Iterator over deterministic, documentation-safe email addresses; e.g. for tests, fixtures, and
+/// doc examples.
+///
+/// The addresses are numbered from 1, and their domains are reserved by
+/// [RFC 2606](https://www.rfc-editor.org/rfc/rfc2606), so that no mail can ever reach a real
+/// mailbox. By default, they are all like `user1@example.com`, as per `EmailAddress::example`;
+/// the variants that are enabled on request take turns with them, in this order:
+/// - `quoted`: a quoted local part with a space, like `"user 2"@example.org`,
+/// - `idn`: an internationalized address, like `jürgen3@bücher.example`.
+///
+/// The same settings always produce the same addresses, in the same order. See the `generator`
+/// module, with the `generator` feature, for realistic random addresses.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::ExampleAddresses;
+///
+/// let examples: Vec<String> = ExampleAddresses::new()
+///     .quoted(true)
+///     .idn(true)
+///     .take(4)
+///     .map(|email| email.to_string())
+///     .collect();
+/// assert_eq!(
+///     examples,
+///     [
+///         "user1@example.com",
+///         "\"user 2\"@example.org",
+///         "jürgen3@bücher.example",
+///         "user4@example.com",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ExampleAddresses {
+    position: usize,
+    quoted: bool,
+    idn: bool,
+}
+
+impl ExampleAddresses {
+    /// Instantiates an iterator over addresses like `user1@example.com`, without variants.
+    pub fn new() -> Self {
+        ExampleAddresses::default()
+    }
+
+    /// Sets whether addresses with a quoted local part are produced. Defaults to `false`.
+    pub fn quoted(self, quoted: bool) -> Self {
+        ExampleAddresses { quoted, ..self }
+    }
+
+    /// Sets whether internationalized addresses are produced. Defaults to `false`.
+    pub fn idn(self, idn: bool) -> Self {
+        ExampleAddresses { idn, ..self }
+    }
+
+    fn kind(&self, number: usize) -> Kind {
+        let mut kinds = Vec::with_capacity(3);
+        kinds.push(Kind::Plain);
+        if self.quoted {
+            kinds.push(Kind::Quoted);
+        }
+        if self.idn {
+            kinds.push(Kind::Idn);
+        }
+        kinds[(number - 1) % kinds.len()]
+    }
+}
+
+impl Iterator for ExampleAddresses {
+    type Item = EmailAddress;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position += 1;
+        let number = self.position;
+        // The parts are valid, and short enough for the `heapless` feature.
+        Some(match self.kind(number) {
+            Kind::Plain => EmailAddress::example(number),
+            Kind::Quoted => {
+                EmailAddress::__new_unchecked(&format!("\"user {}\"", number), "example.org")
+            }
+            Kind::Idn => {
+                EmailAddress::__new_unchecked(&format!("jürgen{}", number), "bücher.example")
+            }
+        })
+    }
+}
+
+impl EmailAddress {
+    /// Returns the documentation-safe address numbered `n`; i.e. `user<n>@example.com`, whose
+    /// domain is reserved by RFC 2606. See `ExampleAddresses` for more variants.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// assert_eq!(EmailAddress::example(1).to_string(), "user1@example.com");
+    /// ```
+    pub fn example(n: usize) -> EmailAddress {
+        EmailAddress::__new_unchecked(&format!("user{}", n), "example.com")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn produces_valid_addresses_on_reserved_domains() {
+        let examples: Vec<EmailAddress> = ExampleAddresses::new().idn(true).take(4).collect();
+        assert_eq!(examples[1].to_string(), "jürgen2@bücher.example");
+        assert_eq!(examples[2], EmailAddress::example(3));
+        let examples = ExampleAddresses::new().quoted(true).idn(true).take(30);
+        for email in examples.chain(Some(EmailAddress::example(usize::MAX))) {
+            let reparsed = EmailAddress::parse(&email.to_string(), None);
+            assert_eq!(reparsed.as_ref(), Some(&email));
+            let domain = email.get_domain();
+            assert!(domain.starts_with("example.") || domain.ends_with(".example"));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! A categorized corpus of valid, obsolete, and invalid addresses, and its export as JSON fixtures;
+//! e.g. for downstream projects and the ports of the parser to other languages, to test against
+//! the same expectations.
+//!
+//! The corpus is made of the test data of the parser, from which the build script generates its
+//! tests too:
+//! - the `test_data` source: the valid and invalid local parts and domains of `.test_data`,
+//!   combined into addresses as the tests do,
+//! - the `isemail` source: the addresses of the is_email test suite, with their diagnoses; e.g.
+//!   `ISEMAIL_DEPREC_LOCALPART`.
+//!
+//! The category of an address is that of its source:
+//! - `valid`: the address is valid,
+//! - `obsolete`: the address is only valid as per the obsolete syntax, that is deprecated,
+//! - `invalid`: the address is invalid.
+//!
+//! The expectations are those of the parser: the canonical form of the addresses that parse with
+//! lax options, and the code of the error of the strict parsing of the others (see
+//! `ErrorKind::code`).
+//!
+//! # Examples
+//! ```
+//! use email_address_parser::fixtures::{corpus, Category};
+//!
+//! # #[cfg(not(feature = "strict-only"))] {
+//! let fixture = corpus()
+//!     .into_iter()
+//!     .find(|fixture| fixture.input() == "test . test@iana.org")
+//!     .unwrap();
+//! assert_eq!((fixture.category(), fixture.source()), (Category::Obsolete, "isemail"));
+//! assert_eq!(fixture.diagnosis(), Some("ISEMAIL_DEPREC_FWS"));
+//! assert_eq!(fixture.canonical(), Some("test.test@iana.org"));
+//! assert_eq!(fixture.error(), Some("invalid_local_part"));
+//! # }
+//!
+//! let mut json = Vec::new();
+//! email_address_parser::fixtures::write_json(&mut json).unwrap();
+//! assert!(String::from_utf8(json).unwrap().contains("\"category\": \"obsolete\""));
+//! ```
+//!
+//! The `export_fixtures` example writes the corpus to a file:
+//! `cargo run --example export_fixtures --features fixtures -- fixtures.json`.
+
+use crate::email_address::{EmailAddress, ParsingOptions};
+use serde::Serialize;
+use std::io;
+
+/// The addresses of the corpus, with their categories, sources, and is_email diagnoses; generated
+/// by the build script from the same test data as the tests of the parser.
+const FIXTURES: &[(&str, Category, &str, Option<&str>)] =
+    include!(concat!(env!("OUT_DIR"), "/fixture_corpus.rs"));
+
+/// The category of a fixture; see the module documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    /// The address is valid.
+    Valid,
+    /// The address is only valid as per the obsolete syntax.
+    Obsolete,
+    /// The address is invalid.
+    Invalid,
+}
+
+/// An address of the corpus, with its category and the expectations of the parser.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Fixture {
+    input: String,
+    category: Category,
+    source: &'static str,
+    diagnosis: Option<&'static str>,
+    canonical: Option<String>,
+    error: Option<&'static str>,
+}
+
+impl Fixture {
+    fn new(
+        input: &str,
+        category: Category,
+        source: &'static str,
+        diagnosis: Option<&'static str>,
+    ) -> Self {
+        let lax = EmailAddress::parse(input, Some(ParsingOptions::new(true)));
+        let strict = EmailAddress::try_parse(input, None);
+        Fixture {
+            canonical: lax.map(|email| email.canonical().to_string()),
+            error: strict.err().map(|error| error.kind().code()),
+            input: String::from(input),
+            category,
+            source,
+            diagnosis,
+        }
+    }
+
+    /// Returns the address.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the category of the address.
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    /// Returns the source of the address: `test_data` or `isemail`.
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+
+    /// Returns the diagnosis of the address in the is_email test suite, if it comes from it; e.g.
+    /// `ISEMAIL_DEPREC_LOCALPART`.
+    pub fn diagnosis(&self) -> Option<&'static str> {
+        self.diagnosis
+    }
+
+    /// Returns the canonical form of the address parsed with lax options, if it parses.
+    pub fn canonical(&self) -> Option<&str> {
+        self.canonical.as_deref()
+    }
+
+    /// Returns the code of the error of the strict parsing of the address, if it fails.
+    pub fn error(&self) -> Option<&'static str> {
+        self.error
+    }
+}
+
+/// Returns the corpus, in a stable order: the addresses of the `test_data` source, and those of
+/// the `isemail` source.
+pub fn corpus() -> Vec<Fixture> {
+    FIXTURES
+        .iter()
+        .map(|&(input, category, source, diagnosis)| {
+            Fixture::new(input, category, source, diagnosis)
+        })
+        .collect()
+}
+
+/// Writes the corpus as a pretty-printed JSON array of objects, with the `input`, `category`,
+/// `source`, `diagnosis`, `canonical`, and `error` of the fixtures; the missing values are `null`.
+pub fn write_json<W: io::Write>(writer: W) -> serde_json::Result<()> {
+    serde_json::to_writer_pretty(writer, &corpus())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    /// Checks the same expectations as the generated tests: the addresses of `test_data` parse
+    /// with strict options or not at all, and those of `isemail` parse with lax options unless
+    /// they are invalid.
+    #[test]
+    fn categories_match_the_parser() {
+        for fixture in corpus() {
+            let is_valid = fixture.category() != Category::Invalid;
+            let lax = EmailAddress::try_parse(fixture.input(), Some(ParsingOptions::new(true)));
+            let is_too_long = matches!(
+                lax.map_err(|error| error.kind()),
+                Err(ErrorKind::LocalPartTooLong) | Err(ErrorKind::DomainTooLong)
+            );
+            match fixture.source() {
+                "test_data" => assert_eq!(fixture.error().is_none(), is_valid, "{:?}", fixture),
+                // The lax options do not allow the obsolete syntax with the `strict-only` feature,
+                // and the parts longer than their capacities with the `heapless` feature.
+                _ if is_valid && cfg!(feature = "strict-only") => (),
+                _ if is_valid && cfg!(feature = "heapless") && is_too_long => (),
+                _ => assert_eq!(fixture.canonical().is_some(), is_valid, "{:?}", fixture),
+            }
+        }
+    }
+
+    #[test]
+    fn writes_json_fixtures() {
+        let mut json = Vec::new();
+        write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        let fixtures = json.as_array().unwrap();
+        assert_eq!(fixtures.len(), corpus().len());
+        assert_eq!(
+            fixtures[0],
+            serde_json::json!({
+                "input": "test@google.com",
+                "category": "valid",
+                "source": "test_data",
+                "diagnosis": null,
+                "canonical": "test@google.com",
+                "error": null,
+            })
+        );
+        let is_email = fixtures
+            .iter()
+            .find(|fixture| fixture["source"] == "isemail")
+            .unwrap();
+        assert_eq!(is_email["diagnosis"], "ISEMAIL_ERR_NODOMAIN");
+    }
+}
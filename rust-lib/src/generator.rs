@@ -0,0 +1,343 @@
+//! Generator of realistic, random email addresses; e.g. for load testing, or seeding a staging database.
+//!
+//! The generated addresses look like those of real users (`jane.doe+news@mail.example`), but all
+//! of their domains are reserved by [RFC 2606](https://www.rfc-editor.org/rfc/rfc2606), so that
+//! no mail can ever reach a real mailbox.
+//!
+//! # Examples
+//! ```
+//! use email_address_parser::generator::Generator;
+//! use rand::rngs::StdRng;
+//! use rand::SeedableRng;
+//!
+//! let generator = Generator::new()
+//!     .providers(&[("mail", 3), ("example.com", 1)])
+//!     .tag_frequency(0.2)
+//!     .idn_share(0.05);
+//! let mut rng = StdRng::seed_from_u64(42);
+//! for email in generator.iter(&mut rng).take(10) {
+//!     assert!(
+//!         ["mail.example", "example.com"].contains(&email.get_domain())
+//!             || !email.get_domain().is_ascii()
+//!     );
+//! }
+//! ```
+
+use crate::email_address::EmailAddress;
+use rand::Rng;
+
+const FIRST_NAMES: &[&str] = &[
+    "james",
+    "mary",
+    "john",
+    "patricia",
+    "robert",
+    "jennifer",
+    "michael",
+    "linda",
+    "david",
+    "elizabeth",
+    "ahmed",
+    "fatima",
+    "wei",
+    "mei",
+    "raj",
+    "priya",
+    "carlos",
+    "maria",
+    "olga",
+    "ivan",
+];
+const LAST_NAMES: &[&str] = &[
+    "smith", "johnson", "williams", "brown", "jones", "garcia", "miller", "davis", "khan", "wang",
+    "li", "patel", "singh", "rossi", "muller", "novak", "kim", "nguyen", "silva", "cohen",
+];
+const IDN_FIRST_NAMES: &[&str] = &[
+    "jürgen", "josé", "zoë", "søren", "łukasz", "đorđe", "李", "ゆき",
+];
+const IDN_LAST_NAMES: &[&str] = &[
+    "müller",
+    "garcía",
+    "øster",
+    "wiśniewski",
+    "ćosić",
+    "王",
+    "田中",
+];
+const IDN_DOMAINS: &[&str] = &[
+    "bücher.example",
+    "münchen.test",
+    "例え.example",
+    "почта.test",
+];
+const TAGS: &[&str] = &["news", "shop", "work", "spam", "test", "billing", "2024"];
+
+/// Top level domains and second level domains reserved by RFC 2606.
+const RESERVED_TLDS: &[&str] = &["example", "test", "invalid", "localhost"];
+const RESERVED_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+/// A generator of random email addresses, with tunable distributions.
+///
+/// By default, the addresses are spread over `example.com`, `example.org`, `example.net`, and
+/// `mail.example`, one in ten has a tag, and none has non-ASCII characters.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    providers: Vec<(String, u32)>,
+    tag_frequency: f64,
+    idn_share: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator {
+            providers: vec![
+                (String::from("example.com"), 4),
+                (String::from("example.org"), 2),
+                (String::from("example.net"), 2),
+                (String::from("mail.example"), 2),
+            ],
+            tag_frequency: 0.1,
+            idn_share: 0.0,
+        }
+    }
+}
+
+impl Generator {
+    /// Instantiates a generator with the default distributions.
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Sets the mix of providers, as pairs of domains and relative weights.
+    ///
+    /// A domain that is not reserved by RFC 2606 is moved under the `example` top level domain;
+    /// e.g. `mail` or `mail.com` become `mail.example` or `mail.com.example` respectively.
+    /// Invalid domains, those too long for the addresses with the longest local parts, and
+    /// providers with no weight are ignored, and an empty mix restores the default providers.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::generator::Generator;
+    ///
+    /// let mut rng = rand::rng();
+    /// let email = Generator::new().providers(&[("gmail.com", 1)]).generate(&mut rng);
+    /// assert_eq!(email.get_domain(), "gmail.com.example");
+    /// ```
+    pub fn providers(self, providers: &[(&str, u32)]) -> Self {
+        let local_part = longest_local_part();
+        let providers: Vec<_> = providers
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .map(|(domain, weight)| (reserved_domain(domain), *weight))
+            .filter(|(domain, _)| {
+                EmailAddress::is_valid(&format!("{}@{}", local_part, domain), None)
+            })
+            .collect();
+        if providers.is_empty() {
+            return Generator {
+                providers: Generator::default().providers,
+                ..self
+            };
+        }
+        Generator { providers, ..self }
+    }
+
+    /// Sets the share of addresses with a tag; e.g. `jane+news@example.com`. It is clamped to `0.0..=1.0`.
+    pub fn tag_frequency(self, tag_frequency: f64) -> Self {
+        Generator {
+            tag_frequency: clamp_share(tag_frequency),
+            ..self
+        }
+    }
+
+    /// Sets the share of internationalized addresses; e.g. `jürgen@bücher.example`.
+    /// It is clamped to `0.0..=1.0`.
+    ///
+    /// An internationalized address has a non-ASCII local part and an internationalized domain,
+    /// that replaces the provider.
+    pub fn idn_share(self, idn_share: f64) -> Self {
+        Generator {
+            idn_share: clamp_share(idn_share),
+            ..self
+        }
+    }
+
+    /// Generates an email address.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> EmailAddress {
+        let is_idn = rng.random_bool(self.idn_share);
+        let (first_names, last_names) = if is_idn {
+            (IDN_FIRST_NAMES, IDN_LAST_NAMES)
+        } else {
+            (FIRST_NAMES, LAST_NAMES)
+        };
+        let first = pick(rng, first_names);
+        let last = pick(rng, last_names);
+        let mut local_part = match rng.random_range(0..5) {
+            0 => format!("{}.{}", first, last),
+            1 => format!("{}_{}", first, last),
+            2 => format!("{}{}", first.chars().next().unwrap_or('x'), last),
+            3 => format!("{}{}", first, rng.random_range(1..100)),
+            _ => format!("{}.{}{}", first, last, rng.random_range(1..10)),
+        };
+        if rng.random_bool(self.tag_frequency) {
+            local_part.push('+');
+            local_part.push_str(pick(rng, TAGS));
+        }
+        let domain = if is_idn {
+            pick(rng, IDN_DOMAINS)
+        } else {
+            self.pick_provider(rng)
+        };
+        // The providers fit the longest local part; see `Generator::providers`.
+        EmailAddress::new(&local_part, domain, None)
+            .expect("generated addresses consist of valid parts")
+    }
+
+    /// Returns an endless iterator of generated email addresses.
+    pub fn iter<'a, R: Rng + ?Sized>(
+        &'a self,
+        rng: &'a mut R,
+    ) -> impl Iterator<Item = EmailAddress> + 'a {
+        std::iter::repeat_with(move || self.generate(rng))
+    }
+
+    fn pick_provider<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        // The weights are summed as `u64`, that cannot overflow, whatever the `u32` weights.
+        let total: u64 = self
+            .providers
+            .iter()
+            .map(|&(_, weight)| u64::from(weight))
+            .sum();
+        let mut point = rng.random_range(0..total);
+        for (domain, weight) in &self.providers {
+            let weight = u64::from(*weight);
+            if point < weight {
+                return domain;
+            }
+            point -= weight;
+        }
+        &self.providers[self.providers.len() - 1].0
+    }
+}
+
+fn pick<'a, R: Rng + ?Sized>(rng: &mut R, values: &[&'a str]) -> &'a str {
+    values[rng.random_range(0..values.len())]
+}
+
+/// Returns a local part as long as the longest that `Generator::generate` can make, in octets: a
+/// tagged `first.last9`, with the longest names and tag.
+fn longest_local_part() -> String {
+    let longest = |values: &[&[&'static str]]| {
+        values
+            .iter()
+            .flat_map(|values| values.iter())
+            .copied()
+            .max_by_key(|value| value.len())
+            .unwrap_or_default()
+    };
+    format!(
+        "{}.{}9+{}",
+        longest(&[FIRST_NAMES, IDN_FIRST_NAMES]),
+        longest(&[LAST_NAMES, IDN_LAST_NAMES]),
+        longest(&[TAGS])
+    )
+}
+
+fn clamp_share(share: f64) -> f64 {
+    if share.is_nan() {
+        0.0
+    } else {
+        share.clamp(0.0, 1.0)
+    }
+}
+
+fn is_reserved(domain: &str) -> bool {
+    let domain = domain.to_lowercase();
+    let ends_with =
+        |reserved: &str| domain == reserved || domain.ends_with(&format!(".{}", reserved));
+    RESERVED_TLDS.iter().any(|tld| ends_with(tld))
+        || RESERVED_DOMAINS.iter().any(|reserved| ends_with(reserved))
+}
+
+fn reserved_domain(domain: &str) -> String {
+    let domain = domain.trim_matches('.');
+    if is_reserved(domain) {
+        String::from(domain)
+    } else {
+        format!("{}.example", domain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generated_addresses_are_valid_and_reserved() {
+        let generator = Generator::new().tag_frequency(0.5).idn_share(0.3);
+        let mut rng = StdRng::seed_from_u64(7);
+        for email in generator.iter(&mut rng).take(1000) {
+            assert!(
+                EmailAddress::is_valid(&email.to_string(), None),
+                "{}",
+                email
+            );
+            assert!(is_reserved(email.get_domain()), "{}", email);
+        }
+    }
+
+    #[test]
+    fn providers_are_moved_under_reserved_domains() {
+        assert_eq!(reserved_domain("gmail.com"), "gmail.com.example");
+        assert_eq!(reserved_domain("mail.test"), "mail.test");
+        assert_eq!(reserved_domain("corp.example.org"), "corp.example.org");
+        assert_eq!(reserved_domain("notexample.com"), "notexample.com.example");
+
+        let generator = Generator::new().providers(&[("", 1), ("-foo", 1)]);
+        assert_eq!(generator.providers, Generator::default().providers);
+
+        // A domain that only fits the short local parts would fail the generation of the others.
+        let long = format!("{}example", "abcdefghi.".repeat(98));
+        assert!(EmailAddress::is_valid(&format!("a@{}", long), None));
+        let generator = Generator::new().providers(&[(&long, 1)]);
+        assert_eq!(generator.providers, Generator::default().providers);
+        let mut rng = StdRng::seed_from_u64(7);
+        let longest = longest_local_part();
+        for email in Generator::new().idn_share(0.5).iter(&mut rng).take(1000) {
+            assert!(email.get_local_part().len() <= longest.len(), "{}", email);
+        }
+    }
+
+    #[test]
+    fn weights_do_not_overflow() {
+        let generator =
+            Generator::new().providers(&[("a.example", u32::MAX), ("b.example", u32::MAX)]);
+        let mut rng = StdRng::seed_from_u64(42);
+        let emails: Vec<_> = generator.iter(&mut rng).take(1000).collect();
+        let a = emails
+            .iter()
+            .filter(|email| email.get_domain() == "a.example")
+            .count();
+        assert!((400..600).contains(&a), "{}", a);
+    }
+
+    #[test]
+    fn distributions_are_honored() {
+        let generator = Generator::new()
+            .providers(&[("a.example", 3), ("b.example", 1), ("c.example", 0)])
+            .tag_frequency(0.25);
+        let mut rng = StdRng::seed_from_u64(42);
+        let emails: Vec<_> = generator.iter(&mut rng).take(4000).collect();
+        let count = |predicate: &dyn Fn(&EmailAddress) -> bool| {
+            emails.iter().filter(|email| predicate(email)).count()
+        };
+        let a = count(&|email| email.get_domain() == "a.example");
+        assert!((2800..3200).contains(&a), "{}", a);
+        assert_eq!(count(&|email| email.get_domain() == "c.example"), 0);
+        let tagged = count(&|email| email.get_local_part().contains('+'));
+        assert!((800..1200).contains(&tagged), "{}", tagged);
+        assert_eq!(count(&|email| !email.to_string().is_ascii()), 0);
+    }
+}
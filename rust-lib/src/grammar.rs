@@ -0,0 +1,191 @@
+use crate::email_address::{ParsingOptions, Rule, RFC5322};
+use crate::error::{ErrorKind, ParseError};
+use crate::prefilter::check_limits;
+use pest::error::InputLocation;
+use pest::Parser;
+
+/// A rule of the grammar that `parse_rule` validates a fragment against.
+///
+/// The rules are those of RFC 5322, with the deviations of the parser; e.g. a domain label can
+/// neither start nor end with a hyphen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RuleKind {
+    /// A whole address (`addr-spec`), like `foo@bar.com`.
+    AddressSingle,
+    /// A local part, like `foo` or `"foo bar"`.
+    LocalPartComplete,
+    /// A domain, like `bar.com` or `[127.0.0.1]`.
+    DomainComplete,
+    /// A domain literal, like `[127.0.0.1]`.
+    DomainLiteral,
+    /// A dot-atom, like `foo.bar`, with optional white space around it.
+    DotAtom,
+    /// A quoted string, like `"foo bar"`, with optional comments and white space around it.
+    QuotedString,
+    /// A comment, like `(foo)`.
+    Comment,
+    /// A whole address of lax parsing, that allows the obsolete syntax, like `foo . bar@baz.com`.
+    /// Nothing matches it with the `strict-only` feature.
+    AddressSingleObs,
+    /// A local part of the obsolete syntax, like `foo . bar` or `"foo".bar`. Nothing matches it
+    /// with the `strict-only` feature.
+    ObsLocalPartComplete,
+    /// A domain of the obsolete syntax, like `bar . com`. Nothing matches it with the
+    /// `strict-only` feature.
+    ObsDomainComplete,
+}
+
+impl RuleKind {
+    /// Returns the rule of the grammar; none for the obsolete ones with the `strict-only` feature,
+    /// that does not compile them.
+    fn rule(self) -> Option<Rule> {
+        let rule = match self {
+            RuleKind::AddressSingle => Rule::address_single,
+            RuleKind::LocalPartComplete => Rule::local_part_complete,
+            RuleKind::DomainComplete => Rule::domain_complete,
+            RuleKind::DomainLiteral => Rule::domain_literal,
+            RuleKind::DotAtom => Rule::dot_atom,
+            RuleKind::QuotedString => Rule::quoted_string,
+            RuleKind::Comment => Rule::comment,
+            #[cfg(not(feature = "strict-only"))]
+            RuleKind::AddressSingleObs => Rule::address_single_obs,
+            #[cfg(not(feature = "strict-only"))]
+            RuleKind::ObsLocalPartComplete => Rule::obs_local_part_complete,
+            #[cfg(not(feature = "strict-only"))]
+            RuleKind::ObsDomainComplete => Rule::obs_domain_complete,
+            #[cfg(feature = "strict-only")]
+            RuleKind::AddressSingleObs
+            | RuleKind::ObsLocalPartComplete
+            | RuleKind::ObsDomainComplete => return None,
+        };
+        Some(rule)
+    }
+
+    /// Returns the kind of the errors of the rule.
+    fn error_kind(self) -> ErrorKind {
+        match self {
+            RuleKind::LocalPartComplete | RuleKind::DotAtom | RuleKind::QuotedString => {
+                ErrorKind::InvalidLocalPart
+            }
+            RuleKind::ObsLocalPartComplete => ErrorKind::InvalidLocalPart,
+            RuleKind::DomainComplete | RuleKind::DomainLiteral => ErrorKind::InvalidDomain,
+            RuleKind::ObsDomainComplete => ErrorKind::InvalidDomain,
+            _ => ErrorKind::Invalid,
+        }
+    }
+}
+
+/// Validates a whole input against exactly a rule of the grammar; e.g. to check a fragment, like
+/// a domain literal, on its own.
+///
+/// Unlike `validate_local_part` and `validate_domain`, this applies no parsing options; e.g. a
+/// domain with a leading hyphen is always invalid, and an obsolete local part is only valid as per
+/// the obsolete rules. The error spans the character where the input stops matching the rule, if
+/// any; it has no hint. With the `strict-only` feature, that does not compile the obsolete rules,
+/// the whole input is the error for them.
+///
+/// The input is checked against the default limits of `ParsingOptions` first; e.g. one longer than
+/// 998 octets fails with `ErrorKind::InputTooLarge`, and one with comments nested deeper than 16
+/// with `ErrorKind::TooComplex`, spanning the whole input.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{parse_rule, ErrorKind, RuleKind};
+///
+/// assert!(parse_rule(RuleKind::DomainLiteral, "[127.0.0.1]").is_ok());
+/// assert!(parse_rule(RuleKind::DomainComplete, "[127.0.0.1]").is_ok());
+/// assert!(parse_rule(RuleKind::DomainLiteral, "bar.com").is_err());
+///
+/// let error = parse_rule(RuleKind::LocalPartComplete, "foo bar").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+/// assert_eq!(error.span(), 4..5);
+/// ```
+pub fn parse_rule(kind: RuleKind, input: &str) -> Result<(), ParseError> {
+    check_limits(input, &ParsingOptions::default())?;
+    let rule = match kind.rule() {
+        Some(rule) => rule,
+        None => {
+            return Err(ParseError::new(
+                kind.error_kind(),
+                input,
+                0..input.len(),
+                None,
+            ))
+        }
+    };
+    let position = match RFC5322::parse(rule, input) {
+        Ok(mut pairs) => match pairs.next().map_or(0, |pair| pair.as_span().end()) {
+            end if end == input.len() => return Ok(()),
+            end => end,
+        },
+        Err(error) => match error.location {
+            InputLocation::Pos(position) => position,
+            InputLocation::Span((start, _)) => start,
+        },
+    };
+    let next = input[position..].chars().next().map_or(0, char::len_utf8);
+    Err(ParseError::new(
+        kind.error_kind(),
+        input,
+        position..position + next,
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn validates_whole_inputs_against_a_rule() {
+        assert!(parse_rule(RuleKind::AddressSingle, "foo@bar.com").is_ok());
+        assert!(parse_rule(RuleKind::DotAtom, " foo.bar ").is_ok());
+        assert!(parse_rule(RuleKind::QuotedString, "\"foo bar\"").is_ok());
+        assert!(parse_rule(RuleKind::Comment, "(foo (bar))").is_ok());
+        let error = parse_rule(RuleKind::Comment, "(foo)bar").unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::Invalid, 5..6));
+        let error = parse_rule(RuleKind::DomainComplete, "-bar.com").unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ErrorKind::InvalidDomain, 0..1)
+        );
+        let error = parse_rule(RuleKind::DomainLiteral, "[127.0.0.1").unwrap_err();
+        assert_eq!(error.span(), 10..10);
+    }
+
+    #[test]
+    fn checks_the_default_limits() {
+        let long = "a".repeat(999);
+        let error = parse_rule(RuleKind::DotAtom, &long).unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ErrorKind::InputTooLarge, 0..999)
+        );
+        let nested = format!("{}{}", "(".repeat(17), ")".repeat(17));
+        let error = parse_rule(RuleKind::Comment, &nested).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TooComplex);
+        let nested = format!("{}{}", "(".repeat(16), ")".repeat(16));
+        assert!(parse_rule(RuleKind::Comment, &nested).is_ok());
+        let spaced = "(a ".repeat(65) + &")".repeat(65);
+        let error = parse_rule(RuleKind::Comment, &spaced).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TooComplex);
+    }
+
+    #[test]
+    fn validates_the_obsolete_syntax() {
+        assert!(parse_rule(RuleKind::LocalPartComplete, "foo . bar").is_err());
+        let is_obsolete_compiled = cfg!(not(feature = "strict-only"));
+        assert_eq!(
+            parse_rule(RuleKind::ObsLocalPartComplete, "foo . bar").is_ok(),
+            is_obsolete_compiled
+        );
+        assert_eq!(
+            parse_rule(RuleKind::ObsDomainComplete, "bar . com").is_ok(),
+            is_obsolete_compiled
+        );
+        let result = parse_rule(RuleKind::AddressSingleObs, "\"foo\".bar@baz.com");
+        assert_eq!(result.is_ok(), is_obsolete_compiled);
+    }
+}
@@ -0,0 +1,381 @@
+use crate::email_address::ParsingOptions;
+use crate::eq_policy::EqPolicy;
+use crate::header::header_fields;
+use crate::mailbox::{parse_mailboxes, Mailbox};
+use crate::normalize::Normalizer;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+/// The headers whose addresses are harvested by default.
+const DEFAULT_HEADERS: &[&str] = &["From", "To", "Cc", "Reply-To", "Return-Path"];
+
+/// Harvests the distinct addresses of messages, like an mbox file or a directory of `.eml` files,
+/// along with where they were found.
+///
+/// Only the heads of the messages are read; the addresses are those of the mailboxes of the `From`,
+/// `To`, `Cc`, `Reply-To`, and `Return-Path` headers by default, skipping the invalid ones. They
+/// are told apart by their normalized forms (see `Normalizer`), which are their canonical forms by
+/// default, and come in the order they were first found.
+///
+/// Only available with the `std` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::Harvester;
+///
+/// let mbox = "From jane@example.com Mon Jan  1 00:00:00 2024\n\
+///             From: Jane Doe <jane@example.com>\n\
+///             To: joe@example.com,\n  \"Team\" <team@example.com>\n\
+///             \n\
+///             Hi!\n\
+///             \n\
+///             From joe@example.com Tue Jan  2 00:00:00 2024\n\
+///             From: joe@EXAMPLE.com\n\
+///             Message-ID: <1@example.com>\n\
+///             \n";
+/// let mut harvester = Harvester::new();
+/// harvester.harvest_mbox(mbox.as_bytes()).unwrap();
+///
+/// let addresses: Vec<String> = harvester
+///     .addresses()
+///     .iter()
+///     .map(|harvested| harvested.mailbox().address().to_string())
+///     .collect();
+/// assert_eq!(addresses, ["jane@example.com", "joe@example.com", "team@example.com"]);
+///
+/// let sources = harvester.addresses()[1].sources();
+/// assert_eq!(sources[0].header(), "To");
+/// assert_eq!(sources[1].header(), "From");
+/// assert_eq!(sources[1].message(), 1);
+/// assert_eq!(sources[1].message_id(), Some("<1@example.com>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Harvester {
+    options: ParsingOptions,
+    normalizer: Normalizer,
+    headers: Vec<String>,
+    messages: usize,
+    addresses: Vec<Harvested>,
+    /// The indexes of the addresses by their normalized forms.
+    keys: BTreeMap<String, usize>,
+}
+
+impl Default for Harvester {
+    fn default() -> Self {
+        Harvester {
+            options: ParsingOptions::new(true),
+            normalizer: Normalizer::new(),
+            headers: DEFAULT_HEADERS
+                .iter()
+                .map(|&header| String::from(header))
+                .collect(),
+            messages: 0,
+            addresses: Vec::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Harvester {
+    /// Instantiates a harvester of the default headers, that parses addresses leniently, as per
+    /// `ParsingOptions::new(true)`, and tells them apart by their canonical forms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the options to parse the addresses with.
+    pub fn options(self, options: ParsingOptions) -> Self {
+        Harvester { options, ..self }
+    }
+
+    /// Sets the normalizer of the addresses, to tell them apart.
+    pub fn normalizer(self, normalizer: Normalizer) -> Self {
+        Harvester { normalizer, ..self }
+    }
+
+    /// Sets the comparison policy of the addresses, to tell them apart; the same as setting its
+    /// normalizer.
+    pub fn eq_policy(self, policy: EqPolicy) -> Self {
+        self.normalizer(policy.normalizer())
+    }
+
+    /// Sets the names of the headers to harvest; they are compared ignoring case.
+    pub fn headers(self, headers: &[&str]) -> Self {
+        Harvester {
+            headers: headers.iter().map(|&header| String::from(header)).collect(),
+            ..self
+        }
+    }
+
+    /// Harvests the addresses of a message, from its head; the body, if any, is ignored.
+    pub fn harvest_message(&mut self, message: &str) {
+        self.harvest(message, None);
+    }
+
+    /// Harvests the addresses of the messages of an mbox; i.e. messages that each start with a
+    /// `From ` line.
+    pub fn harvest_mbox<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        self.harvest_mbox_from(reader, None)
+    }
+
+    /// Harvests the addresses of a file, or of the files of a directory and of its subdirectories,
+    /// in order of their names. A file is read as an mbox if it starts with a `From ` line, and as
+    /// a single message, like an `.eml` file, otherwise.
+    pub fn harvest_path<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+            return entries
+                .iter()
+                .try_for_each(|entry| self.harvest_path(entry));
+        }
+        let mut reader = io::BufReader::new(fs::File::open(path)?);
+        if reader.fill_buf()?.starts_with(b"From ") {
+            self.harvest_mbox_from(reader, Some(path))
+        } else {
+            let mut message = Vec::new();
+            io::Read::read_to_end(&mut reader, &mut message)?;
+            self.harvest(&String::from_utf8_lossy(&message), Some(path));
+            Ok(())
+        }
+    }
+
+    /// Returns the harvested addresses, in the order they were first found.
+    pub fn addresses(&self) -> &[Harvested] {
+        &self.addresses
+    }
+
+    /// Consumes the harvester, returning the harvested addresses.
+    pub fn into_addresses(self) -> Vec<Harvested> {
+        self.addresses
+    }
+
+    fn harvest_mbox_from<R: BufRead>(
+        &mut self,
+        mut reader: R,
+        path: Option<&Path>,
+    ) -> io::Result<()> {
+        // The head of the current message, if any; the lines after it are skipped.
+        let mut head: Option<String> = None;
+        let mut in_head = false;
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let text = String::from_utf8_lossy(&line);
+            if text.starts_with("From ") {
+                if let Some(head) = head.take() {
+                    self.harvest(&head, path);
+                }
+                head = Some(String::new());
+                in_head = true;
+            } else if in_head {
+                if text.trim_end_matches(['\r', '\n']).is_empty() {
+                    in_head = false;
+                } else if let Some(head) = &mut head {
+                    head.push_str(&text);
+                }
+            }
+        }
+        if let Some(head) = head {
+            self.harvest(&head, path);
+        }
+        Ok(())
+    }
+
+    fn harvest(&mut self, message: &str, path: Option<&Path>) {
+        let fields = header_fields(message);
+        let message_id = fields
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Message-ID"))
+            .map(|(_, value)| String::from(value.trim()));
+        for (name, value) in &fields {
+            let header = match self
+                .headers
+                .iter()
+                .find(|header| header.eq_ignore_ascii_case(name))
+            {
+                Some(header) => header.clone(),
+                None => continue,
+            };
+            for mailbox in parse_mailboxes(value, &self.options) {
+                let source = HarvestSource {
+                    message: self.messages,
+                    message_id: message_id.clone(),
+                    header: header.clone(),
+                    path: path.map(Path::to_path_buf),
+                };
+                let key = self.normalizer.normalize(mailbox.address()).to_string();
+                match self.keys.get(&key) {
+                    Some(&index) => {
+                        let harvested = &mut self.addresses[index];
+                        if harvested.mailbox.display_name().is_none()
+                            && mailbox.display_name().is_some()
+                        {
+                            harvested.mailbox = mailbox;
+                        }
+                        harvested.sources.push(source);
+                    }
+                    None => {
+                        self.keys.insert(key, self.addresses.len());
+                        self.addresses.push(Harvested {
+                            mailbox,
+                            sources: vec![source],
+                        });
+                    }
+                }
+            }
+        }
+        self.messages += 1;
+    }
+}
+
+/// An address found by a `Harvester`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Harvested {
+    mailbox: Mailbox,
+    sources: Vec<HarvestSource>,
+}
+
+impl Harvested {
+    /// Returns the mailbox of the address; with the first display name it was found with, if any.
+    pub fn mailbox(&self) -> &Mailbox {
+        &self.mailbox
+    }
+
+    /// Returns where the address was found, in order.
+    pub fn sources(&self) -> &[HarvestSource] {
+        &self.sources
+    }
+}
+
+/// Where a `Harvester` found an address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HarvestSource {
+    message: usize,
+    message_id: Option<String>,
+    header: String,
+    path: Option<PathBuf>,
+}
+
+impl HarvestSource {
+    /// Returns the zero-based index of the message, among those harvested.
+    pub fn message(&self) -> usize {
+        self.message
+    }
+
+    /// Returns the `Message-ID` of the message, if it has one.
+    pub fn message_id(&self) -> Option<&str> {
+        self.message_id.as_deref()
+    }
+
+    /// Returns the name of the header, as given to the harvester.
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Returns the path of the file of the message, if it was read from one.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harvests_the_given_headers_of_heads_only() {
+        let message = "Return-Path: <>\r\n\
+                       from: \"Doe, Jane\" <jane@example.com>\r\n\
+                       Bcc: joe@example.com\r\n\
+                       Cc: Team: alice@example.com, invalid;\r\n\
+                       \r\n\
+                       To: bob@example.com\r\n";
+        let mut harvester = Harvester::new();
+        harvester.harvest_message(message);
+        let found: Vec<(String, Option<&str>, &str)> = harvester
+            .addresses()
+            .iter()
+            .map(|harvested| {
+                let mailbox = harvested.mailbox();
+                (
+                    mailbox.address().to_string(),
+                    mailbox.display_name(),
+                    harvested.sources()[0].header(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (String::from("jane@example.com"), Some("Doe, Jane"), "From"),
+                (String::from("alice@example.com"), None, "Cc")
+            ]
+        );
+        let mut harvester = Harvester::new().headers(&["bcc"]);
+        harvester.harvest_message(message);
+        assert_eq!(harvester.addresses()[0].sources()[0].header(), "bcc");
+    }
+
+    #[test]
+    fn keeps_the_first_display_name() {
+        let mut harvester = Harvester::new();
+        harvester.harvest_message("To: jane@example.com\n");
+        harvester.harvest_message("From: Jane <jane@example.com>\nTo: Other <jane@EXAMPLE.com>\n");
+        let harvested = &harvester.addresses()[0];
+        assert_eq!(harvester.addresses().len(), 1);
+        assert_eq!(harvested.mailbox().display_name(), Some("Jane"));
+        let messages: Vec<usize> = harvested
+            .sources()
+            .iter()
+            .map(HarvestSource::message)
+            .collect();
+        assert_eq!(messages, [0, 1, 1]);
+    }
+
+    #[test]
+    fn harvests_files_and_directories() {
+        let directory = std::env::temp_dir().join(format!("harvest-{}", std::process::id()));
+        fs::create_dir_all(directory.join("inbox")).unwrap();
+        fs::write(
+            directory.join("a.eml"),
+            "From: jane@example.com\n\nFrom joe@example.com\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("inbox").join("mbox"),
+            "From x Mon Jan  1 00:00:00 2024\nTo: joe@example.com\n\n>From: x\n",
+        )
+        .unwrap();
+        let mut harvester = Harvester::new();
+        let result = harvester.harvest_path(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        result.unwrap();
+        let found: Vec<(String, &Path)> = harvester
+            .addresses()
+            .iter()
+            .map(|harvested| {
+                let path = harvested.sources()[0].path().unwrap();
+                (
+                    harvested.mailbox().address().to_string(),
+                    path.strip_prefix(&directory).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (String::from("jane@example.com"), Path::new("a.eml")),
+                (String::from("joe@example.com"), Path::new("inbox/mbox"))
+            ]
+        );
+    }
+}
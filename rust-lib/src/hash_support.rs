@@ -0,0 +1,196 @@
+use crate::email_address::EmailAddress;
+use crate::eq_policy::EqPolicy;
+use alloc::string::{String, ToString};
+use core::fmt;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+impl EmailAddress {
+    /// Returns the Gravatar hash of the address: the hexadecimal MD5 digest of its lower cased
+    /// canonical form.
+    ///
+    /// The canonical form drops the comments and folding white space, and the redundant quotes,
+    /// hence the hash is the same for every spelling of the address that Gravatar treats as one.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse(" MyEmailAddress@example.com ", None).unwrap();
+    /// assert_eq!(email.gravatar_hash(), "0bc83cb571cd1c50ba6f3e8a78ef1346");
+    /// ```
+    pub fn gravatar_hash(&self) -> String {
+        hex(&Md5::digest(self.hash_input().as_bytes()))
+    }
+
+    /// Returns the hexadecimal SHA-256 digest of the lower cased canonical form of the address;
+    /// e.g. to join analytics or advertising audiences on the address without sharing it.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("MyEmailAddress@Example.com", None).unwrap();
+    /// assert_eq!(
+    ///     email.sha256_hash(),
+    ///     "84059b07d4be67b806386c0aad8070a23f18836bbaae342275dc0a83414c32ee"
+    /// );
+    /// ```
+    pub fn sha256_hash(&self) -> String {
+        hex(&Sha256::digest(self.hash_input().as_bytes()))
+    }
+
+    /// Returns the hexadecimal HMAC-SHA256 of the lower cased canonical form of the address, with
+    /// the given key; the same as `KeyedHasher::new(key).hash(self)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("Jane@Example.com", None).unwrap();
+    /// assert_eq!(
+    ///     email.hmac_sha256(b"secret"),
+    ///     "fb817989d942e7ffb3d4b8b204f7abca29f4c25c3fa46574da84c50f30d07513"
+    /// );
+    /// ```
+    pub fn hmac_sha256(&self, key: &[u8]) -> String {
+        KeyedHasher::new(key).hash(self)
+    }
+
+    fn hash_input(&self) -> String {
+        self.canonical().to_string().to_lowercase()
+    }
+}
+
+/// Maps email addresses to stable pseudonymous identifiers: the hexadecimal HMAC-SHA256 of their
+/// normalized forms, with a secret key; e.g. to count the distinct users of analytics events
+/// without storing their addresses, as per the pseudonymization of the GDPR.
+///
+/// Unlike a plain digest, like `EmailAddress::sha256_hash`, the identifiers cannot be reversed by
+/// hashing guessed addresses without the key; rotating the key unlinks the identifiers. The
+/// addresses are normalized as per a comparison policy (see `EqPolicy`) before they are hashed,
+/// so that the spellings of a mailbox that the policy considers the same map to the same
+/// identifier. By default, the local parts are compared case-insensitively, and the domains are
+/// always lower cased.
+///
+/// Requires the `hash` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, EqPolicy, KeyedHasher};
+///
+/// let hasher = KeyedHasher::new(b"secret");
+/// let email = EmailAddress::parse("Jane@Example.com", None).unwrap();
+/// let other = EmailAddress::parse("\"jane\"@example.com", None).unwrap();
+/// assert_eq!(hasher.hash(&email), hasher.hash(&other));
+///
+/// let hasher = hasher.eq_policy(EqPolicy::new().strip_tags(true).provider_rules(true));
+/// let email = EmailAddress::parse("j.ane+news@googlemail.com", None).unwrap();
+/// let other = EmailAddress::parse("jane@gmail.com", None).unwrap();
+/// assert_eq!(hasher.hash(&email), hasher.hash(&other));
+/// ```
+#[derive(Clone)]
+pub struct KeyedHasher {
+    mac: Hmac<Sha256>,
+    policy: EqPolicy,
+}
+
+impl KeyedHasher {
+    /// Instantiates a hasher with the given secret key, of any length; it should be at least 32
+    /// random octets.
+    pub fn new(key: &[u8]) -> Self {
+        KeyedHasher {
+            mac: Hmac::new_from_slice(key).expect("HMAC takes keys of any length"),
+            policy: EqPolicy::new().ci_local_part(true),
+        }
+    }
+
+    /// Sets the comparison policy that the addresses are normalized with before they are hashed;
+    /// it is always made to compare the local parts case-insensitively. Defaults to
+    /// `EqPolicy::new().ci_local_part(true)`.
+    pub fn eq_policy(self, policy: EqPolicy) -> Self {
+        KeyedHasher {
+            policy: policy.ci_local_part(true),
+            ..self
+        }
+    }
+
+    /// Returns the pseudonymous identifier of an address.
+    pub fn hash(&self, email: &EmailAddress) -> String {
+        let mut mac = self.mac.clone();
+        mac.update(self.policy.key(email).as_str().as_bytes());
+        hex(&mac.finalize().into_bytes())
+    }
+}
+
+/// Omits the key.
+impl fmt::Debug for KeyedHasher {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("KeyedHasher")
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+fn hex(digest: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        hex.push(DIGITS[usize::from(byte >> 4)] as char);
+        hex.push(DIGITS[usize::from(byte & 0xf)] as char);
+    }
+    hex
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use crate::ParsingOptions;
+    use alloc::format;
+
+    #[test]
+    fn hashes_the_lower_cased_canonical_form() {
+        let expected = "0bc83cb571cd1c50ba6f3e8a78ef1346";
+        for input in &[
+            "myemailaddress@example.com",
+            "MyEmailAddress@EXAMPLE.com",
+            "\"MyEmailAddress\"@example.com",
+            "(home) MyEmailAddress@example.com",
+        ] {
+            let email = EmailAddress::parse(input, Some(ParsingOptions::new(true))).unwrap();
+            assert_eq!(email.gravatar_hash(), expected, "{}", input);
+        }
+        let email = EmailAddress::parse("Jane@Bücher.de", None).unwrap();
+        assert_eq!(email.gravatar_hash(), "7f596e233054e8ad228c404ec52966df");
+        assert_eq!(
+            email.sha256_hash(),
+            "dce7dbc78b5c3f71d4bdc46b933ac53ff22d93ad90ad85668eb8b0166cdceee8"
+        );
+    }
+
+    #[test]
+    fn keyed_hashes_depend_on_the_key_and_the_policy() {
+        let email = EmailAddress::parse(
+            "(home) Jane+News@GMAIL.com",
+            Some(ParsingOptions::new(true)),
+        )
+        .unwrap();
+        assert_eq!(
+            KeyedHasher::new(b"secret")
+                .eq_policy(EqPolicy::new().strip_tags(true))
+                .hash(&email),
+            "79166aba856edb32107e0d428696fd327bd14a8d013de5bc1247d184d3184642"
+        );
+        assert_ne!(email.hmac_sha256(b"secret"), email.hmac_sha256(b"other"));
+        assert_ne!(
+            email.hmac_sha256(b"secret"),
+            KeyedHasher::new(b"secret")
+                .eq_policy(EqPolicy::new().strip_tags(true))
+                .hash(&email)
+        );
+        let debug = format!("{:?}", KeyedHasher::new(b"secret"));
+        assert!(!debug.contains("secret"), "{}", debug);
+    }
+}
@@ -0,0 +1,550 @@
+use crate::canonical::drops_controls;
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::mailbox::{parse_mailboxes, write_display_name, Address, Mailbox};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+impl EmailAddress {
+    /// Returns whether the address, as it is rendered (e.g. by `to_string`), is safe to write into
+    /// a message header; i.e. it contains no line break, nor any other control character but
+    /// horizontal tab, that could end the header, or inject another one.
+    ///
+    /// Strictly parsed addresses are not always safe: comments and quoted strings may contain
+    /// folding white space, and with lax parsing, quoted pairs may even escape a bare CR, LF, or NUL.
+    /// See `header_safe` for a form that is safe whenever possible.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ParsingOptions};
+    ///
+    /// assert!(EmailAddress::parse("foo@bar.com", None).unwrap().is_header_safe());
+    ///
+    /// let email = EmailAddress::parse("\"foo\r\n bar\"@bar.com", None).unwrap();
+    /// assert!(!email.is_header_safe());
+    /// ```
+    pub fn is_header_safe(&self) -> bool {
+        is_header_safe(&self.to_string())
+    }
+
+    /// Returns the canonical form of the address, if it is safe to write into a message header as
+    /// per `is_header_safe`; `None` otherwise.
+    ///
+    /// As the canonical form drops the comments, and unfolds the quoted strings, this is only
+    /// `None` for an address with a control character in its local part or its domain literal,
+    /// which lax parsing accepts as per the obsolete syntax, and that the canonical form drops, or
+    /// with a Unicode line separator. This is the strict output mode for building headers,
+    /// refusing any address that could break one, or that would not be the same without it.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ParsingOptions};
+    ///
+    /// let email = EmailAddress::parse("\"foo\r\n bar\"@bar.com", None).unwrap();
+    /// assert_eq!(email.header_safe().unwrap().to_string(), "\"foo bar\"@bar.com");
+    ///
+    /// let email = EmailAddress::parse("\"foo\\\nbar\"@bar.com", Some(ParsingOptions::new(true)));
+    /// assert_eq!(email.unwrap().header_safe(), None);
+    /// ```
+    pub fn header_safe(&self) -> Option<EmailAddress> {
+        if drops_controls(self.get_local_part(), self.get_domain()) {
+            return None;
+        }
+        Some(self.canonical()).filter(EmailAddress::is_header_safe)
+    }
+}
+
+/// Returns whether a text is safe to write into a message header; i.e. it contains no control
+/// character but horizontal tab, nor any Unicode line or paragraph separator.
+pub(crate) fn is_header_safe(text: &str) -> bool {
+    !text
+        .chars()
+        .any(|c| (c.is_control() && c != '\t') || c == '\u{2028}' || c == '\u{2029}')
+}
+
+/// Error returned when a header cannot be written safely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HeaderError {
+    /// The name of the header is empty, or contains a character other than printable ASCII, or a
+    /// colon.
+    InvalidName(String),
+    /// The content contains a line break, or another character that could end the header, or
+    /// inject another one; see `EmailAddress::is_header_safe`.
+    UnsafeContent(String),
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::InvalidName(name) => write!(formatter, "Invalid header name {:?}.", name),
+            HeaderError::UnsafeContent(content) => {
+                write!(formatter, "Unsafe header content {:?}.", content)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderError {}
+
+/// Writes header fields of comma separated items, like the mailboxes of a `To` header, folded
+/// between the items so that the lines do not exceed the recommended length of RFC 5322 (78
+/// characters, without the CRLF).
+///
+/// Each continuation line starts with a single space, and an item is never split; an item that is
+/// longer than a line on its own is written on a line of its own, as there is no safe place to
+/// fold it. The lengths are counted in octets, as per RFC 6532. The content is refused if it is
+/// not safe to write into a header, instead of ever writing a line break from it.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, HeaderFolder};
+///
+/// let recipients: Vec<_> = (1..=8)
+///     .map(|index| EmailAddress::new(&format!("recipient{}", index), "example.com", None).unwrap())
+///     .collect();
+/// assert_eq!(
+///     HeaderFolder::new().fold_addresses("To", &recipients).unwrap(),
+///     "To: recipient1@example.com, recipient2@example.com, recipient3@example.com,\r\n \
+///      recipient4@example.com, recipient5@example.com, recipient6@example.com,\r\n \
+///      recipient7@example.com, recipient8@example.com"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeaderFolder {
+    line_length: usize,
+    encode_words: bool,
+}
+
+impl Default for HeaderFolder {
+    fn default() -> Self {
+        HeaderFolder {
+            line_length: 78,
+            encode_words: false,
+        }
+    }
+}
+
+impl HeaderFolder {
+    /// Instantiates a folder with the recommended line length of 78.
+    pub fn new() -> Self {
+        HeaderFolder::default()
+    }
+
+    /// Sets the maximum length of a line, without the CRLF.
+    pub fn line_length(self, line_length: usize) -> Self {
+        HeaderFolder {
+            line_length,
+            ..self
+        }
+    }
+
+    /// Sets whether the non-ASCII display names of the mailboxes and groups are written as RFC 2047
+    /// encoded words (see `Mailbox::to_encoded_header_value`), instead of quoted strings; `false`
+    /// by default.
+    ///
+    /// Requires the `rfc2047` feature.
+    #[cfg(feature = "rfc2047")]
+    pub fn encode_words(self, encode_words: bool) -> Self {
+        HeaderFolder {
+            encode_words,
+            ..self
+        }
+    }
+
+    /// Returns the header field with the given name, and the items separated by commas, folded as
+    /// needed; without the final CRLF.
+    pub fn fold<I, S>(&self, name: &str, items: I) -> Result<String, HeaderError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut tokens: Vec<String> = Vec::new();
+        for item in items {
+            if let Some(last) = tokens.last_mut() {
+                last.push(',');
+            }
+            tokens.push(String::from(item.as_ref()));
+        }
+        self.fold_tokens(name, tokens)
+    }
+
+    /// Returns the header field with the given name, and the header safe forms of the addresses
+    /// (see `EmailAddress::header_safe`) separated by commas, folded as needed.
+    pub fn fold_addresses(
+        &self,
+        name: &str,
+        addresses: &[EmailAddress],
+    ) -> Result<String, HeaderError> {
+        let mut items = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            match address.header_safe() {
+                Some(safe) => items.push(safe.to_string()),
+                None => return Err(HeaderError::UnsafeContent(address.to_string())),
+            }
+        }
+        self.fold(name, items)
+    }
+
+    /// Returns the header field with the given name, and the mailboxes (see
+    /// `Mailbox::to_header_value`, and `encode_words`) separated by commas, folded as needed.
+    pub fn fold_mailboxes(&self, name: &str, mailboxes: &[Mailbox]) -> Result<String, HeaderError> {
+        let mut items = Vec::with_capacity(mailboxes.len());
+        for mailbox in mailboxes {
+            items.push(mailbox.header_value(self.encode_words)?);
+        }
+        self.fold(name, items)
+    }
+
+    /// Returns the header field with the given name, and the address list separated by commas,
+    /// folded as needed; between the addresses, as well as between the members of the groups.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{Address, EmailAddress, Group, HeaderFolder, Mailbox};
+    ///
+    /// let mailbox = |address| Mailbox::new(None, EmailAddress::parse(address, None).unwrap());
+    /// let addresses = [
+    ///     Address::from(mailbox("boss@example.com")),
+    ///     Address::from(Group::new(
+    ///         "Team",
+    ///         vec![mailbox("alice@example.com"), mailbox("bob@example.com")],
+    ///     )),
+    ///     Address::from(Group::new("Undisclosed recipients", Vec::new())),
+    /// ];
+    /// assert_eq!(
+    ///     HeaderFolder::new().line_length(60).fold_address_list("To", &addresses).unwrap(),
+    ///     "To: boss@example.com, Team: alice@example.com,\r\n \
+    ///      bob@example.com;, Undisclosed recipients:;"
+    /// );
+    /// ```
+    pub fn fold_address_list(
+        &self,
+        name: &str,
+        addresses: &[Address],
+    ) -> Result<String, HeaderError> {
+        let mut tokens: Vec<String> = Vec::new();
+        for address in addresses {
+            if let Some(last) = tokens.last_mut() {
+                last.push(',');
+            }
+            match address {
+                Address::Mailbox(mailbox) => tokens.push(mailbox.header_value(self.encode_words)?),
+                Address::Group(group) => {
+                    let mut display_name = String::new();
+                    write_display_name(group.name(), self.encode_words, &mut display_name)?;
+                    display_name.push(':');
+                    if group.mailboxes().is_empty() {
+                        display_name.push(';');
+                        tokens.push(display_name);
+                        continue;
+                    }
+                    tokens.push(display_name);
+                    for (index, mailbox) in group.mailboxes().iter().enumerate() {
+                        let mut value = mailbox.header_value(self.encode_words)?;
+                        value.push(if index + 1 < group.mailboxes().len() {
+                            ','
+                        } else {
+                            ';'
+                        });
+                        tokens.push(value);
+                    }
+                }
+            }
+        }
+        self.fold_tokens(name, tokens)
+    }
+
+    /// Returns the header field with the given name, and the tokens separated by white space,
+    /// folded before any token that would not fit on the line.
+    fn fold_tokens(&self, name: &str, tokens: Vec<String>) -> Result<String, HeaderError> {
+        let is_valid_name =
+            !name.is_empty() && name.bytes().all(|byte| matches!(byte, 33..=57 | 59..=126));
+        if !is_valid_name {
+            return Err(HeaderError::InvalidName(String::from(name)));
+        }
+        let mut field = String::from(name);
+        field.push(':');
+        let mut line_start = 0;
+        for (index, token) in tokens.iter().enumerate() {
+            if !is_header_safe(token) {
+                return Err(HeaderError::UnsafeContent(token.clone()));
+            }
+            if index > 0 && field.len() - line_start + 1 + token.len() > self.line_length {
+                field.push_str("\r\n");
+                line_start = field.len();
+            }
+            field.push(' ');
+            field.push_str(token);
+        }
+        Ok(field)
+    }
+}
+
+/// The headers of a message whose values are address lists, or a single mailbox, as per RFC 5322,
+/// along with the common non-standard ones; e.g. `Delivered-To`.
+pub const ADDRESS_HEADERS: &[&str] = &[
+    "From",
+    "Sender",
+    "Reply-To",
+    "To",
+    "Cc",
+    "Bcc",
+    "Return-Path",
+    "Resent-From",
+    "Resent-Sender",
+    "Resent-To",
+    "Resent-Cc",
+    "Resent-Bcc",
+    "Delivered-To",
+    "Disposition-Notification-To",
+    "Errors-To",
+];
+
+/// Returns the mailboxes of the address headers (see `ADDRESS_HEADERS`) of the head of a message,
+/// grouped by header name, as spelled in `ADDRESS_HEADERS`; e.g. to process received messages
+/// without a full MIME parser.
+///
+/// The head is everything before the first empty line of the message; the rest, if any, is
+/// ignored. The fields are unfolded, their names compared ignoring case, and the mailboxes of
+/// the fields with the same name, like those of repeated `Resent-To` fields, are merged in order.
+/// The invalid mailboxes are skipped, and so are the headers without a valid one.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::scan_header_block;
+///
+/// let head = "Subject: Hi\r\n\
+///             from: Jane Doe <jane@example.com>\r\n\
+///             To: joe@example.com,\r\n \"Team\" <team@example.com>\r\n\
+///             Return-Path: <>\r\n";
+/// let mailboxes = scan_header_block(head, None);
+/// assert_eq!(mailboxes.keys().collect::<Vec<_>>(), [&"From", &"To"]);
+/// assert_eq!(mailboxes["From"][0].display_name(), Some("Jane Doe"));
+/// assert_eq!(mailboxes["To"][1].address().to_string(), "team@example.com");
+/// ```
+pub fn scan_header_block(
+    head: &str,
+    options: Option<ParsingOptions>,
+) -> BTreeMap<&'static str, Vec<Mailbox>> {
+    let options = options.unwrap_or_default();
+    let mut mailboxes: BTreeMap<&'static str, Vec<Mailbox>> = BTreeMap::new();
+    for (name, value) in header_fields(head) {
+        let header = ADDRESS_HEADERS
+            .iter()
+            .find(|header| header.eq_ignore_ascii_case(name));
+        if let Some(header) = header {
+            let found = parse_mailboxes(&value, &options);
+            if !found.is_empty() {
+                mailboxes.entry(header).or_default().extend(found);
+            }
+        }
+    }
+    mailboxes
+}
+
+/// Returns the fields of the head of a message, unfolded, as names and values; the head ends at the
+/// first empty line, and the lines that are not fields, like the `From ` line of an mbox, are
+/// skipped.
+pub(crate) fn header_fields(head: &str) -> Vec<(&str, String)> {
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    for line in head.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            break;
+        } else if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = fields.last_mut() {
+                value.push_str(line);
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            if !name.is_empty() && !name.contains([' ', '\t']) {
+                fields.push((name, String::from(value)));
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Group;
+    use crate::ParsingOptions;
+
+    #[test]
+    fn unfolds_header_fields() {
+        let head = "From jane@example.com Mon Jan  1 00:00:00 2024\n\
+                    To: a@example.com,\r\n\tb@example.com\r\n\
+                    X-Empty:\n\
+                    invalid\n\
+                    \n\
+                    Cc: c@example.com\n";
+        assert_eq!(
+            header_fields(head),
+            [
+                ("To", String::from(" a@example.com,\tb@example.com")),
+                ("X-Empty", String::new())
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_repeated_address_headers() {
+        let head = "Resent-To: a@example.com\nresent-to: b@example.com, invalid\nTo: x\n";
+        let mailboxes = scan_header_block(head, None);
+        let addresses: Vec<String> = mailboxes["Resent-To"]
+            .iter()
+            .map(|mailbox| mailbox.address().to_string())
+            .collect();
+        assert_eq!(addresses, ["a@example.com", "b@example.com"]);
+        assert_eq!(mailboxes.len(), 1);
+    }
+
+    #[test]
+    fn rejects_header_breaking_characters() {
+        assert!(is_header_safe("\"foo\tbar\"@bar.com"));
+        assert!(is_header_safe("foö@bücher.de"));
+        for text in &[
+            "a\rb",
+            "a\nb",
+            "a\u{0}b",
+            "a\u{7f}b",
+            "a\u{85}b",
+            "a\u{2028}b",
+        ] {
+            assert!(!is_header_safe(text), "{:?}", text);
+        }
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn canonical_form_is_safe_unless_a_control_character_is_escaped() {
+        let lax = Some(ParsingOptions::new(true));
+        let email = EmailAddress::parse("(a\r\n comment) foo@bar.com", lax).unwrap();
+        assert!(!email.is_header_safe());
+        assert_eq!(email.header_safe().unwrap().to_string(), "foo@bar.com");
+
+        for input in &[
+            "\"foo\\\rbar\"@bar.com",
+            "\"foo\\\u{0}\"@bar.com",
+            "\"\u{1}\"@bar.com",
+        ] {
+            let email = EmailAddress::parse(input, Some(ParsingOptions::new(true))).unwrap();
+            assert_eq!(email.header_safe(), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn folds_between_items() {
+        let folder = HeaderFolder::new().line_length(20);
+        assert_eq!(folder.fold("To", Vec::<&str>::new()).unwrap(), "To:");
+        assert_eq!(folder.fold("To", ["a@b.c"]).unwrap(), "To: a@b.c");
+        assert_eq!(
+            folder.fold("Cc", ["a@b.c", "d@e.f", "gh@ij.kl"]).unwrap(),
+            "Cc: a@b.c, d@e.f,\r\n gh@ij.kl"
+        );
+        assert_eq!(
+            folder
+                .fold("To", ["longer.than.a.line@example.com", "a@b.c"])
+                .unwrap(),
+            "To: longer.than.a.line@example.com,\r\n a@b.c"
+        );
+        for (index, line) in HeaderFolder::new()
+            .fold("To", vec!["foo@bar.com"; 30])
+            .unwrap()
+            .split("\r\n")
+            .enumerate()
+        {
+            assert!(line.len() <= 78, "{:?}", line);
+            assert_eq!(line.starts_with(' '), index > 0, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn refuses_unsafe_names_and_content() {
+        let folder = HeaderFolder::new();
+        assert_eq!(
+            folder.fold("To: Bcc", ["a@b.c"]),
+            Err(HeaderError::InvalidName(String::from("To: Bcc")))
+        );
+        assert_eq!(
+            folder
+                .fold("To", ["a@b.c\r\nBcc: d@e.f"])
+                .unwrap_err()
+                .to_string(),
+            "Unsafe header content \"a@b.c\\r\\nBcc: d@e.f\"."
+        );
+        let email = EmailAddress::parse("\"a\\\nb\"@c.d", Some(ParsingOptions::new(true))).unwrap();
+        assert!(folder.fold_addresses("To", &[email]).is_err());
+        let email = EmailAddress::parse("\"a\r\n b\"@c.d", None).unwrap();
+        assert_eq!(
+            folder.fold_addresses("To", &[email]).unwrap(),
+            "To: \"a b\"@c.d"
+        );
+    }
+
+    #[test]
+    fn folds_address_lists_within_groups() {
+        let mailbox = |name: Option<&str>, address| {
+            Mailbox::new(name, EmailAddress::parse(address, None).unwrap())
+        };
+        let folder = HeaderFolder::new().line_length(40);
+        let addresses = [
+            Address::from(Group::new(
+                "Team",
+                vec![
+                    mailbox(Some("Alice A."), "alice@example.com"),
+                    mailbox(None, "bob@example.com"),
+                ],
+            )),
+            Address::from(mailbox(Some("Carol"), "carol@example.com")),
+            Address::from(Group::new("", Vec::new())),
+        ];
+        assert_eq!(
+            folder.fold_address_list("Cc", &addresses).unwrap(),
+            "Cc: Team:\r\n \"Alice A.\" <alice@example.com>,\r\n \
+             bob@example.com;,\r\n \
+             Carol <carol@example.com>, \"\":;"
+        );
+        assert_eq!(
+            folder
+                .fold_mailboxes("To", &[mailbox(None, "a@b.c"), mailbox(Some("D"), "d@e.f")])
+                .unwrap(),
+            "To: a@b.c, D <d@e.f>"
+        );
+        let unsafe_group = Address::from(Group::new("a\rb", Vec::new()));
+        assert!(folder.fold_address_list("To", &[unsafe_group]).is_err());
+    }
+
+    #[test]
+    fn encodes_non_ascii_display_names_as_configured() {
+        let mailbox = Mailbox::new(
+            Some("Jürgen"),
+            EmailAddress::parse("jurgen@example.com", None).unwrap(),
+        );
+        let addresses = [Address::from(Group::new("Équipe", vec![mailbox.clone()]))];
+        let folder = HeaderFolder::new();
+        assert_eq!(
+            folder.fold_address_list("To", &addresses).unwrap(),
+            "To: \"Équipe\": \"Jürgen\" <jurgen@example.com>;"
+        );
+        let folder = HeaderFolder {
+            encode_words: true,
+            ..folder
+        };
+        assert_eq!(
+            folder.fold_address_list("To", &addresses).unwrap(),
+            "To: =?UTF-8?B?w4lxdWlwZQ==?=: =?UTF-8?B?SsO8cmdlbg==?= <jurgen@example.com>;"
+        );
+        #[cfg(feature = "rfc2047")]
+        assert_eq!(
+            HeaderFolder::new()
+                .encode_words(true)
+                .fold_mailboxes("To", &[mailbox])
+                .unwrap(),
+            "To: =?UTF-8?B?SsO8cmdlbg==?= <jurgen@example.com>"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::canonical::canonical_domain;
+use crate::email_address::ParsingOptions;
+use crate::error::Hint;
+use alloc::string::String;
+
+/// Returns the input with the hyphens at the edges of the domain labels that the options allow
+/// replaced with letters, for the grammar, that rejects them, to validate the rest; or `None` if
+/// there is nothing to replace.
+///
+/// A hyphen is at an edge if it is not between two characters of the label; inside comments and
+/// domain literals, that are not made of labels, replacing one never changes the validity.
+pub(crate) fn mask_hyphens(
+    input: &str,
+    domain_start: usize,
+    options: &ParsingOptions,
+) -> Option<String> {
+    if !options.allow_leading_hyphen && !options.allow_trailing_hyphen {
+        return None;
+    }
+    let is_label_char = |byte: Option<&u8>| {
+        byte.is_some_and(|&byte| byte == b'-' || byte.is_ascii_alphanumeric() || byte >= 0x80)
+    };
+    let bytes = input.as_bytes();
+    let mut masked: Option<String> = None;
+    for index in domain_start..bytes.len() {
+        if bytes[index] != b'-' {
+            continue;
+        }
+        let is_leading = index == domain_start || !is_label_char(bytes.get(index - 1));
+        let is_trailing = !is_label_char(bytes.get(index + 1));
+        if (is_leading && options.allow_leading_hyphen && !is_trailing)
+            || (is_trailing && options.allow_trailing_hyphen && !is_leading)
+            || (is_leading
+                && is_trailing
+                && options.allow_leading_hyphen
+                && options.allow_trailing_hyphen)
+        {
+            let masked = masked.get_or_insert_with(|| String::from(input));
+            // A hyphen and a letter are both one byte long; hence, the spans are preserved.
+            masked.replace_range(index..index + 1, "a");
+        }
+    }
+    masked
+}
+
+/// Checks the labels of a (syntactically valid) domain against the strictness options.
+pub(crate) fn check_labels(domain: &str, options: &ParsingOptions) -> Result<(), Hint> {
+    if options.allow_double_hyphen
+        && options.allow_numeric_tld
+        && options.allow_numeric_labels
+        && options.allows_underscore()
+        && !options.rfc3696
+    {
+        return Ok(());
+    }
+    let domain = canonical_domain(domain);
+    if domain.starts_with('[') {
+        return Ok(());
+    }
+    let is_numeric = |label: &str| label.bytes().all(|byte| byte.is_ascii_digit());
+    for label in domain.split('.') {
+        if !options.allow_double_hyphen && label.contains("--") && !label.starts_with("xn--") {
+            return Err(Hint::DoubleHyphen);
+        }
+        if !options.allow_numeric_labels && is_numeric(label) {
+            return Err(Hint::NumericLabel);
+        }
+        if (!options.allows_underscore() || options.rfc3696) && label.contains('_') {
+            return Err(Hint::Underscore);
+        }
+        let is_host_name_character =
+            |c: char| c.is_ascii_alphanumeric() || c == '-' || !c.is_ascii();
+        if options.rfc3696 && !label.chars().all(is_host_name_character) {
+            return Err(Hint::InvalidLabelCharacter);
+        }
+    }
+    let top_level = domain.rsplit('.').next().unwrap_or_default();
+    if (!options.allow_numeric_tld || options.rfc3696) && is_numeric(top_level) {
+        return Err(Hint::NumericTopLevelDomain);
+    }
+    Ok(())
+}
+
+/// Returns whether a (syntactically valid) domain is a host name with an underscore, that RFC 5322
+/// allows, but RFC 1035 does not.
+pub(crate) fn has_underscore(domain: &str) -> bool {
+    let domain = canonical_domain(domain);
+    !domain.starts_with('[') && domain.contains('_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_only_the_allowed_hyphens() {
+        let leading = ParsingOptions::default().allow_leading_hyphen(true);
+        let trailing = ParsingOptions::default().allow_trailing_hyphen(true);
+        let both = leading.clone().allow_trailing_hyphen(true);
+        let input = "-a@-b-.c--d.-(-x-)[-]-";
+        assert_eq!(mask_hyphens(input, 3, &ParsingOptions::default()), None);
+        assert_eq!(
+            mask_hyphens(input, 3, &leading).unwrap(),
+            "-a@ab-.c--d.-(ax-)[-]-"
+        );
+        assert_eq!(
+            mask_hyphens(input, 3, &trailing).unwrap(),
+            "-a@-ba.c--d.-(-xa)[-]-"
+        );
+        assert_eq!(
+            mask_hyphens(input, 3, &both).unwrap(),
+            "-a@aba.c--d.a(axa)[a]a"
+        );
+    }
+
+    #[test]
+    fn checks_labels() {
+        let options = ParsingOptions::default()
+            .allow_double_hyphen(false)
+            .allow_numeric_tld(false);
+        assert_eq!(check_labels("xn--bcher-kva.de", &options), Ok(()));
+        assert_eq!(check_labels("a--b.de", &options), Err(Hint::DoubleHyphen));
+        assert_eq!(check_labels("123.de", &options), Ok(()));
+        assert_eq!(
+            check_labels("a.123", &options),
+            Err(Hint::NumericTopLevelDomain)
+        );
+        assert_eq!(check_labels("[1.2.3.4]", &options), Ok(()));
+        let options = options.allow_numeric_labels(false);
+        assert_eq!(check_labels("123.de", &options), Err(Hint::NumericLabel));
+        assert_eq!(check_labels("1a.de", &options), Ok(()));
+        assert_eq!(check_labels("my_host.de", &options), Ok(()));
+        let options = options.allow_underscore(false);
+        assert_eq!(check_labels("my_host.de", &options), Err(Hint::Underscore));
+        let options = ParsingOptions::new(true);
+        assert_eq!(check_labels("my_host.de", &options), Err(Hint::Underscore));
+        let options = options.allow_underscore(true);
+        assert_eq!(check_labels("my_host.de", &options), Ok(()));
+    }
+
+    #[test]
+    fn finds_underscores_in_host_names() {
+        assert!(has_underscore("my_host.internal.corp"));
+        assert!(!has_underscore("my-host.de (my_host)"));
+        assert!(!has_underscore("[my_host]"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+/// An `EmailAddress` that is parsed with lax options when it is read back from a storage.
+///
+/// By default, the database integrations parse the stored value strictly, and fail to decode
+/// a value that is not a valid address as per RFC 5322.
+/// Legacy data often contains addresses with obsolete parts; use this wrapper for such columns
+/// to decode them with `ParsingOptions::new(true)` instead.
+/// When written to a storage, it behaves exactly like the wrapped `EmailAddress`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, LaxEmailAddress};
+///
+/// let email = LaxEmailAddress::from(EmailAddress::new("foo", "bar.com", None).unwrap());
+/// assert_eq!(email.get_domain(), "bar.com");
+/// assert_eq!(format!("{}", email), "foo@bar.com");
+///
+/// let email: EmailAddress = email.into();
+/// assert_eq!(email.get_local_part(), "foo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct LaxEmailAddress(pub EmailAddress);
+
+impl Deref for LaxEmailAddress {
+    type Target = EmailAddress;
+
+    fn deref(&self) -> &EmailAddress {
+        &self.0
+    }
+}
+
+impl From<EmailAddress> for LaxEmailAddress {
+    fn from(email: EmailAddress) -> Self {
+        LaxEmailAddress(email)
+    }
+}
+
+impl From<LaxEmailAddress> for EmailAddress {
+    fn from(email: LaxEmailAddress) -> Self {
+        email.0
+    }
+}
+
+impl fmt::Display for LaxEmailAddress {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, formatter)
+    }
+}
+
+/// Parses an email address with lax options, i.e. `ParsingOptions::new(true)`, explaining the
+/// failure with a `ParseError`; as the database integrations do with the stored values.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{ErrorKind, LaxEmailAddress};
+///
+/// let email: LaxEmailAddress = "foo@bar.com".parse().unwrap();
+/// assert_eq!(email.get_domain(), "bar.com");
+///
+/// let error = "foo@-bar.com".parse::<LaxEmailAddress>().unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+/// ```
+impl FromStr for LaxEmailAddress {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        EmailAddress::try_parse(input, Some(ParsingOptions::new(true))).map(LaxEmailAddress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn parses_valid_addresses() {
+        let email: LaxEmailAddress = "foö@bücher.de".parse().unwrap();
+        assert_eq!(
+            email.0,
+            EmailAddress::new("foö", "bücher.de", None).unwrap()
+        );
+        let error = "foo@bar@baz.com".parse::<LaxEmailAddress>().unwrap_err();
+        assert_eq!(error.input(), "foo@bar@baz.com");
+        let error = "foo@-bar.com".parse::<LaxEmailAddress>().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn parses_obsolete_addresses() {
+        let value = "\u{0d}\u{0a} test@iana.org";
+        assert!(value.parse::<EmailAddress>().is_err());
+        let email: LaxEmailAddress = value.parse().unwrap();
+        assert_eq!(alloc::format!("{}", email), value);
+    }
+}
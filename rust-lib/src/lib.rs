@@ -17,12 +17,35 @@
 //! assert!(EmailAddress::parse("test@-iana.org", None).is_none());
 //! ```
 //!
+//! To learn why an input is not an email address, parse it with `str::parse` instead.
+//! The returned `ParseError` tells which part of the input is invalid, and if possible, how to fix it.
+//! ```
+//! use email_address_parser::*;
+//!
+//! let error = "test@-iana.org".parse::<EmailAddress>().unwrap_err();
+//! assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+//! assert_eq!(error.hint(), Some(Hint::MisplacedHyphen));
+//! ```
+//!
+//! The messages of the errors are in English; to show them in other languages, implement a
+//! `MessageProvider`, and display the errors with `ParseError::localize`.
+//!
 //! To parse an email address with obsolete parts (as per RFC 5322) in it, pass `None` as the second argument to have non-strict parsing.
 //! ```
 //! use email_address_parser::*;
 //!
+//! # #[cfg(not(feature = "strict-only"))] {
 //! let email = EmailAddress::parse("\u{0d}\u{0a} \u{0d}\u{0a} test@iana.org", Some(ParsingOptions::new(true)));
 //! assert!(email.is_some());
+//! # }
+//! ```
+//!
+//! ## `no_std` support
+//! The parser only needs an allocator; with the default `std` feature disabled, the crate is
+//! `no_std`, and relies on `alloc` instead. This drops the WASM panic hook and the
+//! `std::error::Error` implementation of `ParseError`, and the optional integrations need `std`.
+//! ```toml
+//! email-address-parser = { version = "1", default-features = false }
 //! ```
 //!
 //! ## Unicode support
@@ -35,11 +58,341 @@
 //! assert!(format!("{}", EmailAddress::parse("foö@bücher.de", None).unwrap()) == "foö@bücher.de");
 //! assert!(EmailAddress::is_valid("foö@bücher.de", None));
 //! ```
+//!
+//! ## Validating the parts separately
+//! `validate_local_part` and `validate_domain` validate the two parts of an address on their own;
+//! e.g. for a form with separate inputs.
+//! ```
+//! use email_address_parser::{validate_domain, validate_local_part, ParsingOptions};
+//!
+//! let options = ParsingOptions::default();
+//! assert!(validate_local_part("foo", &options).is_ok());
+//! assert!(validate_domain("-bar.com", &options).is_err());
+//! ```
+//!
+//! ## Tokenizing email addresses
+//! `tokenize` splits an input into the tokens of an email address, like atoms, dots, quoted strings, and
+//! comments, with their spans; e.g. for syntax highlighting. It never fails, so that it works on invalid and
+//! partial inputs as well.
+//! ```
+//! use email_address_parser::{tokenize, TokenKind};
+//!
+//! let token = tokenize("john@example.com").nth(2).unwrap();
+//! assert_eq!((token.kind(), token.span()), (TokenKind::DomainLabel, 5..12));
+//! ```
+//!
+//! A `ParsedAddress` structures those tokens into a tree of the segments of the local part and of the domain,
+//! and of the comments, with their ranges; e.g. for the hovers and quick fixes of editors.
+//!
+//! ## Finding email addresses in text
+//! A `Scanner` finds the email addresses in a free text, along with their positions.
+//! ```
+//! # #[cfg(not(feature = "strict-only"))] {
+//! use email_address_parser::Scanner;
+//!
+//! let found: Vec<_> = Scanner::new("Mail foo@bar.com, or baz@qux.org.").map(|m| m.as_str()).collect();
+//! assert_eq!(found, vec!["foo@bar.com", "baz@qux.org"]);
+//! # }
+//! ```
+//!
+//! ## Email address literals
+//! The `email!` macro validates an address literal at compile time, so that constant addresses
+//! cannot be typos.
+//! ```
+//! use email_address_parser::{email, EmailAddress};
+//!
+//! let support: EmailAddress = email!("support@example.com");
+//! assert_eq!(support.get_domain(), "example.com");
+//! ```
+//!
+//! ## Normalizing email addresses
+//! A `Normalizer` maps the different spellings of a mailbox to one form; e.g. to deduplicate a list.
+//! ```
+//! use email_address_parser::{EmailAddress, Normalizer};
+//!
+//! let normalizer = Normalizer::new().lowercase_local_part(true).strip_tags(true).provider_rules(true);
+//! let email = EmailAddress::parse("Jane.Doe+news@googlemail.com", None).unwrap();
+//! assert_eq!(normalizer.normalize(&email).to_string(), "janedoe@gmail.com");
+//! ```
+//!
+//! An `EqPolicy` configures the same relaxations once as the semantics of comparing addresses,
+//! shared by `EmailAddress::is_equivalent`, `CanonicalEmail::with_policy`, and the deduplication
+//! of `EmailAddressList`.
+//!
+//! ## Masking email addresses
+//! A `MaskPolicy` hides most of an address for display in user interfaces, logs, or support tools.
+//! ```
+//! use email_address_parser::{DomainMask, EmailAddress, MaskPolicy};
+//!
+//! let email = EmailAddress::parse("foo@example.com", None).unwrap();
+//! let policy = MaskPolicy::new().domain(DomainMask::Labels);
+//! assert_eq!(email.mask(&policy), "f**@e******.com");
+//! ```
+//!
+//! ## Sanitizing email addresses
+//! `EmailAddress::sanitize` turns an accepted input into the form to use in an SMTP envelope,
+//! checked against the length limits of RFC 5321.
+//! ```
+//! use email_address_parser::EmailAddress;
+//!
+//! let email = EmailAddress::parse("\"foo\"@BAR.com", None).unwrap();
+//! assert_eq!(email.sanitize().unwrap().to_string(), "foo@bar.com");
+//! ```
+//!
+//! ## Writing email addresses into headers
+//! An address may contain line breaks in its comments or quoted strings, that could inject headers
+//! into a message. `EmailAddress::header_safe` returns a form that is safe to write, or `None`.
+//! ```
+//! use email_address_parser::EmailAddress;
+//!
+//! let email = EmailAddress::parse("\"foo\r\n bar\"@bar.com", None).unwrap();
+//! assert!(!email.is_header_safe());
+//! assert_eq!(email.header_safe().unwrap().to_string(), "\"foo bar\"@bar.com");
+//! ```
+//!
+//! A `Mailbox` pairs an address with a display name, quoted or encoded as needed.
+//! ```
+//! use email_address_parser::{EmailAddress, Mailbox};
+//!
+//! let mailbox = Mailbox::new(Some("Doe, Jane"), EmailAddress::parse("jane@bar.com", None).unwrap());
+//! assert_eq!(mailbox.to_header_value().unwrap(), "\"Doe, Jane\" <jane@bar.com>");
+//! ```
+//!
+//! A `HeaderFolder` writes a header field of many addresses, folded at 78 characters.
+//! ```
+//! use email_address_parser::{EmailAddress, HeaderFolder};
+//!
+//! let to = [EmailAddress::parse("foo@bar.com", None).unwrap()];
+//! assert_eq!(HeaderFolder::new().fold_addresses("To", &to).unwrap(), "To: foo@bar.com");
+//! ```
+//!
+//! ## `mailto:` URIs
+//! `EmailAddress::to_uri` and `Mailto` write `mailto:` URIs as per RFC 6068, percent-encoded as needed.
+//! ```
+//! use email_address_parser::{EmailAddress, Mailto};
+//!
+//! let email = EmailAddress::parse("foo@bar.com", None).unwrap();
+//! assert_eq!(email.to_uri(), "mailto:foo@bar.com");
+//! assert_eq!(Mailto::new(email).subject("Hi there").to_string(), "mailto:foo@bar.com?subject=Hi%20there");
+//! ```
+//!
+//! ## Untrusted input
+//! Parsing never panics, whatever the input is; an input that is not an email address is only ever
+//! reported as such. This is verified by the fuzz targets in the `fuzz` directory, and any panic
+//! found by them is a bug.
+//!
+//! ## Optional features
+//! - `serde`: implements `Serialize` and `Deserialize` for `EmailAddress`, using its string representation,
+//!   for `EmailAddressList`, as a sequence of addresses, and for `ParsingOptions`; and `Deserialize` for
+//!   `Policy`, from its configuration.
+//! - `arbitrary`: implements `Arbitrary` for `EmailAddress`, and provides `AddressCandidate` for
+//!   structure-aware fuzzing.
+//! - `async`: provides `ParseEmailStream`, parsing the strings of a `futures::Stream` as email addresses with
+//!   bounded concurrency, e.g. in asynchronous ETL services. This works with `no_std` as well.
+//! - `clap`: provides `EmailAddressValueParser` to take `EmailAddress` arguments with a choice of strictness.
+//! - `csv`: provides `CsvImporter`, importing the addresses of a column of a CSV into a report of the accepted,
+//!   repaired, and rejected rows.
+//! - `diesel`: implements diesel's `ToSql` and `FromSql` for `EmailAddress` and `LaxEmailAddress`, stored as `Text`.
+//!   The SQLite backend is supported with the `diesel-sqlite` feature.
+//! - `fixtures`: provides the `fixtures` module, exporting a categorized corpus of valid, obsolete, and invalid
+//!   addresses as JSON fixtures, for other projects to test against the same expectations.
+//! - `generator`: provides the `generator` module generating realistic random addresses with RFC 2606 domains.
+//! - `hash`: provides `EmailAddress::gravatar_hash` and `EmailAddress::sha256_hash`, hashing the lower cased
+//!   canonical form of the address, and `KeyedHasher` (with `EmailAddress::hmac_sha256`), mapping addresses to
+//!   pseudonymous identifiers with a secret key, and `Pseudonymizer`, de-identifying addresses into tokens that
+//!   a `PseudonymStore` can re-identify. This works with `no_std` as well.
+//! - `heapless`: stores the local part and the domain of `EmailAddress` in fixed-capacity buffers of
+//!   `LOCAL_PART_CAPACITY` and `DOMAIN_CAPACITY` octets (the limits of RFC 5321), instead of on the heap.
+//!   A longer part fails to parse with `ErrorKind::LocalPartTooLong` or `ErrorKind::DomainTooLong`.
+//!   This works with `no_std` as well.
+//! - `idna`: enables `Normalizer::punycode`, converting internationalized domains to their ASCII form, and
+//!   `EmailAddress::domain_ascii`, `EmailAddress::domain_unicode`, and `EmailAddress::domain_eq_idna`.
+//! - `log`: logs the repairs and the failures of the parses, and the rejections of `Policy::validate`, at the
+//!   debug and trace levels; e.g. to find out why the rows of an import were dropped. The inputs are redacted
+//!   unless `set_redaction(false)` is called. This works with `no_std` as well.
+//! - `miette`: implements `miette::Diagnostic` for `ParseError`, labeling the offending part of the input, with
+//!   the hint as help; e.g. for the error reports of command-line tools.
+//! - `postgres-types`: implements `ToSql` and `FromSql` of [postgres-types](https://crates.io/crates/postgres-types)
+//!   for `EmailAddress` and `LaxEmailAddress`, stored as text. This makes them usable with `postgres`, and `tokio-postgres`.
+//! - `proptest`: provides the `strategy` module generating valid and near-miss addresses, and implements
+//!   proptest's `Arbitrary` for `EmailAddress`.
+//! - `quickcheck`: implements quickcheck's `Arbitrary` for `EmailAddress`, shrinking toward shorter addresses.
+//! - `rfc2047`: provides `Mailbox::to_encoded_header_value` and `HeaderFolder::encode_words`, writing the
+//!   non-ASCII display names as RFC 2047 encoded words, instead of quoted strings as per RFC 6532; for recipients
+//!   that do not support the latter.
+//! - `rusqlite`: implements rusqlite's `ToSql` and `FromSql` for `EmailAddress` and `LaxEmailAddress`, stored as `TEXT`.
+//! - `schemars`: implements `JsonSchema` for `EmailAddress`, describing it as a string with the `email` format;
+//!   a loose `pattern` can be added per field, with `EmailAddress::json_schema_with_pattern`.
+//! - `sqlx`: implements `sqlx::Type`, `Encode`, and `Decode` for `EmailAddress` and `LaxEmailAddress`, stored as text.
+//! - `strict-only`: compiles the strict grammar only, dropping the rules of the obsolete syntax, and the `Scanner`
+//!   (with `EmailParser::find_all` and `AsyncScanner`); e.g. for WASM or embedded builds that never parse in lax
+//!   mode, to shrink the grammar tables and the code. The other relaxations of lax parsing remain, but the obsolete
+//!   syntax is invalid, and never hinted at, as if it were not an address at all. Unlike the other features, this
+//!   one removes functionality; hence, it is for final builds only, rather than for libraries.
+//! - `tokio`: provides `AsyncScanner`, finding the email addresses in a `tokio::io::AsyncBufRead`, like a file or
+//!   a socket, line by line; e.g. for the services that tail logs or ingest uploads without blocking the runtime.
+//! - `tracing`: records a span for each parse and each `Policy::validate`, with events for the repairs and the
+//!   failures, at the debug and trace levels. The inputs are redacted unless `set_redaction(false)` is called.
+//!   This works with `no_std` as well.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 #[macro_use]
 extern crate pest_derive;
+// The tests print, and build vectors and strings, with the macros of `std` in any case.
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
 
+mod address_literal;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_support;
+#[cfg(feature = "async")]
+mod async_support;
+mod autocomplete;
+mod bulk;
+mod bytes;
+mod canonical;
+mod canonical_email;
+#[cfg(feature = "clap")]
+mod clap_support;
+#[cfg(feature = "csv")]
+mod csv_support;
+mod diagnostic;
+mod dictionary;
+#[cfg(feature = "diesel")]
+mod diesel_support;
+mod domain_set;
 mod email_address;
+mod eq_policy;
+mod error;
+#[cfg(any(feature = "tracing", feature = "log"))]
+mod events;
+mod example;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+#[cfg(feature = "generator")]
+pub mod generator;
+mod grammar;
+#[cfg(feature = "std")]
+mod harvest;
+#[cfg(feature = "hash")]
+mod hash_support;
+mod header;
+mod labels;
+mod lax;
+mod list;
+mod literal;
+mod mailbox;
+mod mailto;
+mod mask;
+mod messages;
+#[cfg(feature = "miette")]
+mod miette_support;
+mod no_reply;
+mod normalize;
+mod ordering;
+mod parsed;
+mod parser;
+mod partial;
+mod pattern;
+mod placeholder;
+mod policy;
+#[cfg(feature = "postgres-types")]
+mod postgres_support;
+mod prefilter;
+mod prefix;
+#[cfg(feature = "hash")]
+mod pseudonymize;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_support;
+#[cfg(any(feature = "tracing", feature = "log"))]
+mod redact;
+mod repair;
+mod restrictions;
+mod routing;
+mod rules;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_support;
+mod sanitize;
+#[cfg(not(feature = "strict-only"))]
+mod scanner;
+#[cfg(feature = "schemars")]
+mod schemars_support;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "sqlx")]
+mod sqlx_support;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+mod suggest;
+mod tokens;
+#[cfg(all(feature = "tokio", not(feature = "strict-only")))]
+mod tokio_support;
+mod validate;
+
+pub use self::address_literal::AddressLiteral;
+#[cfg(feature = "arbitrary")]
+pub use self::arbitrary_support::AddressCandidate;
+#[cfg(feature = "async")]
+pub use self::async_support::{ParseEmailStream, ParseJob, ParsedEmailStream};
+pub use self::autocomplete::complete_domain;
+pub use self::bulk::{ParseEmails, ParsedEmails, ValidEmails};
+pub use self::bytes::InvalidUtf8;
+pub use self::canonical::{needs_quoting, quote_local_part};
+pub use self::canonical_email::CanonicalEmail;
+#[cfg(feature = "clap")]
+pub use self::clap_support::EmailAddressValueParser;
+#[cfg(feature = "csv")]
+pub use self::csv_support::{CsvImportError, CsvImporter, ImportReport, ImportedRow, RejectedRow};
+pub use self::diagnostic::Diagnostic;
+pub use self::dictionary::{DISPOSABLE_DOMAINS, POPULAR_DOMAINS};
+pub use self::domain_set::DomainSet;
 #[doc(inline)]
 pub use self::email_address::EmailAddress;
 pub use self::email_address::ParsingOptions;
+pub use self::email_address::{DOMAIN_CAPACITY, LOCAL_PART_CAPACITY};
+pub use self::eq_policy::EqPolicy;
+pub use self::error::{ErrorKind, Hint, ParseError};
+pub use self::example::ExampleAddresses;
+pub use self::grammar::{parse_rule, RuleKind};
+#[cfg(feature = "std")]
+pub use self::harvest::{HarvestSource, Harvested, Harvester};
+#[cfg(feature = "hash")]
+pub use self::hash_support::KeyedHasher;
+pub use self::header::{scan_header_block, HeaderError, HeaderFolder, ADDRESS_HEADERS};
+pub use self::lax::LaxEmailAddress;
+pub use self::list::EmailAddressList;
+#[doc(hidden)]
+pub use self::literal::{from_literal as __from_literal, literal_separator as __literal_separator};
+pub use self::mailbox::{Address, Group, Mailbox};
+pub use self::mailto::Mailto;
+pub use self::mask::{DomainMask, MaskPolicy};
+pub use self::messages::{EnglishMessages, LocalizedError, MessageProvider};
+pub use self::no_reply::NoReplyAddresses;
+pub use self::normalize::Normalizer;
+pub use self::ordering::group_by_domain;
+pub use self::parsed::{AddressPart, Comment, ParsedAddress, Segment, SegmentKind};
+pub use self::parser::EmailParser;
+#[doc(hidden)]
+pub use self::partial::WasmPartialValidation;
+pub use self::partial::{validate_partial, Expecting, PartialValidation};
+pub use self::pattern::AddressPattern;
+pub use self::placeholder::{PlaceholderHeuristics, PlaceholderSignal};
+pub use self::policy::{Policy, PolicyError};
+#[cfg(feature = "hash")]
+pub use self::pseudonymize::{PseudonymStore, Pseudonymizer};
+#[cfg(any(feature = "tracing", feature = "log"))]
+pub use self::redact::set_redaction;
+pub use self::rules::{
+    DisposableDomains, DomainDenylist, DomainTypos, RequireDomains, RequireTopLevelDomain,
+    RoleAccounts, ValidationRule, Violation,
+};
+#[cfg(not(feature = "strict-only"))]
+pub use self::scanner::{AddressMatch, MultipleAt, Scanner};
+pub use self::suggest::suggest;
+pub use self::tokens::{tokenize, Token, TokenKind, Tokens};
+#[cfg(all(feature = "tokio", not(feature = "strict-only")))]
+pub use self::tokio_support::{AsyncScanner, FoundAddress};
+pub use self::validate::{validate_domain, validate_local_part};
@@ -0,0 +1,296 @@
+use crate::email_address::EmailAddress;
+use crate::eq_policy::EqPolicy;
+use crate::normalize::Normalizer;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+/// A list of distinct email addresses, in order of insertion; e.g. a mailing list.
+///
+/// Addresses are told apart by their normalized forms (see `Normalizer`), which are their canonical
+/// forms by default; inserting an address that is already in the list keeps the first one.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, EmailAddressList, Normalizer};
+///
+/// let mut list = EmailAddressList::new();
+/// assert!(list.insert(EmailAddress::parse("jane@example.com", None).unwrap()));
+/// assert!(!list.insert(EmailAddress::parse("jane@EXAMPLE.com", None).unwrap()));
+/// assert!(list.insert(EmailAddress::parse("Jane@example.com", None).unwrap()));
+/// assert_eq!(list.len(), 2);
+///
+/// let mut list = EmailAddressList::with_normalizer(Normalizer::new().lowercase_local_part(true));
+/// list.extend(["jane@example.com", "Jane@example.com"].iter().filter_map(|input| EmailAddress::parse(input, None)));
+/// assert_eq!(list.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailAddressList {
+    normalizer: Normalizer,
+    addresses: Vec<EmailAddress>,
+    /// The normalized forms of the addresses.
+    keys: BTreeSet<String>,
+}
+
+impl EmailAddressList {
+    /// Instantiates an empty list, that tells addresses apart by their canonical forms.
+    pub fn new() -> Self {
+        EmailAddressList::default()
+    }
+
+    /// Instantiates an empty list, that tells addresses apart by their forms normalized by the
+    /// given normalizer.
+    pub fn with_normalizer(normalizer: Normalizer) -> Self {
+        EmailAddressList {
+            normalizer,
+            ..EmailAddressList::default()
+        }
+    }
+
+    /// Instantiates an empty list, that tells addresses apart as per the given comparison policy.
+    pub fn with_policy(policy: EqPolicy) -> Self {
+        EmailAddressList::with_normalizer(policy.normalizer())
+    }
+
+    fn key(&self, email: &EmailAddress) -> String {
+        self.normalizer.normalize(email).to_string()
+    }
+
+    /// Inserts an address at the end of the list, unless it is already in it; returns whether it
+    /// was inserted.
+    pub fn insert(&mut self, email: EmailAddress) -> bool {
+        let inserted = self.keys.insert(self.key(&email));
+        if inserted {
+            self.addresses.push(email);
+        }
+        inserted
+    }
+
+    /// Removes an address from the list; returns whether it was in it.
+    pub fn remove(&mut self, email: &EmailAddress) -> bool {
+        let key = self.key(email);
+        let removed = self.keys.remove(&key);
+        if removed {
+            let normalizer = self.normalizer;
+            self.addresses
+                .retain(|address| normalizer.normalize(address).to_string() != key);
+        }
+        removed
+    }
+
+    /// Returns whether an address is in the list.
+    pub fn contains(&self, email: &EmailAddress) -> bool {
+        self.keys.contains(&self.key(email))
+    }
+
+    /// Returns the number of addresses in the list.
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Returns whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Returns an iterator over the addresses, in order.
+    pub fn iter(&self) -> core::slice::Iter<'_, EmailAddress> {
+        self.addresses.iter()
+    }
+
+    /// Returns the addresses, in order.
+    pub fn as_slice(&self) -> &[EmailAddress] {
+        &self.addresses
+    }
+
+    /// Sorts the addresses by their normalized domains, and then by their normalized local parts.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, EmailAddressList};
+    ///
+    /// let mut list: EmailAddressList = ["b@x.org", "a@y.org", "a@X.org"]
+    ///     .iter()
+    ///     .filter_map(|input| EmailAddress::parse(input, None))
+    ///     .collect();
+    /// list.sort();
+    /// let sorted: Vec<_> = list.iter().map(ToString::to_string).collect();
+    /// assert_eq!(sorted, ["a@X.org", "b@x.org", "a@y.org"]);
+    /// ```
+    pub fn sort(&mut self) {
+        let normalizer = self.normalizer;
+        self.addresses.sort_by_cached_key(|email| {
+            let normalized = normalizer.normalize(email);
+            (
+                String::from(normalized.get_domain()),
+                String::from(normalized.get_local_part()),
+            )
+        });
+    }
+
+    /// Groups the addresses by their normalized domains; the addresses of each domain are in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, EmailAddressList};
+    ///
+    /// let list: EmailAddressList = ["a@x.org", "b@y.org", "c@X.org"]
+    ///     .iter()
+    ///     .filter_map(|input| EmailAddress::parse(input, None))
+    ///     .collect();
+    /// let groups = list.group_by_domain();
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(groups["x.org"].len(), 2);
+    /// ```
+    pub fn group_by_domain(&self) -> BTreeMap<String, Vec<&EmailAddress>> {
+        let mut groups: BTreeMap<String, Vec<&EmailAddress>> = BTreeMap::new();
+        for email in &self.addresses {
+            let domain = String::from(self.normalizer.normalize(email).get_domain());
+            groups.entry(domain).or_default().push(email);
+        }
+        groups
+    }
+
+    /// Returns the addresses of this list followed by the other addresses of the other list.
+    pub fn union(&self, other: &EmailAddressList) -> EmailAddressList {
+        let mut union = self.clone();
+        union.extend(other.iter().cloned());
+        union
+    }
+
+    /// Returns the addresses of this list that are also in the other list, in order.
+    pub fn intersection(&self, other: &EmailAddressList) -> EmailAddressList {
+        self.retained(other, true)
+    }
+
+    /// Returns the addresses of this list that are not in the other list, in order; e.g. to apply
+    /// a suppression list.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, EmailAddressList};
+    ///
+    /// let parse = |inputs: &[&str]| -> EmailAddressList {
+    ///     inputs.iter().filter_map(|input| EmailAddress::parse(input, None)).collect()
+    /// };
+    /// let recipients = parse(&["jane@example.com", "john@example.com"]);
+    /// let suppressed = parse(&["john@EXAMPLE.COM"]);
+    /// let difference = recipients.difference(&suppressed);
+    /// assert_eq!(difference.as_slice(), parse(&["jane@example.com"]).as_slice());
+    /// ```
+    pub fn difference(&self, other: &EmailAddressList) -> EmailAddressList {
+        self.retained(other, false)
+    }
+
+    /// Returns the addresses of this list that are in the other list or not, as per `is_in_other`;
+    /// both lists are compared by the normalizer of this list.
+    fn retained(&self, other: &EmailAddressList, is_in_other: bool) -> EmailAddressList {
+        let other_keys: BTreeSet<String> = other.iter().map(|email| self.key(email)).collect();
+        let mut retained = EmailAddressList::with_normalizer(self.normalizer);
+        retained.extend(
+            self.iter()
+                .filter(|email| other_keys.contains(&self.key(email)) == is_in_other)
+                .cloned(),
+        );
+        retained
+    }
+}
+
+impl Extend<EmailAddress> for EmailAddressList {
+    fn extend<I: IntoIterator<Item = EmailAddress>>(&mut self, iter: I) {
+        for email in iter {
+            self.insert(email);
+        }
+    }
+}
+
+impl FromIterator<EmailAddress> for EmailAddressList {
+    fn from_iter<I: IntoIterator<Item = EmailAddress>>(iter: I) -> Self {
+        let mut list = EmailAddressList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl IntoIterator for EmailAddressList {
+    type Item = EmailAddress;
+    type IntoIter = alloc::vec::IntoIter<EmailAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.addresses.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EmailAddressList {
+    type Item = &'a EmailAddress;
+    type IntoIter = core::slice::Iter<'a, EmailAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.addresses.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(inputs: &[&str]) -> EmailAddressList {
+        inputs
+            .iter()
+            .map(|input| EmailAddress::parse(input, None).unwrap())
+            .collect()
+    }
+
+    fn strings(list: &EmailAddressList) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn deduplicates_by_normalized_form() {
+        let mut emails = list(&["jane@x.org", "\"jane\"@X.ORG", "jane+news@x.org"]);
+        assert_eq!(strings(&emails), ["jane@x.org", "jane+news@x.org"]);
+        assert!(emails.contains(&EmailAddress::parse("jane@x.Org", None).unwrap()));
+
+        assert!(emails.remove(&EmailAddress::parse("\"jane\"@x.org", None).unwrap()));
+        assert!(!emails.remove(&EmailAddress::parse("jane@x.org", None).unwrap()));
+        assert_eq!(strings(&emails), ["jane+news@x.org"]);
+
+        let mut emails = EmailAddressList::with_normalizer(Normalizer::new().strip_tags(true));
+        emails.extend(list(&["jane@x.org", "jane+news@x.org"]));
+        assert_eq!(strings(&emails), ["jane@x.org"]);
+        let mut emails = EmailAddressList::with_policy(EqPolicy::new().ci_local_part(true));
+        emails.extend(list(&["jane@x.org", "Jane@x.org"]));
+        assert_eq!(strings(&emails), ["jane@x.org"]);
+    }
+
+    #[test]
+    fn sorts_and_groups_by_domain() {
+        let mut emails = list(&["b@y.org", "c@X.org", "a@y.org", "d@x.org"]);
+        let groups = emails.group_by_domain();
+        assert_eq!(groups.keys().collect::<Vec<_>>(), ["x.org", "y.org"]);
+        assert_eq!(
+            groups["x.org"],
+            [&emails.as_slice()[1], &emails.as_slice()[3]]
+        );
+
+        emails.sort();
+        assert_eq!(
+            strings(&emails),
+            ["c@X.org", "d@x.org", "a@y.org", "b@y.org"]
+        );
+    }
+
+    #[test]
+    fn set_operations_keep_the_order_of_the_list() {
+        let left = list(&["c@x.org", "a@x.org", "b@x.org"]);
+        let right = list(&["b@X.org", "d@x.org", "c@x.org"]);
+        assert_eq!(
+            strings(&left.union(&right)),
+            ["c@x.org", "a@x.org", "b@x.org", "d@x.org"]
+        );
+        assert_eq!(strings(&left.intersection(&right)), ["c@x.org", "b@x.org"]);
+        assert_eq!(strings(&left.difference(&right)), ["a@x.org"]);
+        assert!(left.difference(&left).is_empty());
+    }
+}
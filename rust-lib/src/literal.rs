@@ -0,0 +1,274 @@
+use crate::email_address::{EmailAddress, DOMAIN_CAPACITY, LOCAL_PART_CAPACITY};
+
+/// Expands a string literal (or a `&str` constant) to an `EmailAddress`, failing to compile if it
+/// is not a valid email address.
+///
+/// The literal is validated at compile time, and the expansion cannot fail at run time; hence,
+/// constant addresses in configurations or tests cannot be typos.
+///
+/// Only the common forms of addresses are supported: the local part has to be a dot-atom, or
+/// a quoted string, and the domain has to be a host name, or a domain literal, without comments or
+/// folding white space. The parts also have to be within the length limits of RFC 5321
+/// (`LOCAL_PART_CAPACITY` and `DOMAIN_CAPACITY`). Use `EmailAddress::parse` for anything else.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{email, EmailAddress};
+///
+/// let email: EmailAddress = email!("foo@bar.com");
+/// assert_eq!(email.get_local_part(), "foo");
+/// assert_eq!(email.get_domain(), "bar.com");
+///
+/// const SUPPORT: &str = "\"support team\"@example.org";
+/// assert_eq!(email!(SUPPORT).get_local_part(), "\"support team\"");
+/// ```
+///
+/// An invalid address does not compile.
+/// ```compile_fail
+/// use email_address_parser::email;
+///
+/// let email = email!("foo@-bar.com");
+/// ```
+#[macro_export]
+macro_rules! email {
+    ($address:expr) => {{
+        const ADDRESS: &str = $address;
+        const _: () = assert!(
+            $crate::__literal_separator(ADDRESS).is_some(),
+            "invalid email address literal"
+        );
+        $crate::__from_literal(ADDRESS)
+    }};
+}
+
+/// Returns the byte index of the `@` separating the local part and the domain of a literal, if the
+/// literal is a valid email address.
+///
+/// Being a `const fn`, this cannot use the grammar; it accepts a conservative subset of strict
+/// parsing instead: a dot-atom or a quoted string without line breaks as the local part, and a
+/// dot-atom or a domain literal without line breaks as the domain, without any comments or white
+/// space around them. Every literal it accepts is valid as per strict parsing.
+#[doc(hidden)]
+pub const fn literal_separator(address: &str) -> Option<usize> {
+    let bytes = address.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+    let is_quoted = bytes[0] == b'"';
+    let at = if is_quoted {
+        match quoted_string_end(bytes) {
+            Some(end) => end,
+            None => return None,
+        }
+    } else {
+        let mut index = 0;
+        while index < bytes.len() && bytes[index] != b'@' {
+            index += 1;
+        }
+        index
+    };
+    if at >= bytes.len() || bytes[at] != b'@' || at > LOCAL_PART_CAPACITY {
+        return None;
+    }
+    if !is_quoted && !is_dot_atom(bytes, 0, at) {
+        return None;
+    }
+    let domain = at + 1;
+    if bytes.len() - domain > DOMAIN_CAPACITY {
+        return None;
+    }
+    let is_valid_domain = if domain < bytes.len() && bytes[domain] == b'[' {
+        is_domain_literal(bytes, domain)
+    } else {
+        is_dot_atom(bytes, domain, bytes.len())
+    };
+    if is_valid_domain {
+        Some(at)
+    } else {
+        None
+    }
+}
+
+/// Instantiates an `EmailAddress` from a literal that is validated by `literal_separator`.
+#[doc(hidden)]
+pub fn from_literal(address: &str) -> EmailAddress {
+    let at = literal_separator(address).expect("the literal is validated by `email!`");
+    EmailAddress::__new_unchecked(&address[..at], &address[at + 1..])
+}
+
+const fn is_atext(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric()
+        || matches!(
+            byte,
+            b'!' | b'#'
+                | b'$'
+                | b'%'
+                | b'&'
+                | b'\''
+                | b'*'
+                | b'+'
+                | b'-'
+                | b'/'
+                | b'='
+                | b'?'
+                | b'^'
+                | b'_'
+                | b'`'
+                | b'{'
+                | b'|'
+                | b'}'
+                | b'~'
+        )
+        // A byte of a non-ASCII character, as the input is valid UTF-8.
+        || byte >= 0x80
+}
+
+const fn is_printable(byte: u8) -> bool {
+    matches!(byte, 0x21..=0x7e)
+}
+
+const fn is_white_space(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+/// Returns whether the bytes in the range are dot separated atoms, none of which starts or ends
+/// with a hyphen.
+pub(crate) const fn is_dot_atom(bytes: &[u8], start: usize, end: usize) -> bool {
+    let mut atom_start = start;
+    let mut index = start;
+    while index <= end {
+        if index == end || bytes[index] == b'.' {
+            if index == atom_start || bytes[atom_start] == b'-' || bytes[index - 1] == b'-' {
+                return false;
+            }
+            atom_start = index + 1;
+        } else if !is_atext(bytes[index]) {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Returns the byte index after the closing quote of the quoted string at the start of the bytes.
+const fn quoted_string_end(bytes: &[u8]) -> Option<usize> {
+    let mut index = 1;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte == b'"' {
+            return Some(index + 1);
+        } else if byte == b'\\' {
+            if index + 1 < bytes.len()
+                && (is_printable(bytes[index + 1]) || is_white_space(bytes[index + 1]))
+            {
+                index += 2;
+                continue;
+            }
+            return None;
+        } else if !(is_printable(byte) || is_white_space(byte) || byte >= 0x80) {
+            return None;
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Returns whether the bytes from the start are a domain literal.
+const fn is_domain_literal(bytes: &[u8], start: usize) -> bool {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte == b']' {
+            return index == bytes.len() - 1;
+        } else if byte == b'['
+            || byte == b'\\'
+            || !(is_printable(byte) || is_white_space(byte) || byte >= 0x80)
+        {
+            return false;
+        }
+        index += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn accepts_common_addresses() {
+        for address in &[
+            "foo@bar.com",
+            "foo.bar+baz@bar-baz.co.uk",
+            "foö@bücher.de",
+            "\"john doe\"@example.org",
+            "\"a\\\"b@c\"@example.org",
+            "\"\"@example.org",
+            "x@[127.0.0.1]",
+            "x@[IPv6:::1]",
+            "a--b@c--d.com",
+        ] {
+            let at = literal_separator(address).unwrap();
+            let email = EmailAddress::parse(address, None).unwrap();
+            assert_eq!(email.get_local_part(), &address[..at]);
+            assert_eq!(email.get_domain(), &address[at + 1..]);
+            assert_eq!(from_literal(address), email);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_and_unsupported_addresses() {
+        for address in &[
+            "",
+            "foo",
+            "@bar.com",
+            "foo@",
+            "foo@-bar.com",
+            "foo@bar-.com",
+            "-foo@bar.com",
+            ".foo@bar.com",
+            "foo..bar@bar.com",
+            "foo@bar..com",
+            "foo@bar.com.",
+            "\"foo@bar.com",
+            "\"foo\"bar@bar.com",
+            "foo@[1.2.3.4",
+            "foo@[1.2].com",
+            "foo@[1[2]",
+            "(comment)foo@bar.com",
+            " foo@bar.com",
+            "foo@bar.com ",
+            "a@b@c.com",
+        ] {
+            assert_eq!(literal_separator(address), None, "{}", address);
+        }
+        let long_local_part = format!("{}@bar.com", "a".repeat(LOCAL_PART_CAPACITY + 1));
+        assert_eq!(literal_separator(&long_local_part), None);
+    }
+
+    /// Every short combination of the significant characters is either rejected, or valid as per
+    /// strict parsing with the same parts.
+    #[test]
+    fn accepts_only_valid_addresses() {
+        let alphabet = ["a", "-", ".", "@", "\"", "\\", "[", "]", " ", "ö", "("];
+        let mut inputs = vec![String::new()];
+        for _ in 0..5 {
+            let mut longer = Vec::new();
+            for input in &inputs {
+                for c in &alphabet {
+                    longer.push(format!("{}{}", input, c));
+                }
+            }
+            for input in &longer {
+                if let Some(at) = literal_separator(input) {
+                    let email = EmailAddress::parse(input, None)
+                        .unwrap_or_else(|| panic!("{:?} is accepted", input));
+                    assert_eq!(email.get_local_part(), &input[..at]);
+                }
+            }
+            inputs = longer;
+        }
+    }
+}
@@ -0,0 +1,674 @@
+use crate::diagnostic::Diagnostic;
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::{ErrorKind, ParseError};
+use crate::header::{is_header_safe, HeaderError};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// An email address with an optional display name; e.g. `Jane Doe <jane@example.com>`, as in the
+/// address headers of a message.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, Mailbox};
+///
+/// let address = EmailAddress::parse("jane@example.com", None).unwrap();
+/// let mailbox = Mailbox::new(Some("Doe, Jane"), address);
+/// assert_eq!(mailbox.to_header_value().unwrap(), "\"Doe, Jane\" <jane@example.com>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mailbox {
+    display_name: Option<String>,
+    address: EmailAddress,
+}
+
+impl Mailbox {
+    /// Instantiates a mailbox; an empty display name is the same as none.
+    pub fn new(display_name: Option<&str>, address: EmailAddress) -> Self {
+        Mailbox {
+            display_name: display_name
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from),
+            address,
+        }
+    }
+
+    /// Parses a mailbox, as in an address header; i.e. an address, or a display name followed by
+    /// an address in angle brackets, like `"Doe, Jane" <jane@example.com>`.
+    ///
+    /// The display name is unquoted, without comments, and with its white space collapsed. An
+    /// invalid address is reported by the error of parsing it alone.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::Mailbox;
+    ///
+    /// let mailbox = Mailbox::parse("\"Doe, Jane\" (work) <jane@example.com>", None).unwrap();
+    /// assert_eq!(mailbox.display_name(), Some("Doe, Jane"));
+    /// assert_eq!(mailbox.address().to_string(), "jane@example.com");
+    ///
+    /// let mailbox = Mailbox::parse("jane@example.com", None).unwrap();
+    /// assert_eq!(mailbox.display_name(), None);
+    ///
+    /// assert!(Mailbox::parse("Jane <jane@example.com", None).is_err());
+    /// ```
+    pub fn parse(input: &str, options: Option<ParsingOptions>) -> Result<Mailbox, ParseError> {
+        let input = input.trim();
+        let characters = top_level(input);
+        let opening = characters.iter().find(|&&(_, c)| c == '<');
+        let (display_name, address) = match opening {
+            Some(&(opening, _)) => {
+                let closing = characters
+                    .iter()
+                    .find(|&&(index, c)| index > opening && c == '>')
+                    .map(|&(index, _)| index)
+                    .filter(|&closing| phrase_text(&input[closing + 1..]).is_empty())
+                    .ok_or_else(|| {
+                        ParseError::new(ErrorKind::Invalid, input, opening..input.len(), None)
+                    })?;
+                let display_name = phrase_text(&input[..opening]);
+                (Some(display_name), &input[opening + 1..closing])
+            }
+            None => (None, input),
+        };
+        let address = EmailAddress::try_parse(address, options)?;
+        Ok(Mailbox::new(display_name.as_deref(), address))
+    }
+
+    /// Returns the display name, if there is one.
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// Returns the email address.
+    pub fn address(&self) -> &EmailAddress {
+        &self.address
+    }
+
+    /// Returns the mailbox as it should be written into an outgoing header.
+    ///
+    /// The address is written in its header safe form (see `EmailAddress::header_safe`). The
+    /// display name is written as it is if it only consists of atoms, and as a quoted string if it
+    /// contains specials, like commas or dots, or non-ASCII characters, as per RFC 6532; see
+    /// `to_encoded_header_value` for RFC 2047 encoded words instead. A display name or an address
+    /// that could break the header is refused.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, Mailbox};
+    ///
+    /// let address = EmailAddress::parse("jane@example.com", None).unwrap();
+    /// assert_eq!(
+    ///     Mailbox::new(None, address.clone()).to_header_value().unwrap(),
+    ///     "jane@example.com"
+    /// );
+    /// assert_eq!(
+    ///     Mailbox::new(Some("Jane Doe"), address.clone()).to_header_value().unwrap(),
+    ///     "Jane Doe <jane@example.com>"
+    /// );
+    /// assert_eq!(
+    ///     Mailbox::new(Some("Jane \"JD\" Doe"), address.clone()).to_header_value().unwrap(),
+    ///     "\"Jane \\\"JD\\\" Doe\" <jane@example.com>"
+    /// );
+    /// assert!(Mailbox::new(Some("Jane\r\nBcc: x@y.z"), address).to_header_value().is_err());
+    /// ```
+    pub fn to_header_value(&self) -> Result<String, HeaderError> {
+        self.header_value(false)
+    }
+
+    /// Returns the mailbox as it should be written into an outgoing header, like
+    /// `to_header_value`, but with a display name with non-ASCII characters written as RFC 2047
+    /// encoded words, in UTF-8 and base64; for recipients that do not support RFC 6532.
+    ///
+    /// Requires the `rfc2047` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, Mailbox};
+    ///
+    /// let address = EmailAddress::parse("jurgen@example.com", None).unwrap();
+    /// assert_eq!(
+    ///     Mailbox::new(Some("Jürgen"), address).to_encoded_header_value().unwrap(),
+    ///     "=?UTF-8?B?SsO8cmdlbg==?= <jurgen@example.com>"
+    /// );
+    /// ```
+    #[cfg(feature = "rfc2047")]
+    pub fn to_encoded_header_value(&self) -> Result<String, HeaderError> {
+        self.header_value(true)
+    }
+
+    /// Returns the mailbox as it should be written into an outgoing header, with a non-ASCII
+    /// display name as RFC 2047 encoded words or not.
+    pub(crate) fn header_value(&self, encode_words: bool) -> Result<String, HeaderError> {
+        let address = match self.address.header_safe() {
+            Some(address) => address.to_string(),
+            None => return Err(HeaderError::UnsafeContent(self.address.to_string())),
+        };
+        let name = match &self.display_name {
+            Some(name) => name,
+            None => return Ok(address),
+        };
+        let mut value = String::new();
+        write_display_name(name, encode_words, &mut value)?;
+        value.push_str(" <");
+        value.push_str(&address);
+        value.push('>');
+        Ok(value)
+    }
+}
+
+/// An address of an address list; i.e. a mailbox, or a named group of mailboxes, as in
+/// `Team: alice@example.com, bob@example.com;`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+// A mailbox is only large with the `heapless` feature, that is meant to avoid allocations.
+#[allow(clippy::large_enum_variant)]
+pub enum Address {
+    /// A single mailbox.
+    Mailbox(Mailbox),
+    /// A named group of mailboxes, possibly empty.
+    Group(Group),
+}
+
+impl From<Mailbox> for Address {
+    fn from(mailbox: Mailbox) -> Self {
+        Address::Mailbox(mailbox)
+    }
+}
+
+impl From<Group> for Address {
+    fn from(group: Group) -> Self {
+        Address::Group(group)
+    }
+}
+
+impl Address {
+    /// Parses an address list, like the value of a `To` header; i.e. mailboxes and named groups of
+    /// mailboxes, separated by commas. Empty items, as in `a@example.com,,b@example.com`, are
+    /// skipped as per the obsolete syntax.
+    ///
+    /// With lax parsing options, semicolons outside of groups separate the items as well, as in
+    /// the lists of Outlook, like `jane@example.com; joe@example.com`; see
+    /// `parse_list_with_diagnostics` to know whether they did.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{Address, Mailbox};
+    ///
+    /// let value = "Jane <jane@example.com>, Team: alice@example.com, bob@example.com;";
+    /// let addresses = Address::parse_list(value, None).unwrap();
+    /// assert_eq!(addresses.len(), 2);
+    /// match &addresses[1] {
+    ///     Address::Group(group) => {
+    ///         assert_eq!(group.name(), "Team");
+    ///         assert_eq!(group.mailboxes().len(), 2);
+    ///     }
+    ///     Address::Mailbox(_) => unreachable!(),
+    /// }
+    ///
+    /// assert!(Address::parse_list("jane@example.com, joe", None).is_err());
+    /// ```
+    pub fn parse_list(
+        value: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<Vec<Address>, ParseError> {
+        Address::parse_list_with_diagnostics(value, options).map(|(addresses, _)| addresses)
+    }
+
+    /// Parses an address list like `parse_list`, also returning the diagnostics on how the list
+    /// was split; i.e. `Diagnostic::SemicolonSeparator` if semicolons separated its items.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// let value = "Jane <jane@example.com>; joe@example.com";
+    /// assert!(Address::parse_list(value, None).is_err());
+    ///
+    /// let options = ParsingOptions::new(true);
+    /// let (addresses, diagnostics) =
+    ///     Address::parse_list_with_diagnostics(value, Some(options)).unwrap();
+    /// assert_eq!(addresses.len(), 2);
+    /// assert_eq!(diagnostics, vec![Diagnostic::SemicolonSeparator]);
+    /// ```
+    pub fn parse_list_with_diagnostics(
+        value: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<(Vec<Address>, Vec<Diagnostic>), ParseError> {
+        let options = options.unwrap_or_default();
+        let parse = |item: &str| Mailbox::parse(item, Some(options.clone()));
+        let (pieces, has_semicolons) = split_address_list(value, options.is_lax);
+        let addresses = pieces
+            .into_iter()
+            .map(|piece| match piece {
+                Piece::Mailbox(item) => parse(item).map(Address::Mailbox),
+                Piece::Group(name, items) => {
+                    let mailboxes = items.into_iter().map(parse).collect::<Result<_, _>>()?;
+                    Ok(Address::Group(Group::new(&phrase_text(name), mailboxes)))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let mut diagnostics = Vec::new();
+        if has_semicolons {
+            diagnostics.push(Diagnostic::SemicolonSeparator);
+        }
+        Ok((addresses, diagnostics))
+    }
+}
+
+/// Returns the mailboxes of an address list, including those of its groups, skipping the invalid
+/// ones; e.g. to harvest the addresses of received messages.
+pub(crate) fn parse_mailboxes(value: &str, options: &ParsingOptions) -> Vec<Mailbox> {
+    split_address_list(value, options.is_lax)
+        .0
+        .into_iter()
+        .flat_map(|piece| match piece {
+            Piece::Mailbox(item) => alloc::vec![item],
+            Piece::Group(_, items) => items,
+        })
+        .filter_map(|item| Mailbox::parse(item, Some(options.clone())).ok())
+        .collect()
+}
+
+/// A piece of an address list.
+enum Piece<'v> {
+    Mailbox(&'v str),
+    /// The name and the mailboxes of a group.
+    Group(&'v str, Vec<&'v str>),
+}
+
+/// Splits an address list into mailboxes and groups, skipping the empty items; the semicolons
+/// outside of groups separate the items as well if `semicolons` is set, and whether they did is
+/// returned along with the pieces.
+fn split_address_list(value: &str, semicolons: bool) -> (Vec<Piece<'_>>, bool) {
+    let mut pieces = Vec::new();
+    let mut has_semicolons = false;
+    let mut group: Option<(&str, Vec<&str>)> = None;
+    let (mut start, mut angle_depth, mut has_at) = (0, 0, false);
+    for (index, c) in top_level(value) {
+        match c {
+            '<' => angle_depth += 1,
+            '>' if angle_depth > 0 => angle_depth -= 1,
+            '@' => has_at = true,
+            ':' if angle_depth == 0 && !has_at && group.is_none() => {
+                group = Some((&value[start..index], Vec::new()));
+                start = index + 1;
+            }
+            ',' if angle_depth == 0 => {
+                push(&value[start..index], &mut group, &mut pieces);
+                start = index + 1;
+                has_at = false;
+            }
+            ';' if angle_depth == 0 && group.is_some() => {
+                push(&value[start..index], &mut group, &mut pieces);
+                start = index + 1;
+                has_at = false;
+                if let Some((name, items)) = group.take() {
+                    pieces.push(Piece::Group(name, items));
+                }
+            }
+            ';' if angle_depth == 0 && semicolons => {
+                push(&value[start..index], &mut group, &mut pieces);
+                start = index + 1;
+                has_at = false;
+                has_semicolons = true;
+            }
+            _ => (),
+        }
+    }
+    push(&value[start..], &mut group, &mut pieces);
+    if let Some((name, items)) = group {
+        pieces.push(Piece::Group(name, items));
+    }
+    (pieces, has_semicolons)
+}
+
+/// Pushes an item of an address list into the current group if any, and as a mailbox otherwise,
+/// unless it is empty.
+fn push<'v>(
+    item: &'v str,
+    group: &mut Option<(&'v str, Vec<&'v str>)>,
+    pieces: &mut Vec<Piece<'v>>,
+) {
+    if item.trim().is_empty() {
+        return;
+    }
+    match group {
+        Some((_, items)) => items.push(item),
+        None => pieces.push(Piece::Mailbox(item)),
+    }
+}
+
+/// Returns the characters of a header value that are neither in a quoted string nor in a comment,
+/// with their byte offsets.
+fn top_level(value: &str) -> Vec<(usize, char)> {
+    let mut characters = Vec::new();
+    let (mut in_quotes, mut comment_depth, mut escaped) = (false, 0, false);
+    for (index, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes || comment_depth > 0 => escaped = true,
+            '"' if comment_depth == 0 => in_quotes = !in_quotes,
+            '(' if !in_quotes => comment_depth += 1,
+            ')' if !in_quotes && comment_depth > 0 => comment_depth -= 1,
+            _ if in_quotes || comment_depth > 0 => (),
+            _ => characters.push((index, c)),
+        }
+    }
+    characters
+}
+
+/// Returns the text of a phrase, like a display name; i.e. without comments, with the quoted
+/// strings unquoted, and with the white space collapsed into single spaces.
+fn phrase_text(phrase: &str) -> String {
+    let mut text = String::new();
+    let mut has_space = false;
+    let push = |text: &mut String, c: char, has_space: &mut bool| {
+        if *has_space && !text.is_empty() {
+            text.push(' ');
+        }
+        *has_space = false;
+        text.push(c);
+    };
+    let mut chars = phrase.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(c) = chars.next() {
+                                push(&mut text, c, &mut has_space);
+                            }
+                        }
+                        '\r' | '\n' => (),
+                        c => push(&mut text, c, &mut has_space),
+                    }
+                }
+            }
+            '(' => {
+                let mut depth = 1;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '(' => depth += 1,
+                        ')' if depth == 1 => break,
+                        ')' => depth -= 1,
+                        _ => (),
+                    }
+                }
+                has_space = true;
+            }
+            c if c.is_whitespace() => has_space = true,
+            c => push(&mut text, c, &mut has_space),
+        }
+    }
+    text
+}
+
+/// A named group of mailboxes; e.g. `Undisclosed recipients:;`, without any mailboxes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Group {
+    name: String,
+    mailboxes: Vec<Mailbox>,
+}
+
+impl Group {
+    /// Instantiates a group.
+    pub fn new(name: &str, mailboxes: Vec<Mailbox>) -> Self {
+        Group {
+            name: String::from(name.trim()),
+            mailboxes,
+        }
+    }
+
+    /// Returns the name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the mailboxes of the group.
+    pub fn mailboxes(&self) -> &[Mailbox] {
+        &self.mailboxes
+    }
+}
+
+/// Writes a display name as it should be written into a header: as it is if it only consists of
+/// atoms, as RFC 2047 encoded words if it has non-ASCII characters and they are to be encoded, and
+/// as a quoted string otherwise.
+pub(crate) fn write_display_name(
+    name: &str,
+    encode_words: bool,
+    value: &mut String,
+) -> Result<(), HeaderError> {
+    if !is_header_safe(name) {
+        return Err(HeaderError::UnsafeContent(String::from(name)));
+    }
+    if !name.is_empty() && name.chars().all(|c| c == ' ' || is_atext(c)) {
+        value.push_str(name);
+    } else if encode_words && !name.is_ascii() {
+        write_encoded_words(name, value);
+    } else {
+        value.push('"');
+        for c in name.chars() {
+            if c == '"' || c == '\\' {
+                value.push('\\');
+            }
+            value.push(c);
+        }
+        value.push('"');
+    }
+    Ok(())
+}
+
+/// Returns whether a character is allowed in an atom of a display name; unlike in an address, only
+/// ASCII characters are, as non-ASCII ones are either encoded, or quoted.
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+/// Writes a text as RFC 2047 encoded words, in UTF-8 and base64, separated by spaces; each word is
+/// at most 75 characters long, as per RFC 2047, and holds whole characters only.
+fn write_encoded_words(text: &str, output: &mut String) {
+    // "=?UTF-8?B?" and "?=" leave 63 characters; i.e. 15 base64 groups of 3 octets each.
+    const MAX_OCTETS: usize = 45;
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = start;
+        for (index, c) in text[start..].char_indices() {
+            if index + c.len_utf8() > MAX_OCTETS {
+                break;
+            }
+            end = start + index + c.len_utf8();
+        }
+        if start > 0 {
+            output.push(' ');
+        }
+        output.push_str("=?UTF-8?B?");
+        encode_base64(&text.as_bytes()[start..end], output);
+        output.push_str("?=");
+        start = end;
+    }
+}
+
+fn encode_base64(bytes: &[u8], output: &mut String) {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | u32::from(byte) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                output.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailbox(display_name: &str) -> Mailbox {
+        let address = EmailAddress::parse("foo@bar.com", None).unwrap();
+        Mailbox::new(Some(display_name), address)
+    }
+
+    #[test]
+    fn quotes_display_names_with_specials() {
+        assert_eq!(
+            mailbox(" Foo Bar ").to_header_value().unwrap(),
+            "Foo Bar <foo@bar.com>"
+        );
+        assert_eq!(mailbox("").display_name(), None);
+        assert_eq!(
+            mailbox("Foo B. Bar").to_header_value().unwrap(),
+            "\"Foo B. Bar\" <foo@bar.com>"
+        );
+        assert_eq!(
+            mailbox("a\\b").to_header_value().unwrap(),
+            "\"a\\\\b\" <foo@bar.com>"
+        );
+        assert_eq!(
+            mailbox("Foo\u{85}Bar").to_header_value(),
+            Err(HeaderError::UnsafeContent(String::from("Foo\u{85}Bar")))
+        );
+    }
+
+    #[test]
+    fn parses_mailboxes() {
+        let mailbox = Mailbox::parse(
+            " (x) Jane  \"A. \\\"JD\\\"\" Doe <jane@example.com> (y)",
+            None,
+        );
+        assert_eq!(mailbox.unwrap().display_name(), Some("Jane A. \"JD\" Doe"));
+        #[cfg(not(feature = "strict-only"))]
+        {
+            let mailbox = Mailbox::parse(
+                "<@relay.org:jane@example.com>",
+                Some(ParsingOptions::new(true)),
+            );
+            assert_eq!(mailbox.unwrap().address().to_string(), "jane@example.com");
+        }
+        let mailbox = Mailbox::parse("\"<a>\"@example.com", None).unwrap();
+        assert_eq!(mailbox.display_name(), None);
+        assert_eq!(
+            Mailbox::parse("Jane <jane@example.com> Doe", None)
+                .unwrap_err()
+                .span(),
+            5..27
+        );
+        assert!(Mailbox::parse("<>", None).is_err());
+    }
+
+    #[test]
+    fn parses_address_lists() {
+        let value = "a@example.com,, \"b, c\" <b@example.com>, Empty:;, Team: d@example.com, \"e, f\" <e@example.com> ;, g@example.com";
+        let addresses = Address::parse_list(value, None).unwrap();
+        assert_eq!(addresses.len(), 5);
+        assert_eq!(
+            addresses[2],
+            Address::Group(Group::new("Empty", Vec::new()))
+        );
+        match &addresses[3] {
+            Address::Group(group) => assert_eq!(group.mailboxes().len(), 2),
+            Address::Mailbox(_) => panic!(),
+        }
+        let mailboxes = parse_mailboxes(
+            "joe, Team: d@example.com, x;, Jane <jane@example.com>",
+            &ParsingOptions::default(),
+        );
+        let mailboxes: Vec<String> = mailboxes
+            .iter()
+            .map(|mailbox| mailbox.address().to_string())
+            .collect();
+        assert_eq!(mailboxes, ["d@example.com", "jane@example.com"]);
+        assert!(Address::parse_list("", None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn splits_lists_on_semicolons_in_lax_mode() {
+        let value = "a@example.com; Team: b@example.com, c@example.com; \"d; e\" <d@example.com>;";
+        assert!(Address::parse_list(value, None).is_err());
+        let options = ParsingOptions::new(true);
+        let (addresses, diagnostics) =
+            Address::parse_list_with_diagnostics(value, Some(options.clone())).unwrap();
+        assert_eq!(addresses.len(), 3);
+        assert!(matches!(&addresses[1], Address::Group(group) if group.mailboxes().len() == 2));
+        assert_eq!(diagnostics, [Diagnostic::SemicolonSeparator]);
+        let (_, diagnostics) =
+            Address::parse_list_with_diagnostics("Team: a@example.com;", Some(options.clone()))
+                .unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            parse_mailboxes("a@example.com;b@example.com", &options).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn quotes_non_ascii_display_names() {
+        assert_eq!(
+            mailbox("Jürgen").to_header_value().unwrap(),
+            "\"Jürgen\" <foo@bar.com>"
+        );
+    }
+
+    #[test]
+    fn encodes_non_ascii_display_names() {
+        assert_eq!(
+            mailbox("Jürgen").header_value(true).unwrap(),
+            "=?UTF-8?B?SsO8cmdlbg==?= <foo@bar.com>"
+        );
+        assert_eq!(
+            mailbox("Jane Doe").header_value(true).unwrap(),
+            "Jane Doe <foo@bar.com>"
+        );
+        #[cfg(feature = "rfc2047")]
+        assert_eq!(
+            mailbox("Jürgen").to_encoded_header_value(),
+            mailbox("Jürgen").header_value(true)
+        );
+        let value = mailbox(&"ü".repeat(30)).header_value(true).unwrap();
+        let words: Vec<&str> = value.split(' ').collect();
+        assert_eq!(words.len(), 3);
+        assert!(words[..2].iter().all(|word| word.len() <= 75));
+        assert_eq!(words[1], "=?UTF-8?B?w7zDvMO8w7zDvMO8w7zDvA==?=");
+    }
+
+    #[test]
+    fn encodes_base64() {
+        for (input, expected) in &[
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+        ] {
+            let mut output = String::new();
+            encode_base64(input.as_bytes(), &mut output);
+            assert_eq!(&output, expected);
+        }
+    }
+
+    #[test]
+    fn groups_keep_their_members() {
+        let address = EmailAddress::parse("foo@bar.com", None).unwrap();
+        let group = Group::new(" Team, A ", vec![Mailbox::new(None, address)]);
+        assert_eq!(group.name(), "Team, A");
+        assert_eq!(group.mailboxes().len(), 1);
+        assert_eq!(Address::from(group.clone()), Address::Group(group));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::email_address::EmailAddress;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+impl EmailAddress {
+    /// Returns the `mailto:` URI of the address, as per RFC 6068; see `Mailto` for one with more
+    /// recipients, or a subject and a body.
+    ///
+    /// The canonical form of the address is written, percent-encoding the characters that are not
+    /// allowed in the URI, including the non-ASCII ones as UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@bar.com", None).unwrap();
+    /// assert_eq!(email.to_uri(), "mailto:foo@bar.com");
+    ///
+    /// let email = EmailAddress::parse("\"foo bar\"@bücher.de", None).unwrap();
+    /// assert_eq!(email.to_uri(), "mailto:%22foo%20bar%22@b%C3%BCcher.de");
+    /// ```
+    pub fn to_uri(&self) -> String {
+        Mailto::new(self.clone()).to_string()
+    }
+}
+
+/// A `mailto:` URI, as per RFC 6068, with its recipients and header fields; written by its
+/// `Display` implementation.
+///
+/// The addresses are written in their canonical forms. The subject and the body are
+/// percent-encoded as UTF-8, and the line breaks in the body are written as CRLF, as RFC 6068
+/// requires.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, Mailto};
+///
+/// let to = EmailAddress::parse("foo@bar.com", None).unwrap();
+/// let cc = EmailAddress::parse("baz@bar.com", None).unwrap();
+/// let uri = Mailto::new(to).cc(cc).subject("Hello & welcome").body("Line 1\nLine 2");
+/// assert_eq!(
+///     uri.to_string(),
+///     "mailto:foo@bar.com?cc=baz@bar.com&subject=Hello%20%26%20welcome&body=Line%201%0D%0ALine%202"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mailto {
+    to: Vec<EmailAddress>,
+    cc: Vec<EmailAddress>,
+    bcc: Vec<EmailAddress>,
+    subject: Option<String>,
+    body: Option<String>,
+}
+
+impl Mailto {
+    /// Instantiates a URI with one recipient.
+    pub fn new(to: EmailAddress) -> Self {
+        Mailto {
+            to: vec![to],
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: None,
+            body: None,
+        }
+    }
+
+    /// Adds a recipient.
+    pub fn to(mut self, to: EmailAddress) -> Self {
+        self.to.push(to);
+        self
+    }
+
+    /// Adds a carbon copy recipient.
+    pub fn cc(mut self, cc: EmailAddress) -> Self {
+        self.cc.push(cc);
+        self
+    }
+
+    /// Adds a blind carbon copy recipient.
+    pub fn bcc(mut self, bcc: EmailAddress) -> Self {
+        self.bcc.push(bcc);
+        self
+    }
+
+    /// Sets the subject.
+    pub fn subject(self, subject: &str) -> Self {
+        Mailto {
+            subject: Some(String::from(subject)),
+            ..self
+        }
+    }
+
+    /// Sets the body.
+    pub fn body(self, body: &str) -> Self {
+        Mailto {
+            body: Some(String::from(body)),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Mailto {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("mailto:")?;
+        write_addresses(&self.to, formatter)?;
+        let mut separator = '?';
+        let mut write_field = |formatter: &mut fmt::Formatter<'_>, name: &str| {
+            write!(formatter, "{}{}=", separator, name)?;
+            separator = '&';
+            Ok(())
+        };
+        for (name, addresses) in &[("cc", &self.cc), ("bcc", &self.bcc)] {
+            if !addresses.is_empty() {
+                write_field(formatter, name)?;
+                write_addresses(addresses, formatter)?;
+            }
+        }
+        if let Some(subject) = &self.subject {
+            write_field(formatter, "subject")?;
+            percent_encode(subject, FIELD_CHARS, formatter)?;
+        }
+        if let Some(body) = &self.body {
+            write_field(formatter, "body")?;
+            let mut lines = body.split('\n');
+            let first_line = lines.next().unwrap_or_default();
+            percent_encode(first_line.trim_end_matches('\r'), FIELD_CHARS, formatter)?;
+            for line in lines {
+                formatter.write_str("%0D%0A")?;
+                percent_encode(line.trim_end_matches('\r'), FIELD_CHARS, formatter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the canonical forms of the addresses, separated by commas.
+fn write_addresses(addresses: &[EmailAddress], formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, address) in addresses.iter().enumerate() {
+        if index > 0 {
+            formatter.write_str(",")?;
+        }
+        let canonical = address.canonical();
+        percent_encode(canonical.get_local_part(), ADDRESS_CHARS, formatter)?;
+        formatter.write_str("@")?;
+        percent_encode(canonical.get_domain(), ADDRESS_CHARS, formatter)?;
+    }
+    Ok(())
+}
+
+/// The characters besides the alphanumeric ones that are written as they are in the parts of an
+/// address; RFC 6068 requires `&`, `;`, and `=` to be percent-encoded in them, and so are `@` and
+/// `,`, to keep the separators of the addresses unambiguous.
+const ADDRESS_CHARS: &[u8] = b"-._~!$'()*+:";
+
+/// The characters besides the alphanumeric ones that are written as they are in the values of the
+/// header fields; i.e. the `qchar`s of RFC 6068.
+const FIELD_CHARS: &[u8] = b"-._~!$'()*+,;:@";
+
+/// Writes a text, percent-encoding its octets other than the alphanumeric and the given ones.
+fn percent_encode(text: &str, chars: &[u8], formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || chars.contains(&byte) {
+            formatter.write_str(char::from(byte).encode_utf8(&mut [0; 1]))?;
+        } else {
+            write!(formatter, "%{:02X}", byte)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(input: &str) -> EmailAddress {
+        EmailAddress::parse(input, None).unwrap()
+    }
+
+    #[test]
+    fn percent_encodes_addresses() {
+        assert_eq!(email("Foo+Bar@BAR.com").to_uri(), "mailto:Foo+Bar@bar.com");
+        assert_eq!(
+            email("\"not@me\"@example.org").to_uri(),
+            "mailto:%22not%40me%22@example.org"
+        );
+        assert_eq!(
+            email("\"a,b?c&d=e;f\"@example.org").to_uri(),
+            "mailto:%22a%2Cb%3Fc%26d%3De%3Bf%22@example.org"
+        );
+        assert_eq!(
+            email("~user/%x#y@[127.0.0.1]").to_uri(),
+            "mailto:~user%2F%25x%23y@%5B127.0.0.1%5D"
+        );
+    }
+
+    #[test]
+    fn writes_recipients_and_fields() {
+        let uri = Mailto::new(email("a@b.c"))
+            .to(email("d@e.f"))
+            .bcc(email("g@h.i"))
+            .bcc(email("j@k.l"))
+            .subject("Grüße; a=b, c?")
+            .body("a\r\nb\nc\n");
+        assert_eq!(
+            uri.to_string(),
+            "mailto:a@b.c,d@e.f?bcc=g@h.i,j@k.l&subject=Gr%C3%BC%C3%9Fe;%20a%3Db,%20c%3F&body=a%0D%0Ab%0D%0Ac%0D%0A"
+        );
+        assert_eq!(
+            Mailto::new(email("a@b.c")).body("").to_string(),
+            "mailto:a@b.c?body="
+        );
+    }
+}
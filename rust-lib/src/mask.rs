@@ -0,0 +1,187 @@
+use alloc::string::String;
+
+/// How `MaskPolicy` masks the domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DomainMask {
+    /// Shows the domain as it is; e.g. `f***@example.com`.
+    Keep,
+    /// Masks every label but the top level one, showing only their first characters;
+    /// e.g. `f***@e******.com`. The content of a domain literal is masked entirely.
+    Labels,
+    /// Masks the whole domain; e.g. `f***@***********`.
+    Hide,
+}
+
+/// Policy for masking an email address, for safe display in user interfaces, logs, or support tools.
+///
+/// By default, the first character of the local part is shown, the rest of it is masked with a `*`
+/// per character, and the domain is shown as it is. The visible characters of the local part are
+/// configured with `visible_prefix` and `visible_suffix`; at least one character is always masked,
+/// however short the local part is. With `collapse`, every masked run is shown as a single mask
+/// character, which also hides the length; e.g. `f…o@example.com`.
+///
+/// The canonical form of the address is masked, hence comments and folding white space never show.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{DomainMask, EmailAddress, MaskPolicy};
+///
+/// let email = EmailAddress::parse("foo@example.com", None).unwrap();
+/// assert_eq!(email.mask(&MaskPolicy::new()), "f**@example.com");
+///
+/// let policy = MaskPolicy::new().domain(DomainMask::Labels);
+/// assert_eq!(email.mask(&policy), "f**@e******.com");
+///
+/// let policy = MaskPolicy::new().visible_suffix(1).mask_char('…').collapse(true);
+/// assert_eq!(email.mask(&policy), "f…o@example.com");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaskPolicy {
+    visible_prefix: usize,
+    visible_suffix: usize,
+    mask_char: char,
+    collapse: bool,
+    domain: DomainMask,
+}
+
+impl Default for MaskPolicy {
+    fn default() -> Self {
+        MaskPolicy {
+            visible_prefix: 1,
+            visible_suffix: 0,
+            mask_char: '*',
+            collapse: false,
+            domain: DomainMask::Keep,
+        }
+    }
+}
+
+impl MaskPolicy {
+    /// Instantiates the default policy.
+    pub fn new() -> Self {
+        MaskPolicy::default()
+    }
+
+    /// Sets the number of visible characters at the start of the local part.
+    pub fn visible_prefix(self, visible_prefix: usize) -> Self {
+        MaskPolicy {
+            visible_prefix,
+            ..self
+        }
+    }
+
+    /// Sets the number of visible characters at the end of the local part.
+    pub fn visible_suffix(self, visible_suffix: usize) -> Self {
+        MaskPolicy {
+            visible_suffix,
+            ..self
+        }
+    }
+
+    /// Sets the character replacing the masked characters.
+    pub fn mask_char(self, mask_char: char) -> Self {
+        MaskPolicy { mask_char, ..self }
+    }
+
+    /// Enables/disables showing every masked run as a single mask character.
+    pub fn collapse(self, collapse: bool) -> Self {
+        MaskPolicy { collapse, ..self }
+    }
+
+    /// Sets how the domain is masked.
+    pub fn domain(self, domain: DomainMask) -> Self {
+        MaskPolicy { domain, ..self }
+    }
+
+    /// Masks a canonical local part and domain.
+    pub(crate) fn apply(&self, local_part: &str, domain: &str) -> String {
+        let mut masked = String::new();
+        self.mask(
+            local_part,
+            self.visible_prefix,
+            self.visible_suffix,
+            &mut masked,
+        );
+        masked.push('@');
+        match self.domain {
+            DomainMask::Keep => masked.push_str(domain),
+            DomainMask::Hide => self.mask(domain, 0, 0, &mut masked),
+            DomainMask::Labels if domain.starts_with('[') => {
+                masked.push('[');
+                self.mask(&domain[1..domain.len() - 1], 0, 0, &mut masked);
+                masked.push(']');
+            }
+            DomainMask::Labels => {
+                let (labels, top_level) = match domain.rfind('.') {
+                    Some(dot) => domain.split_at(dot),
+                    None => (domain, ""),
+                };
+                for (index, label) in labels.split('.').enumerate() {
+                    if index > 0 {
+                        masked.push('.');
+                    }
+                    self.mask(label, 1, 0, &mut masked);
+                }
+                masked.push_str(top_level);
+            }
+        }
+        masked
+    }
+
+    /// Masks a text, showing at most the given numbers of characters at its start and end, and
+    /// masking at least one character.
+    fn mask(&self, text: &str, prefix: usize, suffix: usize, masked: &mut String) {
+        let length = text.chars().count();
+        if length == 0 {
+            return;
+        }
+        let prefix = prefix.min(length - 1);
+        let suffix = suffix.min(length - 1 - prefix);
+        let hidden = length - prefix - suffix;
+        masked.extend(text.chars().take(prefix));
+        let mask_length = if self.collapse { 1 } else { hidden };
+        masked.extend(core::iter::repeat_n(self.mask_char, mask_length));
+        masked.extend(text.chars().skip(prefix + hidden));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmailAddress;
+
+    fn mask(input: &str, policy: MaskPolicy) -> String {
+        EmailAddress::parse(input, None).unwrap().mask(&policy)
+    }
+
+    #[test]
+    fn masks_local_part() {
+        let policy = MaskPolicy::new().visible_prefix(2).visible_suffix(2);
+        assert_eq!(mask("jane.doe@bar.com", policy), "ja****oe@bar.com");
+        assert_eq!(mask("jane@bar.com", policy), "ja*e@bar.com");
+        assert_eq!(mask("jö@bar.com", policy), "j*@bar.com");
+        assert_eq!(mask("j@bar.com", policy), "*@bar.com");
+        assert_eq!(
+            mask("(comment)\"Jane\"@BAR.com", MaskPolicy::new()),
+            "J***@bar.com"
+        );
+    }
+
+    #[test]
+    fn collapses_masked_runs() {
+        let policy = MaskPolicy::new()
+            .mask_char('…')
+            .collapse(true)
+            .domain(DomainMask::Labels);
+        assert_eq!(mask("jane.doe@mail.example.co", policy), "j…@m….e….co");
+    }
+
+    #[test]
+    fn masks_domain() {
+        let labels = MaskPolicy::new().domain(DomainMask::Labels);
+        assert_eq!(mask("foo@bücher.de", labels), "f**@b*****.de");
+        assert_eq!(mask("foo@[127.0.0.1]", labels), "f**@[*********]");
+        let hide = MaskPolicy::new().domain(DomainMask::Hide);
+        assert_eq!(mask("foo@bar.com", hide), "f**@*******");
+    }
+}
@@ -0,0 +1,214 @@
+use crate::error::{ErrorKind, Hint, ParseError};
+use core::fmt;
+
+/// A provider of the messages of the parse errors; e.g. to show them in the languages of the users
+/// of an application.
+///
+/// The messages are written into a formatter, so that a provider can either write fixed strings or
+/// format the offending part of the input into its messages. As `ErrorKind` and `Hint` are non
+/// exhaustive, a provider should fall back to `EnglishMessages`, or to a generic message, for the
+/// kinds and hints it does not know; their codes stay stable across versions.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+/// use std::fmt;
+///
+/// struct French;
+///
+/// impl MessageProvider for French {
+///     fn error(&self, formatter: &mut fmt::Formatter<'_>, kind: ErrorKind, part: &str) -> fmt::Result {
+///         match kind {
+///             ErrorKind::InvalidDomain => write!(formatter, "Domaine invalide « {} ».", part),
+///             _ => EnglishMessages.error(formatter, kind, part),
+///         }
+///     }
+///
+///     fn hint(&self, formatter: &mut fmt::Formatter<'_>, hint: Hint) -> fmt::Result {
+///         match hint {
+///             Hint::MisplacedHyphen => formatter.write_str(
+///                 "Un label de domaine ne peut ni commencer ni finir par un tiret.",
+///             ),
+///             _ => EnglishMessages.hint(formatter, hint),
+///         }
+///     }
+/// }
+///
+/// let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+/// assert_eq!(
+///     error.localize(&French).to_string(),
+///     "Domaine invalide « -bar.com ». Un label de domaine ne peut ni commencer ni finir par un tiret."
+/// );
+/// ```
+pub trait MessageProvider {
+    /// Writes the message of an error of a kind, given the offending part of the input; see
+    /// `ParseError::span`.
+    fn error(&self, formatter: &mut fmt::Formatter<'_>, kind: ErrorKind, part: &str)
+        -> fmt::Result;
+
+    /// Writes the message of a hint.
+    fn hint(&self, formatter: &mut fmt::Formatter<'_>, hint: Hint) -> fmt::Result;
+
+    /// Writes the whole message of a parse error; by default, the message of its kind, followed by
+    /// a space and the message of its hint, if any.
+    fn parse_error(&self, formatter: &mut fmt::Formatter<'_>, error: &ParseError) -> fmt::Result {
+        self.error(formatter, error.kind(), &error.input()[error.span()])?;
+        match error.hint() {
+            Some(hint) => {
+                formatter.write_str(" ")?;
+                self.hint(formatter, hint)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// The default messages of the parse errors, in English; those of their `Display` implementations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnglishMessages;
+
+impl MessageProvider for EnglishMessages {
+    fn error(
+        &self,
+        formatter: &mut fmt::Formatter<'_>,
+        kind: ErrorKind,
+        part: &str,
+    ) -> fmt::Result {
+        match kind {
+            ErrorKind::Invalid => write!(formatter, "Invalid email address '{}'.", part),
+            ErrorKind::InvalidLocalPart => write!(formatter, "Invalid local part '{}'.", part),
+            ErrorKind::InvalidDomain => write!(formatter, "Invalid domain '{}'.", part),
+            ErrorKind::LocalPartTooLong => write!(formatter, "Too long local part '{}'.", part),
+            ErrorKind::DomainTooLong => write!(formatter, "Too long domain '{}'.", part),
+            ErrorKind::AddressTooLong => write!(formatter, "Too long email address '{}'.", part),
+            ErrorKind::InputTooLarge => {
+                write!(formatter, "Too large input of {} octets.", part.len())
+            }
+            ErrorKind::TooComplex => write!(formatter, "Too complex input '{}'.", part),
+            ErrorKind::QuotedLocalPart => {
+                write!(formatter, "Quoted local part '{}' is not allowed.", part)
+            }
+        }
+    }
+
+    fn hint(&self, formatter: &mut fmt::Formatter<'_>, hint: Hint) -> fmt::Result {
+        formatter.write_str(match hint {
+            Hint::MissingAt => {
+                "An email address must contain an '@' separating the local part and the domain."
+            }
+            Hint::MultipleAt => "Only one '@' is allowed outside a quoted local part.",
+            Hint::EmptyLocalPart => "The local part cannot be empty.",
+            Hint::MisplacedDot => {
+                "An unquoted local part can neither start nor end with a dot, nor contain consecutive dots."
+            }
+            Hint::UnquotedSpecial => {
+                "Special characters like spaces, commas, or brackets are only allowed in a quoted local part."
+            }
+            Hint::EmptyDomain => "The domain cannot be empty.",
+            Hint::MisplacedDomainDot => {
+                "A domain can neither start nor end with a dot, nor contain consecutive dots."
+            }
+            Hint::MisplacedHyphen => "A domain label can neither start nor end with a hyphen.",
+            Hint::DoubleHyphen => {
+                "A domain label cannot contain consecutive hyphens, unless it starts with 'xn--'."
+            }
+            Hint::NumericTopLevelDomain => "The top-level domain cannot be all-numeric.",
+            Hint::NumericLabel => "A domain label cannot be all-numeric.",
+            Hint::Comment => "Comments are not allowed.",
+            Hint::Underscore => "A domain label cannot contain an underscore.",
+            Hint::DomainLiteral => "Domain literals are not allowed; use a host name instead.",
+            Hint::ObsoleteSyntax => {
+                "The address contains obsolete syntax, and can only be parsed with lax parsing options."
+            }
+            Hint::Whitespace => "White space is only allowed in a quoted local part.",
+            Hint::InvalidLabelCharacter => {
+                "A domain label can only contain letters, digits, and hyphens."
+            }
+            Hint::InvalidUtf8 => "The input is not valid UTF-8.",
+            Hint::NonAscii => "Only ASCII characters are allowed.",
+        })
+    }
+}
+
+/// A parse error displayed with the messages of a `MessageProvider`; see `ParseError::localize`.
+pub struct LocalizedError<'e, P: ?Sized> {
+    error: &'e ParseError,
+    provider: &'e P,
+}
+
+impl<'e, P: MessageProvider + ?Sized> LocalizedError<'e, P> {
+    pub(crate) fn new(error: &'e ParseError, provider: &'e P) -> Self {
+        LocalizedError { error, provider }
+    }
+}
+
+impl<P: MessageProvider + ?Sized> fmt::Display for LocalizedError<'_, P> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.provider.parse_error(formatter, self.error)
+    }
+}
+
+impl<P: ?Sized> fmt::Debug for LocalizedError<'_, P> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LocalizedError")
+            .field("error", self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email_address::EmailAddress;
+    use alloc::string::ToString;
+
+    struct Codes;
+
+    impl MessageProvider for Codes {
+        fn error(
+            &self,
+            formatter: &mut fmt::Formatter<'_>,
+            kind: ErrorKind,
+            part: &str,
+        ) -> fmt::Result {
+            write!(formatter, "{}({})", kind.code(), part)
+        }
+
+        fn hint(&self, formatter: &mut fmt::Formatter<'_>, hint: Hint) -> fmt::Result {
+            formatter.write_str(hint.code())
+        }
+
+        fn parse_error(
+            &self,
+            formatter: &mut fmt::Formatter<'_>,
+            error: &ParseError,
+        ) -> fmt::Result {
+            self.error(formatter, error.kind(), &error.input()[error.span()])?;
+            if let Some(hint) = error.hint() {
+                formatter.write_str(": ")?;
+                self.hint(formatter, hint)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn renders_with_the_provider() {
+        let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+        assert_eq!(
+            error.localize(&Codes).to_string(),
+            "invalid_domain(-bar.com): misplaced_hyphen"
+        );
+        assert_eq!(
+            error.localize(&EnglishMessages).to_string(),
+            error.to_string()
+        );
+        let provider: &dyn MessageProvider = &Codes;
+        let error = "foo".parse::<EmailAddress>().unwrap_err();
+        assert_eq!(
+            error.localize(provider).to_string(),
+            "invalid(foo): missing_at"
+        );
+    }
+}
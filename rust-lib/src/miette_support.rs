@@ -0,0 +1,85 @@
+use crate::error::{ErrorKind, ParseError};
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use std::fmt::Display;
+
+/// Returns the label of the offending part of the input of an error of a kind.
+fn label(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Invalid => "not an email address",
+        ErrorKind::InvalidLocalPart => "invalid local part",
+        ErrorKind::InvalidDomain => "invalid domain",
+        ErrorKind::LocalPartTooLong => "too long local part",
+        ErrorKind::DomainTooLong => "too long domain",
+        ErrorKind::AddressTooLong => "too long email address",
+        ErrorKind::InputTooLarge => "too large input",
+        ErrorKind::TooComplex => "too complex input",
+        ErrorKind::QuotedLocalPart => "quoted local part",
+    }
+}
+
+/// Labels the offending part of the input, with the hint as help; the code is the code of the
+/// kind, prefixed with `email_address_parser::`.
+///
+/// Only available with the `miette` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+/// use miette::Diagnostic;
+///
+/// let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+/// assert_eq!(
+///     error.code().unwrap().to_string(),
+///     "email_address_parser::invalid_domain"
+/// );
+/// let label = error.labels().unwrap().next().unwrap();
+/// assert_eq!((label.offset(), label.len()), (4, 8));
+/// assert_eq!(label.label(), Some("invalid domain"));
+/// assert_eq!(
+///     error.help().unwrap().to_string(),
+///     "A domain label can neither start nor end with a hyphen."
+/// );
+/// ```
+impl Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(format!(
+            "email_address_parser::{}",
+            self.kind().code()
+        )))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.hint()
+            .map(|hint| Box::new(hint) as Box<dyn Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.input)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = LabeledSpan::at(self.span(), label(self.kind()));
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email_address::EmailAddress;
+
+    #[test]
+    fn labels_the_offending_part() {
+        let error = "foo".parse::<EmailAddress>().unwrap_err();
+        let labels: Vec<LabeledSpan> = error.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!((labels[0].offset(), labels[0].len()), (0, 3));
+        assert_eq!(labels[0].label(), Some("not an email address"));
+        let source = error.source_code().unwrap();
+        let contents = source.read_span(&labels[0].inner().clone(), 0, 0).unwrap();
+        assert_eq!(contents.data(), b"foo");
+        let error = "foo@-bar.com".parse::<EmailAddress>().unwrap_err();
+        let label = error.labels().unwrap().next().unwrap();
+        assert_eq!(&error.input()[label.offset()..][..label.len()], "-bar.com");
+    }
+}
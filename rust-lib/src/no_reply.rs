@@ -0,0 +1,151 @@
+use crate::email_address::EmailAddress;
+use crate::rules::{ValidationRule, Violation};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The words of no-reply local parts, without separators, by language.
+const NO_REPLY_WORDS: &[&str] = &[
+    // English
+    "noreply",
+    "donotreply",
+    "dontreply",
+    "noreplies",
+    "noresponse",
+    "donotrespond",
+    // French
+    "nepasrepondre",
+    "pasdereponse",
+    // German
+    "nichtantworten",
+    "keineantwort",
+    "bittenichtantworten",
+    // Spanish
+    "noresponder",
+    "norespuesta",
+    "norespondas",
+    // Italian
+    "nonrispondere",
+    // Portuguese
+    "naoresponda",
+    "naoresponder",
+    "naorespondas",
+    // Dutch
+    "nietantwoorden",
+    "nietbeantwoorden",
+    "geenantwoord",
+    // Swedish, Danish, and Norwegian
+    "svarainte",
+    "ikkesvar",
+    "ikkebesvar",
+    // Polish
+    "nieodpowiadaj",
+];
+
+/// Returns a lower case letter without its diacritic, for the languages of `NO_REPLY_WORDS`;
+/// e.g. `e` for `é`.
+fn fold(c: char) -> char {
+    match c {
+        'à'..='å' => 'a',
+        'ç' => 'c',
+        'è'..='ë' => 'e',
+        'ì'..='ï' => 'i',
+        'ñ' | 'ń' => 'n',
+        'ò'..='ö' | 'ø' => 'o',
+        'ù'..='ü' => 'u',
+        _ => c,
+    }
+}
+
+/// Returns whether a local part is that of a no-reply address; see `EmailAddress::is_no_reply`.
+fn is_no_reply(local_part: &str) -> bool {
+    let local_part: String = local_part.to_lowercase().chars().map(fold).collect();
+    let local_part = local_part.split('+').next().unwrap_or_default();
+    let words: Vec<&str> = local_part
+        .split(['-', '_', '.'])
+        .map(|word| word.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .collect();
+    // A run of the words, joined, may be a no-reply word; e.g. `billing-do-not-reply`.
+    (0..words.len()).any(|start| {
+        let mut joined = String::new();
+        words[start..].iter().any(|word| {
+            joined.push_str(word);
+            NO_REPLY_WORDS.contains(&joined.as_str())
+        })
+    })
+}
+
+impl EmailAddress {
+    /// Returns whether the address is a no-reply one, that only sends messages, and that nobody
+    /// reads the replies to; e.g. `no-reply@`, `donotreply@`, or `noreply+bounces@`, and their
+    /// variants in a few European languages, like `ne-pas-repondre@` or `nicht-antworten@`.
+    ///
+    /// Unlike role accounts, which reach a function, these are outbound-only; e.g. for the
+    /// pipelines that handle the replies to skip them. The local part is compared ignoring case,
+    /// diacritics, sub-addresses (tags), separators (`-`, `_`, and `.`), and trailing digits; and
+    /// it matches if it has a no-reply word, like `billing-noreply`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// assert!(EmailAddress::parse("No_Reply@example.com", None).unwrap().is_no_reply());
+    /// assert!(EmailAddress::parse("billing.do-not-reply@example.com", None).unwrap().is_no_reply());
+    /// assert!(!EmailAddress::parse("replies@example.com", None).unwrap().is_no_reply());
+    /// ```
+    pub fn is_no_reply(&self) -> bool {
+        is_no_reply(&self.local_part_unquoted())
+    }
+}
+
+/// Rejects the no-reply addresses, as per `EmailAddress::is_no_reply`; e.g. for a form whose
+/// address is used to reply. Code `no_reply`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// assert!(NoReplyAddresses.check(&EmailAddress::parse("jane@example.com", None).unwrap()).is_ok());
+/// assert!(NoReplyAddresses.check(&EmailAddress::parse("noreply@example.com", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoReplyAddresses;
+
+impl ValidationRule for NoReplyAddresses {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        if email.is_no_reply() {
+            return Err(Violation::new(
+                "no_reply",
+                format!("No-reply addresses like '{}' are not allowed.", email),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_no_reply_local_parts() {
+        for local_part in &[
+            "noreply",
+            "No-Reply",
+            "do_not_reply",
+            "noreply+github",
+            "noreply2",
+            "notifications.noreply",
+            "ne-pas-répondre",
+            "bitte.nicht.antworten",
+            "no-responder",
+            "não.responda",
+            "niet-beantwoorden",
+        ] {
+            assert!(is_no_reply(local_part), "{}", local_part);
+        }
+        for local_part in &["reply", "noreen", "no", "info", "norepl", "snoreply"] {
+            assert!(!is_no_reply(local_part), "{}", local_part);
+        }
+    }
+}
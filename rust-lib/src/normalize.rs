@@ -0,0 +1,434 @@
+use crate::email_address::EmailAddress;
+use alloc::string::String;
+use wasm_bindgen::prelude::*;
+
+/// Addressing rules of a mailbox provider.
+struct Provider {
+    /// Domains of the provider.
+    domains: &'static [&'static str],
+    /// Separator of the sub-address (tag) in the local part; e.g. `+` in `jane+news@gmail.com`.
+    tag_separator: char,
+    /// Whether the dots in the local part are insignificant; e.g. `j.ane@gmail.com` is `jane@gmail.com`.
+    ignores_dots: bool,
+}
+
+const PROVIDERS: &[Provider] = &[
+    Provider {
+        domains: &["gmail.com", "googlemail.com"],
+        tag_separator: '+',
+        ignores_dots: true,
+    },
+    Provider {
+        domains: &["outlook.com", "hotmail.com", "live.com", "msn.com"],
+        tag_separator: '+',
+        ignores_dots: false,
+    },
+    Provider {
+        domains: &["yahoo.com", "ymail.com", "rocketmail.com"],
+        tag_separator: '-',
+        ignores_dots: false,
+    },
+    Provider {
+        domains: &["icloud.com", "me.com", "mac.com"],
+        tag_separator: '+',
+        ignores_dots: false,
+    },
+    Provider {
+        domains: &["fastmail.com", "fastmail.fm"],
+        tag_separator: '+',
+        ignores_dots: false,
+    },
+    Provider {
+        domains: &["proton.me", "protonmail.com", "pm.me"],
+        tag_separator: '+',
+        ignores_dots: false,
+    },
+];
+
+/// Domains that reach the same mailboxes as another domain.
+const ALIASES: &[(&str, &str)] = &[("googlemail.com", "gmail.com")];
+
+fn provider(domain: &str) -> Option<&'static Provider> {
+    PROVIDERS
+        .iter()
+        .find(|provider| provider.domains.contains(&domain))
+}
+
+/// Normalizes email addresses beyond their canonical forms; e.g. to deduplicate a mailing list.
+///
+/// Normalization starts with the canonical form (see `EmailAddress::canonical`), and then applies
+/// the enabled steps, none of which is enabled by default:
+/// - `lowercase_local_part`: lower cases the local part. Strictly speaking, the local part is
+///   case-sensitive, but virtually all providers treat it case-insensitively.
+/// - `strip_tags`: removes the sub-address (tag) from the local part; e.g. `jane+news` becomes `jane`.
+/// - `provider_rules`: applies the rules of well-known providers; i.e. their tag separators (e.g. `-`
+///   for Yahoo), insignificant dots (e.g. for Gmail), and domain aliases (e.g. `googlemail.com`
+///   for `gmail.com`). Only the aliases that reach the same mailbox are merged.
+/// - `punycode`: converts an internationalized domain to its ASCII form (requires the `idna` feature).
+///
+/// Quoted local parts are only ever lower cased, as their tags and dots are ambiguous.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, Normalizer};
+///
+/// let normalizer = Normalizer::new()
+///     .lowercase_local_part(true)
+///     .strip_tags(true)
+///     .provider_rules(true);
+/// let email = EmailAddress::parse("J.Ane+News@GoogleMail.com", None).unwrap();
+/// assert_eq!(normalizer.normalize(&email).to_string(), "jane@gmail.com");
+///
+/// let email = EmailAddress::parse("jane-news@yahoo.com", None).unwrap();
+/// assert_eq!(normalizer.normalize(&email).to_string(), "jane@yahoo.com");
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Normalizer {
+    lowercase_local_part: bool,
+    strip_tags: bool,
+    provider_rules: bool,
+    /// Without the `idna` feature, there is no conversion to the ASCII form, hence no such step.
+    #[cfg(feature = "idna")]
+    punycode: bool,
+}
+
+impl Normalizer {
+    /// Instantiates a normalizer that only canonicalizes.
+    pub fn new() -> Self {
+        Normalizer::default()
+    }
+
+    /// Enables/disables lower casing the local part.
+    pub fn lowercase_local_part(self, lowercase_local_part: bool) -> Self {
+        Normalizer {
+            lowercase_local_part,
+            ..self
+        }
+    }
+
+    /// Enables/disables removing the tag from the local part.
+    pub fn strip_tags(self, strip_tags: bool) -> Self {
+        Normalizer { strip_tags, ..self }
+    }
+
+    /// Enables/disables the rules of well-known providers.
+    pub fn provider_rules(self, provider_rules: bool) -> Self {
+        Normalizer {
+            provider_rules,
+            ..self
+        }
+    }
+
+    /// Enables/disables converting internationalized domains to their ASCII form; e.g. `bücher.de`
+    /// becomes `xn--bcher-kva.de`.
+    #[cfg(feature = "idna")]
+    pub fn punycode(self, punycode: bool) -> Self {
+        Normalizer { punycode, ..self }
+    }
+
+    /// Returns the normalized form of the given email address.
+    pub fn normalize(&self, email: &EmailAddress) -> EmailAddress {
+        let canonical = email.canonical();
+        let mut domain = String::from(canonical.get_domain());
+        let provider = if self.provider_rules {
+            provider(&domain)
+        } else {
+            None
+        };
+        if provider.is_some() {
+            if let Some(&(_, alias_of)) = ALIASES.iter().find(|(alias, _)| *alias == domain) {
+                domain = String::from(alias_of);
+            }
+        }
+        #[cfg(feature = "idna")]
+        if self.punycode {
+            domain = to_ascii(&domain);
+        }
+
+        let mut local_part = String::from(canonical.get_local_part());
+        if !local_part.starts_with('"') {
+            if self.strip_tags {
+                let separator = provider.map_or('+', |provider| provider.tag_separator);
+                if let Some(index) = local_part.find(separator).filter(|&index| index > 0) {
+                    local_part.truncate(index);
+                }
+            }
+            if provider.is_some_and(|provider| provider.ignores_dots) {
+                local_part.retain(|c| c != '.');
+            }
+        }
+        if self.lowercase_local_part {
+            local_part = local_part.to_lowercase();
+        }
+        EmailAddress::new(&local_part, &domain, None).unwrap_or(canonical)
+    }
+}
+
+/// The WASM surface of the normalizer, whose steps are properties; e.g. `normalizer.stripTags = true`.
+#[wasm_bindgen]
+impl Normalizer {
+    #[doc(hidden)]
+    #[wasm_bindgen(constructor)]
+    pub fn _new() -> Normalizer {
+        Normalizer::new()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = lowercaseLocalPart)]
+    pub fn _lowercase_local_part(&self) -> bool {
+        self.lowercase_local_part
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(setter = lowercaseLocalPart)]
+    pub fn _set_lowercase_local_part(&mut self, lowercase_local_part: bool) {
+        self.lowercase_local_part = lowercase_local_part;
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = stripTags)]
+    pub fn _strip_tags(&self) -> bool {
+        self.strip_tags
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(setter = stripTags)]
+    pub fn _set_strip_tags(&mut self, strip_tags: bool) {
+        self.strip_tags = strip_tags;
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = providerRules)]
+    pub fn _provider_rules(&self) -> bool {
+        self.provider_rules
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(setter = providerRules)]
+    pub fn _set_provider_rules(&mut self, provider_rules: bool) {
+        self.provider_rules = provider_rules;
+    }
+
+    #[doc(hidden)]
+    #[cfg(feature = "idna")]
+    #[wasm_bindgen(getter = punycode)]
+    pub fn _punycode(&self) -> bool {
+        self.punycode
+    }
+
+    #[doc(hidden)]
+    #[cfg(feature = "idna")]
+    #[wasm_bindgen(setter = punycode)]
+    pub fn _set_punycode(&mut self, punycode: bool) {
+        self.punycode = punycode;
+    }
+}
+
+#[wasm_bindgen]
+impl EmailAddress {
+    /// This is a WASM wrapper over `Normalizer::normalize`; e.g. to deduplicate addresses in a
+    /// form as the backend does.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "normalize")]
+    pub fn _normalize(&self, normalizer: &Normalizer) -> EmailAddress {
+        normalizer.normalize(self)
+    }
+}
+
+#[cfg(feature = "idna")]
+pub(crate) fn to_ascii(domain: &str) -> String {
+    if domain.starts_with('[') {
+        return String::from(domain);
+    }
+    idna::domain_to_ascii(domain).unwrap_or_else(|_| String::from(domain))
+}
+
+#[cfg(not(feature = "idna"))]
+pub(crate) fn to_ascii(domain: &str) -> String {
+    String::from(domain)
+}
+
+#[cfg(feature = "idna")]
+fn to_unicode(domain: &str) -> String {
+    if domain.starts_with('[') {
+        return String::from(domain);
+    }
+    match idna::domain_to_unicode(domain) {
+        (unicode, Ok(())) => unicode,
+        (_, Err(_)) => String::from(domain),
+    }
+}
+
+#[cfg(feature = "idna")]
+impl EmailAddress {
+    /// Returns the canonical domain in its ASCII form, with A-labels; e.g. `xn--bcher-kva.example`
+    /// for `Bücher.example`. A domain literal, or a domain that is not a valid internationalized
+    /// domain name, is only canonicalized.
+    ///
+    /// Requires the `idna` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@Bücher.example", None).unwrap();
+    /// assert_eq!(email.domain_ascii(), "xn--bcher-kva.example");
+    /// ```
+    pub fn domain_ascii(&self) -> String {
+        to_ascii(self.canonical().get_domain())
+    }
+
+    /// Returns the canonical domain in its Unicode form, with U-labels; e.g. `bücher.example` for
+    /// `xn--bcher-kva.example`. A domain literal, or a domain that is not a valid internationalized
+    /// domain name, is only canonicalized.
+    ///
+    /// Requires the `idna` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("foo@XN--BCHER-KVA.example", None).unwrap();
+    /// assert_eq!(email.domain_unicode(), "bücher.example");
+    /// ```
+    pub fn domain_unicode(&self) -> String {
+        to_unicode(self.canonical().get_domain())
+    }
+}
+
+/// The renderings of the domain for WASM, e.g. to display the U-labels and to submit the A-labels.
+#[cfg(feature = "idna")]
+#[wasm_bindgen]
+impl EmailAddress {
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = domainAscii)]
+    pub fn _domain_ascii(&self) -> String {
+        self.domain_ascii()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(getter = domainUnicode)]
+    pub fn _domain_unicode(&self) -> String {
+        self.domain_unicode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn normalize(normalizer: Normalizer, input: &str) -> String {
+        let email = EmailAddress::parse(input, None).unwrap();
+        normalizer.normalize(&email).to_string()
+    }
+
+    #[test]
+    fn only_canonicalizes_by_default() {
+        assert_eq!(
+            normalize(Normalizer::new(), "\"J Ane+x\"@GMAIL.com"),
+            "\"J Ane+x\"@gmail.com"
+        );
+        assert_eq!(
+            normalize(Normalizer::new(), "J.Ane+x@gmail.com"),
+            "J.Ane+x@gmail.com"
+        );
+    }
+
+    #[test]
+    fn strips_tags() {
+        let normalizer = Normalizer::new().strip_tags(true);
+        assert_eq!(normalize(normalizer, "jane+news@bar.com"), "jane@bar.com");
+        assert_eq!(normalize(normalizer, "+news@bar.com"), "+news@bar.com");
+        assert_eq!(
+            normalize(normalizer, "jane-news@yahoo.com"),
+            "jane-news@yahoo.com"
+        );
+        assert_eq!(
+            normalize(normalizer.provider_rules(true), "jane-news@yahoo.com"),
+            "jane@yahoo.com"
+        );
+    }
+
+    #[test]
+    fn applies_provider_rules() {
+        let normalizer = Normalizer::new().provider_rules(true);
+        assert_eq!(
+            normalize(normalizer, "j.a.ne@googlemail.com"),
+            "jane@gmail.com"
+        );
+        assert_eq!(
+            normalize(normalizer, "j.ane@hotmail.com"),
+            "j.ane@hotmail.com"
+        );
+        assert_eq!(
+            normalize(normalizer, "\"j.ane\"@gmail.com"),
+            "jane@gmail.com"
+        );
+        assert_eq!(
+            normalize(normalizer, "\"j ane\"@gmail.com"),
+            "\"j ane\"@gmail.com"
+        );
+        assert_eq!(
+            normalize(normalizer, "j.ane@example.com"),
+            "j.ane@example.com"
+        );
+    }
+
+    #[test]
+    fn lower_cases_local_part() {
+        let normalizer = Normalizer::new().lowercase_local_part(true);
+        assert_eq!(normalize(normalizer, "JÖRG@bar.com"), "jörg@bar.com");
+        assert_eq!(normalize(normalizer, "\"A B\"@bar.com"), "\"a b\"@bar.com");
+    }
+
+    #[test]
+    fn wasm_properties_set_the_steps() {
+        let mut normalizer = Normalizer::_new();
+        normalizer._set_strip_tags(true);
+        normalizer._set_provider_rules(true);
+        assert!(normalizer._strip_tags() && !normalizer._lowercase_local_part());
+        assert_eq!(
+            normalizer,
+            Normalizer::new().strip_tags(true).provider_rules(true)
+        );
+        let email = EmailAddress::parse("J.Ane+x@GoogleMail.com", None).unwrap();
+        assert_eq!(email._normalize(&normalizer).to_string(), "JAne@gmail.com");
+    }
+
+    #[cfg(feature = "idna")]
+    #[test]
+    fn converts_domain_to_punycode() {
+        let normalizer = Normalizer::new().punycode(true);
+        assert_eq!(
+            normalize(normalizer, "foo@Bücher.de"),
+            "foo@xn--bcher-kva.de"
+        );
+        assert_eq!(normalize(normalizer, "foo@[127.0.0.1]"), "foo@[127.0.0.1]");
+    }
+
+    #[cfg(all(feature = "idna", not(feature = "strict-only")))]
+    #[test]
+    fn renders_both_forms_of_the_domain() {
+        for input in &[
+            "foo@Bücher.de",
+            "foo@xn--bcher-kva.DE",
+            "foo@ (c) bücher . de",
+        ] {
+            let email = EmailAddress::parse(input, Some(crate::ParsingOptions::new(true))).unwrap();
+            assert_eq!(email._domain_ascii(), "xn--bcher-kva.de", "{}", input);
+            assert_eq!(email._domain_unicode(), "bücher.de", "{}", input);
+        }
+        let email = EmailAddress::parse("foo@[127.0.0.1]", None).unwrap();
+        assert_eq!(email.domain_ascii(), "[127.0.0.1]");
+        assert_eq!(email.domain_unicode(), "[127.0.0.1]");
+        let mut normalizer = Normalizer::_new();
+        normalizer._set_punycode(true);
+        assert!(normalizer._punycode());
+        assert_eq!(
+            normalize(normalizer, "foo@bücher.de"),
+            "foo@xn--bcher-kva.de"
+        );
+    }
+}
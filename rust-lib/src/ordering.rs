@@ -0,0 +1,103 @@
+use crate::email_address::EmailAddress;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+impl EmailAddress {
+    /// Compares the addresses by their canonical domains, and then by their canonical local parts;
+    /// e.g. to sort them with `sort_by`. See `sort_key` to sort many of them.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut emails: Vec<EmailAddress> = ["b@x.org", "a@y.org", "a@X.org"]
+    ///     .iter()
+    ///     .filter_map(|input| EmailAddress::parse(input, None))
+    ///     .collect();
+    /// emails.sort_by(EmailAddress::cmp_by_domain);
+    /// let sorted: Vec<_> = emails.iter().map(ToString::to_string).collect();
+    /// assert_eq!(sorted, ["a@X.org", "b@x.org", "a@y.org"]);
+    /// ```
+    pub fn cmp_by_domain(&self, other: &EmailAddress) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+
+    /// Returns the key of the address to sort it by domain; i.e. its canonical domain, and then its
+    /// canonical local part. Computing the key once per address, as `sort_by_cached_key` does, is
+    /// cheaper than `cmp_by_domain` for many addresses.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let mut emails: Vec<EmailAddress> = ["b@x.org", "a@y.org", "\"a\"@X.org"]
+    ///     .iter()
+    ///     .filter_map(|input| EmailAddress::parse(input, None))
+    ///     .collect();
+    /// emails.sort_by_cached_key(EmailAddress::sort_key);
+    /// assert_eq!(emails[0].to_string(), "\"a\"@X.org");
+    /// assert_eq!(emails[0].sort_key(), (String::from("x.org"), String::from("a")));
+    /// ```
+    pub fn sort_key(&self) -> (String, String) {
+        let canonical = self.canonical();
+        (
+            String::from(canonical.get_domain()),
+            String::from(canonical.get_local_part()),
+        )
+    }
+}
+
+/// Groups addresses by their canonical domains; the addresses of each domain are in order. See
+/// `EmailAddressList::group_by_domain` to group them by their normalized domains instead.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{group_by_domain, EmailAddress};
+///
+/// let emails = ["a@x.org", "b@y.org", "c@X.org"]
+///     .iter()
+///     .filter_map(|input| EmailAddress::parse(input, None));
+/// let groups = group_by_domain(emails);
+/// assert_eq!(groups.len(), 2);
+/// let x: Vec<_> = groups["x.org"].iter().map(ToString::to_string).collect();
+/// assert_eq!(x, ["a@x.org", "c@X.org"]);
+/// ```
+pub fn group_by_domain<I>(emails: I) -> BTreeMap<String, Vec<EmailAddress>>
+where
+    I: IntoIterator<Item = EmailAddress>,
+{
+    let mut groups: BTreeMap<String, Vec<EmailAddress>> = BTreeMap::new();
+    for email in emails {
+        let domain = String::from(email.canonical().get_domain());
+        groups.entry(domain).or_default().push(email);
+    }
+    groups
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use crate::email_address::ParsingOptions;
+
+    #[test]
+    fn orders_by_canonical_domain_then_local_part() {
+        let options = Some(ParsingOptions::new(true));
+        let parse = |input| EmailAddress::parse(input, options.clone()).unwrap();
+        assert_eq!(
+            parse("z@a.org").cmp_by_domain(&parse("a@b.org")),
+            Ordering::Less
+        );
+        assert_eq!(
+            parse("a . b@(comment)A.org").cmp_by_domain(&parse("\"a.b\"@a.org")),
+            Ordering::Equal
+        );
+        assert_eq!(
+            parse("B@a.org").cmp_by_domain(&parse("a@a.org")),
+            Ordering::Less
+        );
+        let groups = group_by_domain(alloc::vec![parse("a@(x)B.org"), parse("b@b.org")]);
+        assert_eq!(groups["b.org"].len(), 2);
+    }
+}
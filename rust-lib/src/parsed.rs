@@ -0,0 +1,352 @@
+use crate::tokens::{tokenize, TokenKind};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The structure of an email address, with the ranges of its components in the input; e.g. for
+/// the hovers and quick fixes of editors, or for linters of addresses in configuration files.
+///
+/// The structure is built from the tokens of `tokenize`, and therefore leniently: an invalid or
+/// partial input has a structure as well, with its unexpected characters in `invalid`. Parse the
+/// input to validate it.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{ParsedAddress, SegmentKind};
+///
+/// let parsed = ParsedAddress::new("john.\"doe\"@(home)[127.0.0.1]");
+/// let segments = parsed.local_part().segments();
+/// assert_eq!(segments[1].kind(), SegmentKind::QuotedString);
+/// assert_eq!(segments[1].content(), "doe");
+/// assert_eq!(parsed.at(), Some(10..11));
+///
+/// let domain = parsed.domain().unwrap();
+/// assert_eq!(domain.segments()[0].content(), "127.0.0.1");
+/// assert_eq!(domain.segments()[0].content_span(), 18..27);
+/// assert_eq!(parsed.comments()[0].content(), "home");
+/// assert_eq!(parsed.segment_at(20).unwrap().text(), "[127.0.0.1]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAddress<'i> {
+    input: &'i str,
+    local_part: AddressPart<'i>,
+    at: Option<Range<usize>>,
+    domain: Option<AddressPart<'i>>,
+    comments: Vec<Comment<'i>>,
+    invalid: Vec<Range<usize>>,
+}
+
+impl<'i> ParsedAddress<'i> {
+    /// Builds the structure of an input; an input without an `@` is all local part.
+    pub fn new(input: &'i str) -> Self {
+        let mut local_part = AddressPart::empty(0);
+        let mut at = None;
+        let mut domain: Option<AddressPart<'i>> = None;
+        let mut comments = Vec::new();
+        let mut invalid = Vec::new();
+        // The starts of the open comments, with their nested comments.
+        let mut open: Vec<(usize, Vec<Comment<'i>>)> = Vec::new();
+        for token in tokenize(input) {
+            let span = token.span();
+            let part = domain.as_mut().unwrap_or(&mut local_part);
+            match token.kind() {
+                TokenKind::CommentStart => open.push((span.start, Vec::new())),
+                TokenKind::CommentEnd => {
+                    if let Some((start, nested)) = open.pop() {
+                        let comment = Comment::new(input, start..span.end, span.start, nested);
+                        match open.last_mut() {
+                            Some((_, parent)) => parent.push(comment),
+                            None => comments.push(comment),
+                        }
+                    }
+                }
+                TokenKind::CommentText | TokenKind::Whitespace => (),
+                TokenKind::At => {
+                    at = Some(span.clone());
+                    domain = Some(AddressPart::empty(span.end));
+                }
+                TokenKind::Dot => part.extend(span, |part, span| part.dots.push(span)),
+                TokenKind::Invalid => {
+                    part.extend(span.clone(), |_, _| ());
+                    invalid.push(span);
+                }
+                kind => {
+                    let kind = match kind {
+                        TokenKind::LocalPartAtom => SegmentKind::Atom,
+                        TokenKind::QuotedString => SegmentKind::QuotedString,
+                        TokenKind::DomainLiteral => SegmentKind::DomainLiteral,
+                        _ => SegmentKind::Label,
+                    };
+                    let segment = Segment::new(input, kind, span.clone());
+                    part.extend(span, |part, _| part.segments.push(segment));
+                }
+            }
+        }
+        // The unterminated comments run up to the end of the input.
+        while let Some((start, nested)) = open.pop() {
+            let comment = Comment::new(input, start..input.len(), input.len(), nested);
+            match open.last_mut() {
+                Some((_, parent)) => parent.push(comment),
+                None => comments.push(comment),
+            }
+        }
+        ParsedAddress {
+            input,
+            local_part,
+            at,
+            domain,
+            comments,
+            invalid,
+        }
+    }
+
+    /// Returns the input.
+    pub fn input(&self) -> &'i str {
+        self.input
+    }
+
+    /// Returns the local part.
+    pub fn local_part(&self) -> &AddressPart<'i> {
+        &self.local_part
+    }
+
+    /// Returns the range of the `@` separating the local part and the domain, if any.
+    pub fn at(&self) -> Option<Range<usize>> {
+        self.at.clone()
+    }
+
+    /// Returns the domain, if there is an `@`.
+    pub fn domain(&self) -> Option<&AddressPart<'i>> {
+        self.domain.as_ref()
+    }
+
+    /// Returns the top-level comments, in the local part and in the domain, in order.
+    pub fn comments(&self) -> &[Comment<'i>] {
+        &self.comments
+    }
+
+    /// Returns the ranges of the characters that cannot appear where they are; e.g. a second `@`.
+    pub fn invalid(&self) -> &[Range<usize>] {
+        &self.invalid
+    }
+
+    /// Returns the segment, of the local part or of the domain, at a byte offset of the input, if
+    /// any; e.g. to show a hover.
+    pub fn segment_at(&self, offset: usize) -> Option<&Segment<'i>> {
+        self.local_part
+            .segments
+            .iter()
+            .chain(self.domain.iter().flat_map(|domain| domain.segments.iter()))
+            .find(|segment| segment.span.contains(&offset))
+    }
+}
+
+/// The local part or the domain of a `ParsedAddress`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressPart<'i> {
+    span: Range<usize>,
+    segments: Vec<Segment<'i>>,
+    dots: Vec<Range<usize>>,
+}
+
+impl<'i> AddressPart<'i> {
+    fn empty(offset: usize) -> Self {
+        AddressPart {
+            span: offset..offset,
+            segments: Vec::new(),
+            dots: Vec::new(),
+        }
+    }
+
+    /// Adds a component to the part, extending its span over it.
+    fn extend(&mut self, span: Range<usize>, add: impl FnOnce(&mut Self, Range<usize>)) {
+        if self.span.is_empty() {
+            self.span = span.clone();
+        } else {
+            self.span.end = span.end;
+        }
+        add(self, span);
+    }
+
+    /// Returns the range of the part, from its first segment or dot to its last one, without the
+    /// white space and comments around them; an empty range at its start if it has none.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the segments of the part, in order; i.e. its atoms and quoted strings, or its
+    /// labels and domain literal.
+    pub fn segments(&self) -> &[Segment<'i>] {
+        &self.segments
+    }
+
+    /// Returns the ranges of the dots between the segments.
+    pub fn dots(&self) -> &[Range<usize>] {
+        &self.dots
+    }
+}
+
+/// The kind of a `Segment`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SegmentKind {
+    /// An atom of the local part.
+    Atom,
+    /// A quoted string; in the local part, or misplaced in the domain.
+    QuotedString,
+    /// A label of the domain.
+    Label,
+    /// A domain literal.
+    DomainLiteral,
+}
+
+/// A segment of a local part or of a domain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment<'i> {
+    kind: SegmentKind,
+    text: &'i str,
+    span: Range<usize>,
+    content_span: Range<usize>,
+}
+
+impl<'i> Segment<'i> {
+    fn new(input: &'i str, kind: SegmentKind, span: Range<usize>) -> Self {
+        let text = &input[span.clone()];
+        let content_span = match kind {
+            SegmentKind::QuotedString => delimited_content(span.clone(), text, '"'),
+            SegmentKind::DomainLiteral => delimited_content(span.clone(), text, ']'),
+            SegmentKind::Atom | SegmentKind::Label => span.clone(),
+        };
+        Segment {
+            kind,
+            text,
+            span,
+            content_span,
+        }
+    }
+
+    /// Returns the kind of the segment.
+    pub fn kind(&self) -> SegmentKind {
+        self.kind
+    }
+
+    /// Returns the text of the segment, as it is in the input.
+    pub fn text(&self) -> &'i str {
+        self.text
+    }
+
+    /// Returns the range of the segment.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the content of the segment; i.e. without the double quotes of a quoted string, or
+    /// the brackets of a domain literal. The quoted pairs are kept as they are.
+    pub fn content(&self) -> &'i str {
+        let start = self.content_span.start - self.span.start;
+        &self.text[start..start + self.content_span.len()]
+    }
+
+    /// Returns the range of the content of the segment.
+    pub fn content_span(&self) -> Range<usize> {
+        self.content_span.clone()
+    }
+}
+
+/// A comment of a `ParsedAddress`, with its nested comments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Comment<'i> {
+    text: &'i str,
+    span: Range<usize>,
+    content_end: usize,
+    nested: Vec<Comment<'i>>,
+}
+
+impl<'i> Comment<'i> {
+    fn new(input: &'i str, span: Range<usize>, content_end: usize, nested: Vec<Self>) -> Self {
+        Comment {
+            text: &input[span.clone()],
+            span,
+            content_end,
+            nested,
+        }
+    }
+
+    /// Returns the text of the comment, with its parentheses.
+    pub fn text(&self) -> &'i str {
+        self.text
+    }
+
+    /// Returns the range of the comment; up to the end of the input if it is unterminated.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the content of the comment, without its parentheses, but with its nested comments.
+    pub fn content(&self) -> &'i str {
+        &self.text[1..self.content_end - self.span.start]
+    }
+
+    /// Returns the range of the content of the comment.
+    pub fn content_span(&self) -> Range<usize> {
+        self.span.start + 1..self.content_end
+    }
+
+    /// Returns the nested comments, in order.
+    pub fn nested(&self) -> &[Comment<'i>] {
+        &self.nested
+    }
+}
+
+/// Returns the range of the content of a delimited token, whose last character is the closing
+/// delimiter if it is terminated.
+fn delimited_content(span: Range<usize>, text: &str, end: char) -> Range<usize> {
+    let mut chars = text.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            c if c == end => return span.start + 1..span.start + index,
+            _ => (),
+        }
+    }
+    span.start + 1..span.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_the_tree_of_nested_and_unterminated_comments() {
+        let parsed = ParsedAddress::new(" a(x(y)z) . b @ c(d");
+        let local_part = parsed.local_part();
+        assert_eq!(local_part.span(), 1..13);
+        assert_eq!(local_part.dots().first(), Some(&(10..11)));
+        assert_eq!(local_part.segments()[1].text(), "b");
+        let comments = parsed.comments();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].content(), "x(y)z");
+        assert_eq!(comments[0].nested()[0].span(), 4..7);
+        assert_eq!(comments[1].text(), "(d");
+        assert_eq!(comments[1].content_span(), 18..19);
+        assert_eq!(parsed.domain().unwrap().span(), 16..17);
+    }
+
+    #[test]
+    fn structures_invalid_inputs() {
+        let parsed = ParsedAddress::new("\"a\\\"@b,c");
+        assert_eq!(parsed.at(), None);
+        let segment = &parsed.local_part().segments()[0];
+        assert_eq!(segment.kind(), SegmentKind::QuotedString);
+        assert_eq!(segment.content(), "a\\\"@b,c");
+        let parsed = ParsedAddress::new("a@b,c@");
+        assert_eq!(parsed.invalid(), [3..4, 5..6]);
+        assert_eq!(parsed.domain().unwrap().span(), 2..6);
+        assert_eq!(parsed.segment_at(4).unwrap().text(), "c");
+        assert!(parsed.segment_at(1).is_none());
+        let parsed = ParsedAddress::new("a@");
+        assert_eq!(parsed.domain().unwrap().span(), 2..2);
+        assert!(parsed.domain().unwrap().segments().is_empty());
+    }
+}
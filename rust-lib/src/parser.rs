@@ -0,0 +1,101 @@
+use crate::bulk::{ParseEmails, ParsedEmails};
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+#[cfg(not(feature = "strict-only"))]
+use crate::scanner::Scanner;
+
+/// A reusable handle that parses email addresses as per the options it holds; e.g. in hot loops,
+/// where the options would otherwise be passed, and cloned, on every call.
+///
+/// The options are set once, when the parser is instantiated; the data the parsing depends on,
+/// like dictionaries, may later be cached by the handle as well.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// # #[cfg(not(feature = "strict-only"))] {
+/// let parser = EmailParser::new(ParsingOptions::new(true));
+/// let email = parser.parse("\"foo\".bar@baz.com").unwrap();
+/// assert_eq!(email.get_local_part(), "\"foo\".bar");
+///
+/// let results: Vec<_> = parser.parse_many(["foo@bar.com", "foo"]).collect();
+/// assert!(results[0].is_ok() && results[1].is_err());
+///
+/// let found: Vec<_> = parser.find_all("Mail foo@bar.com or baz@qux.org.").collect();
+/// assert_eq!(found[1].email().to_string(), "baz@qux.org");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmailParser {
+    options: ParsingOptions,
+}
+
+impl EmailParser {
+    /// Instantiates a parser holding the given options.
+    pub fn new(options: ParsingOptions) -> Self {
+        EmailParser { options }
+    }
+
+    /// Returns the options the parser holds.
+    pub fn options(&self) -> &ParsingOptions {
+        &self.options
+    }
+
+    /// Parses a given string as an email address, like `EmailAddress::try_parse` with the options
+    /// of the parser.
+    pub fn parse(&self, input: &str) -> Result<EmailAddress, ParseError> {
+        EmailAddress::parse_with(input, &self.options).map(|(email, _)| email)
+    }
+
+    /// Parses each string of an iterator as an email address, like `ParseEmails::parse_emails`
+    /// with the options of the parser.
+    pub fn parse_many<I>(&self, inputs: I) -> ParsedEmails<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        inputs.into_iter().parse_emails(&self.options)
+    }
+
+    /// Finds the email addresses in a free text, like `Scanner`, validating them with the options
+    /// of the parser. Not available with the `strict-only` feature.
+    #[cfg(not(feature = "strict-only"))]
+    pub fn find_all<'t>(&self, text: &'t str) -> Scanner<'t> {
+        Scanner::new(text).options(self.options.clone())
+    }
+}
+
+impl From<ParsingOptions> for EmailParser {
+    fn from(options: ParsingOptions) -> Self {
+        EmailParser::new(options)
+    }
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn parses_as_per_the_options() {
+        let strict = EmailParser::default();
+        let lax = EmailParser::from(ParsingOptions::new(true));
+        assert!(strict.parse("foo . bar@baz.com").is_err());
+        assert_eq!(
+            lax.parse("foo . bar@baz.com").ok(),
+            EmailAddress::parse("foo . bar@baz.com", Some(ParsingOptions::new(true)))
+        );
+        let results: Vec<_> = lax.parse_many(&["a@b.com", "a.\"b\"@c.com", "@"]).collect();
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+    }
+
+    #[test]
+    fn finds_as_per_the_options() {
+        let strict = EmailParser::default();
+        let hyphens = EmailParser::new(ParsingOptions::default().allow_leading_hyphen(true));
+        let found: Vec<_> = hyphens.find_all("See foo@-bar.com, foo@bar.com.").collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(strict.find_all("See foo@-bar.com, foo@bar.com.").count(), 1);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::find_separator;
+use crate::prefilter::{find_complexity, is_too_large};
+use alloc::string::String;
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::*;
+
+/// What a partial input is expecting next to be an email address; see `validate_partial`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expecting {
+    /// More of the local part; e.g. after `foo.`.
+    LocalPart,
+    /// The `@` after the local part; e.g. after `foo`.
+    At,
+    /// More of the domain; e.g. after `foo@` or `foo@bar.`.
+    Domain,
+    /// The closing double quote of a quoted string; e.g. after `"foo bar`.
+    ClosingQuote,
+    /// The closing square bracket of a domain literal; e.g. after `foo@[127.0`.
+    ClosingBracket,
+    /// The closing parenthesis of a comment; e.g. after `foo@[127.0.0.1] (work`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::*;
+    ///
+    /// assert_eq!(
+    ///     validate_partial("foo@[127.0.0.1] (work", &ParsingOptions::default()),
+    ///     PartialValidation::Incomplete { expecting: Expecting::ClosingParenthesis }
+    /// );
+    /// ```
+    ClosingParenthesis,
+}
+
+/// The validity of a partial input, like the value of a form field as it is being typed; see
+/// `validate_partial`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartialValidation {
+    /// The input is an email address.
+    Complete,
+    /// The input is not an email address yet, but it can become one.
+    Incomplete {
+        /// What the input is expecting next.
+        expecting: Expecting,
+    },
+    /// The input cannot become an email address, whatever follows.
+    Invalid {
+        /// The byte offset of the first character that prevents it.
+        at: usize,
+    },
+}
+
+/// Validates an input that may not be complete yet as per the options; e.g. to give feedback as an
+/// address is typed, without flagging `foo@exam` as an error before `ple.com` is typed.
+///
+/// An input is incomplete if it can become an email address by appending to it. Note that an
+/// input that is complete may still be the beginning of another address; e.g. `foo@bar.co` is
+/// complete, even if `foo@bar.com` is being typed.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let options = ParsingOptions::default();
+/// assert_eq!(validate_partial("foo@bar.com", &options), PartialValidation::Complete);
+/// assert_eq!(
+///     validate_partial("foo", &options),
+///     PartialValidation::Incomplete { expecting: Expecting::At }
+/// );
+/// assert_eq!(
+///     validate_partial("foo@bar.", &options),
+///     PartialValidation::Incomplete { expecting: Expecting::Domain }
+/// );
+/// assert_eq!(
+///     validate_partial("\"foo bar", &options),
+///     PartialValidation::Incomplete { expecting: Expecting::ClosingQuote }
+/// );
+/// assert_eq!(validate_partial("foo..bar@", &options), PartialValidation::Invalid { at: 4 });
+/// ```
+pub fn validate_partial(input: &str, options: &ParsingOptions) -> PartialValidation {
+    // Beyond the limits of the options, appending cannot make an address, hence the grammar does
+    // not run on such an input.
+    if is_too_large(input, options) {
+        let at = (0..=options.max_input_length)
+            .rev()
+            .find(|&index| input.is_char_boundary(index))
+            .unwrap_or(0);
+        return PartialValidation::Invalid { at };
+    }
+    if let Some(at) = find_complexity(input, options) {
+        return PartialValidation::Invalid { at };
+    }
+    if let Some(expecting) = expecting(input, options) {
+        return expecting.map_or(PartialValidation::Complete, |expecting| {
+            PartialValidation::Incomplete { expecting }
+        });
+    }
+    // The completable inputs are closed under prefixes, so that the longest completable prefix
+    // is found by a binary search; the empty one is always completable.
+    let boundaries: Vec<usize> = input.char_indices().map(|(index, _)| index).collect();
+    let (mut low, mut high) = (0, boundaries.len());
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if expecting(&input[..boundaries[middle]], options).is_some() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    PartialValidation::Invalid {
+        at: boundaries[low],
+    }
+}
+
+/// Returns `Some(None)` if the input is an email address, `Some(Some(expecting))` if appending to
+/// it can make one, and `None` otherwise.
+fn expecting(input: &str, options: &ParsingOptions) -> Option<Option<Expecting>> {
+    if input.is_empty() {
+        return Some(Some(Expecting::LocalPart));
+    }
+    let is_valid = |suffix: &str| {
+        let mut completed = String::from(input);
+        completed.push_str(suffix);
+        EmailAddress::is_valid(&completed, Some(options.clone()))
+    };
+    let completions: &[(&str, Option<Expecting>)] = match find_separator(input) {
+        Some(_) => &[
+            ("", None),
+            ("a", Some(Expecting::Domain)),
+            ("]", Some(Expecting::ClosingBracket)),
+            (")", Some(Expecting::ClosingParenthesis)),
+        ],
+        None => &[
+            ("@a", Some(Expecting::At)),
+            ("a@a", Some(Expecting::LocalPart)),
+            ("\"@a", Some(Expecting::ClosingQuote)),
+            ("a\"@a", Some(Expecting::ClosingQuote)),
+            (")@a", Some(Expecting::ClosingParenthesis)),
+        ],
+    };
+    completions
+        .iter()
+        .find(|(suffix, _)| is_valid(suffix))
+        .map(|(_, expecting)| *expecting)
+}
+
+/// The validity of a partial input, for WASM; see `validate_partial`.
+#[doc(hidden)]
+#[wasm_bindgen(js_name = "PartialValidation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmPartialValidation(PartialValidation);
+
+#[wasm_bindgen(js_class = "PartialValidation")]
+impl WasmPartialValidation {
+    /// Returns whether the input is an email address.
+    #[wasm_bindgen(getter, js_name = "isComplete")]
+    pub fn is_complete(&self) -> bool {
+        self.0 == PartialValidation::Complete
+    }
+
+    /// Returns what the input is expecting next, if it is incomplete.
+    #[wasm_bindgen(getter)]
+    pub fn expecting(&self) -> Option<Expecting> {
+        match self.0 {
+            PartialValidation::Incomplete { expecting } => Some(expecting),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte offset of the first character that prevents the input from becoming an
+    /// email address, if it is invalid.
+    #[wasm_bindgen(getter)]
+    pub fn at(&self) -> Option<usize> {
+        match self.0 {
+            PartialValidation::Invalid { at } => Some(at),
+            _ => None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl EmailAddress {
+    /// This is a WASM wrapper over `validate_partial`.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "validatePartial")]
+    pub fn _validate_partial(
+        input: &str,
+        options: Option<ParsingOptions>,
+    ) -> WasmPartialValidation {
+        WasmPartialValidation(validate_partial(input, &options.unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    fn incomplete(expecting: Expecting) -> PartialValidation {
+        PartialValidation::Incomplete { expecting }
+    }
+
+    #[test]
+    fn accepts_prefixes_of_addresses() {
+        let options = ParsingOptions::default();
+        let address = "\"jane doe\"@[127.0.0.1]";
+        for end in 0..address.len() {
+            let validation = validate_partial(&address[..end], &options);
+            assert!(
+                matches!(validation, PartialValidation::Incomplete { .. }),
+                "{}",
+                &address[..end]
+            );
+        }
+        assert_eq!(
+            validate_partial("", &options),
+            incomplete(Expecting::LocalPart)
+        );
+        assert_eq!(
+            validate_partial("foo.", &options),
+            incomplete(Expecting::LocalPart)
+        );
+        assert_eq!(
+            validate_partial("foo@", &options),
+            incomplete(Expecting::Domain)
+        );
+        assert_eq!(
+            validate_partial("foo@bar-", &options),
+            incomplete(Expecting::Domain)
+        );
+        assert_eq!(
+            validate_partial("foo@[127.0", &options),
+            incomplete(Expecting::ClosingBracket)
+        );
+        assert_eq!(
+            validate_partial("foo@[127.0.0.1] (work", &options),
+            incomplete(Expecting::ClosingParenthesis)
+        );
+        assert_eq!(
+            validate_partial("foo@exam", &options),
+            PartialValidation::Complete
+        );
+    }
+
+    #[test]
+    fn locates_the_first_invalid_character() {
+        let options = ParsingOptions::default();
+        assert_eq!(
+            validate_partial("foo@-bar.com", &options),
+            PartialValidation::Invalid { at: 4 }
+        );
+        assert_eq!(
+            validate_partial("föö@bar..com", &options),
+            PartialValidation::Invalid { at: 10 }
+        );
+        assert_eq!(
+            validate_partial("@", &options),
+            PartialValidation::Invalid { at: 0 }
+        );
+        let options = ParsingOptions::default().allow_quoted_local_part(false);
+        assert_eq!(
+            validate_partial("\"foo", &options),
+            PartialValidation::Invalid { at: 0 }
+        );
+    }
+
+    #[test]
+    fn rejects_inputs_beyond_the_limits() {
+        let options = ParsingOptions::default();
+        let long = format!("foo@{}", "é".repeat(500));
+        assert_eq!(
+            validate_partial(&long, &options),
+            PartialValidation::Invalid { at: 998 }
+        );
+        let long = format!("fooo@{}", "é".repeat(500));
+        assert_eq!(
+            validate_partial(&long, &options),
+            PartialValidation::Invalid { at: 997 }
+        );
+        let nested = format!("foo@[127.0.0.1] {}", "(".repeat(17));
+        assert_eq!(
+            validate_partial(&nested, &options),
+            PartialValidation::Invalid { at: 32 }
+        );
+        let spaced = format!("foo@[127.0.0.1]{}", " (a)".repeat(65));
+        assert_eq!(
+            validate_partial(&spaced, &options),
+            PartialValidation::Invalid { at: 271 }
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::canonical::{canonical_domain, unquoted_local_part};
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::{find_separator, ErrorKind, Hint, ParseError};
+use crate::validate::{validate_domain, validate_local_part};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// A pattern of email addresses, like `*@example.com`, `admin@*`, or `*@*.example.com`; e.g. for
+/// routing rules and suppression lists.
+///
+/// The local part of a pattern is either `*`, that matches any local part, or a local part, that
+/// matches the same local part once unquoted, ignoring case. The domain is either `*`, that
+/// matches any domain, or a domain whose labels are matched one by one, ignoring case; a `*`
+/// label matches a single label, but as the first label, it matches one or more of them, so that
+/// `*.example.com` matches any subdomain of `example.com`, but not `example.com` itself.
+///
+/// Labels are matched as a whole, never as substrings: `*@example.com` matches neither
+/// `foo@notexample.com` nor `foo@example.com.evil`. A domain literal only matches the same domain
+/// literal, or a `*` domain.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{AddressPattern, EmailAddress};
+///
+/// let email = EmailAddress::parse("Admin@mail.Example.com", None).unwrap();
+/// assert!("*@*.example.com".parse::<AddressPattern>().unwrap().matches(&email));
+/// assert!("admin@*".parse::<AddressPattern>().unwrap().matches(&email));
+/// assert!("*@mail.*.com".parse::<AddressPattern>().unwrap().matches(&email));
+/// assert!(!"*@example.com".parse::<AddressPattern>().unwrap().matches(&email));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddressPattern {
+    pattern: String,
+    /// The lower case unquoted local part, or `None` for `*`.
+    local_part: Option<String>,
+    /// The lower case labels of the domain, with `None` for `*`; or the domain literal as a single
+    /// label. A `*` domain has no label.
+    labels: Vec<Option<String>>,
+}
+
+impl AddressPattern {
+    /// Parses a pattern; the parts other than `*` are validated leniently, as per
+    /// `validate_local_part` and `validate_domain` with lax options.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{AddressPattern, ErrorKind, Hint};
+    ///
+    /// assert!(AddressPattern::new("\"john doe\"@*.example.com").is_ok());
+    ///
+    /// let error = AddressPattern::new("*.example.com").unwrap_err();
+    /// assert_eq!(error.hint(), Some(Hint::MissingAt));
+    ///
+    /// let error = AddressPattern::new("*@example.*.").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+    /// ```
+    pub fn new(pattern: &str) -> Result<AddressPattern, ParseError> {
+        let options = ParsingOptions::new(true);
+        let at = find_separator(pattern).ok_or_else(|| {
+            ParseError::new(
+                ErrorKind::Invalid,
+                pattern,
+                0..pattern.len(),
+                Some(Hint::MissingAt),
+            )
+        })?;
+        let (local_part, domain) = (&pattern[..at], &pattern[at + 1..]);
+        let domain_error = |hint| {
+            ParseError::new(
+                ErrorKind::InvalidDomain,
+                pattern,
+                at + 1..pattern.len(),
+                hint,
+            )
+        };
+        let local_part = match local_part {
+            "*" => None,
+            _ => {
+                validate_local_part(local_part, &options).map_err(|error| {
+                    ParseError::new(ErrorKind::InvalidLocalPart, pattern, 0..at, error.hint())
+                })?;
+                Some(unquoted_local_part(local_part).to_lowercase())
+            }
+        };
+        let labels = match domain {
+            "*" => Vec::new(),
+            _ if domain.starts_with('[') => {
+                validate_domain(domain, &options).map_err(|error| domain_error(error.hint()))?;
+                alloc::vec![Some(canonical_domain(domain))]
+            }
+            _ => {
+                // A wildcard label is validated as any other label would be.
+                let labels: Vec<&str> = domain.split('.').collect();
+                let substituted: Vec<&str> = labels
+                    .iter()
+                    .map(|label| if *label == "*" { "a" } else { label })
+                    .collect();
+                validate_domain(&substituted.join("."), &options)
+                    .map_err(|error| domain_error(error.hint()))?;
+                labels
+                    .iter()
+                    .map(|label| Some(canonical_domain(label)).filter(|_| *label != "*"))
+                    .collect()
+            }
+        };
+        Ok(AddressPattern {
+            pattern: String::from(pattern),
+            local_part,
+            labels,
+        })
+    }
+
+    /// Returns whether an address matches the pattern.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{AddressPattern, EmailAddress};
+    ///
+    /// let pattern = AddressPattern::new("*@*.example.com").unwrap();
+    /// assert!(pattern.matches(&EmailAddress::parse("foo@a.b.example.com", None).unwrap()));
+    /// assert!(!pattern.matches(&EmailAddress::parse("foo@example.com", None).unwrap()));
+    /// assert!(!pattern.matches(&EmailAddress::parse("foo@badexample.com", None).unwrap()));
+    /// ```
+    pub fn matches(&self, email: &EmailAddress) -> bool {
+        if let Some(local_part) = &self.local_part {
+            if email.local_part_unquoted().to_lowercase() != *local_part {
+                return false;
+            }
+        }
+        if self.labels.is_empty() {
+            return true;
+        }
+        let domain = canonical_domain(email.get_domain());
+        let labels: Vec<&str> = match domain.starts_with('[') {
+            true => alloc::vec![domain.as_str()],
+            false => domain.split('.').collect(),
+        };
+        let is_length_matching = match self.labels[0] {
+            // A leading wildcard label matches one or more labels.
+            None => labels.len() >= self.labels.len(),
+            Some(_) => labels.len() == self.labels.len(),
+        };
+        is_length_matching
+            && self
+                .labels
+                .iter()
+                .rev()
+                .zip(labels.iter().rev())
+                .all(|(pattern, label)| pattern.as_deref().is_none_or(|pattern| pattern == *label))
+    }
+}
+
+impl FromStr for AddressPattern {
+    type Err = ParseError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        AddressPattern::new(pattern)
+    }
+}
+
+impl fmt::Display for AddressPattern {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn matches(pattern: &str, input: &str) -> bool {
+        let email = EmailAddress::parse(input, Some(ParsingOptions::new(true))).unwrap();
+        AddressPattern::new(pattern).unwrap().matches(&email)
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn matches_whole_labels() {
+        assert!(matches("*@example.com", "foo@EXAMPLE.com"));
+        assert!(matches("*@example.com", "foo@(comment)example . com"));
+        assert!(!matches("*@example.com", "foo@notexample.com"));
+        assert!(!matches("*@example.com", "foo@example.com.evil"));
+        assert!(!matches("*@example.com", "foo@mail.example.com"));
+        assert!(matches("*@*.example.com", "foo@mail.example.com"));
+        assert!(matches("*@*.example.com", "foo@a.b.example.com"));
+        assert!(!matches("*@*.example.com", "foo@example.com"));
+        assert!(matches("*@mail.*.com", "foo@mail.example.com"));
+        assert!(!matches("*@mail.*.com", "foo@mail.a.b.com"));
+        assert!(matches("*@*", "foo@[127.0.0.1]"));
+        assert!(matches("*@[127.0.0.1]", "foo@[ 127.0.0.1 ]"));
+        assert!(!matches("*@*.0.0.1", "foo@[127.0.0.1]"));
+    }
+
+    #[test]
+    fn matches_unquoted_local_parts_ignoring_case() {
+        assert!(matches("admin@*", "Admin@example.com"));
+        assert!(matches("admin@*", "\"admin\"@example.com"));
+        assert!(matches("\"john doe\"@*", "\"John Doe\"@example.com"));
+        assert!(!matches("admin@*", "admin+tag@example.com"));
+        assert!(!matches("admin@example.org", "admin@example.com"));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let error = AddressPattern::new("foo..bar@*").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+        assert_eq!(error.span(), 0..8);
+        let error = AddressPattern::new("*@-example.com").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+        assert_eq!(error.hint(), Some(Hint::MisplacedHyphen));
+        assert!(AddressPattern::new("*@").is_err());
+        assert_eq!(
+            AddressPattern::new("*@*.Example.com").unwrap().to_string(),
+            "*@*.Example.com"
+        );
+    }
+}
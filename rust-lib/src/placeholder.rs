@@ -0,0 +1,354 @@
+use crate::email_address::EmailAddress;
+use crate::rules::{ValidationRule, Violation};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The local parts that people type instead of their addresses, ignoring trailing digits.
+const PLACEHOLDER_WORDS: &[&str] = &[
+    "anonymous",
+    "dummy",
+    "email",
+    "fake",
+    "foo",
+    "foobar",
+    "junk",
+    "na",
+    "nil",
+    "no",
+    "noemail",
+    "nomail",
+    "none",
+    "noone",
+    "nobody",
+    "null",
+    "sample",
+    "spam",
+    "test",
+    "tester",
+    "testing",
+    "unknown",
+    "user",
+];
+
+/// The domains of placeholder addresses, on top of the reserved ones.
+const PLACEHOLDER_DOMAINS: &[&str] = &[
+    "email.invalid",
+    "fake.com",
+    "noemail.com",
+    "nomail.com",
+    "none.com",
+    "null.com",
+    "test.com",
+];
+
+/// The reserved top-level domains of RFC 2606 and RFC 6761.
+const RESERVED_TOP_LEVEL_DOMAINS: &[&str] = &["example", "invalid", "localhost", "test"];
+
+/// The rows of keys, and the sequences, that keyboard mashing follows.
+const KEYBOARD_ROWS: &[&str] = &[
+    "qwertyuiop",
+    "asdfghjkl",
+    "zxcvbnm",
+    "1234567890",
+    "abcdefghijklmnopqrstuvwxyz",
+];
+
+/// A signal that an address is a placeholder rather than a real one; see `PlaceholderHeuristics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PlaceholderSignal {
+    /// The domain is reserved for documentation and testing, like `example.com` or `foo.test`, or
+    /// it is a well-known placeholder domain, like `test.com`.
+    ReservedDomain,
+    /// The local part is a placeholder word, like `test`, `noemail`, or `none1`.
+    PlaceholderWord,
+    /// The local part, or the first label of the domain if it has 4 characters or more, is
+    /// keyboard mashing, like `asdf`, `qwerty`, `1234`, or `aaaa`.
+    KeyboardPattern,
+    /// The local part is the first label of the domain, like `test@test.com`.
+    RepeatedPart,
+    /// Both the local part and the first label of the domain are single characters, like
+    /// `a@a.com`.
+    SingleCharacters,
+}
+
+impl PlaceholderSignal {
+    /// Returns a stable, snake case code of the signal; e.g. `keyboard_pattern`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlaceholderSignal::ReservedDomain => "reserved_domain",
+            PlaceholderSignal::PlaceholderWord => "placeholder_word",
+            PlaceholderSignal::KeyboardPattern => "keyboard_pattern",
+            PlaceholderSignal::RepeatedPart => "repeated_part",
+            PlaceholderSignal::SingleCharacters => "single_characters",
+        }
+    }
+}
+
+/// The heuristics that flag the addresses that are likely placeholders, or test addresses, rather
+/// than real ones; e.g. `test@test.com`, `asdf@asdf.com`, `a@a.com`, or `noemail@...`. A signal
+/// for the quality of sign-ups, rather than a proof: the addresses may still be deliverable.
+///
+/// The local parts are compared ignoring case and sub-addresses (tags). Each signal of
+/// `PlaceholderSignal` is a heuristic that can be disabled; all are enabled by default, and more
+/// placeholder words can be added. As a `ValidationRule`, it rejects the placeholders with the
+/// `placeholder` code.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let email = EmailAddress::parse("asdf@asdf.com", None).unwrap();
+/// assert!(email.looks_like_placeholder());
+/// assert!(!EmailAddress::parse("jane@gmail.com", None).unwrap().looks_like_placeholder());
+///
+/// let heuristics = PlaceholderHeuristics::new().keyboard_patterns(false).words(["customer"]);
+/// assert_eq!(heuristics.signal(&email), Some(PlaceholderSignal::RepeatedPart));
+/// let email = EmailAddress::parse("customer42@gmail.com", None).unwrap();
+/// assert_eq!(heuristics.signal(&email), Some(PlaceholderSignal::PlaceholderWord));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderHeuristics {
+    reserved_domains: bool,
+    placeholder_words: bool,
+    keyboard_patterns: bool,
+    repeated_parts: bool,
+    single_characters: bool,
+    words: Vec<String>,
+}
+
+impl Default for PlaceholderHeuristics {
+    fn default() -> Self {
+        PlaceholderHeuristics {
+            reserved_domains: true,
+            placeholder_words: true,
+            keyboard_patterns: true,
+            repeated_parts: true,
+            single_characters: true,
+            words: Vec::new(),
+        }
+    }
+}
+
+impl PlaceholderHeuristics {
+    /// Instantiates the heuristics with all the signals enabled.
+    pub fn new() -> Self {
+        PlaceholderHeuristics::default()
+    }
+
+    /// Sets whether reserved and placeholder domains are flagged. Defaults to `true`.
+    pub fn reserved_domains(self, reserved_domains: bool) -> Self {
+        PlaceholderHeuristics {
+            reserved_domains,
+            ..self
+        }
+    }
+
+    /// Sets whether placeholder words are flagged. Defaults to `true`.
+    pub fn placeholder_words(self, placeholder_words: bool) -> Self {
+        PlaceholderHeuristics {
+            placeholder_words,
+            ..self
+        }
+    }
+
+    /// Sets whether keyboard mashing is flagged. Defaults to `true`.
+    pub fn keyboard_patterns(self, keyboard_patterns: bool) -> Self {
+        PlaceholderHeuristics {
+            keyboard_patterns,
+            ..self
+        }
+    }
+
+    /// Sets whether a local part that repeats the domain is flagged. Defaults to `true`.
+    pub fn repeated_parts(self, repeated_parts: bool) -> Self {
+        PlaceholderHeuristics {
+            repeated_parts,
+            ..self
+        }
+    }
+
+    /// Sets whether single-character parts are flagged. Defaults to `true`.
+    pub fn single_characters(self, single_characters: bool) -> Self {
+        PlaceholderHeuristics {
+            single_characters,
+            ..self
+        }
+    }
+
+    /// Adds placeholder words to the built-in ones; e.g. the names of the fields of a form.
+    pub fn words<'w>(mut self, words: impl IntoIterator<Item = &'w str>) -> Self {
+        self.words
+            .extend(words.into_iter().map(|word| word.to_lowercase()));
+        self
+    }
+
+    /// Returns the first signal that the address is a placeholder, if any; in the order of
+    /// `PlaceholderSignal`.
+    pub fn signal(&self, email: &EmailAddress) -> Option<PlaceholderSignal> {
+        let local_part = email.local_part_unquoted().to_lowercase();
+        let local_part = local_part.split('+').next().unwrap_or_default();
+        let domain = email
+            .canonical()
+            .get_domain()
+            .trim_end_matches('.')
+            .to_string();
+        let label = domain.split('.').next().unwrap_or_default();
+        let top_level_domain = domain.rsplit('.').next().unwrap_or_default();
+        let word = local_part.trim_end_matches(|c: char| c.is_ascii_digit());
+        if self.reserved_domains
+            && (RESERVED_TOP_LEVEL_DOMAINS.contains(&top_level_domain)
+                || PLACEHOLDER_DOMAINS.contains(&domain.as_str())
+                || ["example.com", "example.net", "example.org"]
+                    .iter()
+                    .any(|reserved| is_domain_or_subdomain(&domain, reserved)))
+        {
+            return Some(PlaceholderSignal::ReservedDomain);
+        }
+        if self.placeholder_words
+            && (PLACEHOLDER_WORDS.contains(&word) || self.words.iter().any(|w| w == word))
+        {
+            return Some(PlaceholderSignal::PlaceholderWord);
+        }
+        // Short labels are common in the domains of real providers; e.g. `sdf.org`.
+        let is_mashed_label = label.len() >= 4 && is_keyboard_pattern(label);
+        if self.keyboard_patterns && (is_keyboard_pattern(local_part) || is_mashed_label) {
+            return Some(PlaceholderSignal::KeyboardPattern);
+        }
+        if self.repeated_parts && !domain.starts_with('[') && local_part == label {
+            return Some(PlaceholderSignal::RepeatedPart);
+        }
+        if self.single_characters && local_part.chars().count() == 1 && label.chars().count() == 1 {
+            return Some(PlaceholderSignal::SingleCharacters);
+        }
+        None
+    }
+
+    /// Returns whether the address is likely a placeholder, as per the heuristics.
+    pub fn looks_like_placeholder(&self, email: &EmailAddress) -> bool {
+        self.signal(email).is_some()
+    }
+}
+
+impl ValidationRule for PlaceholderHeuristics {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        match self.signal(email) {
+            Some(signal) => Err(Violation::new(
+                "placeholder",
+                format!(
+                    "'{}' looks like a placeholder rather than a real address ({}).",
+                    email,
+                    signal.code()
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns whether a domain is another one, or one of its subdomains.
+fn is_domain_or_subdomain(domain: &str, other: &str) -> bool {
+    domain == other
+        || domain
+            .strip_suffix(other)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+/// Returns whether a string of at least 3 characters is a single repeated character, or a run of
+/// keys of a row of the keyboard, or of the alphabet, in either direction; like `aaa`, `asdf`, or
+/// `4321`.
+fn is_keyboard_pattern(part: &str) -> bool {
+    if part.len() < 3 {
+        return false;
+    }
+    let mut chars = part.chars();
+    let first = chars.next();
+    if chars.all(|c| Some(c) == first) {
+        return true;
+    }
+    let reversed: String = part.chars().rev().collect();
+    KEYBOARD_ROWS
+        .iter()
+        .any(|row| row.contains(part) || row.contains(reversed.as_str()))
+}
+
+impl EmailAddress {
+    /// Returns whether the address is likely a placeholder, or a test address, as per the default
+    /// `PlaceholderHeuristics`; e.g. `test@test.com`, `asdf@asdf.com`, or `a@a.com`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// assert!(EmailAddress::parse("noemail@gmail.com", None).unwrap().looks_like_placeholder());
+    /// assert!(EmailAddress::parse("a@a.com", None).unwrap().looks_like_placeholder());
+    /// ```
+    pub fn looks_like_placeholder(&self) -> bool {
+        PlaceholderHeuristics::new().looks_like_placeholder(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(input: &str) -> Option<PlaceholderSignal> {
+        PlaceholderHeuristics::new().signal(&EmailAddress::parse(input, None).unwrap())
+    }
+
+    #[test]
+    fn flags_placeholders() {
+        let reserved = Some(PlaceholderSignal::ReservedDomain);
+        for input in &["jane@example.com", "jane@mail.EXAMPLE.org", "jane@x.test"] {
+            assert_eq!(signal(input), reserved, "{}", input);
+        }
+        assert_eq!(signal("jane@test.com"), reserved);
+        let word = Some(PlaceholderSignal::PlaceholderWord);
+        for input in &["test@gmail.com", "NoEmail+x@yahoo.com", "\"none123\"@x.org"] {
+            assert_eq!(signal(input), word, "{}", input);
+        }
+        let keyboard = Some(PlaceholderSignal::KeyboardPattern);
+        for input in &[
+            "asdf@asdf.com",
+            "qwerty@x.org",
+            "jane@zzzz.com",
+            "4321@x.org",
+        ] {
+            assert_eq!(signal(input), keyboard, "{}", input);
+        }
+        assert_eq!(
+            signal("jane@jane.com"),
+            Some(PlaceholderSignal::RepeatedPart)
+        );
+        assert_eq!(signal("a@b.co"), Some(PlaceholderSignal::SingleCharacters));
+        for input in &[
+            "jane@gmail.com",
+            "j@gmail.com",
+            "dave@sdf.org",
+            "a@[127.0.0.1]",
+        ] {
+            assert_eq!(signal(input), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn heuristics_can_be_disabled() {
+        let email = EmailAddress::parse("test@test.com", None).unwrap();
+        let heuristics = PlaceholderHeuristics::new().reserved_domains(false);
+        assert_eq!(
+            heuristics.signal(&email),
+            Some(PlaceholderSignal::PlaceholderWord)
+        );
+        let heuristics = heuristics.placeholder_words(false);
+        assert_eq!(
+            heuristics.signal(&email),
+            Some(PlaceholderSignal::RepeatedPart)
+        );
+        let heuristics = heuristics.repeated_parts(false);
+        assert!(!heuristics.looks_like_placeholder(&email));
+        assert!(heuristics.check(&email).is_ok());
+        let violation = PlaceholderHeuristics::new().check(&email).unwrap_err();
+        assert_eq!(violation.code(), "placeholder");
+    }
+}
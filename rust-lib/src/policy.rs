@@ -0,0 +1,384 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::error::ParseError;
+#[cfg(any(feature = "tracing", feature = "log"))]
+use crate::events;
+#[cfg(feature = "tracing")]
+use crate::redact::Redacted;
+use crate::rules::{
+    DisposableDomains, DomainDenylist, RequireDomains, RequireTopLevelDomain, RoleAccounts,
+    ValidationRule, Violation,
+};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use wasm_bindgen::prelude::*;
+
+/// An acceptance policy for email addresses: the parsing options, and the rules that the parsed
+/// addresses have to follow, in order; e.g. to encode the policy of an organization once, and to
+/// run it both in a backend and, through WASM, in a form.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let policy = Policy::new()
+///     .rule(RoleAccounts)
+///     .rule(DomainDenylist::new(["spam.example"]))
+///     .rule(|email: &EmailAddress| {
+///         if email.get_local_part().len() < 2 {
+///             return Err(Violation::new("too_short", "The local part is too short."));
+///         }
+///         Ok(())
+///     });
+///
+/// assert!(policy.validate("jane@example.com").is_ok());
+/// let error = policy.validate("info@example.com").unwrap_err();
+/// assert_eq!(error.code(), "role_account");
+/// assert_eq!(policy.validate("j@example.com").unwrap_err().code(), "too_short");
+/// assert_eq!(policy.validate("jane@-example.com").unwrap_err().code(), "invalid_domain");
+/// ```
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Policy {
+    options: ParsingOptions,
+    repair: bool,
+    canonicalize: bool,
+    rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl Policy {
+    /// Instantiates a policy with the default parsing options, and no rules.
+    pub fn new() -> Self {
+        Policy::default()
+    }
+
+    /// Sets the parsing options.
+    pub fn options(self, options: ParsingOptions) -> Self {
+        Policy { options, ..self }
+    }
+
+    /// Sets whether the common mistakes of typed addresses are repaired before parsing, as by
+    /// `EmailAddress::parse_with_repair`. Defaults to `false`.
+    pub fn repair(self, repair: bool) -> Self {
+        Policy { repair, ..self }
+    }
+
+    /// Sets whether the accepted addresses are canonicalized, as by `EmailAddress::canonical`.
+    /// Defaults to `false`.
+    pub fn canonicalize(self, canonicalize: bool) -> Self {
+        Policy {
+            canonicalize,
+            ..self
+        }
+    }
+
+    /// Appends a rule.
+    pub fn rule(mut self, rule: impl ValidationRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Checks an address against the rules, failing with the violation of the first rule that it
+    /// does not follow.
+    pub fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        self.rules.iter().try_for_each(|rule| rule.check(email))
+    }
+
+    /// Returns the violations of all of the rules that an address does not follow, in order.
+    pub fn violations(&self, email: &EmailAddress) -> Vec<Violation> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.check(email).err())
+            .collect()
+    }
+
+    /// Parses an input as per the parsing options, and checks the address against the rules; the
+    /// address is repaired and canonicalized as per the policy.
+    pub fn validate(&self, input: &str) -> Result<EmailAddress, PolicyError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("validate", input = %Redacted(input)).entered();
+        let options = Some(self.options.clone());
+        let parsed = match self.repair {
+            true => EmailAddress::parse_with_repair(input, options).map(|(email, _)| email),
+            false => EmailAddress::try_parse(input, options),
+        };
+        let mut email = parsed.map_err(PolicyError::Invalid)?;
+        if self.canonicalize {
+            email = email.canonical();
+        }
+        if let Err(violation) = self.check(&email) {
+            #[cfg(any(feature = "tracing", feature = "log"))]
+            events::rejected(input, violation.code());
+            return Err(PolicyError::Rejected(violation));
+        }
+        Ok(email)
+    }
+
+    /// Returns the policy of a signup form: the white space around the address is trimmed, and the
+    /// domain needs a top-level domain; the addresses of disposable mailbox providers, and those of
+    /// role accounts are rejected, in this order.
+    ///
+    /// The likely typos in the domains are not rejected, since a domain close to a popular one may
+    /// well be real; use `suggest` to prompt for a correction instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{suggest, Policy};
+    ///
+    /// let policy = Policy::signup_form();
+    /// assert!(policy.validate(" jane@example.com ").is_ok());
+    /// assert_eq!(policy.validate("jane@localhost").unwrap_err().code(), "missing_top_level_domain");
+    /// let email = policy.validate("jane@gmial.com").unwrap();
+    /// assert_eq!(suggest(&email, &[]).unwrap().to_string(), "jane@gmail.com");
+    /// assert_eq!(policy.validate("jane@mailinator.com").unwrap_err().code(), "disposable_domain");
+    /// assert_eq!(policy.validate("info@example.com").unwrap_err().code(), "role_account");
+    /// ```
+    pub fn signup_form() -> Self {
+        Policy::new()
+            .options(ParsingOptions::default().trim(true))
+            .rule(RequireTopLevelDomain)
+            .rule(DisposableDomains)
+            .rule(RoleAccounts)
+    }
+
+    /// Returns the policy of an import of an existing list: the parsing is lax, the white space
+    /// and the wrappers around the address are stripped, the common mistakes are repaired, and the
+    /// addresses are canonicalized; there are no rules.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::Policy;
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let policy = Policy::list_import();
+    /// let email = policy.validate(" <Jane . Doe@@Example.com>, ").unwrap();
+    /// assert_eq!(email.to_string(), "Jane.Doe@example.com");
+    /// # }
+    /// ```
+    pub fn list_import() -> Self {
+        Policy::new()
+            .options(ParsingOptions::new(true).trim(true).strip_wrappers(true))
+            .repair(true)
+            .canonicalize(true)
+    }
+
+    /// Returns a strict policy for corporate systems: the parsing is strict, without comments,
+    /// quoted local parts, domain literals, underscores, or numeric top-level domains; and the
+    /// domain needs a top-level domain. The addresses of disposable mailbox providers are
+    /// rejected. Append a `RequireDomains` rule to require the corporate domains.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{Policy, RequireDomains};
+    ///
+    /// let policy = Policy::corporate_strict().rule(RequireDomains::new(["example.com"]));
+    /// assert!(policy.validate("jane.doe@example.com").is_ok());
+    /// assert_eq!(policy.validate("\"jane doe\"@example.com").unwrap_err().code(), "quoted_local_part");
+    /// assert_eq!(policy.validate("jane@example.org").unwrap_err().code(), "domain_not_allowed");
+    /// ```
+    pub fn corporate_strict() -> Self {
+        let options = ParsingOptions::default()
+            .allow_comments(false)
+            .allow_quoted_local_part(false)
+            .allow_domain_literal(false)
+            .allow_underscore(false)
+            .allow_numeric_tld(false);
+        Policy::new()
+            .options(options)
+            .rule(RequireTopLevelDomain)
+            .rule(DisposableDomains)
+    }
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Policy")
+            .field("options", &self.options)
+            .field("repair", &self.repair)
+            .field("canonicalize", &self.canonicalize)
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
+
+/// The WASM surface of the policy, that can only use the built-in rules.
+#[wasm_bindgen]
+impl Policy {
+    #[doc(hidden)]
+    #[wasm_bindgen(constructor)]
+    pub fn _new(options: Option<ParsingOptions>) -> Policy {
+        Policy::new().options(options.unwrap_or_default())
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "signupForm")]
+    pub fn _signup_form() -> Policy {
+        Policy::signup_form()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "listImport")]
+    pub fn _list_import() -> Policy {
+        Policy::list_import()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "corporateStrict")]
+    pub fn _corporate_strict() -> Policy {
+        Policy::corporate_strict()
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "denyDomains")]
+    pub fn _deny_domains(&mut self, domains: Vec<String>) {
+        let rule = DomainDenylist::new(domains.iter().map(String::as_str));
+        self.rules.push(Box::new(rule));
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "requireDomains")]
+    pub fn _require_domains(&mut self, domains: Vec<String>) {
+        let rule = RequireDomains::new(domains.iter().map(String::as_str));
+        self.rules.push(Box::new(rule));
+    }
+
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "rejectRoleAccounts")]
+    pub fn _reject_role_accounts(&mut self) {
+        self.rules.push(Box::new(RoleAccounts));
+    }
+
+    /// Returns the code of the error of `validate`, if any; e.g. `invalid_domain` or `role_account`.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "validate")]
+    pub fn _validate(&self, input: &str) -> Option<String> {
+        self.validate(input)
+            .err()
+            .map(|error| error.code().to_string())
+    }
+}
+
+/// Error returned when an input is not accepted by a `Policy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The input is not an email address as per the parsing options.
+    Invalid(ParseError),
+    /// The address does not follow a rule.
+    Rejected(Violation),
+}
+
+impl PolicyError {
+    /// Returns the code of the kind of the parse error, or of the violation; e.g. `invalid_domain`
+    /// or `role_account`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PolicyError::Invalid(error) => error.kind().code(),
+            PolicyError::Rejected(violation) => violation.code(),
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Invalid(error) => error.fmt(formatter),
+            PolicyError::Rejected(violation) => violation.fmt(formatter),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PolicyError::Invalid(error) => Some(error),
+            PolicyError::Rejected(violation) => Some(violation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_run_in_order() {
+        let policy = Policy::new()
+            .options(ParsingOptions::new(true))
+            .rule(RequireDomains::new(["example.com"]))
+            .rule(RoleAccounts);
+        let email = EmailAddress::parse("info@example.org", None).unwrap();
+        let codes: Vec<_> = policy
+            .violations(&email)
+            .iter()
+            .map(Violation::code)
+            .collect();
+        assert_eq!(codes, ["domain_not_allowed", "role_account"]);
+        assert_eq!(
+            policy.check(&email).unwrap_err().code(),
+            "domain_not_allowed"
+        );
+
+        #[cfg(not(feature = "strict-only"))]
+        assert!(policy.validate("jane . doe@example.com").is_ok());
+        let error = policy.validate("info@example.com").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Role accounts like 'info' are not allowed."
+        );
+        assert!(matches!(
+            Policy::new().validate("jane . doe@example.com"),
+            Err(PolicyError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn presets_repair_and_canonicalize_as_documented() {
+        let email = Policy::list_import()
+            .validate("\"jane\"@@EXAMPLE.com.")
+            .unwrap();
+        assert_eq!(email.to_string(), "jane@example.com");
+        let email = Policy::signup_form().validate("Jane@EXAMPLE.com").unwrap();
+        assert_eq!(email.to_string(), "Jane@EXAMPLE.com");
+        assert!(Policy::signup_form().validate("jane@@example.com").is_err());
+        for input in &[
+            "jane@yahoo.de",
+            "jane@hotmail.it",
+            "jane@yahoo.ca",
+            "jane@yahoo.es",
+            "jane@email.com",
+            "jane@gmial.com",
+        ] {
+            assert!(Policy::signup_form().validate(input).is_ok(), "{}", input);
+        }
+        assert!(Policy::corporate_strict()
+            .validate("jane@my_host.example.com")
+            .is_err());
+        assert!(Policy::corporate_strict()
+            .validate("jane@example.123")
+            .is_err());
+    }
+
+    #[test]
+    fn wasm_surface_builds_the_built_in_rules() {
+        let mut policy = Policy::_new(None);
+        policy._deny_domains(vec![String::from("spam.example")]);
+        policy._reject_role_accounts();
+        assert_eq!(policy._validate("jane@example.com"), None);
+        assert_eq!(
+            policy._validate("jane@spam.example").as_deref(),
+            Some("denied_domain")
+        );
+        assert_eq!(
+            policy._validate("admin@example.com").as_deref(),
+            Some("role_account")
+        );
+        policy._require_domains(vec![String::from("example.com")]);
+        assert_eq!(
+            policy._validate("jane@example.org").as_deref(),
+            Some("domain_not_allowed")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! `EmailAddress` is stored in the text-like columns (`TEXT`, `VARCHAR`, `BPCHAR`, `NAME`, and
+//! `CITEXT`); i.e. as its string representation.
+//!
+//! On read, the stored value is parsed strictly. Use `LaxEmailAddress` for columns containing
+//! legacy addresses with obsolete parts.
+//!
+//! ```ignore
+//! use email_address_parser::EmailAddress;
+//!
+//! let email = EmailAddress::parse("foo@bar.com", None).unwrap();
+//! client.execute("INSERT INTO users (email) VALUES ($1)", &[&email])?;
+//!
+//! let row = client.query_one("SELECT email FROM users", &[])?;
+//! let email: EmailAddress = row.get(0);
+//! ```
+
+use crate::email_address::EmailAddress;
+use crate::lax::LaxEmailAddress;
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+macro_rules! impl_postgres_text {
+    ($type:ty) => {
+        impl<'a> FromSql<'a> for $type {
+            fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+                let value = <&str as FromSql>::from_sql(ty, raw)?;
+                Ok(value.parse::<$type>()?)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <&str as FromSql>::accepts(ty)
+            }
+        }
+
+        impl ToSql for $type {
+            fn to_sql(
+                &self,
+                ty: &Type,
+                out: &mut BytesMut,
+            ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+                <&str as ToSql>::to_sql(&self.to_string().as_str(), ty, out)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <&str as ToSql>::accepts(ty)
+            }
+
+            to_sql_checked!();
+        }
+    };
+}
+
+impl_postgres_text!(EmailAddress);
+impl_postgres_text!(LaxEmailAddress);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_address_is_written_as_text() {
+        let email = EmailAddress::new("foö", "bücher.de", None).unwrap();
+        let mut buf = BytesMut::new();
+        email.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+        assert_eq!(&buf[..], "foö@bücher.de".as_bytes());
+        assert!(email.to_sql_checked(&Type::INT4, &mut buf).is_err());
+    }
+
+    #[test]
+    fn email_address_is_read_from_text() {
+        let email = EmailAddress::from_sql(&Type::VARCHAR, b"foo@bar.com").unwrap();
+        assert_eq!(email, EmailAddress::new("foo", "bar.com", None).unwrap());
+        assert!(<EmailAddress as FromSql>::accepts(&Type::TEXT));
+        assert!(!<EmailAddress as FromSql>::accepts(&Type::BYTEA));
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn legacy_address_is_read_only_as_lax_email_address() {
+        let raw = b"\r\n test@iana.org";
+        assert!(EmailAddress::from_sql(&Type::TEXT, raw).is_err());
+        let email = LaxEmailAddress::from_sql(&Type::TEXT, raw).unwrap();
+        assert_eq!(email.get_domain(), "iana.org");
+    }
+}
@@ -0,0 +1,231 @@
+use crate::email_address::ParsingOptions;
+use crate::error::{ErrorKind, ParseError};
+use core::ops::Range;
+
+/// The longest input that is parsed by default, in octets; the limit of a line of a message
+/// header, as per RFC 5322.
+pub(crate) const MAX_INPUT_LENGTH: usize = 998;
+
+/// The deepest nesting of comments that is parsed by default.
+pub(crate) const MAX_COMMENT_DEPTH: usize = 16;
+
+/// The most runs of white space that are parsed by default.
+pub(crate) const MAX_WHITESPACE_RUNS: usize = 64;
+
+/// Why `prefilter` rejects an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Rejection {
+    /// The syntax of the input is invalid; `diagnose` explains why.
+    Syntax,
+    /// The input nests comments deeper, or has more runs of white space, than the options allow.
+    TooComplex,
+    /// The part in the range has a non-ASCII character, and the options do not allow
+    /// internationalized addresses; with the kind of the error.
+    NonAscii(ErrorKind, Range<usize>),
+}
+
+/// Rejects the inputs that are obviously not addresses as per the options before the grammar
+/// runs; i.e. those:
+/// - with comments nested deeper than `ParsingOptions::max_comment_depth`, or with more runs of
+///   white space than `ParsingOptions::max_whitespace_runs`, whose parsing may be expensive (see
+///   `find_complexity`),
+/// - without an `@` outside quoted strings, comments, and domain literals, or with more than one
+///   in strict mode (the obsolete source routes of lax mode have more),
+/// - with a NUL that is not a quoted pair,
+/// - with a non-ASCII character, if the options do not allow internationalized addresses.
+///
+/// An input that passes may still be invalid, but one that does not never is. The length of the
+/// input is checked before its repairs; see `is_too_large`.
+pub(crate) fn prefilter(input: &str, options: &ParsingOptions) -> Result<(), Rejection> {
+    if find_complexity(input, options).is_some() {
+        return Err(Rejection::TooComplex);
+    }
+    let mut in_quotes = false;
+    let mut in_literal = false;
+    let mut comment_depth = 0;
+    let mut escaped = false;
+    let mut separator = None;
+    let mut separators = 0;
+    let mut non_ascii = None;
+    for (index, &byte) in input.as_bytes().iter().enumerate() {
+        if byte >= 0x80 && non_ascii.is_none() {
+            non_ascii = Some(index);
+        }
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        let is_nested = in_quotes || in_literal || comment_depth > 0;
+        match byte {
+            b'\0' => return Err(Rejection::Syntax),
+            b'\\' if is_nested => escaped = true,
+            b'"' if !in_literal && comment_depth == 0 => in_quotes = !in_quotes,
+            b'[' if !is_nested => in_literal = true,
+            b']' if in_literal => in_literal = false,
+            b'(' if !in_quotes && !in_literal => comment_depth += 1,
+            b')' if comment_depth > 0 => comment_depth -= 1,
+            b'@' if !is_nested => {
+                separator = Some(index);
+                separators += 1;
+            }
+            _ => (),
+        }
+    }
+    // The local part and the domain have no such `@`; hence, the last one separates them.
+    let at = separator.ok_or(Rejection::Syntax)?;
+    if separators > 1 && !options.is_lax {
+        return Err(Rejection::Syntax);
+    }
+    match non_ascii {
+        Some(index) if !options.allow_utf8 && index < at => {
+            Err(Rejection::NonAscii(ErrorKind::InvalidLocalPart, 0..at))
+        }
+        Some(_) if !options.allow_utf8 => Err(Rejection::NonAscii(
+            ErrorKind::InvalidDomain,
+            at + 1..input.len(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the offset of the byte where an input nests comments deeper than
+/// `ParsingOptions::max_comment_depth` allows, or starts a run of white space beyond
+/// `ParsingOptions::max_whitespace_runs`, if any.
+pub(crate) fn find_complexity(input: &str, options: &ParsingOptions) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut in_literal = false;
+    let mut comment_depth = 0;
+    let mut escaped = false;
+    let mut whitespace_runs = 0;
+    let mut in_whitespace = false;
+    for (index, &byte) in input.as_bytes().iter().enumerate() {
+        let is_whitespace = matches!(byte, b' ' | b'\t' | b'\r' | b'\n');
+        if is_whitespace && !in_whitespace {
+            whitespace_runs += 1;
+            if whitespace_runs > options.max_whitespace_runs {
+                return Some(index);
+            }
+        }
+        in_whitespace = is_whitespace;
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match byte {
+            b'\\' if in_quotes || in_literal || comment_depth > 0 => escaped = true,
+            b'"' if !in_literal && comment_depth == 0 => in_quotes = !in_quotes,
+            b'[' if !in_quotes && comment_depth == 0 => in_literal = true,
+            b']' if in_literal => in_literal = false,
+            b'(' if !in_quotes && !in_literal => {
+                comment_depth += 1;
+                if comment_depth > options.max_comment_depth {
+                    return Some(index);
+                }
+            }
+            b')' if comment_depth > 0 => comment_depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns whether an input is longer than `ParsingOptions::max_input_length` allows; it is checked
+/// before anything else, so that the cost of rejecting a huge input is constant.
+pub(crate) fn is_too_large(input: &str, options: &ParsingOptions) -> bool {
+    input.len() > options.max_input_length
+}
+
+/// Checks an input against the limits of the options, before a grammar entry point other than
+/// `EmailAddress::parse` runs on it; e.g. `parse_rule`, on a fragment without an `@`. It fails
+/// with `ErrorKind::InputTooLarge` or `ErrorKind::TooComplex`, spanning the whole input.
+pub(crate) fn check_limits(input: &str, options: &ParsingOptions) -> Result<(), ParseError> {
+    let kind = if is_too_large(input, options) {
+        ErrorKind::InputTooLarge
+    } else if find_complexity(input, options).is_some() {
+        ErrorKind::TooComplex
+    } else {
+        return Ok(());
+    };
+    Err(ParseError::new(kind, input, 0..input.len(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email_address::EmailAddress;
+    use crate::error::Hint;
+    use alloc::format;
+
+    #[test]
+    fn rejects_obviously_invalid_inputs() {
+        let strict = ParsingOptions::default();
+        let lax = ParsingOptions::new(true);
+        assert_eq!(prefilter("foo", &strict), Err(Rejection::Syntax));
+        assert_eq!(prefilter("\"foo@bar\"(@)", &strict), Err(Rejection::Syntax));
+        assert_eq!(
+            prefilter("foo@bar@baz.com", &strict),
+            Err(Rejection::Syntax)
+        );
+        assert_eq!(prefilter("@a,@b:foo@bar.com", &lax), Ok(()));
+        assert_eq!(prefilter("foo@[a@b]", &strict), Ok(()));
+        assert_eq!(prefilter("\"a(@\"@b.com", &strict), Ok(()));
+        assert_eq!(prefilter("fo\0o@bar.com", &lax), Err(Rejection::Syntax));
+        assert_eq!(prefilter("\"fo\\\0o\"@bar.com", &strict), Ok(()));
+        let nested = format!("{}foo@bar.com", "(".repeat(MAX_COMMENT_DEPTH + 1));
+        assert_eq!(prefilter(&nested, &lax), Err(Rejection::TooComplex));
+        let spaced = format!("{}@bar.com", "f o".repeat(MAX_WHITESPACE_RUNS + 1));
+        assert_eq!(prefilter(&spaced, &lax), Err(Rejection::TooComplex));
+        assert_eq!(prefilter("\"f  o\t\r\n o\"@bar.com", &strict), Ok(()));
+        assert_eq!(find_complexity(&nested, &lax), Some(MAX_COMMENT_DEPTH));
+        assert_eq!(
+            find_complexity("\"((\"@[(((]", &lax.clone().max_comment_depth(1)),
+            None
+        );
+        assert_eq!(
+            find_complexity(&spaced, &lax),
+            Some(MAX_WHITESPACE_RUNS * 3 + 1)
+        );
+        let long = format!("foo@{}.com", "a".repeat(MAX_INPUT_LENGTH));
+        assert!(is_too_large(&long, &strict));
+        assert!(!is_too_large(&long, &strict.clone().max_input_length(2048)));
+        let ascii = ParsingOptions::default().allow_utf8(false);
+        assert_eq!(prefilter("jürgen@bücher.example", &strict), Ok(()));
+        assert_eq!(
+            prefilter("jürgen@bücher.example", &ascii),
+            Err(Rejection::NonAscii(ErrorKind::InvalidLocalPart, 0..7))
+        );
+        assert_eq!(
+            prefilter("foo@bücher.example", &ascii),
+            Err(Rejection::NonAscii(ErrorKind::InvalidDomain, 4..19))
+        );
+    }
+
+    #[test]
+    fn explains_the_rejections() {
+        let error = EmailAddress::try_parse("foo@bar@baz.com", None).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::MultipleAt));
+        let long = format!("foo@{}.com", "a".repeat(MAX_INPUT_LENGTH));
+        let error = EmailAddress::try_parse(&long, None).unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ErrorKind::InputTooLarge, 0..long.len())
+        );
+        let options = ParsingOptions::default().max_comment_depth(1);
+        let error = EmailAddress::try_parse("\"a\"((b))@c.com", Some(options.clone())).unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::TooComplex, 0..14));
+        assert!(EmailAddress::parse("\"a\"(b)@c.com", Some(options)).is_some());
+        let options = ParsingOptions::default().max_whitespace_runs(1).trim(true);
+        assert!(EmailAddress::parse(" foo @bar.com ", Some(options.clone())).is_some());
+        assert!(EmailAddress::parse("foo @ bar.com", Some(options)).is_none());
+        let options = ParsingOptions::default().allow_utf8(false).trim(true);
+        let error = EmailAddress::try_parse(" foo@bücher.example", Some(options)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+        assert_eq!((error.hint(), error.span()), (Some(Hint::NonAscii), 5..20));
+        let options = ParsingOptions::default().allow_utf8(false);
+        assert!(EmailAddress::new("foo", "bücher.example", Some(options)).is_err());
+        let mut options = ParsingOptions::default();
+        options._set_allow_utf8(false);
+        assert!(!options._allow_utf8());
+        assert!(EmailAddress::parse("jürgen@example.com", Some(options)).is_none());
+    }
+}
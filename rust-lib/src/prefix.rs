@@ -0,0 +1,118 @@
+use crate::email_address::{EmailAddress, ParsingOptions, Rule, RFC5322};
+use crate::error::{diagnose, find_separator, ParseError};
+use crate::labels::mask_hyphens;
+use crate::prefilter::check_limits;
+use pest::Parser;
+
+impl EmailAddress {
+    /// Parses the longest address at the start of an input, returning it along with the rest of
+    /// the input; e.g. to embed the parsing of addresses into a parser of a larger format, like
+    /// the commands of SMTP, or Sieve scripts.
+    ///
+    /// The address is an addr-spec as per the options, without wrappers, and the white space
+    /// after it is left in the rest. The match is the longest valid address, not the longest run
+    /// of the characters of addresses; hence, the rest can start with such characters if they do
+    /// not make a valid address, e.g. `foo@bar-.com x` yields `foo@bar`, and `-.com x` as the rest.
+    /// The callers that expect a delimiter after the address have to check the rest for it.
+    ///
+    /// If there is no address at the start of the input, the error is that of parsing the whole
+    /// input; if the address is invalid as per the options, e.g. as its domain has an all-numeric
+    /// top-level domain that they do not allow, the error is that of parsing the address alone.
+    /// The limits of the options apply to the whole input; e.g. `ParsingOptions::max_input_length`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let (email, rest) = EmailAddress::parse_prefix("foo@bar.com SIZE=1024", None).unwrap();
+    /// assert_eq!(email.to_string(), "foo@bar.com");
+    /// assert_eq!(rest, " SIZE=1024");
+    ///
+    /// let (email, rest) = EmailAddress::parse_prefix("\"a b\"@[127.0.0.1]>", None).unwrap();
+    /// assert_eq!(email.get_domain(), "[127.0.0.1]");
+    /// assert_eq!(rest, ">");
+    ///
+    /// assert!(EmailAddress::parse_prefix("<foo@bar.com>", None).is_err());
+    /// ```
+    pub fn parse_prefix(
+        input: &str,
+        options: Option<ParsingOptions>,
+    ) -> Result<(EmailAddress, &str), ParseError> {
+        let options = options.unwrap_or_default();
+        check_limits(input, &options)?;
+        let masked = find_separator(input).and_then(|at| mask_hyphens(input, at + 1, &options));
+        let text = masked.as_deref().unwrap_or(input);
+        let parsed = match RFC5322::parse(Rule::address_spec, text) {
+            Ok(parsed) => Ok(parsed),
+            #[cfg(not(feature = "strict-only"))]
+            Err(_) if options.is_lax => RFC5322::parse(Rule::address_spec_obs, text),
+            Err(error) => Err(error),
+        };
+        let end = match parsed.ok().and_then(|mut pairs| pairs.next()) {
+            Some(pair) => pair.as_span().end(),
+            None => return Err(diagnose(input, options.is_lax)),
+        };
+        let end = input[..end].trim_end_matches([' ', '\t', '\r', '\n']).len();
+        let email = EmailAddress::try_parse(&input[..end], Some(options))?;
+        Ok((email, &input[end..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use alloc::format;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parses_the_longest_address_at_the_start() {
+        let (email, rest) = EmailAddress::parse_prefix("foo@bar.com.", None).unwrap();
+        assert_eq!((email.to_string().as_str(), rest), ("foo@bar.com", "."));
+        let (email, rest) = EmailAddress::parse_prefix("foo@bar.com@baz", None).unwrap();
+        assert_eq!((email.get_domain(), rest), ("bar.com", "@baz"));
+        let (_, rest) = EmailAddress::parse_prefix("foo@bar.com \t", None).unwrap();
+        assert_eq!(rest, " \t");
+        assert!(EmailAddress::parse_prefix("foo . bar@baz.com", None).is_err());
+        #[cfg(not(feature = "strict-only"))]
+        {
+            let lax = Some(ParsingOptions::new(true));
+            let (email, rest) = EmailAddress::parse_prefix("foo . bar@baz.com;", lax).unwrap();
+            assert_eq!((email.get_local_part(), rest), ("foo . bar", ";"));
+        }
+    }
+
+    #[test]
+    fn leaves_the_characters_of_an_invalid_continuation_in_the_rest() {
+        let (email, rest) = EmailAddress::parse_prefix("foo@bar-.com x", None).unwrap();
+        assert_eq!((email.get_domain(), rest), ("bar", "-.com x"));
+        let (email, rest) = EmailAddress::parse_prefix("foo@bar..com", None).unwrap();
+        assert_eq!((email.get_domain(), rest), ("bar", "..com"));
+        let (email, rest) = EmailAddress::parse_prefix("foo@bar.com-x", None).unwrap();
+        assert_eq!((email.get_domain(), rest), ("bar.com-x", ""));
+    }
+
+    #[test]
+    fn checks_the_limits_of_the_whole_input() {
+        let long = format!("foo@bar.com {}", "a".repeat(1000));
+        let error = EmailAddress::parse_prefix(&long, None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InputTooLarge);
+        let options = ParsingOptions::default().max_input_length(2048);
+        assert!(EmailAddress::parse_prefix(&long, Some(options)).is_ok());
+        let nested = format!("foo@bar.com {}", "(".repeat(17));
+        let error = EmailAddress::parse_prefix(&nested, None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TooComplex);
+    }
+
+    #[test]
+    fn checks_the_address_as_per_the_options() {
+        let options = ParsingOptions::default().allow_leading_hyphen(true);
+        let (email, _) = EmailAddress::parse_prefix("foo@-bar.com x", Some(options)).unwrap();
+        assert_eq!(email.get_domain(), "-bar.com");
+        let options = ParsingOptions::default().allow_numeric_tld(false);
+        let error = EmailAddress::parse_prefix("foo@bar.123 x", Some(options)).unwrap_err();
+        assert_eq!(error.input(), "foo@bar.123");
+        let error = EmailAddress::parse_prefix("@bar.com foo@bar.com", None).unwrap_err();
+        assert_eq!(error.input(), "@bar.com foo@bar.com");
+    }
+}
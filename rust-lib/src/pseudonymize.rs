@@ -0,0 +1,206 @@
+use crate::email_address::EmailAddress;
+use crate::eq_policy::EqPolicy;
+use crate::hash_support::KeyedHasher;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::convert::Infallible;
+use core::fmt;
+
+/// Records the addresses behind the tokens of a `Pseudonymizer`, to re-identify them; e.g. a table
+/// of a database that only a few are granted access to.
+///
+/// It is implemented by `()`, which records nothing, by `BTreeMap<String, EmailAddress>`, and by
+/// `HashMap<String, EmailAddress>` with the `std` feature.
+pub trait PseudonymStore {
+    /// The error of the store; e.g. that of its database.
+    type Error;
+
+    /// Records the address behind a token. It is called every time an address is pseudonymized;
+    /// hence, with the spellings of a mailbox that map to the same token too.
+    fn record(&mut self, token: &str, email: &EmailAddress) -> Result<(), Self::Error>;
+
+    /// Returns the address behind a token, if it is recorded.
+    fn lookup(&self, token: &str) -> Result<Option<EmailAddress>, Self::Error>;
+}
+
+impl PseudonymStore for () {
+    type Error = Infallible;
+
+    fn record(&mut self, _: &str, _: &EmailAddress) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn lookup(&self, _: &str) -> Result<Option<EmailAddress>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Keeps the address that a token was first recorded with.
+impl PseudonymStore for BTreeMap<String, EmailAddress> {
+    type Error = Infallible;
+
+    fn record(&mut self, token: &str, email: &EmailAddress) -> Result<(), Self::Error> {
+        if !self.contains_key(token) {
+            self.insert(String::from(token), email.clone());
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, token: &str) -> Result<Option<EmailAddress>, Self::Error> {
+        Ok(self.get(token).cloned())
+    }
+}
+
+/// Keeps the address that a token was first recorded with.
+#[cfg(feature = "std")]
+impl<H: std::hash::BuildHasher> PseudonymStore
+    for std::collections::HashMap<String, EmailAddress, H>
+{
+    type Error = Infallible;
+
+    fn record(&mut self, token: &str, email: &EmailAddress) -> Result<(), Self::Error> {
+        if !self.contains_key(token) {
+            self.insert(String::from(token), email.clone());
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, token: &str) -> Result<Option<EmailAddress>, Self::Error> {
+        Ok(self.get(token).cloned())
+    }
+}
+
+impl<S: PseudonymStore + ?Sized> PseudonymStore for &mut S {
+    type Error = S::Error;
+
+    fn record(&mut self, token: &str, email: &EmailAddress) -> Result<(), Self::Error> {
+        (**self).record(token, email)
+    }
+
+    fn lookup(&self, token: &str) -> Result<Option<EmailAddress>, Self::Error> {
+        (**self).lookup(token)
+    }
+}
+
+/// De-identifies email addresses, e.g. those of a data export, by mapping them to opaque tokens:
+/// the identifiers of a `KeyedHasher` with a caller-provided key. The tokens are deterministic,
+/// hence the pseudonymized records of a mailbox can still be joined; and the same for every
+/// spelling of it, as per the comparison policy.
+///
+/// With a store (see `PseudonymStore`), the addresses behind the tokens are recorded, so that
+/// they can be selectively re-identified; without one, the tokens cannot be reversed.
+///
+/// Requires the `hash` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, Pseudonymizer};
+/// use std::collections::BTreeMap;
+///
+/// let mut vault = BTreeMap::new();
+/// let mut pseudonymizer = Pseudonymizer::new(b"secret").store(&mut vault);
+/// let email = EmailAddress::parse("Jane@Example.com", None).unwrap();
+/// let token = pseudonymizer.pseudonymize(&email).unwrap();
+/// assert_eq!(token, "fb817989d942e7ffb3d4b8b204f7abca29f4c25c3fa46574da84c50f30d07513");
+///
+/// let other = EmailAddress::parse("jane@example.com", None).unwrap();
+/// assert_eq!(pseudonymizer.pseudonymize(&other).unwrap(), token);
+/// assert_eq!(pseudonymizer.reidentify(&token).unwrap(), Some(email));
+/// assert_eq!(vault.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct Pseudonymizer<S = ()> {
+    hasher: KeyedHasher,
+    store: S,
+}
+
+impl Pseudonymizer {
+    /// Instantiates a pseudonymizer with the given secret key, and no store; see
+    /// `KeyedHasher::new`.
+    pub fn new(key: &[u8]) -> Self {
+        Pseudonymizer {
+            hasher: KeyedHasher::new(key),
+            store: (),
+        }
+    }
+}
+
+impl<S: PseudonymStore> Pseudonymizer<S> {
+    /// Sets the comparison policy of the addresses, whose equivalent spellings map to the same
+    /// token; see `KeyedHasher::eq_policy`.
+    pub fn eq_policy(self, policy: EqPolicy) -> Self {
+        Pseudonymizer {
+            hasher: self.hasher.eq_policy(policy),
+            ..self
+        }
+    }
+
+    /// Sets the store that records the addresses behind the tokens.
+    pub fn store<T: PseudonymStore>(self, store: T) -> Pseudonymizer<T> {
+        Pseudonymizer {
+            hasher: self.hasher,
+            store,
+        }
+    }
+
+    /// Returns the token of an address, and records it in the store.
+    pub fn pseudonymize(&mut self, email: &EmailAddress) -> Result<String, S::Error> {
+        let token = self.hasher.hash(email);
+        self.store.record(&token, email)?;
+        Ok(token)
+    }
+
+    /// Returns the address behind a token, if the store recorded it.
+    pub fn reidentify(&self, token: &str) -> Result<Option<EmailAddress>, S::Error> {
+        self.store.lookup(token)
+    }
+
+    /// Consumes the pseudonymizer, returning its store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+/// Omits the store, whose addresses are the very data that is pseudonymized; and the key, as the
+/// `KeyedHasher` does.
+impl<S> fmt::Debug for Pseudonymizer<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Pseudonymizer")
+            .field("hasher", &self.hasher)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_the_first_spelling_of_a_token() {
+        let mut pseudonymizer = Pseudonymizer::new(b"secret")
+            .eq_policy(EqPolicy::new().strip_tags(true))
+            .store(BTreeMap::new());
+        let email = EmailAddress::parse("jane+news@example.com", None).unwrap();
+        let other = EmailAddress::parse("JANE@example.com", None).unwrap();
+        let token = pseudonymizer.pseudonymize(&email).unwrap();
+        assert_eq!(pseudonymizer.pseudonymize(&other).unwrap(), token);
+        assert_eq!(pseudonymizer.reidentify(&token).unwrap(), Some(email));
+        assert_eq!(pseudonymizer.reidentify("unknown").unwrap(), None);
+        assert_eq!(pseudonymizer.into_store().len(), 1);
+
+        let mut pseudonymizer = Pseudonymizer::new(b"secret");
+        let token = pseudonymizer.pseudonymize(&other).unwrap();
+        assert_eq!(pseudonymizer.reidentify(&token).unwrap(), None);
+    }
+
+    #[test]
+    fn debug_omits_the_store() {
+        let mut pseudonymizer = Pseudonymizer::new(b"secret").store(BTreeMap::new());
+        let email = EmailAddress::parse("jane@example.com", None).unwrap();
+        pseudonymizer.pseudonymize(&email).unwrap();
+        let debug = format!("{:?}", pseudonymizer);
+        assert!(debug.starts_with("Pseudonymizer { hasher: KeyedHasher"));
+        assert!(!debug.contains("jane"));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::email_address::{EmailAddress, LOCAL_PART_CAPACITY};
+use quickcheck::{Arbitrary, Gen};
+
+const ATEXT: &[char] = &[
+    'a', 'b', 'x', 'z', 'A', 'Z', '0', '9', '!', '#', '$', '%', '&', '\'', '*', '+', '/', '=', '?',
+    '^', '_', '`', '{', '|', '}', '~', 'ö', '中',
+];
+const QTEXT: &[char] = &[
+    'a', 'z', '0', ' ', '!', '(', ')', ',', ':', ';', '<', '>', '@', '.', 'ö',
+];
+const LDH: &[char] = &['a', 'b', 'm', 'x', 'z', '0', '9', 'ä', '中'];
+const TLDS: &[&str] = &["com", "org", "de", "io", "example"];
+
+/// Generates email addresses that can be parsed strictly, and shrinks them toward `a@a.com`.
+///
+/// The generated addresses have dot-atom or quoted local parts, and host names or IPv4 domain
+/// literals. Shrinking shortens the local part and the domain, so that a failing property is
+/// reported with the shortest address that still fails.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+/// use quickcheck::{quickcheck, TestResult};
+///
+/// fn displays_address(email: EmailAddress) -> TestResult {
+///     TestResult::from_bool(email.to_string().contains('@'))
+/// }
+/// quickcheck(displays_address as fn(EmailAddress) -> TestResult);
+/// ```
+impl Arbitrary for EmailAddress {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let local_part = if ratio(g, 5) {
+            quoted_string(g)
+        } else {
+            let mut local_part = dot_atom(g, ATEXT);
+            truncate_segments(&mut local_part, LOCAL_PART_CAPACITY);
+            local_part
+        };
+        let domain = if ratio(g, 5) {
+            let octets = <[u8; 4]>::arbitrary(g);
+            format!("[{}.{}.{}.{}]", octets[0], octets[1], octets[2], octets[3])
+        } else {
+            format!("{}.{}", dot_atom(g, LDH), g.choose(TLDS).unwrap())
+        };
+        EmailAddress::new(&local_part, &domain, None)
+            .expect("generated addresses consist of valid parts")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let local_part = self.get_local_part();
+        let domain = self.get_domain();
+        let mut candidates = Vec::new();
+        for shorter in shorter_local_parts(local_part) {
+            candidates.push((shorter, String::from(domain)));
+        }
+        for shorter in shorter_domains(domain) {
+            candidates.push((String::from(local_part), shorter));
+        }
+        let shrunk: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|(local_part, domain)| EmailAddress::new(&local_part, &domain, None).ok())
+            .collect();
+        Box::new(shrunk.into_iter())
+    }
+}
+
+fn ratio(g: &mut Gen, denominator: u32) -> bool {
+    u32::arbitrary(g) % denominator == 0
+}
+
+fn run(g: &mut Gen, alphabet: &[char]) -> String {
+    let len = 1 + usize::arbitrary(g) % g.size().clamp(1, 8);
+    (0..len).map(|_| *g.choose(alphabet).unwrap()).collect()
+}
+
+/// A dot-atom; the hyphens are placed so that no segment starts or ends with one.
+fn dot_atom(g: &mut Gen, alphabet: &[char]) -> String {
+    let segments = 1 + usize::arbitrary(g) % 3;
+    let segments: Vec<_> = (0..segments)
+        .map(|_| {
+            let mut segment = run(g, alphabet);
+            if ratio(g, 4) {
+                segment.push('-');
+                segment.push_str(&run(g, alphabet));
+            }
+            segment
+        })
+        .collect();
+    segments.join(".")
+}
+
+/// Drops the last segments of a dot-atom until it is at most `max_len` octets long. The segments of
+/// `dot_atom` always fit, but the non-ASCII characters can make several of them too long.
+fn truncate_segments(dot_atom: &mut String, max_len: usize) {
+    while dot_atom.len() > max_len {
+        dot_atom.truncate(dot_atom.rfind('.').unwrap());
+    }
+}
+
+fn quoted_string(g: &mut Gen) -> String {
+    let content: String = (0..usize::arbitrary(g) % 8)
+        .map(|_| *g.choose(QTEXT).unwrap())
+        .collect();
+    format!("\"{}\"", content)
+}
+
+/// Strictly shorter local parts, from the shortest one.
+fn shorter_local_parts(local_part: &str) -> Vec<String> {
+    let mut shorter = vec![String::from("a")];
+    match local_part
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+    {
+        Some(content) => shorter.push(format!("\"{}\"", without_last_char(content))),
+        None => {
+            shorter.push(without_last_char(local_part));
+            if let Some(dot) = local_part.rfind('.') {
+                shorter.push(String::from(&local_part[..dot]));
+            }
+        }
+    }
+    shorter.retain(|candidate| candidate.len() < local_part.len());
+    shorter
+}
+
+fn without_last_char(value: &str) -> String {
+    let mut value = String::from(value);
+    value.pop();
+    value
+}
+
+/// Strictly shorter domains with at least two labels, from the shortest one.
+fn shorter_domains(domain: &str) -> Vec<String> {
+    let mut shorter = vec![String::from("a.com")];
+    if let Some(dot) = domain.find('.') {
+        if domain[dot + 1..].contains('.') {
+            shorter.push(String::from(&domain[dot + 1..]));
+        }
+        if let Some((index, _)) = domain[..dot].char_indices().nth(1) {
+            shorter.push(format!("{}{}", &domain[..index], &domain[dot..]));
+        }
+    }
+    shorter.retain(|candidate| candidate.len() < domain.len());
+    shorter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::{QuickCheck, TestResult};
+
+    #[test]
+    fn arbitrary_email_address_is_valid() {
+        fn property(email: EmailAddress) -> bool {
+            EmailAddress::is_valid(&email.to_string(), None)
+        }
+        QuickCheck::new().quickcheck(property as fn(EmailAddress) -> bool);
+    }
+
+    #[test]
+    fn long_local_parts_are_truncated_to_whole_segments() {
+        let segment = "中".repeat(17);
+        let mut local_part = [segment.as_str(), &segment, &segment].join(".");
+        truncate_segments(&mut local_part, LOCAL_PART_CAPACITY);
+        assert_eq!(local_part, segment);
+
+        let mut local_part = String::from("foo.bar");
+        truncate_segments(&mut local_part, LOCAL_PART_CAPACITY);
+        assert_eq!(local_part, "foo.bar");
+    }
+
+    #[test]
+    fn shrinking_yields_shorter_valid_addresses() {
+        fn property(email: EmailAddress) -> TestResult {
+            let length = email.to_string().len();
+            TestResult::from_bool(email.shrink().all(|shrunk| {
+                EmailAddress::is_valid(&shrunk.to_string(), None)
+                    && shrunk.to_string().len() < length
+            }))
+        }
+        QuickCheck::new().quickcheck(property as fn(EmailAddress) -> TestResult);
+    }
+
+    #[test]
+    fn shrinking_reaches_minimal_address() {
+        let mut email = EmailAddress::new("foo.bar", "mail.example.com", None).unwrap();
+        while let Some(shrunk) = email.shrink().next() {
+            email = shrunk;
+        }
+        assert_eq!(email.to_string(), "a@a.com");
+    }
+}
@@ -0,0 +1,55 @@
+use crate::error::find_separator;
+use crate::mask::MaskPolicy;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static REDACTION: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether the inputs recorded in the traces and logs are redacted; they are by default, so
+/// that the traces and logs do not hold the addresses as they are.
+///
+/// A redacted input shows as an address masked by the default `MaskPolicy`; e.g.
+/// `j***@example.com` for `jane@example.com`, or as one `*` per character if it has no `@`. The
+/// domain, and the first character of the local part, are retained; hence, the records can still
+/// be personal data, e.g. with the domain of a small company, and have to be handled as such. Only
+/// turn the redaction off to debug with inputs that are not personal data, like test fixtures.
+///
+/// Only available with the `tracing` or `log` feature.
+pub fn set_redaction(redact: bool) {
+    REDACTION.store(redact, Ordering::Relaxed);
+}
+
+/// An input as it is recorded in the traces and logs; i.e. redacted unless `set_redaction(false)` was
+/// called. It is only redacted if it is formatted, when the trace or log is enabled.
+pub(crate) struct Redacted<'i>(pub(crate) &'i str);
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = self.0;
+        if !REDACTION.load(Ordering::Relaxed) {
+            return formatter.write_str(input);
+        }
+        match find_separator(input) {
+            Some(at) => {
+                formatter.write_str(&MaskPolicy::new().apply(&input[..at], &input[at + 1..]))
+            }
+            None => input.chars().try_for_each(|_| formatter.write_str("*")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn masks_the_local_part() {
+        assert_eq!(Redacted("jane@example.com").to_string(), "j***@example.com");
+        assert_eq!(
+            Redacted("\"a@b\"@example.com").to_string(),
+            "\"****@example.com"
+        );
+        assert_eq!(Redacted("jäne").to_string(), "****");
+    }
+}
@@ -0,0 +1,436 @@
+use crate::diagnostic::Diagnostic;
+use crate::email_address::ParsingOptions;
+use crate::error::find_separator;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A repaired copy of an input, with the offsets of its octets in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Repaired {
+    pub(crate) text: String,
+    /// The offset in the input of each octet of the text, and of its end.
+    offsets: Vec<usize>,
+}
+
+impl Repaired {
+    pub(crate) fn new(input: &str) -> Repaired {
+        Repaired {
+            text: String::from(input),
+            offsets: (0..=input.len()).collect(),
+        }
+    }
+
+    /// Returns the range of the input that a range of the text was repaired from.
+    pub(crate) fn source(&self, range: Range<usize>) -> Range<usize> {
+        self.offsets[range.start]..self.offsets[range.end]
+    }
+
+    /// Replaces a range of the text; the replacement comes from the source of the range, and a
+    /// deletion at the end of the text, from the source of the text before it.
+    pub(crate) fn replace(&mut self, range: Range<usize>, replacement: &str) {
+        let start = self.offsets[range.start];
+        let replaced = if replacement.is_empty() && range.end == self.text.len() {
+            range.start + 1..range.end + 1
+        } else {
+            range.clone()
+        };
+        self.text.replace_range(range, replacement);
+        self.offsets
+            .splice(replaced, core::iter::repeat_n(start, replacement.len()));
+    }
+}
+
+/// Returns a repaired copy of the input as per the options, or `None` if there is nothing to
+/// repair; each kind of repair is reported to the diagnostics, in order.
+pub(crate) fn repair(
+    input: &str,
+    options: &ParsingOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Repaired> {
+    if !options.map_to_ascii && !options.fix_mistakes {
+        return None;
+    }
+    let mut repaired = Repaired::new(input);
+    if options.map_to_ascii {
+        map_to_ascii(&mut repaired, diagnostics);
+    }
+    if options.fix_mistakes {
+        collapse_separators(&mut repaired, diagnostics);
+        strip_trailing_punctuation(&mut repaired, diagnostics);
+        insert_top_level_domain_dot(&mut repaired, diagnostics);
+        replace_spaces_with_dots(&mut repaired, diagnostics);
+    }
+    if repaired.text == input {
+        return None;
+    }
+    Some(repaired)
+}
+
+fn report(diagnostics: &mut Vec<Diagnostic>, diagnostic: Diagnostic) {
+    if !diagnostics.contains(&diagnostic) {
+        diagnostics.push(diagnostic);
+    }
+}
+
+/// Maps the full-width forms and the smart punctuation marks to their ASCII equivalents.
+fn map_to_ascii(repaired: &mut Repaired, diagnostics: &mut Vec<Diagnostic>) {
+    let mappings: Vec<_> = repaired
+        .text
+        .char_indices()
+        .filter_map(|(index, c)| {
+            let (mapped, diagnostic) = ascii_equivalent(c)?;
+            Some((index..index + c.len_utf8(), mapped, diagnostic))
+        })
+        .collect();
+    for (_, _, diagnostic) in &mappings {
+        report(diagnostics, *diagnostic);
+    }
+    // From the end, so that the ranges of the other mappings are kept.
+    for (range, mapped, _) in mappings.into_iter().rev() {
+        repaired.replace(range, mapped.encode_utf8(&mut [0; 4]));
+    }
+}
+
+/// Returns the ASCII equivalent of a full-width form, or of a smart punctuation mark, along with
+/// the kind of the mapping.
+fn ascii_equivalent(c: char) -> Option<(char, Diagnostic)> {
+    let mapped = match c {
+        // The full-width forms of the printable ASCII characters.
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(u32::from(c) - 0xFEE0)?,
+        '\u{3000}' => ' ',
+        '\u{3002}' | '\u{FF61}' => '.',
+        '\u{FE6B}' => '@',
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => {
+            return Some(('\'', Diagnostic::SmartPunctuation))
+        }
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => {
+            return Some(('"', Diagnostic::SmartPunctuation))
+        }
+        _ => return None,
+    };
+    Some((mapped, Diagnostic::FullWidthForms))
+}
+
+/// Collapses a repeated `@`, like in `foo@@bar.com`, to a single one.
+fn collapse_separators(repaired: &mut Repaired, diagnostics: &mut Vec<Diagnostic>) {
+    let at = match find_separator(&repaired.text) {
+        Some(at) => at,
+        None => return,
+    };
+    let repeated = repaired.text[at..].len() - repaired.text[at..].trim_start_matches('@').len();
+    if repeated > 1 {
+        repaired.replace(at..at + repeated, "@");
+        report(diagnostics, Diagnostic::CollapsedSeparators);
+    }
+}
+
+/// Strips the punctuation after the domain, like in `foo@bar.com.` or `foo@bar.com,`; the white
+/// space after it, if any, is left to trimming.
+fn strip_trailing_punctuation(repaired: &mut Repaired, diagnostics: &mut Vec<Diagnostic>) {
+    let at = match find_separator(&repaired.text) {
+        Some(at) => at,
+        None => return,
+    };
+    let end = repaired.text.trim_end().len();
+    let stripped = repaired.text[..end]
+        .trim_end_matches(&TRAILING_PUNCTUATION[..])
+        .len()
+        .max(at + 1);
+    if stripped < end {
+        repaired.replace(stripped..end, "");
+        report(diagnostics, Diagnostic::StrippedTrailingPunctuation);
+    }
+}
+
+/// Inserts the missing dot before a common top-level domain of a host name without dots, like in
+/// `foo@gmailcom`.
+fn insert_top_level_domain_dot(repaired: &mut Repaired, diagnostics: &mut Vec<Diagnostic>) {
+    let at = match find_separator(&repaired.text) {
+        Some(at) => at,
+        None => return,
+    };
+    let domain = repaired.text[at + 1..].trim_end();
+    if !domain
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    {
+        return;
+    }
+    let position = COMMON_TOP_LEVEL_DOMAINS.iter().find_map(|tld| {
+        let position = domain.len().checked_sub(tld.len())?;
+        let is_missing_dot = domain[position..].eq_ignore_ascii_case(tld)
+            && domain[..position].ends_with(|c: char| c.is_ascii_alphanumeric());
+        Some(position).filter(|_| is_missing_dot)
+    });
+    if let Some(position) = position {
+        let position = at + 1 + position;
+        repaired.replace(position..position, ".");
+        report(diagnostics, Diagnostic::InsertedTopLevelDomainDot);
+    }
+}
+
+/// Replaces the spaces between the labels of a host name, like in `foo@gmail com`, with dots.
+fn replace_spaces_with_dots(repaired: &mut Repaired, diagnostics: &mut Vec<Diagnostic>) {
+    let at = match find_separator(&repaired.text) {
+        Some(at) => at,
+        None => return,
+    };
+    let domain = repaired.text[at + 1..].trim();
+    let offset = repaired.text.len() - repaired.text[at + 1..].trim_start().len();
+    if !domain
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b' ')
+    {
+        return;
+    }
+    // The domain is trimmed, so that each run of spaces is between two other octets.
+    let octets = domain.as_bytes();
+    let mut spaces = Vec::new();
+    let mut index = 0;
+    while index < octets.len() {
+        if octets[index] != b' ' {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while octets[index] == b' ' {
+            index += 1;
+        }
+        if octets[start - 1] != b'.' && octets[index] != b'.' {
+            spaces.push(offset + start..offset + index);
+        }
+    }
+    for range in spaces.iter().rev() {
+        repaired.replace(range.clone(), ".");
+    }
+    if !spaces.is_empty() {
+        report(diagnostics, Diagnostic::ReplacedSpacesWithDots);
+    }
+}
+
+const TRAILING_PUNCTUATION: [char; 4] = ['.', ',', ';', ':'];
+
+/// The top-level domains that `insert_top_level_domain_dot` recognizes.
+pub(crate) const COMMON_TOP_LEVEL_DOMAINS: [&str; 5] = ["com", "net", "org", "edu", "gov"];
+
+/// Returns the part of the input to parse as per the options, without the white space and the
+/// wrappers around the address, along with its offset in the input; each repair is reported to
+/// the diagnostics, in order.
+///
+/// The wrappers are removed from the outside in; e.g. `<mailto:foo@bar.com>` is unwrapped to
+/// `foo@bar.com`. Surrounding double quotes are removed only if the `@` is inside them, as the
+/// input would be a quoted local part otherwise. Without `strip_wrappers`, lax parsing still
+/// removes a single pair of angle brackets.
+pub(crate) fn unwrap<'i>(
+    input: &'i str,
+    options: &ParsingOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> (&'i str, usize) {
+    let mut start = 0;
+    let mut end = input.len();
+    loop {
+        if options.trim {
+            let text = &input[start..end];
+            let trimmed = text.trim_start();
+            start += text.len() - trimmed.len();
+            end = start + trimmed.trim_end().len();
+            if end - start < text.len() && !diagnostics.contains(&Diagnostic::Trimmed) {
+                diagnostics.push(Diagnostic::Trimmed);
+            }
+        }
+        let text = &input[start..end];
+        let is_wrapped =
+            |open, close| text.len() >= 2 && text.starts_with(open) && text.ends_with(close);
+        if !options.strip_wrappers {
+            // Lax parsing accepts a bare angle-addr, as in `<foo@bar.com>`.
+            if options.is_lax && is_wrapped('<', '>') {
+                start += 1;
+                end -= 1;
+                diagnostics.push(Diagnostic::StrippedAngleBrackets);
+            }
+            break;
+        }
+        if text
+            .get(..MAILTO.len())
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case(MAILTO))
+        {
+            start += MAILTO.len();
+            diagnostics.push(Diagnostic::StrippedMailto);
+        } else if is_wrapped('<', '>') {
+            start += 1;
+            end -= 1;
+            diagnostics.push(Diagnostic::StrippedAngleBrackets);
+        } else if is_wrapped('"', '"') && find_separator(text).is_none() {
+            start += 1;
+            end -= 1;
+            diagnostics.push(Diagnostic::StrippedQuotes);
+        } else {
+            break;
+        }
+    }
+    (&input[start..end], start)
+}
+
+const MAILTO: &str = "mailto:";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unwrapped<'i>(
+        input: &'i str,
+        options: &ParsingOptions,
+    ) -> (&'i str, usize, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let (text, offset) = unwrap(input, options, &mut diagnostics);
+        (text, offset, diagnostics)
+    }
+
+    #[test]
+    fn maps_to_ascii_as_per_the_options() {
+        let options = ParsingOptions::default().map_to_ascii(true);
+        let mut diagnostics = Vec::new();
+        assert_eq!(
+            repair(
+                "ｆｏｏ＠ｂａｒ.com",
+                &ParsingOptions::default(),
+                &mut diagnostics
+            ),
+            None
+        );
+        assert_eq!(repair("foö@bar.com", &options, &mut diagnostics), None);
+        assert!(diagnostics.is_empty());
+
+        let input = "‘ö’　ｆ＠ｂ。ｃ";
+        let repaired = repair(input, &options, &mut diagnostics).unwrap();
+        assert_eq!(repaired.text, "'ö' f@b.c");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::SmartPunctuation, Diagnostic::FullWidthForms]
+        );
+        assert_eq!(&input[repaired.source(1..3)], "ö");
+        assert_eq!(&input[repaired.source(5..10)], "ｆ＠ｂ。ｃ");
+        assert_eq!(&input[repaired.source(0..0)], "");
+    }
+
+    fn fixed(input: &str) -> (String, Vec<Diagnostic>) {
+        let options = ParsingOptions {
+            fix_mistakes: true,
+            ..ParsingOptions::default()
+        };
+        let mut diagnostics = Vec::new();
+        let text =
+            repair(input, &options, &mut diagnostics).map_or(String::from(input), |r| r.text);
+        (text, diagnostics)
+    }
+
+    #[test]
+    fn fixes_common_mistakes() {
+        assert_eq!(fixed("foo@bar.com"), ("foo@bar.com".into(), vec![]));
+        assert_eq!(
+            fixed("\"a@@b\"@@@bar.com"),
+            (
+                "\"a@@b\"@bar.com".into(),
+                vec![Diagnostic::CollapsedSeparators]
+            )
+        );
+        assert_eq!(
+            fixed("foo@bar.com.,; "),
+            (
+                "foo@bar.com ".into(),
+                vec![Diagnostic::StrippedTrailingPunctuation]
+            )
+        );
+        assert_eq!(
+            fixed("foo@."),
+            ("foo@".into(), vec![Diagnostic::StrippedTrailingPunctuation])
+        );
+        assert_eq!(
+            fixed("foo@GmailCom"),
+            (
+                "foo@Gmail.Com".into(),
+                vec![Diagnostic::InsertedTopLevelDomainDot]
+            )
+        );
+        for input in &["foo@com", "foo@-com", "foo@gmail.com", "foo@[gmailcom]"] {
+            assert_eq!(fixed(input), (String::from(*input), vec![]));
+        }
+        assert_eq!(
+            fixed("foo@ mail  example com "),
+            (
+                "foo@ mail.example.com ".into(),
+                vec![Diagnostic::ReplacedSpacesWithDots]
+            )
+        );
+        assert_eq!(fixed("foo@bar .com"), ("foo@bar .com".into(), vec![]));
+    }
+
+    #[test]
+    fn maps_repaired_ranges_to_the_input() {
+        let options = ParsingOptions {
+            fix_mistakes: true,
+            ..ParsingOptions::default().map_to_ascii(true)
+        };
+        let input = "ｆ＠＠ｂ ｃｏｍ．";
+        let repaired = repair(input, &options, &mut Vec::new()).unwrap();
+        assert_eq!(repaired.text, "f@b.com");
+        assert_eq!(&input[repaired.source(0..2)], "ｆ＠＠");
+        assert_eq!(&input[repaired.source(2..4)], "ｂ ");
+        assert_eq!(&input[repaired.source(4..7)], "ｃｏｍ");
+    }
+
+    #[test]
+    fn unwraps_as_per_the_options() {
+        let input = " <MailTo:foo@bar.com> ";
+        assert_eq!(
+            unwrapped(input, &ParsingOptions::default()),
+            (input, 0, vec![])
+        );
+        assert_eq!(
+            unwrapped(input, &ParsingOptions::default().trim(true)),
+            ("<MailTo:foo@bar.com>", 1, vec![Diagnostic::Trimmed])
+        );
+        assert_eq!(
+            unwrapped(input, &ParsingOptions::default().strip_wrappers(true)),
+            (input, 0, vec![])
+        );
+        assert_eq!(
+            unwrapped(
+                input,
+                &ParsingOptions::default().trim(true).strip_wrappers(true)
+            ),
+            (
+                "foo@bar.com",
+                9,
+                vec![
+                    Diagnostic::Trimmed,
+                    Diagnostic::StrippedAngleBrackets,
+                    Diagnostic::StrippedMailto
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn unwraps_quotes_around_the_separator_only() {
+        let options = ParsingOptions::default().strip_wrappers(true);
+        assert_eq!(
+            unwrapped("\"<foo@bar.com>\"", &options),
+            (
+                "foo@bar.com",
+                2,
+                vec![
+                    Diagnostic::StrippedQuotes,
+                    Diagnostic::StrippedAngleBrackets
+                ]
+            )
+        );
+        assert_eq!(
+            unwrapped("\"foo\"@\"bar\"", &options),
+            ("\"foo\"@\"bar\"", 0, vec![])
+        );
+        assert_eq!(unwrapped("\"", &options), ("\"", 0, vec![]));
+        assert_eq!(unwrapped("<>", &options).0, "");
+    }
+}
@@ -0,0 +1,131 @@
+use crate::canonical::canonical_domain;
+use crate::email_address::ParsingOptions;
+use crate::error::{ErrorKind, Hint};
+use crate::labels::check_labels;
+use crate::tokens::{tokenize, TokenKind};
+
+/// Checks a (syntactically valid) local part against the restrictions of the options; failing
+/// with the kind of the error and a hint, if any.
+pub(crate) fn check_local_part(
+    local_part: &str,
+    options: &ParsingOptions,
+) -> Result<(), (ErrorKind, Option<Hint>)> {
+    if (!options.allow_comments || options.rfc3696) && has_comment(local_part) {
+        return Err((ErrorKind::InvalidLocalPart, Some(Hint::Comment)));
+    }
+    if options.rfc3696 && has_whitespace(local_part) {
+        return Err((ErrorKind::InvalidLocalPart, Some(Hint::Whitespace)));
+    }
+    if !options.allow_quoted_local_part && has_quoted_string(local_part) {
+        return Err((ErrorKind::QuotedLocalPart, None));
+    }
+    if !options.allow_utf8 && !local_part.is_ascii() {
+        return Err((ErrorKind::InvalidLocalPart, Some(Hint::NonAscii)));
+    }
+    Ok(())
+}
+
+/// Checks a (syntactically valid) domain against the restrictions of the options; failing with a
+/// hint.
+pub(crate) fn check_domain(domain: &str, options: &ParsingOptions) -> Result<(), Hint> {
+    if (!options.allow_comments || options.rfc3696) && has_comment(domain) {
+        return Err(Hint::Comment);
+    }
+    if (!options.allow_domain_literal || options.rfc3696)
+        && canonical_domain(domain).starts_with('[')
+    {
+        return Err(Hint::DomainLiteral);
+    }
+    if options.rfc3696 && has_whitespace(domain) {
+        return Err(Hint::Whitespace);
+    }
+    if !options.allow_utf8 && !domain.is_ascii() {
+        return Err(Hint::NonAscii);
+    }
+    check_labels(domain, options)
+}
+
+/// Returns whether a (syntactically valid) local part or domain has a comment; i.e. an opening
+/// parenthesis that is neither in a quoted string nor in a domain literal.
+fn has_comment(part: &str) -> bool {
+    let mut in_quotes = false;
+    let mut in_literal = false;
+    let mut escaped = false;
+    for c in part.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes || in_literal => escaped = true,
+            '"' if !in_literal => in_quotes = !in_quotes,
+            '[' if !in_quotes => in_literal = true,
+            ']' if !in_quotes => in_literal = false,
+            '(' if !in_quotes && !in_literal => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Returns whether a (syntactically valid) local part or host name has white space outside its
+/// quoted strings and comments.
+fn has_whitespace(part: &str) -> bool {
+    tokenize(part).any(|token| token.kind() == TokenKind::Whitespace)
+}
+
+/// Returns whether a (syntactically valid) local part has a quoted string; i.e. a double quote that
+/// is not in a comment.
+fn has_quoted_string(local_part: &str) -> bool {
+    let mut comment_depth = 0;
+    let mut escaped = false;
+    for c in local_part.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if comment_depth > 0 => escaped = true,
+            '(' => comment_depth += 1,
+            ')' => comment_depth -= 1,
+            '"' if comment_depth == 0 => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_comments_outside_quoted_strings_and_literals() {
+        for part in &["(x)foo", "foo (x)", "\"a\" (b)", "[1.2.3.4] (x)"] {
+            assert!(has_comment(part), "{}", part);
+        }
+        for part in &["foo", "\"(x)\"", "\"a\\\"(x)\"", "[(x)]", "[\\](x)]"] {
+            assert!(!has_comment(part), "{}", part);
+        }
+    }
+
+    #[test]
+    fn finds_whitespace_outside_quoted_strings_and_comments() {
+        for part in &[" foo", "foo .bar", "\"a\" "] {
+            assert!(has_whitespace(part), "{}", part);
+        }
+        for part in &["foo", "\"a b\"", "foo(a b)"] {
+            assert!(!has_whitespace(part), "{}", part);
+        }
+    }
+
+    #[test]
+    fn finds_quoted_strings_outside_comments() {
+        for part in &["\"foo\"", "foo.\"bar\"", "(x) \"foo\""] {
+            assert!(has_quoted_string(part), "{}", part);
+        }
+        for part in &["foo", "foo(\"x\")", "(\\(\"x\")foo"] {
+            assert!(!has_quoted_string(part), "{}", part);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::canonical::quote_local_part;
+use crate::diagnostic::Diagnostic;
+use crate::email_address::EmailAddress;
+
+/// A legacy route in a local part, to the mailbox of a user on a final host.
+pub(crate) struct LegacyRoute<'a> {
+    pub(crate) diagnostic: Diagnostic,
+    pub(crate) user: &'a str,
+    pub(crate) host: &'a str,
+}
+
+/// Returns the legacy route of an unquoted local part, if it is one; i.e. a bang path, like
+/// `first!final!user`, or else a percent hack, like `user%final%first`.
+pub(crate) fn legacy_route(local_part: &str) -> Option<LegacyRoute<'_>> {
+    let route = match local_part.rsplit_once('!') {
+        Some((hosts, user)) => LegacyRoute {
+            diagnostic: Diagnostic::BangPath,
+            user,
+            host: hosts.rsplit('!').next().unwrap_or_default(),
+        },
+        None => {
+            let (user, hosts) = local_part.split_once('%')?;
+            LegacyRoute {
+                diagnostic: Diagnostic::PercentHack,
+                user,
+                host: hosts.split('%').next().unwrap_or_default(),
+            }
+        }
+    };
+    Some(route).filter(|route| !route.user.is_empty() && !route.host.is_empty())
+}
+
+impl EmailAddress {
+    /// Returns the address that a legacy route in the local part leads to, if there is one; i.e.
+    /// the mailbox of the user on the final host of a UUCP bang path, like `user@final.example` for
+    /// `first.example!final.example!user@relay.example`, or else of a percent hack, like
+    /// `user@final.example` for `user%final.example%first.example@relay.example`.
+    ///
+    /// Such addresses are valid, but hardly used anymore other than to abuse open relays; they are
+    /// among the diagnostics of `EmailAddress::parse_with_diagnostics`. `None` is returned if the
+    /// address has no route, or if its destination is not a valid address.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{Diagnostic, EmailAddress};
+    ///
+    /// let (email, diagnostics) =
+    ///     EmailAddress::parse_with_diagnostics("victim%target.example@relay.example", None).unwrap();
+    /// assert_eq!(diagnostics, [Diagnostic::PercentHack]);
+    /// let destination = email.final_destination().unwrap();
+    /// assert_eq!(destination.to_string(), "victim@target.example");
+    ///
+    /// let email = EmailAddress::parse("target.example!victim@relay.example", None).unwrap();
+    /// assert_eq!(email.final_destination().unwrap().to_string(), "victim@target.example");
+    ///
+    /// let email = EmailAddress::parse("jane@example.com", None).unwrap();
+    /// assert_eq!(email.final_destination(), None);
+    /// ```
+    pub fn final_destination(&self) -> Option<EmailAddress> {
+        let local_part = self.local_part_unquoted();
+        let route = legacy_route(&local_part)?;
+        EmailAddress::new(&quote_local_part(route.user), route.host, None).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+
+    fn destination(input: &str) -> Option<String> {
+        let email = EmailAddress::parse(input, None).unwrap();
+        email.final_destination().map(|email| email.to_string())
+    }
+
+    #[test]
+    fn follows_legacy_routes() {
+        assert_eq!(
+            destination("a.example!b.example!user@relay.example").as_deref(),
+            Some("user@b.example")
+        );
+        assert_eq!(
+            destination("b.example!user%c.example@relay.example").as_deref(),
+            Some("user%c.example@b.example")
+        );
+        assert_eq!(
+            destination("\"john doe%b.example\"@relay.example").as_deref(),
+            Some("\"john doe\"@b.example")
+        );
+        assert_eq!(destination("user%@relay.example"), None);
+        assert_eq!(destination("!user@relay.example"), None);
+        assert_eq!(destination("user%-b.example@relay.example"), None);
+    }
+}
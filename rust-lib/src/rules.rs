@@ -0,0 +1,375 @@
+use crate::dictionary::DISPOSABLE_DOMAINS;
+use crate::domain_set::DomainSet;
+use crate::email_address::EmailAddress;
+use crate::suggest::suggest;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A rule of an acceptance policy, that a (syntactically valid) email address has to follow; e.g.
+/// a domain denylist. See `Policy`.
+///
+/// The rules are usually built-in ones, like `DomainDenylist`, but any closure from an address to
+/// a `Result<(), Violation>` is a rule too.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// struct NoPlusSigns;
+///
+/// impl ValidationRule for NoPlusSigns {
+///     fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+///         if email.get_local_part().contains('+') {
+///             return Err(Violation::new("plus_sign", "Sub-addresses are not allowed."));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let email = EmailAddress::parse("jane+news@example.com", None).unwrap();
+/// assert_eq!(NoPlusSigns.check(&email).unwrap_err().code(), "plus_sign");
+/// ```
+pub trait ValidationRule: Send + Sync {
+    /// Checks an address against the rule.
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation>;
+}
+
+impl<F> ValidationRule for F
+where
+    F: Fn(&EmailAddress) -> Result<(), Violation> + Send + Sync,
+{
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        self(email)
+    }
+}
+
+/// A violation of a `ValidationRule`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Violation {
+    code: &'static str,
+    message: String,
+}
+
+impl Violation {
+    /// Instantiates a violation with a stable, snake case code, and a message for humans.
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Violation {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the code of the violation; e.g. `denied_domain`.
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Returns the message of the violation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Violation {}
+
+/// Returns the lower case domains, without their trailing dots.
+fn lower_case_domains<'d>(domains: impl IntoIterator<Item = &'d str>) -> Vec<String> {
+    domains
+        .into_iter()
+        .map(|domain| domain.trim_end_matches('.').to_lowercase())
+        .collect()
+}
+
+/// Rejects the addresses of the given domains, and of their subdomains; code `denied_domain`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let rule = DomainDenylist::new(["spam.example"]);
+/// assert!(rule.check(&EmailAddress::parse("jane@ham.example", None).unwrap()).is_ok());
+/// assert!(rule.check(&EmailAddress::parse("jane@mail.SPAM.example", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainDenylist {
+    domains: DomainSet,
+}
+
+impl DomainDenylist {
+    /// Instantiates the rule; the domains are compared ignoring case.
+    pub fn new<'d>(domains: impl IntoIterator<Item = &'d str>) -> Self {
+        DomainDenylist {
+            domains: domains.into_iter().collect(),
+        }
+    }
+}
+
+impl ValidationRule for DomainDenylist {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        match self.domains.find_suffix(email.canonical().get_domain()) {
+            Some(domain) => Err(Violation::new(
+                "denied_domain",
+                format!("The addresses of '{}' are not allowed.", domain),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Accepts the addresses of the given domains, and of their subdomains, only; e.g. to require
+/// corporate addresses. Code `domain_not_allowed`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let rule = RequireDomains::new(["example.com"]);
+/// assert!(rule.check(&EmailAddress::parse("jane@eu.example.com", None).unwrap()).is_ok());
+/// assert!(rule.check(&EmailAddress::parse("jane@gmail.com", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequireDomains {
+    domains: DomainSet,
+}
+
+impl RequireDomains {
+    /// Instantiates the rule; the domains are compared ignoring case.
+    pub fn new<'d>(domains: impl IntoIterator<Item = &'d str>) -> Self {
+        RequireDomains {
+            domains: domains.into_iter().collect(),
+        }
+    }
+}
+
+impl ValidationRule for RequireDomains {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        match self.domains.find_suffix(email.canonical().get_domain()) {
+            Some(_) => Ok(()),
+            None => Err(Violation::new(
+                "domain_not_allowed",
+                format!("The addresses of '{}' are not allowed.", email.get_domain()),
+            )),
+        }
+    }
+}
+
+/// Rejects the addresses of the disposable mailbox providers of `DISPOSABLE_DOMAINS`, and of their
+/// subdomains; code `disposable_domain`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// assert!(DisposableDomains.check(&EmailAddress::parse("jane@example.com", None).unwrap()).is_ok());
+/// assert!(DisposableDomains.check(&EmailAddress::parse("jane@mailinator.com", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DisposableDomains;
+
+impl ValidationRule for DisposableDomains {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        let domain = email.canonical().get_domain().to_string();
+        let is_disposable = DISPOSABLE_DOMAINS.iter().any(|disposable| {
+            domain == *disposable
+                || domain
+                    .strip_suffix(disposable)
+                    .is_some_and(|subdomain| subdomain.ends_with('.'))
+        });
+        if is_disposable {
+            return Err(Violation::new(
+                "disposable_domain",
+                format!(
+                    "Disposable addresses like those of '{}' are not allowed.",
+                    domain
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Requires the domain to be a host name with a top-level domain; i.e. neither a dotless host
+/// name, like `localhost`, nor a domain literal. Code `missing_top_level_domain`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// assert!(RequireTopLevelDomain.check(&EmailAddress::parse("jane@example.com", None).unwrap()).is_ok());
+/// assert!(RequireTopLevelDomain.check(&EmailAddress::parse("jane@localhost", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RequireTopLevelDomain;
+
+impl ValidationRule for RequireTopLevelDomain {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        let canonical = email.canonical();
+        let domain = canonical.get_domain();
+        if domain.starts_with('[') || !domain.trim_end_matches('.').contains('.') {
+            return Err(Violation::new(
+                "missing_top_level_domain",
+                format!("The domain '{}' has no top-level domain.", domain),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects the addresses with a likely typo in their domains, suggesting a correction; e.g.
+/// `jane@gmial.com`. See `suggest` for the dictionaries. Code `likely_typo`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// let rule = DomainTypos::new(["hooli.xyz"]);
+/// let violation = rule.check(&EmailAddress::parse("jane@gmial.com", None).unwrap()).unwrap_err();
+/// assert_eq!(violation.message(), "Did you mean 'jane@gmail.com'?");
+/// assert!(rule.check(&EmailAddress::parse("jane@hooli.xzy", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainTypos {
+    dictionary: Vec<String>,
+}
+
+impl DomainTypos {
+    /// Instantiates the rule with a dictionary of domains, on top of `POPULAR_DOMAINS`.
+    pub fn new<'d>(dictionary: impl IntoIterator<Item = &'d str>) -> Self {
+        DomainTypos {
+            dictionary: lower_case_domains(dictionary),
+        }
+    }
+}
+
+impl ValidationRule for DomainTypos {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        let dictionary: Vec<&str> = self.dictionary.iter().map(String::as_str).collect();
+        match suggest(email, &dictionary) {
+            Some(suggested) => Err(Violation::new(
+                "likely_typo",
+                format!("Did you mean '{}'?", suggested),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The local parts of role accounts, that `RoleAccounts` rejects.
+const ROLE_ACCOUNTS: &[&str] = &[
+    "abuse",
+    "admin",
+    "administrator",
+    "billing",
+    "contact",
+    "help",
+    "hostmaster",
+    "info",
+    "marketing",
+    "no-reply",
+    "noreply",
+    "office",
+    "postmaster",
+    "root",
+    "sales",
+    "security",
+    "support",
+    "team",
+    "webmaster",
+];
+
+/// Rejects the addresses of role accounts, that reach a function rather than a person; e.g.
+/// `info@` or `postmaster@`. The local parts are compared ignoring case and sub-addresses (tags).
+/// Code `role_account`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::*;
+///
+/// assert!(RoleAccounts.check(&EmailAddress::parse("jane@example.com", None).unwrap()).is_ok());
+/// assert!(RoleAccounts.check(&EmailAddress::parse("Info+web@example.com", None).unwrap()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RoleAccounts;
+
+impl ValidationRule for RoleAccounts {
+    fn check(&self, email: &EmailAddress) -> Result<(), Violation> {
+        let local_part = email.local_part_unquoted().to_lowercase();
+        let local_part = local_part.split('+').next().unwrap_or_default();
+        if ROLE_ACCOUNTS.contains(&local_part) {
+            return Err(Violation::new(
+                "role_account",
+                format!("Role accounts like '{}' are not allowed.", local_part),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(input: &str) -> EmailAddress {
+        EmailAddress::parse(input, None).unwrap()
+    }
+
+    #[test]
+    fn domains_match_themselves_and_their_subdomains() {
+        let rule = DomainDenylist::new(["Example.com."]);
+        for input in &["a@example.com", "a@EXAMPLE.com", "a@mail.example.com"] {
+            assert_eq!(
+                rule.check(&email(input)).unwrap_err().code(),
+                "denied_domain"
+            );
+        }
+        for input in &["a@notexample.com", "a@example.com.au", "a@[127.0.0.1]"] {
+            assert!(rule.check(&email(input)).is_ok(), "{}", input);
+        }
+        let rule = RequireDomains::new(["example.com"]);
+        let violation = rule.check(&email("a@example.org")).unwrap_err();
+        assert_eq!(violation.code(), "domain_not_allowed");
+        assert_eq!(
+            violation.to_string(),
+            "The addresses of 'example.org' are not allowed."
+        );
+    }
+
+    #[test]
+    fn role_accounts_are_rejected() {
+        for input in &["admin@x.org", "\"No-Reply\"@x.org", "sales+eu@x.org"] {
+            assert_eq!(
+                RoleAccounts.check(&email(input)).unwrap_err().code(),
+                "role_account"
+            );
+        }
+        assert!(RoleAccounts.check(&email("administration@x.org")).is_ok());
+    }
+
+    #[test]
+    fn domains_are_checked_for_disposables_typos_and_top_level_domains() {
+        assert!(DisposableDomains.check(&email("a@eu.YOPMAIL.com")).is_err());
+        assert!(DisposableDomains.check(&email("a@notyopmail.com")).is_ok());
+        for input in &["a@localhost", "a@[127.0.0.1]"] {
+            assert!(
+                RequireTopLevelDomain.check(&email(input)).is_err(),
+                "{}",
+                input
+            );
+        }
+        let rule = DomainTypos::default();
+        assert_eq!(
+            rule.check(&email("a@yahooo.com")).unwrap_err().code(),
+            "likely_typo"
+        );
+        assert!(rule.check(&email("a@yahoo.com")).is_ok());
+    }
+}
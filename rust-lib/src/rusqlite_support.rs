@@ -0,0 +1,99 @@
+//! `EmailAddress` is stored as `TEXT`; i.e. as its string representation.
+//!
+//! On read, the stored value is parsed strictly. Use `LaxEmailAddress` for columns containing
+//! legacy addresses with obsolete parts.
+//!
+//! ```ignore
+//! use email_address_parser::EmailAddress;
+//!
+//! let email = EmailAddress::parse("foo@bar.com", None).unwrap();
+//! connection.execute("INSERT INTO users (email) VALUES (?1)", [&email])?;
+//!
+//! let email: EmailAddress = connection.query_row("SELECT email FROM users", [], |row| row.get(0))?;
+//! ```
+
+use crate::email_address::EmailAddress;
+use crate::lax::LaxEmailAddress;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+macro_rules! impl_rusqlite_text {
+    ($type:ty) => {
+        impl FromSql for $type {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                value
+                    .as_str()?
+                    .parse::<$type>()
+                    .map_err(|error| FromSqlError::Other(error.into()))
+            }
+        }
+
+        impl ToSql for $type {
+            fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+                Ok(ToSqlOutput::from(self.to_string()))
+            }
+        }
+    };
+}
+
+impl_rusqlite_text!(EmailAddress);
+impl_rusqlite_text!(LaxEmailAddress);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn connection() -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute("CREATE TABLE users (email TEXT NOT NULL)", [])
+            .unwrap();
+        connection
+    }
+
+    #[test]
+    fn email_address_round_trips_through_text_column() {
+        let connection = connection();
+        let email = EmailAddress::new("foö", "bücher.de", None).unwrap();
+        connection
+            .execute("INSERT INTO users (email) VALUES (?1)", [&email])
+            .unwrap();
+
+        let stored: String = connection
+            .query_row("SELECT email FROM users", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, "foö@bücher.de");
+        let actual: EmailAddress = connection
+            .query_row("SELECT email FROM users", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(actual, email);
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn legacy_address_is_read_only_as_lax_email_address() {
+        let connection = connection();
+        connection
+            .execute(
+                "INSERT INTO users (email) VALUES (?1)",
+                ["\u{0d}\u{0a} test@iana.org"],
+            )
+            .unwrap();
+
+        let strict = connection.query_row("SELECT email FROM users", [], |row| {
+            row.get::<_, EmailAddress>(0)
+        });
+        assert!(strict.is_err());
+        let lax: LaxEmailAddress = connection
+            .query_row("SELECT email FROM users", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(lax.get_domain(), "iana.org");
+    }
+
+    #[test]
+    fn non_text_value_is_rejected() {
+        let connection = connection();
+        let actual = connection.query_row("SELECT 42", [], |row| row.get::<_, EmailAddress>(0));
+        assert!(actual.is_err());
+    }
+}
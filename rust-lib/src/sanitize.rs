@@ -0,0 +1,299 @@
+use crate::canonical::{quote_local_part, unquoted_local_part};
+use crate::email_address::{EmailAddress, DOMAIN_CAPACITY, LOCAL_PART_CAPACITY};
+use crate::error::{ErrorKind, ParseError};
+use crate::header::is_header_safe;
+use crate::normalize::to_ascii;
+use alloc::format;
+use core::ops::Range;
+
+/// Maximum length of an address in octets; the limit of a path (256 octets) in RFC 5321, without
+/// the angle brackets.
+const ADDRESS_MAX_LENGTH: usize = 254;
+
+/// Maximum length of a domain label in octets, as per RFC 1035.
+const LABEL_MAX_LENGTH: usize = 63;
+
+impl EmailAddress {
+    /// Returns the form of the address to use in an SMTP envelope: the canonical form, with the
+    /// domain converted to its ASCII form (with the `idna` feature), checked against the limits of
+    /// RFC 5321.
+    ///
+    /// That is, the comments and folding white space are dropped, the local part is quoted only if
+    /// it has to be, and the domain is lower cased. It fails with:
+    /// - `ErrorKind::InvalidLocalPart` if the local part has a control character escaped in it,
+    ///   which lax parsing accepts as per the obsolete syntax,
+    /// - any of the errors of `EmailAddress::validate_lengths`.
+    ///
+    /// The input of the error is the sanitized address.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ErrorKind, ParsingOptions};
+    ///
+    /// # #[cfg(not(feature = "strict-only"))] {
+    /// let email = EmailAddress::parse("\"Foo\" . bar@ BAR .com", Some(ParsingOptions::new(true)));
+    /// assert_eq!(email.unwrap().sanitize().unwrap().to_string(), "Foo.bar@bar.com");
+    /// # }
+    ///
+    /// # #[cfg(not(feature = "heapless"))] {
+    /// let email = EmailAddress::new(&"a".repeat(65), "bar.com", None).unwrap();
+    /// assert_eq!(email.sanitize().unwrap_err().kind(), ErrorKind::LocalPartTooLong);
+    /// # }
+    /// ```
+    pub fn sanitize(&self) -> Result<EmailAddress, ParseError> {
+        let canonical = self.canonical();
+        let local_part = canonical.get_local_part();
+        let domain = to_ascii(canonical.get_domain());
+        let address = format!("{}@{}", local_part, domain);
+        let error = |kind, span| Err(ParseError::new(kind, &address, span, None));
+
+        // The canonical form drops the escaped control characters, that would change the address.
+        let quoted = quote_local_part(&unquoted_local_part(self.get_local_part()));
+        if !is_header_safe(&quoted) {
+            let address = format!("{}@{}", quoted, domain);
+            let span = 0..quoted.len();
+            return Err(ParseError::new(
+                ErrorKind::InvalidLocalPart,
+                &address,
+                span,
+                None,
+            ));
+        }
+        if let Err((kind, span)) = check_lengths(local_part, &domain) {
+            return error(kind, span);
+        }
+        EmailAddress::store(local_part, &domain).or_else(|kind| error(kind, 0..address.len()))
+    }
+
+    /// Checks the address against the length limits of RFC 5321, counted in octets of the UTF-8
+    /// encoding rather than in characters, as per RFC 6531; the domain is counted in its ASCII form
+    /// (with the `idna` feature), as it is sent over the wire.
+    ///
+    /// The canonical form of the address is checked; i.e. comments, folding white space, and
+    /// unnecessary quotes do not count. It fails with:
+    /// - `ErrorKind::LocalPartTooLong` if the local part is longer than 64 octets,
+    /// - `ErrorKind::DomainTooLong` if the domain is longer than 255 octets, or any of its labels
+    ///   is longer than 63 octets,
+    /// - `ErrorKind::AddressTooLong` if the address is longer than 254 octets.
+    ///
+    /// The input of the error is the canonical address, with the domain in its ASCII form.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{EmailAddress, ErrorKind};
+    ///
+    /// // 32 characters, but 64 octets.
+    /// let email = EmailAddress::new(&"ö".repeat(32), "bar.com", None).unwrap();
+    /// assert!(email.validate_lengths().is_ok());
+    ///
+    /// # #[cfg(not(feature = "heapless"))] {
+    /// // 33 characters, but 66 octets.
+    /// let email = EmailAddress::new(&"ö".repeat(33), "bar.com", None).unwrap();
+    /// assert_eq!(email.validate_lengths().unwrap_err().kind(), ErrorKind::LocalPartTooLong);
+    /// # }
+    /// ```
+    pub fn validate_lengths(&self) -> Result<(), ParseError> {
+        let canonical = self.canonical();
+        let local_part = canonical.get_local_part();
+        let domain = to_ascii(canonical.get_domain());
+        check_lengths(local_part, &domain).map_err(|(kind, span)| {
+            let address = format!("{}@{}", local_part, domain);
+            ParseError::new(kind, &address, span, None)
+        })
+    }
+
+    /// Returns the length of the canonical form of the address, in octets of its UTF-8 encoding;
+    /// e.g. to check it against the width of a storage column. See `validate_lengths` for the
+    /// limits of RFC 5321, that count the domain in its ASCII form.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    ///
+    /// let email = EmailAddress::parse("(comment)\"jöhn\"@Example.com", None).unwrap();
+    /// assert_eq!(email.len(), 17);
+    /// assert_eq!(email.local_part_len(), 5);
+    /// assert_eq!(email.domain_len(), 11);
+    /// assert!(email.is_within_limits());
+    /// ```
+    // An address is never empty.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        let canonical = self.canonical();
+        canonical.get_local_part().len() + 1 + canonical.get_domain().len()
+    }
+
+    /// Returns the length of the local part of the canonical form, in octets.
+    pub fn local_part_len(&self) -> usize {
+        self.canonical().get_local_part().len()
+    }
+
+    /// Returns the length of the domain of the canonical form, in octets.
+    pub fn domain_len(&self) -> usize {
+        self.canonical().get_domain().len()
+    }
+
+    /// Returns whether the address is within the length limits of RFC 5321; see
+    /// `validate_lengths`.
+    pub fn is_within_limits(&self) -> bool {
+        self.validate_lengths().is_ok()
+    }
+}
+
+/// Checks the lengths of the parts of an address, in octets, against the limits of RFC 5321;
+/// failing with the kind of the error and its span in the address.
+pub(crate) fn check_lengths(
+    local_part: &str,
+    domain: &str,
+) -> Result<(), (ErrorKind, Range<usize>)> {
+    let address_length = local_part.len() + 1 + domain.len();
+    if local_part.len() > LOCAL_PART_CAPACITY {
+        return Err((ErrorKind::LocalPartTooLong, 0..local_part.len()));
+    }
+    let has_long_label = !domain.starts_with('[')
+        && domain
+            .split('.')
+            .any(|label| label.len() > LABEL_MAX_LENGTH);
+    if domain.len() > DOMAIN_CAPACITY || has_long_label {
+        return Err((
+            ErrorKind::DomainTooLong,
+            local_part.len() + 1..address_length,
+        ));
+    }
+    if address_length > ADDRESS_MAX_LENGTH {
+        return Err((ErrorKind::AddressTooLong, 0..address_length));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsingOptions;
+    use alloc::string::ToString;
+
+    fn sanitize(input: &str) -> Result<EmailAddress, ParseError> {
+        EmailAddress::parse(input, Some(ParsingOptions::new(true)))
+            .unwrap()
+            .sanitize()
+    }
+
+    #[test]
+    fn sanitizes_to_the_canonical_form() {
+        assert_eq!(
+            sanitize("(comment)\"foo\"@BAR.com").unwrap().to_string(),
+            "foo@bar.com"
+        );
+        assert_eq!(
+            sanitize("\"foo bar\"@[127.0.0.1]").unwrap().to_string(),
+            "\"foo bar\"@[127.0.0.1]"
+        );
+        #[cfg(feature = "idna")]
+        assert_eq!(
+            sanitize("foö@Bücher.de").unwrap().to_string(),
+            "foö@xn--bcher-kva.de"
+        );
+    }
+
+    #[test]
+    fn checks_length_limits() {
+        let longest_label = "a".repeat(LABEL_MAX_LENGTH);
+        let local_part = "a".repeat(LOCAL_PART_CAPACITY);
+        let domain = format!("{0}.{0}.{1}.com", longest_label, &longest_label[6..]);
+        let address = format!("{}@{}", local_part, domain);
+        assert_eq!(address.len(), ADDRESS_MAX_LENGTH);
+        assert!(sanitize(&address).is_ok());
+
+        // With the `heapless` feature, such a local part does not even parse.
+        #[cfg(not(feature = "heapless"))]
+        {
+            let error = sanitize(&format!("a{}", address)).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::LocalPartTooLong);
+            assert_eq!(error.span(), 0..LOCAL_PART_CAPACITY + 1);
+        }
+
+        let error = sanitize(&format!("{}@a{}", local_part, longest_label)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::DomainTooLong);
+        assert_eq!(&error.input()[error.span()], format!("a{}", longest_label));
+
+        let address = format!("{}@a.{}", local_part, domain);
+        let error = sanitize(&address).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AddressTooLong);
+        assert_eq!(
+            error.to_string(),
+            format!("Too long email address '{}'.", address)
+        );
+    }
+
+    #[cfg(not(feature = "strict-only"))]
+    #[test]
+    fn measures_the_canonical_form() {
+        let email =
+            EmailAddress::parse("a . b@ (c) X.org", Some(ParsingOptions::new(true))).unwrap();
+        assert_eq!((email.local_part_len(), email.domain_len()), (3, 5));
+        assert_eq!(email.len(), 9);
+        let email = EmailAddress::new("\"a b\"", "[127.0.0.1]", None).unwrap();
+        assert_eq!(email.len(), 17);
+        let email = EmailAddress::new("a", &format!("{}.com", "a".repeat(64)), None).unwrap();
+        assert!(!email.is_within_limits());
+    }
+
+    #[test]
+    fn counts_lengths_in_octets() {
+        let lengths = |local_part: &str, domain: &str| {
+            EmailAddress::new(local_part, domain, None)
+                .unwrap()
+                .validate_lengths()
+                .map_err(|error| error.kind())
+        };
+        // "ö" is 2 octets long, and "€" 3 octets long.
+        assert_eq!(lengths(&"ö".repeat(32), "bar.com"), Ok(()));
+        assert_eq!(lengths(&format!("a{}", "€".repeat(21)), "bar.com"), Ok(()));
+        // With the `heapless` feature, such local parts do not even parse.
+        #[cfg(not(feature = "heapless"))]
+        {
+            assert_eq!(
+                lengths(&format!("{}a", "ö".repeat(32)), "bar.com"),
+                Err(ErrorKind::LocalPartTooLong)
+            );
+            assert_eq!(
+                lengths(&"€".repeat(22), "bar.com"),
+                Err(ErrorKind::LocalPartTooLong)
+            );
+        }
+
+        // 64 + 1 + 189 = 254 octets, in 222 characters only.
+        let local_part = "ö".repeat(32);
+        let domain = format!("{0}.{0}.{1}.com", "a".repeat(63), "a".repeat(57));
+        assert_eq!(lengths(&local_part, &domain), Ok(()));
+        let domain = format!("{0}.{0}.{1}.com", "a".repeat(63), "a".repeat(58));
+        assert_eq!(
+            lengths(&local_part, &domain),
+            Err(ErrorKind::AddressTooLong)
+        );
+        // 32 characters, but 64 octets.
+        let domain = format!("{}.com", "ö".repeat(32));
+        #[cfg(not(feature = "idna"))]
+        assert_eq!(lengths("foo", &domain), Err(ErrorKind::DomainTooLong));
+        #[cfg(feature = "idna")]
+        assert_eq!(lengths("foo", &domain), Ok(()));
+    }
+
+    #[cfg(feature = "idna")]
+    #[test]
+    fn counts_the_ascii_form_of_the_domain() {
+        // 60 characters, and 61 octets, but the A-label is longer than 63 octets.
+        let domain = format!("{}ü.de", "a".repeat(59));
+        let email = EmailAddress::new("foo", &domain, None).unwrap();
+        let error = email.validate_lengths().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::DomainTooLong);
+        assert!(error.input().starts_with("foo@xn--"));
+    }
+
+    #[test]
+    fn rejects_escaped_control_characters() {
+        let error = sanitize("\"foo\\\nbar\"@bar.com").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+        assert_eq!(error.input(), "\"foo\\\nbar\"@bar.com");
+    }
+}
@@ -0,0 +1,613 @@
+use crate::dictionary::POPULAR_DOMAINS;
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::repair::{Repaired, COMMON_TOP_LEVEL_DOMAINS};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The length in octets beyond which a domain literal is not matched; way beyond that of an IPv6
+/// address literal.
+const MAX_DOMAIN_LITERAL_LENGTH: usize = 256;
+
+/// An email address found in a text by a `Scanner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressMatch<'t> {
+    text: &'t str,
+    start: usize,
+    end: usize,
+    email: EmailAddress,
+}
+
+impl<'t> AddressMatch<'t> {
+    /// Returns the byte offset of the start of the match in the text.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset of the end of the match in the text.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the byte range of the match in the text.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the matched text.
+    pub fn as_str(&self) -> &'t str {
+        &self.text[self.start..self.end]
+    }
+
+    /// Returns the email address.
+    pub fn email(&self) -> &EmailAddress {
+        &self.email
+    }
+
+    /// Consumes the match, returning the email address.
+    pub fn into_email(self) -> EmailAddress {
+        self.email
+    }
+}
+
+/// How a `Scanner` handles the candidates with several `@`; e.g. two addresses jammed together,
+/// like `foo@bar.comjane@example.org`, or a local part that directly follows a domain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MultipleAt {
+    /// Splits the candidates into addresses where it is likely; the default.
+    ///
+    /// A domain that runs into another `@` is cut after the known top-level domain that starts its
+    /// last label, if any, and the rest of the label is the local part of the next address; e.g.
+    /// `foo@bar.comjane@example.org` yields `foo@bar.com` and `jane@example.org`. The top-level
+    /// domains are the common ones and those of `POPULAR_DOMAINS`. The domain is only cut if a host
+    /// name follows the next `@`, so that a label is not cut at a mere prefix, like `community` in
+    /// `foo@bar.community@x`; a domain that cannot be cut is skipped. A local part that directly
+    /// follows an `@` is kept if it cannot be a domain, i.e. if it has no dot; e.g. `a@b@c.com`
+    /// yields `b@c.com`, but `a@b.xyzc@d.com` yields nothing.
+    #[default]
+    Split,
+    /// Skips the candidates whose domain runs into another `@`, or whose local part directly
+    /// follows one; e.g. nothing is found in `foo@bar.comjane@example.org`.
+    Skip,
+}
+
+/// Iterator over the email addresses in a free text; e.g. a document, a log, or a mail body.
+///
+/// Around every `@`, the scanner takes the longest run of characters that can make a local part and
+/// a domain, trims the punctuation that likely belongs to the text (like a trailing full stop),
+/// and yields it if it is a valid email address as per strict parsing, or as per the options.
+///
+/// To keep false positives low, a domain has to be a host name with at least two labels, or a
+/// domain literal; e.g. `foo@localhost` is not matched. Unquoted local parts are matched as
+/// dot-atoms, and a quoted local part is matched if it directly precedes the `@`, even if it
+/// contains an `@` itself; e.g. `"a@b"@example.com`. The other candidates with several `@` are
+/// handled as per `MultipleAt`.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::Scanner;
+///
+/// let text = "Write to <jane.doe@example.com>, or \"john doe\"@example.org.";
+/// let found: Vec<_> = Scanner::new(text).map(|m| m.as_str()).collect();
+/// assert_eq!(found, vec!["jane.doe@example.com", "\"john doe\"@example.org"]);
+///
+/// let first = Scanner::new(text).next().unwrap();
+/// assert_eq!(first.range(), 10..30);
+/// assert_eq!(first.email().get_domain(), "example.com");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scanner<'t> {
+    text: &'t str,
+    position: usize,
+    /// The end of the last match, before which no local part can start.
+    last_end: usize,
+    /// The state of the scan for the opening quotes of quoted local parts.
+    quotes: QuoteScan,
+    multiple_at: MultipleAt,
+    options: ParsingOptions,
+    /// The decoded document, in HTML mode; it is scanned on the first call to `next`.
+    decoded: Option<Repaired>,
+    /// The matches that are found upfront, in HTML mode.
+    found: Option<alloc::vec::IntoIter<AddressMatch<'t>>>,
+}
+
+impl<'t> Scanner<'t> {
+    /// Instantiates a scanner over the given text.
+    pub fn new(text: &'t str) -> Self {
+        Scanner {
+            text,
+            position: 0,
+            last_end: 0,
+            quotes: QuoteScan::default(),
+            multiple_at: MultipleAt::default(),
+            options: ParsingOptions::default(),
+            decoded: None,
+            found: None,
+        }
+    }
+
+    /// Sets the options that the candidates are parsed with; the default, strict ones by default.
+    ///
+    /// The candidates are taken from the text as they are, whatever the options; e.g. lax options
+    /// accept their obsolete syntax, but do not make them span white space.
+    pub fn options(self, options: ParsingOptions) -> Self {
+        Scanner { options, ..self }
+    }
+
+    /// Sets how the candidates with several `@` are handled; `MultipleAt::Split` by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::{MultipleAt, Scanner};
+    ///
+    /// let text = "foo@bar.comjane@example.org";
+    /// let found: Vec<_> = Scanner::new(text).map(|m| m.as_str()).collect();
+    /// assert_eq!(found, vec!["foo@bar.com", "jane@example.org"]);
+    /// assert_eq!(Scanner::new(text).multiple_at(MultipleAt::Skip).count(), 0);
+    /// ```
+    pub fn multiple_at(self, multiple_at: MultipleAt) -> Self {
+        Scanner {
+            multiple_at,
+            ..self
+        }
+    }
+
+    /// Instantiates a scanner over an HTML document; e.g. a scraped contact page.
+    ///
+    /// The addresses are scanned for in the document as in a text, once the character references,
+    /// like `&#64;` or `&commat;`, are decoded, and the targets of the `mailto:` links are
+    /// percent-decoded; the content of the `script` and `style` elements is skipped. The ranges,
+    /// and the matched texts, are those of the document, as it is; e.g. `foo&#64;bar.com`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::Scanner;
+    ///
+    /// let html = "<a href=\"mailto:jane%2Edoe@example.com?subject=Hi\">Jane</a> \
+    ///             <p>joe&#64;example&period;com</p> \
+    ///             <script>var spam = 'bot@example.com';</script>";
+    /// let found: Vec<_> = Scanner::html(html).collect();
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].email().to_string(), "jane.doe@example.com");
+    /// assert_eq!(found[0].as_str(), "jane%2Edoe@example.com");
+    /// assert_eq!(found[1].email().to_string(), "joe@example.com");
+    /// assert_eq!(found[1].as_str(), "joe&#64;example&period;com");
+    /// ```
+    pub fn html(html: &'t str) -> Self {
+        let mut decoded = Repaired::new(html);
+        for range in raw_text_elements(html).into_iter().rev() {
+            decoded.replace(range, " ");
+        }
+        let references = character_references(&decoded.text);
+        for (range, c) in references.into_iter().rev() {
+            decoded.replace(range, c.encode_utf8(&mut [0; 4]));
+        }
+        let encodings = mailto_encodings(&decoded.text);
+        for (range, text) in encodings.into_iter().rev() {
+            decoded.replace(range, &text);
+        }
+        Scanner {
+            decoded: Some(decoded),
+            ..Scanner::new(html)
+        }
+    }
+
+    /// Scans the decoded document, in HTML mode, mapping the matches back to the document.
+    fn scan_decoded(&self, decoded: &Repaired) -> Vec<AddressMatch<'t>> {
+        Scanner::new(&decoded.text)
+            .multiple_at(self.multiple_at)
+            .options(self.options.clone())
+            .map(|found| {
+                let range = decoded.source(found.range());
+                AddressMatch {
+                    text: self.text,
+                    start: range.start,
+                    end: range.end,
+                    email: found.email,
+                }
+            })
+            .collect()
+    }
+
+    /// Tries to match an address around the `@` at the given byte offset.
+    fn match_at(&mut self, at: usize) -> Option<AddressMatch<'t>> {
+        let start = self.local_part_start(at)?;
+        let end = self.domain_end(at)?;
+        if self.text[..start].ends_with('"') && self.text[end..].starts_with("\"@") {
+            // The candidate is the content of a quoted local part; e.g. `"x@y.org"@example.org`.
+            return None;
+        }
+        let (email, _) = EmailAddress::parse_with(&self.text[start..end], &self.options).ok()?;
+        Some(AddressMatch {
+            text: self.text,
+            start,
+            end,
+            email,
+        })
+    }
+
+    fn local_part_start(&mut self, at: usize) -> Option<usize> {
+        if self.text[..at].ends_with('"') {
+            // A quoted local part may contain an `@` that was tried before.
+            return self.quoted_start(at - 1);
+        }
+        let before = &self.text[self.position..at];
+        let mut start = at;
+        for (index, c) in before.char_indices().rev() {
+            if !(is_atext(c) || c == '.') {
+                break;
+            }
+            start = self.position + index;
+        }
+        while self.text[start..at].starts_with('.') {
+            start += 1;
+        }
+        if self.text[..start].ends_with('@') {
+            // The local part directly follows a domain that could not be cut.
+            let is_domain = self.text[start..at].contains('.');
+            if self.multiple_at == MultipleAt::Skip || is_domain {
+                return None;
+            }
+        }
+        if start == at {
+            None
+        } else {
+            Some(start)
+        }
+    }
+
+    /// Returns the byte offset of the opening quote of the quoted string that the quote at the
+    /// given offset closes, after the last match.
+    ///
+    /// The text is scanned forward once, across the calls, for the last unescaped quote that
+    /// no line break follows; hence, the cost of finding the addresses stays linear in the length
+    /// of the text, whatever its quotes.
+    fn quoted_start(&mut self, closing: usize) -> Option<usize> {
+        if self.quotes.position < self.last_end {
+            self.quotes = QuoteScan {
+                position: self.last_end,
+                ..QuoteScan::default()
+            };
+        }
+        let start = self.quotes.position;
+        for (index, c) in self.text[start..closing].char_indices() {
+            match c {
+                '\n' => self.quotes.last_quote = None,
+                '"' if !self.quotes.is_escaped => self.quotes.last_quote = Some(start + index),
+                _ => (),
+            }
+            // An odd number of backslashes escapes the next character.
+            self.quotes.is_escaped = c == '\\' && !self.quotes.is_escaped;
+        }
+        self.quotes.position = closing;
+        self.quotes.last_quote
+    }
+
+    fn domain_end(&self, at: usize) -> Option<usize> {
+        let after = &self.text[at + 1..];
+        if after.starts_with('[') {
+            // The search is bounded, so that unclosed literals do not make the scan quadratic.
+            let literal = &after.as_bytes()[..after.len().min(MAX_DOMAIN_LITERAL_LENGTH)];
+            return literal
+                .iter()
+                .position(|&byte| byte == b']')
+                .map(|end| at + 1 + end + 1);
+        }
+        let mut end = self.domain_run_end(at);
+        if self.text[end..].starts_with('@') {
+            end = match self.multiple_at {
+                // The domain is only cut if the rest of its last label can be the local part of
+                // an address; i.e. a host name follows the next `@`.
+                MultipleAt::Split if self.is_host_name(end, self.domain_run_end(end)) => {
+                    at + 1 + jammed_domain_len(&self.text[at + 1..end])?
+                }
+                MultipleAt::Split | MultipleAt::Skip => return None,
+            };
+        }
+        if self.is_host_name(at, end) {
+            Some(self.text[..end].trim_end_matches(['.', '-']).len())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the end of the run of the characters of host names after the `@` at the given
+    /// offset.
+    fn domain_run_end(&self, at: usize) -> usize {
+        let after = &self.text[at + 1..];
+        let length = after
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '.'))
+            .unwrap_or(after.len());
+        at + 1 + length
+    }
+
+    /// Returns whether the run of characters between the `@` at the given offset and the end is a
+    /// host name with at least two labels, once the trailing dots and hyphens are trimmed.
+    fn is_host_name(&self, at: usize, end: usize) -> bool {
+        self.text[at + 1..end]
+            .trim_end_matches(['.', '-'])
+            .contains('.')
+    }
+}
+
+/// The state of the forward scan for opening quotes; see `Scanner::quoted_start`.
+#[derive(Debug, Clone, Default)]
+struct QuoteScan {
+    /// The offset up to which the text is scanned.
+    position: usize,
+    /// The offset of the last unescaped quote, if no line break follows it.
+    last_quote: Option<usize>,
+    /// Whether the character at the position is escaped by a backslash.
+    is_escaped: bool,
+}
+
+impl<'t> Iterator for Scanner<'t> {
+    type Item = AddressMatch<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(decoded) = self.decoded.take() {
+            self.found = Some(self.scan_decoded(&decoded).into_iter());
+        }
+        if let Some(found) = &mut self.found {
+            return found.next();
+        }
+        while let Some(offset) = self.text[self.position..].find('@') {
+            let at = self.position + offset;
+            match self.match_at(at) {
+                Some(found) => {
+                    self.position = found.end;
+                    self.last_end = found.end;
+                    return Some(found);
+                }
+                None => self.position = at + 1,
+            }
+        }
+        self.position = self.text.len();
+        None
+    }
+}
+
+/// The named character references that are decoded in HTML mode; those of the characters of
+/// addresses, and of the usual delimiters around them.
+const CHARACTER_REFERENCES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("commat", '@'),
+    ("dash", '-'),
+    ("dot", '.'),
+    ("gt", '>'),
+    ("hyphen", '-'),
+    ("lowbar", '_'),
+    ("lt", '<'),
+    ("nbsp", ' '),
+    ("period", '.'),
+    ("plus", '+'),
+    ("quot", '"'),
+];
+
+/// Returns the ranges of the content of the `script` and `style` elements of an HTML document.
+fn raw_text_elements(html: &str) -> Vec<Range<usize>> {
+    let lower_case = html.to_ascii_lowercase();
+    let mut ranges = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = lower_case[position..].find('<') {
+        let start = position + offset + 1;
+        position = start;
+        let name = ["script", "style"].iter().find(|name| {
+            lower_case[start..].starts_with(*name)
+                && lower_case[start + name.len()..]
+                    .starts_with(|c: char| c == '>' || c == '/' || c.is_ascii_whitespace())
+        });
+        if let Some(name) = name {
+            let content = match lower_case[start..].find('>') {
+                Some(end) => start + end + 1,
+                None => break,
+            };
+            let closing = alloc::format!("</{}", name);
+            let end = lower_case[content..]
+                .find(&closing)
+                .map_or(html.len(), |end| content + end);
+            ranges.push(content..end);
+            position = end;
+        }
+    }
+    ranges
+}
+
+/// Returns the ranges of the character references of an HTML text, like `&#64;`, `&#x40;`, or
+/// `&commat;`, with the characters they refer to.
+fn character_references(text: &str) -> Vec<(Range<usize>, char)> {
+    let mut references = Vec::new();
+    for (start, _) in text.match_indices('&') {
+        let end = match text[start..].find(';').filter(|&end| end <= 10) {
+            Some(end) => start + end,
+            None => continue,
+        };
+        let name = &text[start + 1..end];
+        let c = if let Some(number) = name.strip_prefix('#') {
+            match number.strip_prefix(['x', 'X']) {
+                Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16).ok(),
+                None => number.parse().ok(),
+            }
+            .and_then(char::from_u32)
+        } else {
+            CHARACTER_REFERENCES
+                .iter()
+                .find(|(reference, _)| *reference == name)
+                .map(|&(_, c)| c)
+        };
+        if let Some(c) = c {
+            references.push((start..end + 1, c));
+        }
+    }
+    references
+}
+
+/// Returns the ranges of the percent-encoded octets of the addresses of the `mailto:` URIs of a
+/// text, with their decoded text; the octets that are not valid UTF-8 are left as they are.
+fn mailto_encodings(text: &str) -> Vec<(Range<usize>, alloc::string::String)> {
+    let lower_case = text.to_ascii_lowercase();
+    let mut encodings = Vec::new();
+    for (start, scheme) in lower_case.match_indices("mailto:") {
+        let start = start + scheme.len();
+        let end = text[start..]
+            .find(|c: char| "?#\"'<> \t\r\n".contains(c))
+            .map_or(text.len(), |end| start + end);
+        let bytes = &text.as_bytes()[start..end];
+        let mut index = 0;
+        while index < bytes.len() {
+            let run_start = index;
+            let mut decoded = Vec::new();
+            while let Some(octet) = bytes
+                .get(index + 1..index + 3)
+                .filter(|_| bytes[index] == b'%')
+                .and_then(|hexadecimal| core::str::from_utf8(hexadecimal).ok())
+                .and_then(|hexadecimal| u8::from_str_radix(hexadecimal, 16).ok())
+            {
+                decoded.push(octet);
+                index += 3;
+            }
+            match alloc::string::String::from_utf8(decoded) {
+                Ok(decoded) if !decoded.is_empty() => {
+                    encodings.push((start + run_start..start + index, decoded))
+                }
+                _ => index = run_start + 1,
+            }
+        }
+    }
+    encodings
+}
+
+/// Returns the length of the first domain of a run of domain characters that is jammed with the
+/// local part of a next address, like `bar.comjane` in `foo@bar.comjane@example.org`; i.e. up to
+/// the end of the longest known top-level domain that starts the last label of the run, if any.
+fn jammed_domain_len(run: &str) -> Option<usize> {
+    let (head, last_label) = run.rsplit_once('.')?;
+    let top_level_domains = COMMON_TOP_LEVEL_DOMAINS.iter().copied().chain(
+        POPULAR_DOMAINS
+            .iter()
+            .filter_map(|domain| domain.rsplit('.').next()),
+    );
+    top_level_domains
+        .filter(|tld| {
+            last_label.len() > tld.len()
+                && last_label.as_bytes()[..tld.len()].eq_ignore_ascii_case(tld.as_bytes())
+        })
+        .map(str::len)
+        .max()
+        .map(|len| head.len() + 1 + len)
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+        || "!#$%&'*+-/=?^_`{|}~".contains(c)
+        || (!c.is_ascii() && c.is_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+
+    fn scan(text: &str) -> Vec<&str> {
+        Scanner::new(text).map(|found| found.as_str()).collect()
+    }
+
+    #[test]
+    fn finds_addresses_in_text() {
+        assert_eq!(
+            scan("Contact foo@bar.com or (mailto:baz.qux@example.co.uk)."),
+            vec!["foo@bar.com", "baz.qux@example.co.uk"]
+        );
+        assert_eq!(
+            scan("foö@bücher.de, x@[127.0.0.1]"),
+            vec!["foö@bücher.de", "x@[127.0.0.1]"]
+        );
+        assert_eq!(
+            scan("say \"a \\\" b\"@example.com"),
+            vec!["\"a \\\" b\"@example.com"]
+        );
+    }
+
+    #[test]
+    fn trims_surrounding_punctuation() {
+        assert_eq!(scan("...foo@bar.com."), vec!["foo@bar.com"]);
+        assert_eq!(scan("foo@bar.com-"), vec!["foo@bar.com"]);
+    }
+
+    #[test]
+    fn skips_non_addresses() {
+        assert!(scan("@ foo@ @bar.com foo@localhost a@-b.com user@host").is_empty());
+        assert_eq!(scan("a@b@c.com"), vec!["b@c.com"]);
+    }
+
+    #[test]
+    fn handles_multiple_at() {
+        assert_eq!(
+            scan("say \"a@b\"@example.com or \"x@y.org\"@example.org"),
+            vec!["\"a@b\"@example.com", "\"x@y.org\"@example.org"]
+        );
+        assert_eq!(
+            scan("foo@bar.comjane@example.org, a@b.co.UKjoe@x.io"),
+            vec!["foo@bar.com", "jane@example.org", "a@b.co.UK", "joe@x.io"]
+        );
+        assert!(scan("foo@bar.xyzjane@example.org").is_empty());
+        assert!(scan("foo@bar.community@x a@b.comx@[127.0.0.1]").is_empty());
+        assert_eq!(scan("foo@bar.comjane@x.org@y"), vec!["foo@bar.com"]);
+        let skip = |text| {
+            Scanner::new(text)
+                .multiple_at(MultipleAt::Skip)
+                .map(|found| found.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert!(skip("a@b@c.com foo@bar.comjane@example.org").is_empty());
+        assert_eq!(
+            skip("\"a@b\"@example.com x@y.org"),
+            vec!["\"a@b\"@example.com", "x@y.org"]
+        );
+        let html = "foo&#64;bar.comjane&#64;example.org";
+        assert_eq!(Scanner::html(html).count(), 2);
+        assert_eq!(Scanner::html(html).multiple_at(MultipleAt::Skip).count(), 0);
+    }
+
+    #[test]
+    fn scans_quotes_in_linear_time() {
+        assert_eq!(
+            scan("\"a\nb\"@example.com \"x\\\\\"@example.org \"y\\\"@example.net"),
+            vec!["\"x\\\\\"@example.org"]
+        );
+        let quotes = "\"@".repeat(50_000);
+        assert!(scan(&quotes).is_empty());
+        let literals = "a@[".repeat(50_000);
+        assert!(scan(&literals).is_empty());
+        let literal = format!("a@[{}]", "1".repeat(MAX_DOMAIN_LITERAL_LENGTH));
+        assert!(scan(&literal).is_empty());
+    }
+
+    #[test]
+    fn decodes_html() {
+        let html = "<STYLE>a@example.com</STYLE><Script type=\"x\">b@example.com</script >\
+                    <scripts>c&commat;example.com</scripts>\
+                    <a href='MAILTO:d%40example.com,%C3%A9@example.com%ZZ'>\
+                    &lt;e&#x40;example.com&gt; f&amp;commat;example.com &#0;@example.com";
+        let found: Vec<_> = Scanner::html(html)
+            .map(|found| (found.as_str(), found.email().to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("c&commat;example.com", String::from("c@example.com")),
+                ("d%40example.com", String::from("d@example.com")),
+                ("%C3%A9@example.com", String::from("é@example.com")),
+                ("e&#x40;example.com", String::from("e@example.com")),
+            ]
+        );
+        assert_eq!(Scanner::html("<script>a@example.com").count(), 0);
+    }
+
+    #[test]
+    fn reports_byte_ranges() {
+        let found: Vec<_> = Scanner::new("ö foo@bar.com").collect();
+        assert_eq!(found[0].range(), 3..14);
+        assert_eq!(found[0].clone().into_email().get_local_part(), "foo");
+    }
+}
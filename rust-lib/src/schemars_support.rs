@@ -0,0 +1,90 @@
+use crate::email_address::EmailAddress;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// A deliberately loose pattern, for the consumers of the schema which do not validate `format`.
+const PATTERN: &str = "^.+@.+$";
+
+impl EmailAddress {
+    /// Describes an `EmailAddress` like its `JsonSchema` implementation does, with a deliberately
+    /// loose `pattern` too, for the consumers of the schema which do not validate `format`. It is
+    /// opted into per field, with the `schema_with` attribute of `schemars`.
+    ///
+    /// # Examples
+    /// ```
+    /// use email_address_parser::EmailAddress;
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct SignupRequest {
+    ///     #[schemars(schema_with = "EmailAddress::json_schema_with_pattern")]
+    ///     email: EmailAddress,
+    /// }
+    ///
+    /// let schema = schemars::schema_for!(SignupRequest);
+    /// assert_eq!(schema.get("properties").unwrap()["email"]["pattern"], "^.+@.+$");
+    /// ```
+    pub fn json_schema_with_pattern(generator: &mut SchemaGenerator) -> Schema {
+        let mut schema = <EmailAddress as JsonSchema>::json_schema(generator);
+        schema.insert("pattern".into(), PATTERN.into());
+        schema
+    }
+}
+
+/// Describes an `EmailAddress` as a string with the `email` format. The actual validation is done
+/// by the parser, on deserialization with the `serde` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+///
+/// let schema = schemars::schema_for!(EmailAddress);
+/// assert_eq!(schema.get("type").unwrap(), "string");
+/// assert_eq!(schema.get("format").unwrap(), "email");
+/// assert!(schema.get("pattern").is_none());
+/// ```
+impl JsonSchema for EmailAddress {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("EmailAddress")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed(concat!(module_path!(), "::EmailAddress"))
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "format": "email",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct SignupRequest {
+        email: EmailAddress,
+        backup_email: Option<EmailAddress>,
+        #[schemars(schema_with = "EmailAddress::json_schema_with_pattern")]
+        work_email: EmailAddress,
+    }
+
+    #[test]
+    fn email_address_is_referenced_from_containing_struct() {
+        let schema = schemars::schema_for!(SignupRequest);
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["properties"]["email"]["$ref"], "#/$defs/EmailAddress");
+        assert_eq!(
+            json["$defs"]["EmailAddress"],
+            serde_json::json!({ "type": "string", "format": "email" })
+        );
+        assert_eq!(
+            json["properties"]["work_email"],
+            serde_json::json!({ "type": "string", "format": "email", "pattern": "^.+@.+$" })
+        );
+    }
+}
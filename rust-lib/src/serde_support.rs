@@ -0,0 +1,289 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::list::EmailAddressList;
+use crate::policy::Policy;
+use crate::rules::{
+    DisposableDomains, DomainDenylist, DomainTypos, RequireDomains, RequireTopLevelDomain,
+    RoleAccounts,
+};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+
+/// Serializes an `EmailAddress` as its string representation.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+///
+/// let email = EmailAddress::new("foo", "bar.com", None).unwrap();
+/// assert_eq!(serde_json::to_string(&email).unwrap(), "\"foo@bar.com\"");
+/// ```
+impl Serialize for EmailAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes an `EmailAddress` from a string, using strict parsing.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddress;
+///
+/// let email: EmailAddress = serde_json::from_str("\"foo@bar.com\"").unwrap();
+/// assert_eq!(email.get_domain(), "bar.com");
+///
+/// assert!(serde_json::from_str::<EmailAddress>("\"foo@-bar.com\"").is_err());
+/// ```
+impl<'de> Deserialize<'de> for EmailAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(EmailAddressVisitor)
+    }
+}
+
+struct EmailAddressVisitor;
+
+impl<'de> Visitor<'de> for EmailAddressVisitor {
+    type Value = EmailAddress;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 5322 email address")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+/// Serializes an `EmailAddressList` as a sequence of addresses, in order.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{EmailAddress, EmailAddressList};
+///
+/// let list: EmailAddressList = EmailAddress::parse("foo@bar.com", None).into_iter().collect();
+/// assert_eq!(serde_json::to_string(&list).unwrap(), "[\"foo@bar.com\"]");
+/// ```
+impl Serialize for EmailAddressList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Deserializes an `EmailAddressList` from a sequence of addresses, dropping the duplicates by
+/// canonical form.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::EmailAddressList;
+///
+/// let list: EmailAddressList = serde_json::from_str("[\"foo@bar.com\", \"foo@BAR.com\"]").unwrap();
+/// assert_eq!(list.len(), 1);
+/// ```
+impl<'de> Deserialize<'de> for EmailAddressList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(EmailAddressListVisitor)
+    }
+}
+
+struct EmailAddressListVisitor;
+
+impl<'de> Visitor<'de> for EmailAddressListVisitor {
+    type Value = EmailAddressList;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of RFC 5322 email addresses")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = EmailAddressList::new();
+        while let Some(email) = seq.next_element()? {
+            list.insert(email);
+        }
+        Ok(list)
+    }
+}
+
+/// The configuration of a `Policy`; see its `Deserialize` implementation.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyConfig {
+    #[serde(default)]
+    preset: Option<PresetConfig>,
+    #[serde(default)]
+    options: Option<ParsingOptions>,
+    #[serde(default)]
+    repair: Option<bool>,
+    #[serde(default)]
+    canonicalize: Option<bool>,
+    #[serde(default)]
+    rules: Vec<RuleConfig>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PresetConfig {
+    SignupForm,
+    ListImport,
+    CorporateStrict,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case", deny_unknown_fields)]
+enum RuleConfig {
+    DenyDomains {
+        domains: Vec<String>,
+    },
+    RequireDomains {
+        domains: Vec<String>,
+    },
+    RequireTopLevelDomain,
+    DisposableDomains,
+    RoleAccounts,
+    DomainTypos {
+        #[serde(default)]
+        dictionary: Vec<String>,
+    },
+}
+
+/// Deserializes a `Policy` from its configuration; e.g. from a TOML or a JSON file, so that the
+/// acceptance policy of a service can be tuned without recompiling it. All of the fields are
+/// optional:
+/// - `preset`: the preset to start from; `signup_form`, `list_import`, or `corporate_strict`.
+/// - `options`: the parsing options, that replace those of the preset; each option is named after
+///   its setter, e.g. `allow_comments`, and defaults to its default value.
+/// - `repair` and `canonicalize`: as per the setters of the policy.
+/// - `rules`: the rules to append to those of the preset, each tagged with a `rule` field:
+///   `deny_domains` and `require_domains`, with `domains`; `domain_typos`, with an optional
+///   `dictionary`; and `require_top_level_domain`, `disposable_domains`, and `role_accounts`.
+///
+/// Unknown fields are refused, so that a misspelled option is not silently ignored.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::Policy;
+///
+/// let policy: Policy = serde_json::from_str(r#"{
+///     "options": { "is_lax": true, "allow_domain_literal": false },
+///     "rules": [
+///         { "rule": "deny_domains", "domains": ["spam.example"] },
+///         { "rule": "role_accounts" }
+///     ]
+/// }"#).unwrap();
+/// # #[cfg(not(feature = "strict-only"))]
+/// assert!(policy.validate("jane . doe@example.com").is_ok());
+/// assert_eq!(policy.validate("jane@[127.0.0.1]").unwrap_err().code(), "invalid_domain");
+/// assert_eq!(policy.validate("jane@spam.example").unwrap_err().code(), "denied_domain");
+/// assert_eq!(policy.validate("admin@example.com").unwrap_err().code(), "role_account");
+///
+/// let policy: Policy = serde_json::from_str(r#"{ "preset": "list_import" }"#).unwrap();
+/// assert_eq!(policy.validate(" <jane@@EXAMPLE.com>").unwrap().to_string(), "jane@example.com");
+///
+/// assert!(serde_json::from_str::<Policy>(r#"{ "options": { "alow_comments": false } }"#).is_err());
+/// ```
+impl<'de> Deserialize<'de> for Policy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let config = PolicyConfig::deserialize(deserializer)?;
+        let mut policy = match config.preset {
+            Some(PresetConfig::SignupForm) => Policy::signup_form(),
+            Some(PresetConfig::ListImport) => Policy::list_import(),
+            Some(PresetConfig::CorporateStrict) => Policy::corporate_strict(),
+            None => Policy::new(),
+        };
+        if let Some(options) = config.options {
+            policy = policy.options(options);
+        }
+        if let Some(repair) = config.repair {
+            policy = policy.repair(repair);
+        }
+        if let Some(canonicalize) = config.canonicalize {
+            policy = policy.canonicalize(canonicalize);
+        }
+        for rule in config.rules {
+            policy = match rule {
+                RuleConfig::DenyDomains { domains } => {
+                    policy.rule(DomainDenylist::new(domains.iter().map(String::as_str)))
+                }
+                RuleConfig::RequireDomains { domains } => {
+                    policy.rule(RequireDomains::new(domains.iter().map(String::as_str)))
+                }
+                RuleConfig::RequireTopLevelDomain => policy.rule(RequireTopLevelDomain),
+                RuleConfig::DisposableDomains => policy.rule(DisposableDomains),
+                RuleConfig::RoleAccounts => policy.rule(RoleAccounts),
+                RuleConfig::DomainTypos { dictionary } => {
+                    policy.rule(DomainTypos::new(dictionary.iter().map(String::as_str)))
+                }
+            };
+        }
+        Ok(policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_address_round_trips_through_json() {
+        let email = EmailAddress::new("foö", "bücher.de", None).unwrap();
+        let json = serde_json::to_string(&email).unwrap();
+        assert_eq!(json, "\"foö@bücher.de\"");
+        assert_eq!(serde_json::from_str::<EmailAddress>(&json).unwrap(), email);
+    }
+
+    #[test]
+    fn email_address_list_round_trips_through_json() {
+        let json = "[\"b@x.org\",\"a@x.org\"]";
+        let list: EmailAddressList = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&list).unwrap(), json);
+        assert!(serde_json::from_str::<EmailAddressList>("[\"a@-x.org\"]").is_err());
+    }
+
+    #[test]
+    fn parsing_options_round_trip_through_json() {
+        let options = ParsingOptions::new(true).trim(true).allow_comments(false);
+        let json = serde_json::to_string(&options).unwrap();
+        assert!(!json.contains("fix_mistakes"));
+        let options: ParsingOptions = serde_json::from_str(&json).unwrap();
+        assert!(options.is_lax && options.trim && !options.allow_comments);
+
+        let options: ParsingOptions = serde_json::from_str("{}").unwrap();
+        assert!(!options.is_lax && options.allow_comments);
+    }
+
+    #[test]
+    fn policy_config_extends_its_preset() {
+        let policy: Policy = serde_json::from_str(
+            r#"{
+                "preset": "signup_form",
+                "canonicalize": true,
+                "rules": [{ "rule": "require_domains", "domains": ["example.com"] }]
+            }"#,
+        )
+        .unwrap();
+        let email = policy.validate(" Jane@EXAMPLE.com ").unwrap();
+        assert_eq!(email.to_string(), "Jane@example.com");
+        assert_eq!(
+            policy.validate("info@example.com").unwrap_err().code(),
+            "role_account"
+        );
+        assert_eq!(
+            policy.validate("jane@example.org").unwrap_err().code(),
+            "domain_not_allowed"
+        );
+
+        for json in &[
+            r#"{ "preset": "lax" }"#,
+            r#"{ "rules": [{ "rule": "allow_all" }] }"#,
+            r#"{ "rules": [{ "rule": "deny_domains" }] }"#,
+        ] {
+            assert!(serde_json::from_str::<Policy>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn deserialization_rejects_obsolete_address() {
+        let actual = serde_json::from_str::<EmailAddress>("\"\\r\\n test@iana.org\"");
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! `EmailAddress` is stored as text; i.e. as its string representation.
+//!
+//! On read, the stored value is parsed strictly. Use `LaxEmailAddress` for columns containing
+//! legacy addresses with obsolete parts.
+//!
+//! The conversions are tested against SQLite in `sqlx-tests/`.
+//!
+//! ```ignore
+//! use email_address_parser::{EmailAddress, LaxEmailAddress};
+//!
+//! let email = EmailAddress::parse("foo@bar.com", None).unwrap();
+//! sqlx::query("INSERT INTO users (email) VALUES ($1)")
+//!     .bind(&email)
+//!     .execute(&pool)
+//!     .await?;
+//!
+//! let (email,): (EmailAddress,) = sqlx::query_as("SELECT email FROM users")
+//!     .fetch_one(&pool)
+//!     .await?;
+//! let (legacy,): (LaxEmailAddress,) = sqlx::query_as("SELECT email FROM legacy_users")
+//!     .fetch_one(&pool)
+//!     .await?;
+//! ```
+
+use crate::email_address::EmailAddress;
+use crate::lax::LaxEmailAddress;
+use sqlx::database::Database;
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+
+macro_rules! impl_sqlx_text {
+    ($type:ty) => {
+        impl<DB: Database> Type<DB> for $type
+        where
+            str: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <str as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <str as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: Database> Encode<'q, DB> for $type
+        where
+            String: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer,
+            ) -> Result<IsNull, BoxDynError> {
+                self.to_string().encode(buf)
+            }
+        }
+
+        impl<'r, DB: Database> Decode<'r, DB> for $type
+        where
+            &'r str: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let value = <&str as Decode<DB>>::decode(value)?;
+                Ok(value.parse::<$type>()?)
+            }
+        }
+    };
+}
+
+impl_sqlx_text!(EmailAddress);
+impl_sqlx_text!(LaxEmailAddress);
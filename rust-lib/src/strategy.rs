@@ -0,0 +1,198 @@
+//! [proptest](https://crates.io/crates/proptest) strategies for email addresses.
+//!
+//! The strategies generate strings rather than `EmailAddress` instances, so that they can be fed
+//! to any code handling email addresses. `EmailAddress` also implements `Arbitrary`, to be used
+//! directly with `any::<EmailAddress>()`.
+//!
+//! # Examples
+//! ```
+//! use email_address_parser::{strategy, EmailAddress};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // #[test]
+//!     fn accepts_valid_addresses(input in strategy::addr_spec()) {
+//!         prop_assert!(EmailAddress::is_valid(&input, None));
+//!     }
+//!
+//!     // #[test]
+//!     fn rejects_near_misses(input in strategy::near_miss()) {
+//!         prop_assert!(!EmailAddress::is_valid(&input, None));
+//!     }
+//!
+//!     // #[test]
+//!     fn displays_address(email in any::<EmailAddress>()) {
+//!         prop_assert!(email.to_string().contains('@'));
+//!     }
+//! }
+//! # accepts_valid_addresses();
+//! # rejects_near_misses();
+//! # displays_address();
+//! ```
+
+use crate::email_address::EmailAddress;
+use proptest::arbitrary::Arbitrary;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+
+/// `atext` of a dot-atom segment, which can neither start nor end with a hyphen.
+const SEGMENT: &str = "[a-zA-Z0-9!#$%&'*+/=?^_`{|}~öü中]([a-zA-Z0-9!#$%&'*+/=?^_`{|}~öü中-]{0,8}[a-zA-Z0-9!#$%&'*+/=?^_`{|}~öü中])?";
+/// A quoted string with `qtext`, quoted pairs, and spaces.
+const QUOTED_STRING: &str = "\"([ !#-\\[\\]-~ö]|\\\\[ -~]){0,12}\"";
+/// A comment with `ctext` and spaces.
+const COMMENT: &str = "\\(([!-'*-\\[\\]-~ö ]){0,10}\\)";
+const LABEL: &str = "[a-z0-9äö中]([a-z0-9äö中-]{0,10}[a-z0-9äö中])?";
+const TLD: &str = "[a-z]{2,6}";
+/// `dtext` of a general domain literal.
+const DTEXT: &str = "[!-Z^-~]{1,12}";
+
+/// Generates dot-atom local parts; e.g. `foo.bar`.
+pub fn dot_atom_local_part() -> impl Strategy<Value = String> {
+    prop::collection::vec(SEGMENT, 1..4).prop_map(|segments| segments.join("."))
+}
+
+/// Generates quoted local parts, optionally surrounded by comments; e.g. `(comment)"foo bar"`.
+pub fn quoted_local_part() -> impl Strategy<Value = String> {
+    (
+        prop::option::weighted(0.2, COMMENT),
+        QUOTED_STRING,
+        prop::option::weighted(0.2, COMMENT),
+    )
+        .prop_map(|(before, quoted, after)| {
+            format!(
+                "{}{}{}",
+                before.unwrap_or_default(),
+                quoted,
+                after.unwrap_or_default()
+            )
+        })
+}
+
+/// Generates valid local parts.
+pub fn local_part() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => dot_atom_local_part(),
+        1 => quoted_local_part(),
+    ]
+}
+
+/// Generates host names; e.g. `mail.example.com`.
+pub fn host_name() -> impl Strategy<Value = String> {
+    (prop::collection::vec(LABEL, 1..4), TLD).prop_map(|(mut labels, tld)| {
+        labels.push(tld);
+        labels.join(".")
+    })
+}
+
+/// Generates domain literals, optionally surrounded by comments; e.g. `[127.0.0.1]`.
+pub fn domain_literal() -> impl Strategy<Value = String> {
+    let content = prop_oneof![
+        any::<[u8; 4]>().prop_map(|[a, b, c, d]| format!("{}.{}.{}.{}", a, b, c, d)),
+        DTEXT.prop_map(String::from),
+    ];
+    (
+        prop::option::weighted(0.2, COMMENT),
+        content,
+        prop::option::weighted(0.2, COMMENT),
+    )
+        .prop_map(|(before, content, after)| {
+            format!(
+                "{}[{}]{}",
+                before.unwrap_or_default(),
+                content,
+                after.unwrap_or_default()
+            )
+        })
+}
+
+/// Generates valid domains.
+pub fn domain() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => host_name(),
+        1 => domain_literal(),
+    ]
+}
+
+/// Generates addr-specs, which are valid with strict parsing.
+pub fn addr_spec() -> impl Strategy<Value = String> {
+    (local_part(), domain()).prop_map(|(local_part, domain)| format!("{}@{}", local_part, domain))
+}
+
+/// Generates near misses; i.e. strings that are almost addr-specs, but invalid even with lax parsing.
+///
+/// Each near miss is a valid address with a single, typical mistake; e.g. a missing `@`,
+/// consecutive dots, or a domain label starting with a hyphen.
+pub fn near_miss() -> impl Strategy<Value = String> {
+    let parts = || (dot_atom_local_part(), host_name());
+    prop_oneof![
+        parts().prop_map(|(l, d)| format!("{}{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@@{}", l, d)),
+        parts().prop_map(|(l, d)| format!(".{}@{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}.@{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}..x@{}", l, d)),
+        parts().prop_map(|(l, d)| format!("\"{}@{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@-{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@{}-", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@{}..com", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@.{}", l, d)),
+        parts().prop_map(|(l, d)| format!("{}@[{}", l, d)),
+        dot_atom_local_part().prop_map(|l| format!("{}@", l)),
+        host_name().prop_map(|d| format!("@{}", d)),
+    ]
+}
+
+impl Arbitrary for EmailAddress {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<EmailAddress>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        addr_spec()
+            .prop_filter_map("not an addr-spec", |input| {
+                EmailAddress::parse(&input, None)
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email_address::ParsingOptions;
+
+    proptest! {
+        #[test]
+        fn generated_addr_spec_is_valid(input in addr_spec()) {
+            prop_assert!(EmailAddress::is_valid(&input, None), "{}", input);
+        }
+
+        #[test]
+        fn generated_near_miss_is_invalid(input in near_miss()) {
+            prop_assert!(!EmailAddress::is_valid(&input, None), "{}", input);
+            prop_assert!(!EmailAddress::is_valid(&input, Some(ParsingOptions::new(true))), "{}", input);
+        }
+
+        #[test]
+        fn display_round_trips(input in addr_spec()) {
+            let email = EmailAddress::parse(&input, None).unwrap();
+            let reparsed = EmailAddress::parse(&email.to_string(), None);
+            prop_assert!(reparsed.is_some(), "{}", email);
+            let reparsed = reparsed.unwrap();
+            prop_assert_eq!(reparsed.get_local_part(), email.get_local_part());
+            prop_assert_eq!(reparsed.get_domain(), email.get_domain());
+        }
+
+        #[test]
+        fn canonical_is_valid_and_idempotent(input in addr_spec()) {
+            let canonical = EmailAddress::parse(&input, None).unwrap().canonical();
+            prop_assert!(EmailAddress::is_valid(&canonical.to_string(), None), "{}", canonical);
+            prop_assert_eq!(canonical.canonical(), canonical.clone());
+            let reparsed = EmailAddress::parse(&canonical.to_string(), None).unwrap();
+            prop_assert_eq!(reparsed, canonical);
+        }
+
+        #[test]
+        fn generated_parts_instantiate(local_part in local_part(), domain in domain()) {
+            prop_assert!(EmailAddress::new(&local_part, &domain, None).is_ok());
+        }
+    }
+}
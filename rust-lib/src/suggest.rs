@@ -0,0 +1,162 @@
+use crate::dictionary::POPULAR_DOMAINS;
+use crate::email_address::EmailAddress;
+use alloc::string::String;
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::*;
+
+/// Suggests a correction of a likely typo in the domain of an address; e.g. `jane@gmail.com` for
+/// `jane@gmial.com`.
+///
+/// The domain is compared, ignoring case, to those of the given dictionary, followed by those of
+/// `POPULAR_DOMAINS`; the closest one by edit distance (counting a transposition as one edit) is
+/// suggested, if it is at most one edit away when either domain has up to 6 characters, and two
+/// edits otherwise. A domain in one of the dictionaries is never corrected, and neither is a
+/// regional variant of one: the same name under another country code top-level domain; e.g.
+/// `yahoo.de` is not corrected to `yahoo.fr`. The country codes that are common typos of `com`,
+/// like `co` in `aol.co`, are corrected though.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{suggest, EmailAddress};
+///
+/// let email = EmailAddress::parse("jane@hotmial.con", None).unwrap();
+/// assert_eq!(suggest(&email, &[]).unwrap().to_string(), "jane@hotmail.com");
+///
+/// let email = EmailAddress::parse("jane@hooli.xzy", None).unwrap();
+/// assert_eq!(suggest(&email, &["hooli.xyz"]).unwrap().to_string(), "jane@hooli.xyz");
+///
+/// let email = EmailAddress::parse("jane@example.com", None).unwrap();
+/// assert!(suggest(&email, &[]).is_none());
+///
+/// let email = EmailAddress::parse("jane@hotmail.it", None).unwrap();
+/// assert!(suggest(&email, &[]).is_none());
+/// ```
+pub fn suggest(email: &EmailAddress, dictionary: &[&str]) -> Option<EmailAddress> {
+    let canonical = email.canonical();
+    let domain = canonical.get_domain();
+    let candidates = || dictionary.iter().chain(POPULAR_DOMAINS);
+    if candidates().any(|candidate| candidate.eq_ignore_ascii_case(domain)) {
+        return None;
+    }
+    let length = domain.chars().count();
+    let (_, closest) = candidates()
+        .filter(|candidate| !is_regional_variant(domain, candidate))
+        .filter_map(|candidate| {
+            let distance = edit_distance(domain, candidate);
+            let max_distance = match length.min(candidate.chars().count()) {
+                0..=6 => 1,
+                _ => 2,
+            };
+            Some((distance, candidate)).filter(|_| distance <= max_distance)
+        })
+        .min_by_key(|(distance, _)| *distance)?;
+    EmailAddress::store(email.get_local_part(), &closest.to_lowercase()).ok()
+}
+
+#[wasm_bindgen]
+impl EmailAddress {
+    /// This is a WASM wrapper over `suggest`, with the dictionary as an array of domains; e.g. for
+    /// a "Did you mean…?" prompt of a form.
+    #[doc(hidden)]
+    #[wasm_bindgen(js_name = "suggest")]
+    pub fn _suggest(&self, dictionary: Vec<String>) -> Option<EmailAddress> {
+        let dictionary: Vec<&str> = dictionary.iter().map(String::as_str).collect();
+        suggest(self, &dictionary)
+    }
+}
+
+/// The country code top-level domains that are common typos of `com`, rather than the regional
+/// domains of mailbox providers.
+const COM_TYPOS: &[&str] = &["co", "cm", "om"];
+
+/// Returns whether a domain is the same name as a dictionary one, under another country code
+/// top-level domain; e.g. `yahoo.de` for `yahoo.fr`, or `yahoo.ca` for `yahoo.com`.
+fn is_regional_variant(domain: &str, candidate: &str) -> bool {
+    let name = |domain: &str| domain.split('.').next().map(str::to_ascii_lowercase);
+    let top_level_domain = domain.rsplit('.').next().unwrap_or_default();
+    name(domain) == name(candidate)
+        && top_level_domain.len() == 2
+        && top_level_domain
+            .bytes()
+            .all(|byte| byte.is_ascii_alphabetic())
+        && !COM_TYPOS.contains(&top_level_domain.to_ascii_lowercase().as_str())
+}
+
+/// Returns the optimal string alignment distance between two strings, in characters; i.e. the
+/// number of insertions, deletions, substitutions, and transpositions of adjacent characters, that
+/// turn one into the other.
+pub(crate) fn edit_distance(left: &str, right: &str) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    // The rows of the distances between the prefixes of `left`, and those of `right`.
+    let mut before_previous: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for i in 1..=left.len() {
+        let mut current = Vec::with_capacity(right.len() + 1);
+        current.push(i);
+        for j in 1..=right.len() {
+            let cost = usize::from(left[i - 1] != right[j - 1]);
+            let mut distance = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                distance = distance.min(before_previous[j - 2] + 1);
+            }
+            current.push(distance);
+        }
+        before_previous = core::mem::replace(&mut previous, current);
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance("gmail.com", "gmail.com"), 0);
+        assert_eq!(edit_distance("gmial.com", "gmail.com"), 1);
+        assert_eq!(edit_distance("gmal.com", "gmail.com"), 1);
+        assert_eq!(edit_distance("gmail.con", "gmail.com"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("bücher", "bcüher"), 1);
+    }
+
+    #[test]
+    fn suggests_close_domains_only() {
+        let suggested = |input: &str| {
+            suggest(&EmailAddress::parse(input, None).unwrap(), &[]).map(|email| email.to_string())
+        };
+        assert_eq!(
+            suggested("Jane@GMAL.COM").as_deref(),
+            Some("Jane@gmail.com")
+        );
+        assert_eq!(suggested("jane@yaho.co").as_deref(), Some("jane@yahoo.com"));
+        assert_eq!(suggested("jane@aol.co").as_deref(), Some("jane@aol.com"));
+        assert_eq!(suggested("jane@gmail.COM"), None);
+        assert_eq!(suggested("jane@al.co"), None);
+        assert_eq!(suggested("jane@acme.com"), None);
+        for input in &[
+            "jane@yahoo.de",
+            "jane@hotmail.it",
+            "jane@yahoo.ca",
+            "jane@yahoo.es",
+            "jane@hotmail.co.nz",
+            "jane@email.com",
+        ] {
+            assert_eq!(suggested(input), None, "{}", input);
+        }
+        assert_eq!(
+            suggested("jane@yahoo.cm").as_deref(),
+            Some("jane@yahoo.com")
+        );
+        let email = EmailAddress::parse("jane@hooli.xzy", None).unwrap();
+        let dictionary = [String::from("hooli.xyz")].to_vec();
+        assert_eq!(
+            email._suggest(dictionary).unwrap().to_string(),
+            "jane@hooli.xyz"
+        );
+    }
+}
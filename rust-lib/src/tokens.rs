@@ -0,0 +1,252 @@
+use crate::canonical::is_atext;
+use crate::error::find_separator;
+use core::ops::Range;
+
+/// The kind of a `Token`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// A run of atom characters of the local part; e.g. `john` in `john.doe@example.com`.
+    LocalPartAtom,
+    /// A dot, in the local part or in the domain.
+    Dot,
+    /// A quoted string, including its double quotes; e.g. `"john doe"`. An unterminated quoted
+    /// string runs up to the end of the input.
+    QuotedString,
+    /// The `@` separating the local part and the domain.
+    At,
+    /// A run of atom characters of the domain; e.g. `example` in `john@example.com`.
+    DomainLabel,
+    /// A domain literal, including its brackets; e.g. `[127.0.0.1]`. An unterminated domain
+    /// literal runs up to the end of the input.
+    DomainLiteral,
+    /// The `(` starting a comment, or a nested comment.
+    CommentStart,
+    /// The text of a comment, between its parentheses and those of its nested comments.
+    CommentText,
+    /// The `)` ending a comment, or a nested comment.
+    CommentEnd,
+    /// A run of spaces, tabs, carriage returns, and line feeds.
+    Whitespace,
+    /// A character that cannot appear there; e.g. a second `@`, or a comma.
+    Invalid,
+}
+
+/// A token of an email address, as emitted by `tokenize`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token<'i> {
+    kind: TokenKind,
+    text: &'i str,
+    span: Range<usize>,
+}
+
+impl<'i> Token<'i> {
+    /// Returns the kind of the token.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Returns the text of the token, as it is in the input.
+    pub fn text(&self) -> &'i str {
+        self.text
+    }
+
+    /// Returns the byte range of the token in the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Splits an input into the tokens of an email address, with their spans; e.g. to highlight its
+/// syntax, or to check its structure with custom rules.
+///
+/// The tokenizer is lenient: it never fails, and the spans of its tokens cover the whole input, so
+/// that it also works on invalid and partial inputs, like those being typed. The `@` separating
+/// the local part and the domain is the first one outside quoted strings and comments, as for
+/// parsing; an input without one is all local part.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{tokenize, TokenKind};
+///
+/// let kinds: Vec<(TokenKind, &str)> = tokenize("john.\"doe\"@(home)example.com")
+///     .map(|token| (token.kind(), token.text()))
+///     .collect();
+/// assert_eq!(
+///     kinds,
+///     [
+///         (TokenKind::LocalPartAtom, "john"),
+///         (TokenKind::Dot, "."),
+///         (TokenKind::QuotedString, "\"doe\""),
+///         (TokenKind::At, "@"),
+///         (TokenKind::CommentStart, "("),
+///         (TokenKind::CommentText, "home"),
+///         (TokenKind::CommentEnd, ")"),
+///         (TokenKind::DomainLabel, "example"),
+///         (TokenKind::Dot, "."),
+///         (TokenKind::DomainLabel, "com"),
+///     ]
+/// );
+/// ```
+pub fn tokenize(input: &str) -> Tokens<'_> {
+    Tokens {
+        input,
+        position: 0,
+        separator: find_separator(input),
+        comment_depth: 0,
+    }
+}
+
+/// An iterator over the tokens of an input; see `tokenize`.
+#[derive(Debug, Clone)]
+pub struct Tokens<'i> {
+    input: &'i str,
+    position: usize,
+    separator: Option<usize>,
+    comment_depth: usize,
+}
+
+impl<'i> Tokens<'i> {
+    /// Returns the end of the run of characters starting at the current position, that satisfy a
+    /// predicate.
+    fn run_end(&self, predicate: impl Fn(char) -> bool) -> usize {
+        let rest = &self.input[self.position..];
+        self.position + rest.find(|c| !predicate(c)).unwrap_or(rest.len())
+    }
+
+    /// Returns the end of the delimited token starting at the current position, that ends with the
+    /// first unescaped `end` character after it, or at the end of the input.
+    fn delimited_end(&self, end: char) -> usize {
+        let mut chars = self.input[self.position..].char_indices().skip(1);
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                c if c == end => return self.position + index + 1,
+                _ => (),
+            }
+        }
+        self.input.len()
+    }
+
+    /// Returns the end of the text of a comment starting at the current position.
+    fn comment_text_end(&self) -> usize {
+        let mut chars = self.input[self.position..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '(' | ')' => return self.position + index,
+                _ => (),
+            }
+        }
+        self.input.len()
+    }
+}
+
+impl<'i> Iterator for Tokens<'i> {
+    type Item = Token<'i>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.position;
+        let c = self.input[start..].chars().next()?;
+        let is_domain = self.separator.is_some_and(|at| start > at);
+        let (kind, end) = match c {
+            '(' => {
+                self.comment_depth += 1;
+                (TokenKind::CommentStart, start + 1)
+            }
+            ')' if self.comment_depth > 0 => {
+                self.comment_depth -= 1;
+                (TokenKind::CommentEnd, start + 1)
+            }
+            _ if self.comment_depth > 0 => (TokenKind::CommentText, self.comment_text_end()),
+            ' ' | '\t' | '\r' | '\n' => (
+                TokenKind::Whitespace,
+                self.run_end(|c| matches!(c, ' ' | '\t' | '\r' | '\n')),
+            ),
+            '"' => (TokenKind::QuotedString, self.delimited_end('"')),
+            '[' if is_domain => (TokenKind::DomainLiteral, self.delimited_end(']')),
+            '.' => (TokenKind::Dot, start + 1),
+            '@' if self.separator == Some(start) => (TokenKind::At, start + 1),
+            c if is_atext(c) => {
+                let kind = match is_domain {
+                    true => TokenKind::DomainLabel,
+                    false => TokenKind::LocalPartAtom,
+                };
+                (kind, self.run_end(is_atext))
+            }
+            c => (TokenKind::Invalid, start + c.len_utf8()),
+        };
+        self.position = end;
+        Some(Token {
+            kind,
+            text: &self.input[start..end],
+            span: start..end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn kinds(input: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(input)
+            .map(|token| (token.kind(), token.text()))
+            .collect()
+    }
+
+    #[test]
+    fn tokenizes_comments_literals_and_white_space() {
+        assert_eq!(
+            kinds("a (x(y)\\)) @ [ 127.0.0.1 ]"),
+            [
+                (TokenKind::LocalPartAtom, "a"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommentStart, "("),
+                (TokenKind::CommentText, "x"),
+                (TokenKind::CommentStart, "("),
+                (TokenKind::CommentText, "y"),
+                (TokenKind::CommentEnd, ")"),
+                (TokenKind::CommentText, "\\)"),
+                (TokenKind::CommentEnd, ")"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::At, "@"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::DomainLiteral, "[ 127.0.0.1 ]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn covers_invalid_and_partial_inputs() {
+        assert_eq!(
+            kinds("\"a@b\"@c@d,]"),
+            [
+                (TokenKind::QuotedString, "\"a@b\""),
+                (TokenKind::At, "@"),
+                (TokenKind::DomainLabel, "c"),
+                (TokenKind::Invalid, "@"),
+                (TokenKind::DomainLabel, "d"),
+                (TokenKind::Invalid, ","),
+                (TokenKind::Invalid, "]"),
+            ]
+        );
+        assert_eq!(
+            kinds("jöhn[\"x\\\""),
+            [
+                (TokenKind::LocalPartAtom, "jöhn"),
+                (TokenKind::Invalid, "["),
+                (TokenKind::QuotedString, "\"x\\\""),
+            ]
+        );
+        let input = "a(b@c.d";
+        let spans: Vec<Range<usize>> = tokenize(input).map(|token| token.span()).collect();
+        assert_eq!(spans, [0..1, 1..2, 2..input.len()]);
+        assert_eq!(tokenize("").count(), 0);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::email_address::{EmailAddress, ParsingOptions};
+use crate::scanner::{MultipleAt, Scanner};
+use std::collections::VecDeque;
+use std::io;
+use std::ops::Range;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// The length in octets beyond which a line is scanned in several chunks, to bound the memory
+/// use of the inputs without line breaks; way beyond that of any address.
+const MAX_CHUNK_LENGTH: usize = 64 * 1024;
+
+/// An email address found in a reader by an `AsyncScanner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundAddress {
+    text: String,
+    start: u64,
+    line: u64,
+    email: EmailAddress,
+}
+
+impl FoundAddress {
+    /// Returns the offset in octets of the start of the match in the input.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the offset in octets of the end of the match in the input.
+    pub fn end(&self) -> u64 {
+        self.start + self.text.len() as u64
+    }
+
+    /// Returns the range of the match in the input, in octets.
+    pub fn range(&self) -> Range<u64> {
+        self.start()..self.end()
+    }
+
+    /// Returns the number of the line of the match, starting at 1.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Returns the matched text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the email address.
+    pub fn email(&self) -> &EmailAddress {
+        &self.email
+    }
+
+    /// Consumes the match, returning the email address.
+    pub fn into_email(self) -> EmailAddress {
+        self.email
+    }
+}
+
+/// Finds the email addresses in an asynchronous reader, like a `Scanner` does in a text; e.g. in
+/// a log file that is tailed, an upload, or a socket, without blocking the runtime.
+///
+/// The input is read and scanned line by line; the octets that are not valid UTF-8 are scanned as
+/// white space, and a line longer than 64 KiB is scanned in several chunks, cut at white space.
+/// Hence, the memory use is bounded, whatever the input.
+///
+/// Requires the `tokio` feature.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::AsyncScanner;
+///
+/// let log = "12:00 login jane@example.com\n12:01 mail from <joe@example.org> to jane@example.com\n";
+/// let mut scanner = AsyncScanner::new(log.as_bytes());
+/// futures_executor::block_on(async {
+///     let mut found = Vec::new();
+///     while let Some(address) = scanner.next_address().await.unwrap() {
+///         found.push((address.line(), address.as_str().to_string()));
+///     }
+///     assert_eq!(found[1], (2, String::from("joe@example.org")));
+///     assert_eq!(found.len(), 3);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct AsyncScanner<R> {
+    reader: R,
+    options: ParsingOptions,
+    multiple_at: MultipleAt,
+    chunk_capacity: usize,
+    /// The octets that are read, but not scanned yet.
+    unscanned: Vec<u8>,
+    /// The offset in the input of the next chunk.
+    offset: u64,
+    /// The number of the line of the next chunk.
+    line: u64,
+    found: VecDeque<FoundAddress>,
+    is_done: bool,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncScanner<R> {
+    /// Instantiates a scanner over the given reader.
+    pub fn new(reader: R) -> Self {
+        AsyncScanner {
+            reader,
+            options: ParsingOptions::default(),
+            multiple_at: MultipleAt::default(),
+            chunk_capacity: MAX_CHUNK_LENGTH,
+            unscanned: Vec::new(),
+            offset: 0,
+            line: 1,
+            found: VecDeque::new(),
+            is_done: false,
+        }
+    }
+
+    /// Sets the options that the candidates are parsed with, as per `Scanner::options`.
+    pub fn options(self, options: ParsingOptions) -> Self {
+        AsyncScanner { options, ..self }
+    }
+
+    /// Sets how the candidates with several `@` are handled, as per `Scanner::multiple_at`.
+    pub fn multiple_at(self, multiple_at: MultipleAt) -> Self {
+        AsyncScanner {
+            multiple_at,
+            ..self
+        }
+    }
+
+    /// Returns the next address of the input, reading it as far as needed; or `None` once the
+    /// input is exhausted.
+    ///
+    /// It is cancel safe: the octets that are read, and the addresses found, are kept until they
+    /// are scanned and returned; hence, nothing is lost if the future is dropped before it
+    /// completes, e.g. in `tokio::select!`.
+    pub async fn next_address(&mut self) -> io::Result<Option<FoundAddress>> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Ok(Some(found));
+            }
+            if self.is_done {
+                return Ok(None);
+            }
+            let chunk = self.read_chunk().await?;
+            self.scan(chunk);
+        }
+    }
+
+    /// Consumes the scanner, returning the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next line, or the next chunk of a long line.
+    async fn read_chunk(&mut self) -> io::Result<Vec<u8>> {
+        while !self.unscanned.ends_with(b"\n") && self.unscanned.len() < self.chunk_capacity {
+            let buffer = self.reader.fill_buf().await?;
+            if buffer.is_empty() {
+                self.is_done = true;
+                break;
+            }
+            let wanted = self.chunk_capacity - self.unscanned.len();
+            let length = match buffer.iter().position(|&octet| octet == b'\n') {
+                Some(end) if end < wanted => end + 1,
+                _ => buffer.len().min(wanted),
+            };
+            self.unscanned.extend_from_slice(&buffer[..length]);
+            self.reader.consume(length);
+        }
+        let mut chunk = std::mem::take(&mut self.unscanned);
+        if !chunk.ends_with(b"\n") && !self.is_done {
+            if let Some(space) = chunk.iter().rposition(u8::is_ascii_whitespace) {
+                self.unscanned = chunk.split_off(space + 1);
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Scans a chunk for addresses, and moves past it.
+    fn scan(&mut self, chunk: Vec<u8>) {
+        let text = into_text(chunk);
+        let scanner = Scanner::new(&text)
+            .options(self.options.clone())
+            .multiple_at(self.multiple_at);
+        for found in scanner {
+            self.found.push_back(FoundAddress {
+                text: String::from(found.as_str()),
+                start: self.offset + found.start() as u64,
+                line: self.line,
+                email: found.into_email(),
+            });
+        }
+        self.offset += text.len() as u64;
+        if text.ends_with('\n') {
+            self.line += 1;
+        }
+    }
+}
+
+/// Converts a chunk into a text of the same length, replacing every octet of its invalid UTF-8
+/// sequences with a space; unlike `String::from_utf8_lossy`, this keeps the offsets.
+fn into_text(mut chunk: Vec<u8>) -> String {
+    let mut start = 0;
+    while let Err(error) = std::str::from_utf8(&chunk[start..]) {
+        let invalid = start + error.valid_up_to();
+        let end = error
+            .error_len()
+            .map_or(chunk.len(), |length| invalid + length);
+        chunk[invalid..end].fill(b' ');
+        start = end;
+    }
+    String::from_utf8_lossy(&chunk).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_executor::block_on;
+
+    fn scan_all<R: AsyncBufRead + Unpin>(mut scanner: AsyncScanner<R>) -> Vec<FoundAddress> {
+        block_on(async {
+            let mut found = Vec::new();
+            while let Some(address) = scanner.next_address().await.unwrap() {
+                found.push(address);
+            }
+            found
+        })
+    }
+
+    #[test]
+    fn finds_addresses_with_their_positions() {
+        let input = b"to: jane@example.com\n\xff\xfejoe@example.org, bar@@x\n\nlast@example.net";
+        let found = scan_all(AsyncScanner::new(&input[..]));
+        let found: Vec<_> = found
+            .iter()
+            .map(|address| (address.as_str(), address.line(), address.range()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("jane@example.com", 1, 4..20),
+                ("joe@example.org", 2, 23..38),
+                ("last@example.net", 4, 48..64),
+            ]
+        );
+    }
+
+    #[test]
+    fn cuts_long_lines_at_white_space() {
+        let input = "a foo@example.com bar@example.org jane@é.example";
+        let mut scanner = AsyncScanner::new(input.as_bytes());
+        scanner.chunk_capacity = 20;
+        let found = scan_all(scanner);
+        let found: Vec<_> = found
+            .iter()
+            .map(|address| (address.as_str(), address.start()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("foo@example.com", 2),
+                ("bar@example.org", 18),
+                ("jane@é.example", 34)
+            ]
+        );
+        assert_eq!(into_text(b"a\xc3".to_vec()), "a ");
+    }
+}
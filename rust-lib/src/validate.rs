@@ -0,0 +1,166 @@
+use crate::email_address::{Obsolete, ParsingOptions, Rule, RFC5322};
+use crate::error::{domain_hint, local_part_hint, ErrorKind, Hint, ParseError};
+use crate::labels::mask_hyphens;
+use crate::prefilter::check_limits;
+use crate::restrictions::{check_domain, check_local_part};
+use pest::Parser;
+
+/// Validates a local part on its own, as `EmailAddress::parse` would validate it in an address;
+/// e.g. for a form with separate inputs for the local part and the domain.
+///
+/// The error is of the `ErrorKind::InvalidLocalPart` kind, or `ErrorKind::QuotedLocalPart` as per
+/// the options, and its input is the local part; or `ErrorKind::InputTooLarge` or
+/// `ErrorKind::TooComplex` beyond the limits of the options.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{validate_local_part, Hint, ParsingOptions};
+///
+/// assert!(validate_local_part("foo.bar", &ParsingOptions::default()).is_ok());
+/// assert!(validate_local_part("\"foo bar\"", &ParsingOptions::default()).is_ok());
+///
+/// let error = validate_local_part("foo..bar", &ParsingOptions::default()).unwrap_err();
+/// assert_eq!(error.hint(), Some(Hint::MisplacedDot));
+/// # #[cfg(not(feature = "strict-only"))] {
+/// assert!(validate_local_part("foo . bar", &ParsingOptions::new(true)).is_ok());
+/// # }
+/// ```
+pub fn validate_local_part(local_part: &str, options: &ParsingOptions) -> Result<(), ParseError> {
+    check_limits(local_part, options)?;
+    let error = |kind, hint| Err(ParseError::new(kind, local_part, 0..local_part.len(), hint));
+    if RFC5322::parse(Rule::local_part_complete, local_part).is_err() {
+        let is_obsolete = Obsolete::LocalPart.matches(local_part);
+        if !is_obsolete {
+            return error(ErrorKind::InvalidLocalPart, local_part_hint(local_part));
+        } else if !options.is_lax {
+            return error(ErrorKind::InvalidLocalPart, Some(Hint::ObsoleteSyntax));
+        }
+    }
+    check_local_part(local_part, options).or_else(|(kind, hint)| error(kind, hint))
+}
+
+/// Validates a domain on its own, as `EmailAddress::parse` would validate it in an address; e.g.
+/// for a form with separate inputs for the local part and the domain.
+///
+/// The error is of the `ErrorKind::InvalidDomain` kind, and its input is the domain; or
+/// `ErrorKind::InputTooLarge` or `ErrorKind::TooComplex` beyond the limits of the options.
+///
+/// # Examples
+/// ```
+/// use email_address_parser::{validate_domain, Hint, ParsingOptions};
+///
+/// assert!(validate_domain("bar.com", &ParsingOptions::default()).is_ok());
+/// assert!(validate_domain("[127.0.0.1]", &ParsingOptions::default()).is_ok());
+///
+/// let error = validate_domain("-bar.com", &ParsingOptions::default()).unwrap_err();
+/// assert_eq!(error.hint(), Some(Hint::MisplacedHyphen));
+/// assert_eq!(error.to_string(), "Invalid domain '-bar.com'. A domain label can neither start nor end with a hyphen.");
+/// ```
+pub fn validate_domain(domain: &str, options: &ParsingOptions) -> Result<(), ParseError> {
+    check_limits(domain, options)?;
+    let error = |hint| {
+        Err(ParseError::new(
+            ErrorKind::InvalidDomain,
+            domain,
+            0..domain.len(),
+            hint,
+        ))
+    };
+    let masked = mask_hyphens(domain, 0, options);
+    let syntax = masked.as_deref().unwrap_or(domain);
+    if RFC5322::parse(Rule::domain_complete, syntax).is_err() {
+        let is_obsolete = Obsolete::Domain.matches(syntax);
+        if !is_obsolete {
+            return error(domain_hint(domain));
+        } else if !options.is_lax {
+            return error(Some(Hint::ObsoleteSyntax));
+        }
+    }
+    check_domain(domain, options).or_else(|hint| error(Some(hint)))
+}
+
+#[cfg(all(test, not(feature = "strict-only")))]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn validates_local_parts() {
+        let strict = ParsingOptions::new(false);
+        let lax = ParsingOptions::new(true);
+        for local_part in &["foo", "foo.bar", "\"foo bar\"", "foö", "a--b"] {
+            assert!(
+                validate_local_part(local_part, &strict).is_ok(),
+                "{}",
+                local_part
+            );
+        }
+        for local_part in &["", ".foo", "foo bar", "foo@bar", "\"foo"] {
+            let error = validate_local_part(local_part, &lax).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidLocalPart);
+            assert_eq!(error.input(), *local_part);
+        }
+        let error = validate_local_part("\"foo\".bar", &strict).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::ObsoleteSyntax));
+        assert!(validate_local_part("\"foo\".bar", &lax).is_ok());
+
+        let error =
+            validate_local_part("\"foo\"(x)", &strict.clone().allow_comments(false)).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::Comment));
+        let error = validate_local_part("\"foo\"", &strict.allow_quoted_local_part(false));
+        assert_eq!(error.unwrap_err().kind(), ErrorKind::QuotedLocalPart);
+    }
+
+    #[test]
+    fn validates_domains() {
+        let strict = ParsingOptions::new(false);
+        let lax = ParsingOptions::new(true);
+        for domain in &[
+            "bar.com",
+            "bücher.de",
+            "[127.0.0.1]",
+            "[IPv6:::1]",
+            "google-x.com",
+        ] {
+            assert!(validate_domain(domain, &strict).is_ok(), "{}", domain);
+        }
+        for (domain, hint) in &[
+            ("", Some(Hint::EmptyDomain)),
+            ("bar..com", Some(Hint::MisplacedDomainDot)),
+            ("bar-.com", Some(Hint::MisplacedHyphen)),
+            ("bar@baz.com", Some(Hint::MultipleAt)),
+        ] {
+            let error = validate_domain(domain, &lax).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+            assert_eq!(error.hint(), *hint, "{}", domain);
+        }
+        let error = validate_domain(" bar .com", &strict).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::ObsoleteSyntax));
+        assert!(validate_domain(" bar .com", &lax).is_ok());
+
+        let options = lax.allow_trailing_hyphen(true).allow_numeric_tld(false);
+        assert!(validate_domain("bar-.com", &options).is_ok());
+        let error = validate_domain("bar.123", &options).unwrap_err();
+        assert_eq!(error.hint(), Some(Hint::NumericTopLevelDomain));
+        let error = validate_domain("[127.0.0.1]", &options.allow_domain_literal(false));
+        assert_eq!(error.unwrap_err().hint(), Some(Hint::DomainLiteral));
+    }
+
+    #[test]
+    fn checks_the_limits_of_the_options() {
+        let options = ParsingOptions::default();
+        let long = "a".repeat(999);
+        let error = validate_local_part(&long, &options).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InputTooLarge);
+        let error = validate_domain(&long, &options).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InputTooLarge);
+        let nested = format!("foo{}", "(".repeat(17));
+        let error = validate_local_part(&nested, &options).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TooComplex);
+        let spaced = format!("bar.com{}", " (a)".repeat(65));
+        let error = validate_domain(&spaced, &options).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TooComplex);
+        let error = validate_domain(&spaced, &options.max_whitespace_runs(128)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidDomain);
+    }
+}
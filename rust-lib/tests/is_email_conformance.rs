@@ -0,0 +1,96 @@
+// Runs the is_email corpus (https://github.com/dominicsayers/isemail) against both strict and
+// lax parsing, and reports the conformance per category. It is meant to validate grammar changes,
+// and therefore it runs only if the `IS_EMAIL_CONFORMANCE` environment variable is set:
+//
+// IS_EMAIL_CONFORMANCE=1 cargo test --test is_email_conformance -- --nocapture
+//
+// An address is expected to be accepted unless its category is `ISEMAIL_ERR`, and in strict mode,
+// `ISEMAIL_DEPREC` (i.e. obsolete syntax) as well. Accepting an `ISEMAIL_ERR` address fails the test.
+use email_address_parser::*;
+use std::collections::BTreeMap;
+
+include!(concat!(env!("OUT_DIR"), "/is_email_corpus.rs"));
+
+#[derive(Default)]
+struct Tally {
+    total: usize,
+    strict_accepted: usize,
+    lax_accepted: usize,
+    strict_conforming: usize,
+    lax_conforming: usize,
+}
+
+fn is_expected_valid(category: &str, is_lax: bool) -> bool {
+    match category {
+        "ISEMAIL_ERR" => false,
+        "ISEMAIL_DEPREC" => is_lax,
+        _ => true,
+    }
+}
+
+#[test]
+fn is_email_conformance() {
+    if std::env::var_os("IS_EMAIL_CONFORMANCE").is_none() {
+        return;
+    }
+
+    let mut tallies: BTreeMap<&str, Tally> = BTreeMap::new();
+    let mut wrongly_accepted = Vec::new();
+    for &(id, address, category, diagnosis) in IS_EMAIL_CORPUS {
+        let strict = EmailAddress::is_valid(address, None);
+        let lax = EmailAddress::is_valid(address, Some(ParsingOptions::new(true)));
+        let tally = tallies.entry(category).or_default();
+        tally.total += 1;
+        tally.strict_accepted += strict as usize;
+        tally.lax_accepted += lax as usize;
+        tally.strict_conforming += (strict == is_expected_valid(category, false)) as usize;
+        tally.lax_conforming += (lax == is_expected_valid(category, true)) as usize;
+        if (strict || lax) && category == "ISEMAIL_ERR" {
+            wrongly_accepted.push(format!("#{} {:?} ({})", id, address, diagnosis));
+        }
+        if strict != is_expected_valid(category, false) || lax != is_expected_valid(category, true)
+        {
+            println!(
+                "#{:<3} {:<24} strict: {:<5} lax: {:<5} {:?}",
+                id, diagnosis, strict, lax, address
+            );
+        }
+    }
+
+    let percent = |part: usize, total: usize| 100.0 * part as f64 / total as f64;
+    println!();
+    println!(
+        "{:<24} {:>5} {:>15} {:>15} {:>11} {:>11}",
+        "category", "cases", "strict accepts", "lax accepts", "strict conf", "lax conf"
+    );
+    let mut overall = Tally::default();
+    for (category, tally) in &tallies {
+        println!(
+            "{:<24} {:>5} {:>15} {:>15} {:>10.1}% {:>10.1}%",
+            category,
+            tally.total,
+            tally.strict_accepted,
+            tally.lax_accepted,
+            percent(tally.strict_conforming, tally.total),
+            percent(tally.lax_conforming, tally.total),
+        );
+        overall.total += tally.total;
+        overall.strict_conforming += tally.strict_conforming;
+        overall.lax_conforming += tally.lax_conforming;
+    }
+    println!(
+        "{:<24} {:>5} {:>15} {:>15} {:>10.1}% {:>10.1}%",
+        "overall",
+        overall.total,
+        "",
+        "",
+        percent(overall.strict_conforming, overall.total),
+        percent(overall.lax_conforming, overall.total),
+    );
+
+    assert!(
+        wrongly_accepted.is_empty(),
+        "invalid addresses accepted: {:#?}",
+        wrongly_accepted
+    );
+}